@@ -0,0 +1,481 @@
+//! Bit vector: a growable array of bits packed 64 to a word, for sets of small integers (sieves,
+//!     visited sets in graph searches) in n / 8 bytes.
+//!
+//! Besides reading and writing single bits, it answers rank queries (how many bits are set before
+//!     a position) and select queries (where the k-th set bit is) by counting bits a word at a
+//!     time, and combines whole bit vectors with the &, | and ^ operators a word at a time.
+//!
+//! ```
+//! use rust_datastructures_algorithms::bit_vec::BitVec;
+//!
+//! // sieve of Eratosthenes: bit i is set while i may be prime
+//! let mut primes = BitVec::from_elem(50, true);
+//! primes.clear(0);
+//! primes.clear(1);
+//! for i in 2..8 {
+//!     if primes.get(i) {
+//!         for multiple in (i * i..50).step_by(i) {
+//!             primes.clear(multiple);
+//!         }
+//!     }
+//! }
+//! assert_eq!(primes.count_ones(), 15);
+//! assert_eq!(primes.iter_ones().take(6).collect::<Vec<_>>(), vec![2, 3, 5, 7, 11, 13]);
+//! assert_eq!(primes.rank(20), 8);
+//! assert_eq!(primes.select(8), Some(23));
+//! ```
+
+use std::ops::{ BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not };
+
+
+/// A bit vector struct.
+///
+/// * `words`: The bits, packed into words - bit i is bit i % 64 of word i / 64. Bits past length in
+///         the last word are always 0, so whole words can be counted and compared.
+/// * `length`: Number of bits.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    length: usize
+}
+
+
+/// Iterator yielding the bits of a BitVec struct in order, as bools. Created by BitVec::iter.
+///
+/// * `bits`: the BitVec struct being iterated over.
+/// * `index`: Index of the next bit to be yielded.
+pub struct Iter<'a> {
+    bits: &'a BitVec,
+    index: usize
+}
+
+
+/// Iterator yielding the indexes of the set bits of a BitVec struct in ascending order, skipping a
+/// word of unset bits at a time. Created by BitVec::iter_ones.
+///
+/// * `words`: The words of the BitVec struct.
+/// * `word_index`: Index of the word being scanned.
+/// * `current`: Bits of that word not yet yielded.
+pub struct Ones<'a> {
+    words: &'a [u64],
+    word_index: usize,
+    current: u64
+}
+
+
+// Method implementations for BitVec struct
+impl BitVec {
+
+    /// Return a new, empty BitVec struct.
+    pub fn new() -> BitVec {
+        BitVec { words: Vec::new(), length: 0 }
+    }
+
+    /// Return a new BitVec struct of the input length, with every bit set to the input value.
+    ///
+    /// * `length`: number of bits.
+    /// * `value`: value of every bit.
+    pub fn from_elem(length: usize, value: bool) -> BitVec {
+        let mut bits = BitVec { words: vec![if value { u64::MAX } else { 0 }; length.div_ceil(64)], length };
+        bits.clear_spare_bits();
+        bits
+    }
+
+    /// Return the number of bits.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether there are no bits.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the value of the bit at the input index. Panics if the index is out of bounds.
+    ///
+    /// * `index`: index of the bit.
+    pub fn get(&self, index: usize) -> bool {
+        self.check_index(index);
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Set the bit at the input index to 1. Panics if the index is out of bounds.
+    ///
+    /// * `index`: index of the bit.
+    pub fn set(&mut self, index: usize) {
+        self.check_index(index);
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    /// Set the bit at the input index to 0. Panics if the index is out of bounds.
+    ///
+    /// * `index`: index of the bit.
+    pub fn clear(&mut self, index: usize) {
+        self.check_index(index);
+        self.words[index / 64] &= !(1 << (index % 64));
+    }
+
+    /// Flip the bit at the input index. Panics if the index is out of bounds.
+    ///
+    /// * `index`: index of the bit.
+    pub fn toggle(&mut self, index: usize) {
+        self.check_index(index);
+        self.words[index / 64] ^= 1 << (index % 64);
+    }
+
+    /// Set the bit at the input index to the input value. Panics if the index is out of bounds.
+    ///
+    /// * `index`: index of the bit.
+    /// * `value`: new value of the bit.
+    pub fn assign(&mut self, index: usize, value: bool) {
+        if value {
+            self.set(index);
+        } else {
+            self.clear(index);
+        }
+    }
+
+    /// Set every bit to the input value.
+    ///
+    /// * `value`: new value of every bit.
+    pub fn fill(&mut self, value: bool) {
+        self.words.fill(if value { u64::MAX } else { 0 });
+        self.clear_spare_bits();
+    }
+
+    /// Add a bit to the end.
+    ///
+    /// * `value`: value of the new bit.
+    pub fn push(&mut self, value: bool) {
+        if self.length.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        self.length += 1;
+        self.assign(self.length - 1, value);
+    }
+
+    /// Remove the last bit and return it (unless there are no bits).
+    pub fn pop(&mut self) -> Option<bool> {
+        let last = self.length.checked_sub(1)?;
+        let value = self.get(last);
+        self.clear(last);
+        self.length = last;
+        if self.length.is_multiple_of(64) {
+            self.words.pop();
+        }
+        Some(value)
+    }
+
+    /// Return the number of bits set to 1.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Return the number of bits set to 0.
+    pub fn count_zeros(&self) -> usize {
+        self.length - self.count_ones()
+    }
+
+    /// Return the number of bits set to 1 before the input index, counting whole words at a time
+    /// in O(n / 64). Panics if the index is greater than len().
+    ///
+    /// * `index`: index to count up to (exclusive) - len() counts every bit.
+    pub fn rank(&self, index: usize) -> usize {
+        assert!(index <= self.length, "rank index {} is out of bounds for a bit vector of length {}", index, self.length);
+        let whole: usize = self.words[..index / 64].iter().map(|word| word.count_ones() as usize).sum();
+        let partial = if index.is_multiple_of(64) { 0 } else { (self.words[index / 64] & low_bits(index % 64)).count_ones() as usize };
+        whole + partial
+    }
+
+    /// Return the number of bits set to 0 before the input index. Panics if the index is greater
+    /// than len().
+    ///
+    /// * `index`: index to count up to (exclusive).
+    pub fn rank_zeros(&self, index: usize) -> usize {
+        index - self.rank(index)
+    }
+
+    /// Return the index of the set bit with the input rank - the (rank + 1)-th set bit, so select
+    /// and rank are inverses - or None if fewer bits are set. Finds the word by counting whole
+    /// words in O(n / 64), then the bit within it.
+    ///
+    /// * `rank`: number of set bits before the one to find.
+    pub fn select(&self, rank: usize) -> Option<usize> {
+        let mut remaining = rank;
+        for (word_index, word) in self.words.iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if remaining < ones {
+                let mut word = *word;
+                // drop the lowest set bits until the one wanted is lowest
+                for _ in 0..remaining {
+                    word &= word - 1;
+                }
+                return Some(word_index * 64 + word.trailing_zeros() as usize);
+            }
+            remaining -= ones;
+        }
+        None
+    }
+
+    /// Return an iterator of the bits, in order, as bools.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { bits: self, index: 0 }
+    }
+
+    /// Return an iterator of the indexes of the bits set to 1, in ascending order.
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones { words: &self.words, word_index: 0, current: self.words.first().copied().unwrap_or(0) }
+    }
+
+    /// Panic if the input index is out of bounds.
+    ///
+    /// * `index`: index of a bit.
+    fn check_index(&self, index: usize) {
+        assert!(index < self.length, "index {} is out of bounds for a bit vector of length {}", index, self.length);
+    }
+
+    /// Set the bits past length in the last word to 0.
+    fn clear_spare_bits(&mut self) {
+        if !self.length.is_multiple_of(64) {
+            let last = self.words.len() - 1;
+            self.words[last] &= low_bits(self.length % 64);
+        }
+    }
+
+    /// Combine each word of the input bit vector into the matching word of this one. Panics if
+    /// their lengths differ.
+    ///
+    /// * `other`: the other bit vector.
+    /// * `combine_words`: function combining a word of this bit vector with a word of the other.
+    fn combine_assign<F: Fn(&mut u64, u64)>(&mut self, other: &BitVec, combine_words: F) {
+        assert_eq!(self.length, other.length, "bit vectors of different lengths can't be combined");
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            combine_words(word, *other_word);
+        }
+    }
+}
+
+/// Return a word with the lowest input number of bits set.
+///
+/// * `count`: number of bits, less than 64.
+fn low_bits(count: usize) -> u64 {
+    (1 << count) - 1
+}
+
+impl Extend<bool> for BitVec {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitVec::new();
+        bits.extend(iter);
+        bits
+    }
+}
+
+impl<'a> IntoIterator for &'a BitVec {
+    type Item = bool;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Bitwise operators between bit vectors of the same length; they panic if the lengths differ.
+impl BitAndAssign<&BitVec> for BitVec {
+    fn bitand_assign(&mut self, other: &BitVec) {
+        self.combine_assign(other, |word, other_word| *word &= other_word);
+    }
+}
+
+impl BitOrAssign<&BitVec> for BitVec {
+    fn bitor_assign(&mut self, other: &BitVec) {
+        self.combine_assign(other, |word, other_word| *word |= other_word);
+    }
+}
+
+impl BitXorAssign<&BitVec> for BitVec {
+    fn bitxor_assign(&mut self, other: &BitVec) {
+        self.combine_assign(other, |word, other_word| *word ^= other_word);
+    }
+}
+
+impl BitAnd for &BitVec {
+    type Output = BitVec;
+
+    fn bitand(self, other: &BitVec) -> BitVec {
+        let mut result = self.clone();
+        result &= other;
+        result
+    }
+}
+
+impl BitOr for &BitVec {
+    type Output = BitVec;
+
+    fn bitor(self, other: &BitVec) -> BitVec {
+        let mut result = self.clone();
+        result |= other;
+        result
+    }
+}
+
+impl BitXor for &BitVec {
+    type Output = BitVec;
+
+    fn bitxor(self, other: &BitVec) -> BitVec {
+        let mut result = self.clone();
+        result ^= other;
+        result
+    }
+}
+
+impl Not for &BitVec {
+    type Output = BitVec;
+
+    fn not(self) -> BitVec {
+        let mut result = BitVec { words: self.words.iter().map(|word| !word).collect(), length: self.length };
+        result.clear_spare_bits();
+        result
+    }
+}
+
+
+// Iterator implementations for Iter struct
+impl<'a> Iterator for Iter<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.bits.len() {
+            return None;
+        }
+        self.index += 1;
+        Some(self.bits.get(self.index - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.bits.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+
+// Iterator implementation for Ones struct
+impl<'a> Iterator for Ones<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.word_index += 1;
+            self.current = *self.words.get(self.word_index)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.word_index * 64 + bit)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_bit_vec() -> BitVec {
+    // bit i is set when i is a multiple of 3, across three words
+    (0..150).map(|i| i % 3 == 0).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_vec_works() {
+        let mut bits = setup_bit_vec();
+        assert_eq!(bits.len(), 150);
+        assert_eq!(bits.count_ones(), 50);
+        assert!(bits.get(66) && !bits.get(67));
+        bits.set(67);
+        bits.clear(66);
+        bits.toggle(149);
+        bits.toggle(0);
+        assert!(bits.get(67) && !bits.get(66) && bits.get(149) && !bits.get(0));
+        assert_eq!(bits.count_zeros(), 100);
+
+        assert_eq!(bits.pop(), Some(true));
+        assert_eq!(bits.len(), 149);
+        bits.push(false);
+        assert!(!bits.get(149));
+        while bits.len() > 128 {
+            bits.pop();
+        }
+        assert_eq!(bits.words.len(), 2);
+        bits.fill(true);
+        assert_eq!(bits.count_ones(), 128);
+        assert_eq!(bits, BitVec::from_elem(128, true));
+        assert_eq!(BitVec::from_elem(70, true).words[1], 0b11_1111);
+
+        let mut empty = BitVec::new();
+        assert_eq!(empty.pop(), None);
+        assert_eq!(empty.iter_ones().next(), None);
+        assert_eq!(empty.rank(0), 0);
+    }
+
+    #[test]
+    fn bit_vec_rank_and_select_match_naive_counts() {
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let bits: BitVec = (0..1000).map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            // sparse runs and dense runs
+            seed % 7 < if seed & (1 << 40) == 0 { 1 } else { 6 }
+        }).collect();
+        let ones: Vec<usize> = (0..1000).filter(|i| bits.get(*i)).collect();
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), ones);
+        for index in 0..=1000 {
+            assert_eq!(bits.rank(index), ones.iter().filter(|one| **one < index).count());
+            assert_eq!(bits.rank_zeros(index) + bits.rank(index), index);
+        }
+        for (rank, one) in ones.iter().enumerate() {
+            assert_eq!(bits.select(rank), Some(*one));
+            assert_eq!(bits.rank(*one), rank);
+        }
+        assert_eq!(bits.select(ones.len()), None);
+    }
+
+    #[test]
+    fn bit_vec_bitwise_operations() {
+        let threes = setup_bit_vec();
+        let evens: BitVec = (0..150).map(|i| i % 2 == 0).collect();
+        let sixes = &threes & &evens;
+        assert_eq!(sixes.iter_ones().collect::<Vec<_>>(), (0..150).step_by(6).collect::<Vec<_>>());
+        let either = &threes | &evens;
+        assert_eq!(either.count_ones(), 75 + 50 - 25);
+        let exactly_one = &threes ^ &evens;
+        assert_eq!(exactly_one.count_ones(), either.count_ones() - sixes.count_ones());
+        let odds = !&evens;
+        assert_eq!(odds.count_ones(), 75);
+        assert!(odds.iter().zip(evens.iter()).all(|(odd, even)| odd != even));
+
+        let mut visited = BitVec::from_elem(150, false);
+        visited |= &threes;
+        visited &= &evens;
+        assert_eq!(visited, sixes);
+        visited ^= &sixes;
+        assert_eq!(visited.count_ones(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 150 is out of bounds for a bit vector of length 150")]
+    fn bit_vec_get_panics_past_the_end() {
+        setup_bit_vec().get(150);
+    }
+}
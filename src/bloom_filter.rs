@@ -0,0 +1,514 @@
+//! Bloom filter: a set that answers "possibly present" or "definitely not present" in a fixed
+//!     number of bits, however large the items are. Inserting an item sets k bits chosen by
+//!     hashing it; looking one up checks whether all k are set. Items never inserted can collide
+//!     with set bits (a false positive), but an inserted item is always found.
+//!
+//! BloomFilter::new picks the number of bits m and hashes k for an expected number of items n and
+//!     false positive rate p: m = -n ln(p) / ln(2)^2 and k = (m / n) ln(2). The k bit positions
+//!     come from two hashes by double hashing, `h1 + i * h2` for i from 0 to k - 1.
+//!
+//! Items are hashed with SipHash-1-3 under fixed keys, implemented here rather than taken from
+//!     the standard library's DefaultHasher, whose algorithm may change between Rust releases.
+//!     Integers are fed to it as little-endian bytes, and usize as a u64, so filters built from
+//!     the same items set the same bits on every platform and Rust version, and can be serialized,
+//!     deserialized and combined with each other.
+//!
+//! ```
+//! use rust_datastructures_algorithms::bloom_filter::BloomFilter;
+//!
+//! let mut seen = BloomFilter::new(1000, 0.01);
+//! seen.insert("apple");
+//! seen.insert("banana");
+//! assert!(seen.contains("apple"));
+//! // almost certainly - 1% of absent items are reported present
+//! assert!(!seen.contains("cherry"));
+//!
+//! let restored: BloomFilter<str> = BloomFilter::from_bytes(&seen.to_bytes()).unwrap();
+//! assert!(restored.contains("banana"));
+//! ```
+
+use std::f64::consts::LN_2;
+use std::hash::{ Hash, Hasher };
+use std::marker::PhantomData;
+
+
+/// Version of the serialized format written by to_bytes, stored in the header's first byte.
+const FORMAT_VERSION: u8 = 1;
+
+/// Number of bytes in the header of a serialized BloomFilter: the format version as a u8, the bit
+/// count as a u64 and the hash count as a u32.
+const HEADER_BYTES: usize = 13;
+
+/// Fixed SipHash keys used for every item.
+const HASH_KEYS: (u64, u64) = (0x0706_0504_0302_0100, 0x0F0E_0D0C_0B0A_0908);
+
+
+/// A Bloom filter struct.
+///
+/// * `bits`: The bit array, packed into words - bit i is bit i % 64 of word i / 64. Bits past
+///         bit_count in the last word are always 0.
+/// * `bit_count`: Number of bits in the array (m).
+/// * `hash_count`: Number of bits set per item (k).
+/// * `marker`: Marker for the item type, which is only hashed, never stored.
+pub struct BloomFilter<T: Hash + ?Sized> {
+    bits: Vec<u64>,
+    bit_count: usize,
+    hash_count: u32,
+    marker: PhantomData<fn(&T)>
+}
+
+
+// Method implementations for BloomFilter struct
+impl<T> BloomFilter<T> where T: Hash + ?Sized {
+
+    /// Return a new, empty BloomFilter struct sized so that after the expected number of items
+    /// are inserted, about the input fraction of items never inserted are reported present.
+    /// Panics if the false positive rate isn't strictly between 0 and 1.
+    ///
+    /// * `expected_items`: number of items expected to be inserted (treated as 1 if 0).
+    /// * `false_positive_rate`: target false positive rate, e.g. 0.01 for 1%.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> BloomFilter<T> {
+        assert!(
+            false_positive_rate > 0.0 && false_positive_rate < 1.0,
+            "false positive rate must be between 0 and 1, not {}", false_positive_rate
+        );
+        let items = expected_items.max(1) as f64;
+        let bit_count = (-items * false_positive_rate.ln() / (LN_2 * LN_2)).ceil() as usize;
+        let hash_count = ((bit_count as f64 / items) * LN_2).round().max(1.0) as u32;
+        BloomFilter::with_parameters(bit_count, hash_count)
+    }
+
+    /// Return a new, empty BloomFilter struct with the input number of bits and hashes. Panics if
+    /// either is 0.
+    ///
+    /// * `bit_count`: number of bits in the array (m).
+    /// * `hash_count`: number of bits set per item (k).
+    pub fn with_parameters(bit_count: usize, hash_count: u32) -> BloomFilter<T> {
+        assert!(bit_count > 0 && hash_count > 0, "a Bloom filter needs at least one bit and one hash");
+        BloomFilter { bits: vec![0; bit_count.div_ceil(64)], bit_count, hash_count, marker: PhantomData }
+    }
+
+    /// Return the number of bits in the array (m).
+    pub fn bit_count(&self) -> usize {
+        self.bit_count
+    }
+
+    /// Return the number of bits set per item (k).
+    pub fn hash_count(&self) -> u32 {
+        self.hash_count
+    }
+
+    /// Return the number of bits set in the array.
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Return whether no item has been inserted (no bit is set).
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| *word == 0)
+    }
+
+    /// Insert an item, setting its k bits.
+    ///
+    /// * `item`: item to be inserted.
+    pub fn insert(&mut self, item: &T) {
+        for bit in self.bit_positions(item) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Return whether the item may have been inserted: false means it definitely wasn't, and true
+    /// is wrong for about the filter's false positive rate of items.
+    ///
+    /// * `item`: item to look for.
+    pub fn contains(&self, item: &T) -> bool {
+        self.bit_positions(item).all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Remove every item, clearing every bit.
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    /// Return an estimate of the number of distinct items inserted, from the fraction of bits set:
+    /// -(m / k) ln(1 - X / m) for X bits set.
+    pub fn estimated_len(&self) -> f64 {
+        let unset = 1.0 - self.count_ones() as f64 / self.bit_count as f64;
+        -(self.bit_count as f64 / self.hash_count as f64) * unset.ln()
+    }
+
+    /// Return the expected false positive rate of the filter as it is now: the chance that k
+    /// random bits are all set, (X / m)^k for X bits set.
+    pub fn current_false_positive_rate(&self) -> f64 {
+        (self.count_ones() as f64 / self.bit_count as f64).powi(self.hash_count as i32)
+    }
+
+    /// Return the union of this filter and the input one - the filter of every item inserted into
+    /// either, found by OR-ing the bit arrays. Returns None if the filters have different numbers
+    /// of bits or hashes.
+    ///
+    /// * `other`: the other filter.
+    pub fn union(&self, other: &BloomFilter<T>) -> Option<BloomFilter<T>> {
+        self.combine(other, |left, right| left | right)
+    }
+
+    /// Return the intersection of this filter and the input one, found by AND-ing the bit arrays.
+    /// It contains every item inserted into both, but may report more false positives than a
+    /// filter built from only those items would. Returns None if the filters have different
+    /// numbers of bits or hashes.
+    ///
+    /// * `other`: the other filter.
+    pub fn intersection(&self, other: &BloomFilter<T>) -> Option<BloomFilter<T>> {
+        self.combine(other, |left, right| left & right)
+    }
+
+    /// Return the filter serialized to bytes: the format version as a u8, the bit count as a
+    /// little-endian u64, the hash count as a little-endian u32, then the bit array as
+    /// little-endian u64 words.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_BYTES + 8 * self.bits.len());
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&(self.bit_count as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.hash_count.to_le_bytes());
+        for word in &self.bits {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Return the filter serialized in the input bytes by to_bytes, or None if they aren't a valid
+    /// serialized filter of this format version.
+    ///
+    /// * `bytes`: the serialized filter.
+    pub fn from_bytes(bytes: &[u8]) -> Option<BloomFilter<T>> {
+        let (header, words) = bytes.split_at_checked(HEADER_BYTES)?;
+        if header[0] != FORMAT_VERSION {
+            return None;
+        }
+        let bit_count = usize::try_from(u64::from_le_bytes(header[1..9].try_into().ok()?)).ok()?;
+        let hash_count = u32::from_le_bytes(header[9..].try_into().ok()?);
+        if bit_count == 0 || hash_count == 0 || words.len() != 8 * bit_count.div_ceil(64) {
+            return None;
+        }
+        let bits: Vec<u64> = words.chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().expect("chunks are 8 bytes")))
+            .collect();
+        // bits past the end of the array must be clear, or counts would be off
+        let spare = bits.len() * 64 - bit_count;
+        if spare > 0 && bits[bits.len() - 1] >> (64 - spare) != 0 {
+            return None;
+        }
+        Some(BloomFilter { bits, bit_count, hash_count, marker: PhantomData })
+    }
+
+    /// Return the k bit positions for the input item, by double hashing.
+    ///
+    /// * `item`: item to find the bits of.
+    fn bit_positions(&self, item: &T) -> impl Iterator<Item = usize> {
+        let (first, second) = item_hashes(item);
+        let bit_count = self.bit_count as u64;
+        (0..u64::from(self.hash_count))
+            .map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % bit_count) as usize)
+    }
+
+    /// Return a filter with the same parameters as this one and the input one, whose words are the
+    /// input function of theirs (None if the parameters differ).
+    ///
+    /// * `other`: the other filter.
+    /// * `combine_words`: function combining a word from each filter.
+    fn combine<F: Fn(u64, u64) -> u64>(&self, other: &BloomFilter<T>, combine_words: F) -> Option<BloomFilter<T>> {
+        if self.bit_count != other.bit_count || self.hash_count != other.hash_count {
+            return None;
+        }
+        Some(BloomFilter {
+            bits: self.bits.iter().zip(&other.bits).map(|(left, right)| combine_words(*left, *right)).collect(),
+            bit_count: self.bit_count,
+            hash_count: self.hash_count,
+            marker: PhantomData
+        })
+    }
+}
+
+/// Return two independent hashes of the input item, from a SipHash-1-3 hasher with fixed keys.
+/// The second is made odd so no step of double hashing repeats a position early when the bit
+/// count is a power of two.
+///
+/// * `item`: item to hash.
+pub(crate) fn item_hashes<T: Hash + ?Sized>(item: &T) -> (u64, u64) {
+    let mut hasher = SipHasher13::new(HASH_KEYS.0, HASH_KEYS.1);
+    item.hash(&mut hasher);
+    let first = hasher.finish();
+    // continuing from the first hash's state gives a second, unrelated one
+    hasher.write_u8(0xFF);
+    (first, hasher.finish() | 1)
+}
+
+
+/// A SipHash-1-3 hasher struct: one compression round per 8-byte word and three finalization
+/// rounds, as described by Aumasson and Bernstein. Integers are written as little-endian bytes,
+/// and usize and isize as 64 bits, so the hash of an item doesn't depend on the platform.
+///
+/// * `state`: The four state words v0 to v3.
+/// * `tail`: Bytes written since the last full word, packed little-endian.
+/// * `tail_length`: Number of bytes in tail (0 to 7).
+/// * `length`: Total number of bytes written, of which the low byte goes into the final word.
+#[derive(Clone)]
+pub(crate) struct SipHasher13 {
+    state: [u64; 4],
+    tail: u64,
+    tail_length: usize,
+    length: usize
+}
+
+
+// Method implementations for SipHasher13 struct
+impl SipHasher13 {
+
+    /// Return a new SipHasher13 struct with the input keys.
+    ///
+    /// * `key0`: first half of the 128-bit key.
+    /// * `key1`: second half of the 128-bit key.
+    pub(crate) fn new(key0: u64, key1: u64) -> SipHasher13 {
+        SipHasher13 {
+            state: [
+                key0 ^ 0x736F_6D65_7073_6575,
+                key1 ^ 0x646F_7261_6E64_6F6D,
+                key0 ^ 0x6C79_6765_6E65_7261,
+                key1 ^ 0x7465_6462_7974_6573
+            ],
+            tail: 0,
+            tail_length: 0,
+            length: 0
+        }
+    }
+
+    /// Apply one SipRound to the input state.
+    ///
+    /// * `v`: the state words v0 to v3.
+    fn round(v: &mut [u64; 4]) {
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    }
+
+    /// Mix a full 8-byte word of input into the input state.
+    ///
+    /// * `v`: the state words v0 to v3.
+    /// * `word`: the word, read little-endian.
+    fn compress(v: &mut [u64; 4], word: u64) {
+        v[3] ^= word;
+        SipHasher13::round(v);
+        v[0] ^= word;
+    }
+}
+
+impl Hasher for SipHasher13 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len());
+        let mut bytes = bytes;
+        // top up a partial word left by an earlier write first
+        if self.tail_length > 0 {
+            let taken = bytes.len().min(8 - self.tail_length);
+            for (i, byte) in bytes[..taken].iter().enumerate() {
+                self.tail |= u64::from(*byte) << (8 * (self.tail_length + i));
+            }
+            self.tail_length += taken;
+            bytes = &bytes[taken..];
+            if self.tail_length < 8 {
+                return;
+            }
+            SipHasher13::compress(&mut self.state, self.tail);
+            self.tail = 0;
+            self.tail_length = 0;
+        }
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            SipHasher13::compress(&mut self.state, u64::from_le_bytes(word.try_into().expect("chunks are 8 bytes")));
+        }
+        for (i, byte) in words.remainder().iter().enumerate() {
+            self.tail |= u64::from(*byte) << (8 * i);
+        }
+        self.tail_length = words.remainder().len();
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    // the signed writes default to the unsigned ones of the same width, but isize has to be
+    // widened by sign rather than by zeros
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as i64 as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut v = self.state;
+        let last = ((self.length as u64) << 56) | self.tail;
+        SipHasher13::compress(&mut v, last);
+        v[2] ^= 0xFF;
+        for _ in 0..3 {
+            SipHasher13::round(&mut v);
+        }
+        v[0] ^ v[1] ^ v[2] ^ v[3]
+    }
+}
+
+impl<T> Clone for BloomFilter<T> where T: Hash + ?Sized {
+    fn clone(&self) -> Self {
+        BloomFilter { bits: self.bits.clone(), bit_count: self.bit_count, hash_count: self.hash_count, marker: PhantomData }
+    }
+}
+
+impl<T> PartialEq for BloomFilter<T> where T: Hash + ?Sized {
+    fn eq(&self, other: &Self) -> bool {
+        self.bit_count == other.bit_count && self.hash_count == other.hash_count && self.bits == other.bits
+    }
+}
+
+impl<T> Eq for BloomFilter<T> where T: Hash + ?Sized {}
+
+impl<T> Extend<T> for BloomFilter<T> where T: Hash {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(&item);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_bloom_filter() -> BloomFilter<u32> {
+    let mut filter = BloomFilter::new(1000, 0.01);
+    filter.extend((0..1000).map(|item| item * 7));
+    filter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bloom_filter_works() {
+        let filter = setup_bloom_filter();
+        // m = -1000 ln(0.01) / ln(2)^2, k = (m / 1000) ln(2)
+        assert_eq!(filter.bit_count(), 9586);
+        assert_eq!(filter.hash_count(), 7);
+        assert!((0..1000).all(|item| filter.contains(&(item * 7))));
+        assert!((filter.estimated_len() - 1000.0).abs() < 50.0);
+        assert!((filter.current_false_positive_rate() - 0.01).abs() < 0.003);
+
+        let mut empty: BloomFilter<str> = BloomFilter::new(0, 0.5);
+        assert!(empty.is_empty());
+        assert!(!empty.contains("anything"));
+        empty.insert("anything");
+        assert!(empty.contains("anything"));
+        empty.clear();
+        assert_eq!(empty.count_ones(), 0);
+    }
+
+    #[test]
+    fn bloom_filter_false_positive_rate() {
+        for rate in [0.1, 0.01, 0.001] {
+            let mut filter = BloomFilter::new(5000, rate);
+            filter.extend(0..5000u64);
+            let false_positives = (1_000_000..1_100_000u64).filter(|item| filter.contains(item)).count();
+            let measured = false_positives as f64 / 100_000.0;
+            assert!(measured < rate * 1.5 && measured > rate * 0.5, "rate {} measured {}", rate, measured);
+        }
+    }
+
+    #[test]
+    fn bloom_filter_union_and_intersection() {
+        let mut evens = BloomFilter::new(500, 0.01);
+        let mut thirds = BloomFilter::new(500, 0.01);
+        evens.extend((0..1000u32).step_by(2));
+        thirds.extend((0..1000u32).step_by(3));
+
+        let either = evens.union(&thirds).unwrap();
+        assert!((0..1000u32).filter(|item| item % 2 == 0 || item % 3 == 0).all(|item| either.contains(&item)));
+        let both = evens.intersection(&thirds).unwrap();
+        assert!((0..1000u32).step_by(6).all(|item| both.contains(&item)));
+        assert!(both.count_ones() <= evens.count_ones().min(thirds.count_ones()));
+
+        // a filter built from the items of both sets gets the same bits as the union
+        let mut all = BloomFilter::new(500, 0.01);
+        all.extend((0..1000u32).step_by(2).chain((0..1000u32).step_by(3)));
+        assert!(all == either);
+
+        let other_size: BloomFilter<u32> = BloomFilter::new(100, 0.01);
+        assert!(evens.union(&other_size).is_none());
+        assert!(evens.intersection(&BloomFilter::with_parameters(evens.bit_count(), 3)).is_none());
+    }
+
+    #[test]
+    fn bloom_filter_serialization() {
+        let filter = setup_bloom_filter();
+        let bytes = filter.to_bytes();
+        assert_eq!(bytes.len(), HEADER_BYTES + 8 * 9586usize.div_ceil(64));
+        let restored: BloomFilter<u32> = BloomFilter::from_bytes(&bytes).unwrap();
+        assert!(restored == filter);
+        assert!((0..1000).all(|item| restored.contains(&(item * 7))));
+
+        assert!(BloomFilter::<u32>::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        assert!(BloomFilter::<u32>::from_bytes(&bytes[..4]).is_none());
+        let mut spare_bit_set = bytes.clone();
+        *spare_bit_set.last_mut().unwrap() |= 0x80;
+        assert!(BloomFilter::<u32>::from_bytes(&spare_bit_set).is_none());
+        let mut no_hashes = bytes.clone();
+        no_hashes[9..13].fill(0);
+        assert!(BloomFilter::<u32>::from_bytes(&no_hashes).is_none());
+        let mut other_version = bytes;
+        other_version[0] = FORMAT_VERSION + 1;
+        assert!(BloomFilter::<u32>::from_bytes(&other_version).is_none());
+    }
+
+    #[test]
+    fn bloom_filter_hashes_are_stable() {
+        // pinned so that a change to the hash, which would break every serialized filter, fails
+        assert_eq!(item_hashes(&42u32), (818212726094792717, 10251303801808202929));
+        assert_eq!(item_hashes("apple"), (1235944453740454176, 17008428918584514009));
+        // usize and isize are hashed as 64-bit integers, whatever the platform's pointer width
+        assert_eq!(item_hashes(&usize::MAX), item_hashes(&u64::MAX));
+        assert_eq!(item_hashes(&-1isize), item_hashes(&-1i64));
+
+        // writes split anywhere hash the same as one write
+        let data: Vec<u8> = (0..40).collect();
+        let mut whole = SipHasher13::new(HASH_KEYS.0, HASH_KEYS.1);
+        whole.write(&data);
+        for split in 0..data.len() {
+            let mut parts = SipHasher13::new(HASH_KEYS.0, HASH_KEYS.1);
+            parts.write(&data[..split]);
+            parts.write(&data[split..]);
+            assert_eq!(parts.finish(), whole.finish());
+        }
+
+        let mut filter: BloomFilter<str> = BloomFilter::with_parameters(64, 3);
+        filter.insert("apple");
+        assert_eq!(filter.to_bytes(), [1, 64, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 4, 0, 1, 0, 0, 2]);
+    }
+}
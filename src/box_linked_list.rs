@@ -0,0 +1,348 @@
+//! Singly linked list implementation that owns its Nodes through Option-wrapped Boxes, with a raw
+//!     pointer to the tail Node for O(1) appends. Unlike linked_list.rs there's no Rc reference
+//!     counting or RefCell borrow checking at runtime - in exchange, Nodes can't be shared outside
+//!     the list.
+
+use std::ptr;
+
+
+/// A linked list struct owning a chain of boxed Node structs.
+///
+/// Each Node is owned by the Node before it (the head Node by the list). The tail member is a raw
+/// pointer into the last Box, which the Box's owner doesn't know about - so it's only valid until
+/// that Box is next used to access the Node mutably, or moved (moving a Box asserts it's the only
+/// way to reach its Node). The methods keep it valid by taking the pointer again from the Box
+/// after every move of the tail Node's Box (which only happens while the tail Node is the head or
+/// the Node after it), and by reaching the tail Node mutably only through the tail pointer.
+///
+/// * `head`: An Option-wrapped Box holding the first Node.
+/// * `tail`: Raw pointer to the last Node (null if the list is empty).
+/// * `length`: Number of Nodes in the list.
+pub struct BoxLinkedList<T> {
+    head: Option<Box<Node<T>>>,
+    tail: *mut Node<T>,
+    length: usize
+}
+
+
+/// Node in a BoxLinkedList struct.
+///
+/// * `value`: T must be of type that matches the BoxLinkedList struct this Node is placed in
+/// * `next`: Option holding the Box of the next Node.
+pub struct Node<T> {
+    value: T,
+    next: Option<Box<Node<T>>>
+}
+
+
+/// Iterator yielding references to the values in a BoxLinkedList struct, from head to tail.
+/// Created by BoxLinkedList::iter.
+///
+/// * `next`: reference to the Node holding the next value to be yielded.
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>
+}
+
+
+/// Iterator yielding mutable references to the values in a BoxLinkedList struct, from head to
+/// tail. Created by BoxLinkedList::iter_mut.
+///
+/// * `next`: mutable reference to the Node holding the next value to be yielded.
+/// * `tail`: the list's tail pointer, which the tail Node is reached through (see
+///         BoxLinkedList::link_mut).
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    tail: *mut Node<T>
+}
+
+
+// Method implementations for BoxLinkedList struct
+impl<T> BoxLinkedList<T> {
+
+    /// Return a new, empty BoxLinkedList struct
+    pub fn new() -> BoxLinkedList<T> {
+        BoxLinkedList {
+            head: None,
+            tail: ptr::null_mut(),
+            length: 0
+        }
+    }
+
+    /// Return the number of values in this BoxLinkedList.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this BoxLinkedList holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Add a Node containing value T to the end of the list, through the tail pointer.
+    ///
+    /// * `value`: T to be stored in a new Node at the tail of the list.
+    pub fn add_value(&mut self, value: T) {
+        let slot = if self.tail.is_null() {
+            &mut self.head
+        } else {
+            // SAFETY: a non-null tail points to the last Node, which this list owns, and self is
+            //      mutably borrowed so no reference to it exists
+            unsafe { &mut (*self.tail).next }
+        };
+        // take the pointer from the Box once it's in place, so moving it doesn't invalidate it
+        self.tail = &mut **slot.insert(Box::new(Node { value, next: None }));
+        self.length += 1;
+    }
+
+    /// Add a Node containing value T to the front of the list.
+    ///
+    /// * `value`: T to be stored in a new Node at the head of the list.
+    pub fn push_front(&mut self, value: T) {
+        let new_node = Box::new(Node { value, next: self.head.take() });
+        let new_head = self.head.insert(new_node);
+        // the tail Node's Box was just moved if it's the new head or the Node after it
+        match self.length {
+            0 => { self.tail = &mut **new_head; },
+            1 => { self.tail = new_head.next.as_deref_mut().expect("the old head is the tail"); },
+            _ => {}
+        }
+        self.length += 1;
+    }
+
+    /// Get a reference to the value in the head Node (if the list is not empty).
+    pub fn peek_head_value(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    /// Get a reference to the value in the tail Node (if the list is not empty).
+    pub fn peek_tail_value(&self) -> Option<&T> {
+        // SAFETY: a non-null tail points to a Node this list owns, and self is borrowed for the
+        //      lifetime of the returned reference, so the Node can't be removed or mutated
+        unsafe { self.tail.as_ref().map(|node| &node.value) }
+    }
+
+    /// Remove the head Node from the list and return its value (unless the list is empty).
+    pub fn pop_front(&mut self) -> Option<T> {
+        let old_head = self.head.take()?;
+        let Node { value, next } = *old_head;
+        self.head = next;
+        // the tail Node's Box was just moved if it's the new head
+        match &mut self.head {
+            Some(new_head) if new_head.next.is_none() => { self.tail = &mut **new_head; },
+            Some(_) => {},
+            None => { self.tail = ptr::null_mut(); }
+        }
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Return an iterator of references to the values in this BoxLinkedList, from head to tail.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+
+    /// Return an iterator of mutable references to the values in this BoxLinkedList, from head
+    /// to tail.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: BoxLinkedList::link_mut(&mut self.head, self.tail),
+            tail: self.tail
+        }
+    }
+
+    /// Return a mutable reference to the Node in the input link (if there is one). The tail Node
+    ///     is reached through the tail pointer rather than its Box, as mutably borrowing it through
+    ///     the Box would invalidate the tail pointer.
+    ///
+    /// * `link`: the list's head member, or a Node's next member.
+    /// * `tail`: the list's tail pointer.
+    fn link_mut(link: &mut Option<Box<Node<T>>>, tail: *mut Node<T>) -> Option<&mut Node<T>> {
+        match link {
+            // SAFETY: tail points to the Node in this link, which is mutably borrowed through link
+            Some(node) if ptr::eq(&**node, tail) => unsafe { tail.as_mut() },
+            _ => link.as_deref_mut()
+        }
+    }
+}
+
+impl<T> Default for BoxLinkedList<T> {
+    fn default() -> Self {
+        BoxLinkedList::new()
+    }
+}
+
+// Drop Nodes one at a time from the head, so long lists don't drop their next chain recursively.
+impl<T> Drop for BoxLinkedList<T> {
+    fn drop(&mut self) {
+        let mut opt_node = self.head.take();
+        while let Some(mut node) = opt_node {
+            opt_node = node.next.take();
+        }
+    }
+}
+
+
+// Iterator implementation for Iter struct
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.value)
+    }
+}
+
+
+// Iterator implementation for IterMut struct
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = BoxLinkedList::link_mut(&mut node.next, self.tail);
+        Some(&mut node.value)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_box_linked_list() -> BoxLinkedList<u32> {
+    let mut bll: BoxLinkedList<u32> = BoxLinkedList::new();
+    bll.add_value(2);
+    bll.add_value(4);
+    bll
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linked_list::LinkedList;
+    use std::collections::VecDeque;
+    use std::hint::black_box;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn box_linked_list_works() {
+        let mut bll = setup_box_linked_list();
+        bll.push_front(0);
+        bll.add_value(6);
+        assert_eq!(bll.len(), 4);
+        assert_eq!(bll.peek_head_value(), Some(&0));
+        assert_eq!(bll.peek_tail_value(), Some(&6));
+        assert_eq!(bll.iter().collect::<Vec<_>>(), vec![&0, &2, &4, &6]);
+
+        for value in bll.iter_mut() {
+            *value += 1;
+        }
+        assert_eq!(bll.iter().collect::<Vec<_>>(), vec![&1, &3, &5, &7]);
+
+        // the tail pointer stays usable after the Nodes were mutably borrowed
+        bll.add_value(9);
+        assert_eq!(bll.peek_tail_value(), Some(&9));
+        assert_eq!(bll.len(), 5);
+    }
+
+    #[test]
+    fn box_linked_list_tail_is_reset_when_emptied() {
+        let mut bll = setup_box_linked_list();
+        assert_eq!(bll.pop_front(), Some(2));
+        assert_eq!(bll.pop_front(), Some(4));
+        assert_eq!(bll.pop_front(), None);
+        assert!(bll.is_empty());
+        assert_eq!(bll.peek_tail_value(), None);
+
+        bll.push_front(8);
+        bll.add_value(10);
+        assert_eq!(bll.peek_tail_value(), Some(&10));
+        assert_eq!(bll.iter().collect::<Vec<_>>(), vec![&8, &10]);
+
+        // Miri is slow enough that a shorter list has to do
+        let long_length = if cfg!(miri) { 1_000 } else { 500_000u32 };
+        let mut long_bll = BoxLinkedList::new();
+        for value in 0..long_length {
+            long_bll.add_value(value);
+        }
+        drop(long_bll);
+    }
+
+    #[test]
+    fn box_linked_list_tail_survives_moves_of_its_box() {
+        // the tail Node's Box is moved when it is the head (or the Node after it)
+        let mut bll = BoxLinkedList::new();
+        bll.add_value(1);
+        bll.push_front(0);
+        bll.add_value(2);
+        assert_eq!(bll.pop_front(), Some(0));
+        assert_eq!(bll.pop_front(), Some(1));
+        bll.add_value(3);
+        assert_eq!(bll.peek_tail_value(), Some(&3));
+
+        // the tail Node is mutably borrowed by iter_mut, then appended to
+        for value in bll.iter_mut() {
+            *value *= 10;
+        }
+        bll.add_value(4);
+        assert_eq!(bll.iter().collect::<Vec<_>>(), vec![&20, &30, &4]);
+        assert_eq!(bll.peek_tail_value(), Some(&4));
+    }
+
+    /// Time appending, iterating over and dequeueing every value, for each list and for Vec and
+    /// VecDeque (the Vec-backed std queue). Run with optimizations to get meaningful numbers:
+    ///     cargo test --release box_linked_list_benchmark -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn box_linked_list_benchmark() {
+        const VALUES: u64 = 1_000_000;
+
+        fn time(name: &str, run: impl Fn() -> u64) {
+            let mut best = Duration::MAX;
+            for _ in 0..5 {
+                let start = Instant::now();
+                black_box(run());
+                best = best.min(start.elapsed());
+            }
+            println!("{:>16}: {:?}", name, best);
+        }
+
+        time("BoxLinkedList", || {
+            let mut list = BoxLinkedList::new();
+            for value in 0..VALUES {
+                list.add_value(value);
+            }
+            let sum: u64 = list.iter().sum();
+            while list.pop_front().is_some() {}
+            sum
+        });
+        time("LinkedList (Rc)", || {
+            let mut list = LinkedList::new();
+            for value in 0..VALUES {
+                list.add_value(value);
+            }
+            let sum: u64 = list.iter().sum();
+            while list.pop_front().is_some() {}
+            sum
+        });
+        // a Vec can't dequeue from the front in O(1), so it pops from the back instead
+        time("Vec", || {
+            let mut vec = Vec::new();
+            for value in 0..VALUES {
+                vec.push(value);
+            }
+            let sum: u64 = vec.iter().sum();
+            while vec.pop().is_some() {}
+            sum
+        });
+        time("VecDeque", || {
+            let mut queue = VecDeque::new();
+            for value in 0..VALUES {
+                queue.push_back(value);
+            }
+            let sum: u64 = queue.iter().sum();
+            while queue.pop_front().is_some() {}
+            sum
+        });
+    }
+}
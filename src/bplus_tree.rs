@@ -0,0 +1,772 @@
+//! B+ tree: a B-tree variant where every value lives in a leaf, and internal nodes only hold
+//!     copies of keys to route searches. The leaves are linked into a list in key order, so a
+//!     range scan finds its first key in O(log n) and then just walks along the leaves - the
+//!     layout database indexes use.
+//!
+//! The tree's order works as in the btree module's BTreeMap: with the const generic B, every
+//!     node but the root holds between B - 1 and 2B - 1 keys. Nodes are kept in a Vec and refer
+//!     to each other by index, so the leaf list needs no shared pointers.
+//!
+//! ```
+//! use rust_datastructures_algorithms::bplus_tree::BPlusTree;
+//!
+//! let mut index: BPlusTree<u32, &str> = BPlusTree::new();
+//! for (id, name) in [(7, "g"), (3, "c"), (9, "i"), (1, "a"), (5, "e")] {
+//!     index.insert(id, name);
+//! }
+//! let scanned: Vec<(&u32, &&str)> = index.range(3..8).collect();
+//! assert_eq!(scanned, vec![(&3, &"c"), (&5, &"e"), (&7, &"g")]);
+//! ```
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::mem;
+use std::ops::{ Bound, RangeBounds };
+use crate::btree::DEFAULT_ORDER;
+
+
+/// A B+ tree struct, holding its Nodes in a Vec and referring to them by index.
+///
+/// * `nodes`: Slots holding every Node. Slots of Nodes removed by merges are left Free and
+///         reused.
+/// * `free`: Indexes of the Free slots.
+/// * `root`: Index of the root Node - an empty leaf when the tree is empty.
+/// * `first_leaf`: Index of the leftmost leaf, where scans from the smallest key start. Merges
+///         always fold a Node into its left sibling, so this leaf is never removed.
+/// * `length`: Number of key-value pairs in the tree.
+pub struct BPlusTree<K, V, const B: usize = DEFAULT_ORDER> {
+    nodes: Vec<Node<K, V>>,
+    free: Vec<usize>,
+    root: usize,
+    first_leaf: usize,
+    length: usize
+}
+
+
+/// Node in a BPlusTree struct.
+///
+/// * `Internal`: Routing Node. `keys[i]` is a separator: every key below `children[i]` is less
+///         than it, and every key below `children[i + 1]` is greater than or equal to it.
+///         There is one more child index than there are keys.
+/// * `Leaf`: Holds sorted keys with the value for each at the same index, and the index of the
+///         next leaf in key order (None for the last leaf).
+/// * `Free`: Unused slot.
+enum Node<K, V> {
+    Internal { keys: Vec<K>, children: Vec<usize> },
+    Leaf { keys: Vec<K>, values: Vec<V>, next: Option<usize> },
+    Free
+}
+
+
+/// Iterator yielding references to the key-value pairs of a BPlusTree struct within a range, in
+/// key order, by walking the linked leaves. Created by BPlusTree::range and BPlusTree::iter.
+///
+/// * `nodes`: The tree's Node slots.
+/// * `leaf`: Index of the leaf holding the next pair (None once the scan is over).
+/// * `index`: Position of the next pair in that leaf.
+/// * `end`: Bound the scan stops at.
+pub struct Range<'a, K, V> {
+    nodes: &'a [Node<K, V>],
+    leaf: Option<usize>,
+    index: usize,
+    end: Bound<K>
+}
+
+
+// Method implementations for BPlusTree struct
+impl<K, V, const B: usize> BPlusTree<K, V, B> where K: Ord + Clone {
+
+    /// Return a new, empty BPlusTree struct. Fails to compile if B is less than 2.
+    pub fn new() -> BPlusTree<K, V, B> {
+        const { assert!(B >= 2, "a B+ tree's minimum degree must be at least 2") };
+        BPlusTree {
+            nodes: vec![Node::Leaf { keys: Vec::new(), values: Vec::new(), next: None }],
+            free: Vec::new(),
+            root: 0,
+            first_leaf: 0,
+            length: 0
+        }
+    }
+
+    /// Return the number of key-value pairs in this BPlusTree.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this BPlusTree holds no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the number of levels of Nodes in this BPlusTree (1 for a lone root leaf).
+    pub fn height(&self) -> usize {
+        let mut height = 1;
+        let mut index = self.root;
+        while let Node::Internal { children, .. } = &self.nodes[index] {
+            height += 1;
+            index = children[0];
+        }
+        height
+    }
+
+    /// Insert a key-value pair into the tree. If the key was already in the tree, replace its
+    /// value and return the old one (the key itself is not replaced).
+    ///
+    /// The pair goes into its leaf; a leaf that overflows splits in two, linking the new half in
+    ///     after it, and a copy of the new half's first key is added to the parent as its
+    ///     separator. Overflowing internal Nodes split the same way up the path, except their
+    ///     middle key moves up rather than being copied. A split root grows the tree by a level.
+    ///
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (old_value, split) = self.insert_below(self.root, key, value);
+        if let Some((separator, right)) = split {
+            self.root = self.allocate(Node::Internal { keys: vec![separator], children: vec![self.root, right] });
+        }
+        if old_value.is_none() {
+            self.length += 1;
+        }
+        old_value
+    }
+
+    /// Get a reference to the value associated with the input key (if the key is in the tree).
+    ///
+    /// * `key`: key to look up - any borrowed form of the tree's key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: Ord + ?Sized {
+        match &self.nodes[self.find_leaf(key)] {
+            Node::Leaf { keys, values, .. } => search(keys, key).ok().map(|index| &values[index]),
+            _ => unreachable!("find_leaf returns a leaf")
+        }
+    }
+
+    /// Get a mutable reference to the value associated with the input key (if the key is in the
+    /// tree).
+    ///
+    /// * `key`: key to look up - any borrowed form of the tree's key type.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q>, Q: Ord + ?Sized {
+        let leaf = self.find_leaf(key);
+        match &mut self.nodes[leaf] {
+            Node::Leaf { keys, values, .. } => search(keys, key).ok().map(|index| &mut values[index]),
+            _ => unreachable!("find_leaf returns a leaf")
+        }
+    }
+
+    /// Return whether the input key is in the tree.
+    ///
+    /// * `key`: key to look for - any borrowed form of the tree's key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>, Q: Ord + ?Sized {
+        self.get(key).is_some()
+    }
+
+    /// Remove the input key from the tree and return the value that was associated with it (if
+    /// the key was in the tree).
+    ///
+    /// The pair is removed from its leaf. A Node left with fewer than B - 1 keys takes one from a
+    ///     sibling that has keys to spare (updating their separator in the parent), or else is
+    ///     merged with a sibling, which removes a separator from the parent - so the parent may
+    ///     underflow in turn. A root left with no keys is replaced by its only child.
+    ///
+    /// * `key`: key to be removed - any borrowed form of the tree's key type.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q>, Q: Ord + ?Sized {
+        let value = self.remove_below(self.root, key)?;
+        if let Node::Internal { keys, children } = &self.nodes[self.root] {
+            if keys.is_empty() {
+                let only_child = children[0];
+                self.release(self.root);
+                self.root = only_child;
+            }
+        }
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Return references to the smallest key and its value (if the tree is not empty).
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// Return an iterator of references to the key-value pairs with keys in the input range, in
+    /// key order. The first key is found by a search from the root; after that the scan only
+    /// follows the links between leaves. A range whose start is after its end yields nothing.
+    ///
+    /// * `range`: range of keys to scan, such as `start..end`, `start..=end` or `start..`.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V> {
+        let (leaf, index) = match range.start_bound() {
+            Bound::Unbounded => (self.first_leaf, 0),
+            Bound::Included(start) | Bound::Excluded(start) => {
+                let leaf = self.find_leaf(start);
+                let Node::Leaf { keys, .. } = &self.nodes[leaf] else {
+                    unreachable!("find_leaf returns a leaf");
+                };
+                let index = match range.start_bound() {
+                    Bound::Excluded(_) => keys.partition_point(|key| key <= start),
+                    _ => keys.partition_point(|key| key < start)
+                };
+                (leaf, index)
+            }
+        };
+        Range {
+            nodes: &self.nodes,
+            leaf: Some(leaf),
+            index,
+            end: range.end_bound().cloned()
+        }
+    }
+
+    /// Return an iterator of references to every key-value pair in this BPlusTree, in key order.
+    pub fn iter(&self) -> Range<'_, K, V> {
+        self.range(..)
+    }
+
+    /// Return the index of the leaf the input key is in, or would be inserted into.
+    ///
+    /// * `key`: key to look for - any borrowed form of the tree's key type.
+    fn find_leaf<Q>(&self, key: &Q) -> usize where K: Borrow<Q>, Q: Ord + ?Sized {
+        let mut index = self.root;
+        loop {
+            match &self.nodes[index] {
+                Node::Internal { keys, children } => index = children[child_position(keys, key)],
+                Node::Leaf { .. } => return index,
+                Node::Free => unreachable!("Free slots aren't linked into the tree")
+            }
+        }
+    }
+
+    /// Insert a key-value pair below the Node at the input index. Return the old value if the key
+    /// was already there, and if the Node split, the separator key and index of its new right
+    /// half for the parent to add.
+    ///
+    /// * `index`: index of the Node to insert below.
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    fn insert_below(&mut self, index: usize, key: K, value: V) -> (Option<V>, Option<(K, usize)>) {
+        let (position, child) = match &self.nodes[index] {
+            Node::Internal { keys, children } => {
+                let position = child_position(keys, &key);
+                (position, children[position])
+            },
+            Node::Leaf { .. } => return self.insert_into_leaf(index, key, value),
+            Node::Free => unreachable!("Free slots aren't linked into the tree")
+        };
+        let (old_value, split) = self.insert_below(child, key, value);
+        let Some((separator, right)) = split else {
+            return (old_value, None);
+        };
+
+        let Node::Internal { keys, children } = &mut self.nodes[index] else {
+            unreachable!("the Node was internal above");
+        };
+        keys.insert(position, separator);
+        children.insert(position + 1, right);
+        if keys.len() < 2 * B {
+            return (old_value, None);
+        }
+        // 2B keys: B stay, the next moves up, and the last B - 1 go right
+        let right_keys = keys.split_off(B + 1);
+        let right_children = children.split_off(B + 1);
+        let promoted = keys.pop().expect("the Node has 2B keys");
+        let right = self.allocate(Node::Internal { keys: right_keys, children: right_children });
+        (old_value, Some((promoted, right)))
+    }
+
+    /// Insert a key-value pair into the leaf at the input index, splitting it if it overflows.
+    /// Return values are as for insert_below.
+    ///
+    /// * `index`: index of the leaf.
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    fn insert_into_leaf(&mut self, index: usize, key: K, value: V) -> (Option<V>, Option<(K, usize)>) {
+        let Node::Leaf { keys, values, next } = &mut self.nodes[index] else {
+            unreachable!("insert_into_leaf is only called on leaves");
+        };
+        match keys.binary_search(&key) {
+            Ok(position) => return (Some(mem::replace(&mut values[position], value)), None),
+            Err(position) => {
+                keys.insert(position, key);
+                values.insert(position, value);
+            }
+        }
+        if keys.len() < 2 * B {
+            return (None, None);
+        }
+        // 2B pairs: B stay and B go right, with the right half linked in after this leaf
+        let right_keys = keys.split_off(B);
+        let right_values = values.split_off(B);
+        let right_next = next.take();
+        let separator = right_keys[0].clone();
+        let right = self.allocate(Node::Leaf { keys: right_keys, values: right_values, next: right_next });
+        if let Node::Leaf { next, .. } = &mut self.nodes[index] {
+            *next = Some(right);
+        }
+        (None, Some((separator, right)))
+    }
+
+    /// Remove the input key from below the Node at the input index and return its value (if the
+    /// key is there), fixing any child left underfull on the way back up.
+    ///
+    /// * `index`: index of the Node to remove below.
+    /// * `key`: key to be removed - any borrowed form of the tree's key type.
+    fn remove_below<Q>(&mut self, index: usize, key: &Q) -> Option<V> where K: Borrow<Q>, Q: Ord + ?Sized {
+        let (position, child) = match &mut self.nodes[index] {
+            Node::Leaf { keys, values, .. } => {
+                let position = search(keys, key).ok()?;
+                keys.remove(position);
+                return Some(values.remove(position));
+            },
+            Node::Internal { keys, children } => {
+                let position = child_position(keys, key);
+                (position, children[position])
+            },
+            Node::Free => unreachable!("Free slots aren't linked into the tree")
+        };
+        let value = self.remove_below(child, key)?;
+        if self.key_count(child) < B - 1 {
+            self.fix_underflow(index, position);
+        }
+        Some(value)
+    }
+
+    /// Give the underfull child at the input position of the input internal Node another key:
+    /// rotate one in from a sibling with keys to spare, or else merge the child with a sibling.
+    ///
+    /// * `parent`: index of the internal Node.
+    /// * `position`: position of the underfull child among the Node's children.
+    fn fix_underflow(&mut self, parent: usize, position: usize) {
+        let Node::Internal { children, .. } = &self.nodes[parent] else {
+            unreachable!("only internal Nodes have children");
+        };
+        let left = position.checked_sub(1).map(|left_position| children[left_position]);
+        let right = children.get(position + 1).copied();
+
+        if left.is_some_and(|left| self.key_count(left) >= B) {
+            self.rotate_right(parent, position - 1);
+        } else if right.is_some_and(|right| self.key_count(right) >= B) {
+            self.rotate_left(parent, position);
+        } else if left.is_some() {
+            self.merge_children(parent, position - 1);
+        } else {
+            self.merge_children(parent, position);
+        }
+    }
+
+    /// Move the last key of the child at the input position over to the start of the child after
+    /// it, through the parent's separator between them.
+    ///
+    /// * `parent`: index of the internal Node.
+    /// * `position`: position of the child giving up a key.
+    fn rotate_right(&mut self, parent: usize, position: usize) {
+        let (mut left, mut right) = self.take_children(parent, position);
+        let Node::Internal { keys: separators, .. } = &mut self.nodes[parent] else {
+            unreachable!("only internal Nodes have children");
+        };
+        match (&mut left, &mut right) {
+            (Node::Leaf { keys: left_keys, values: left_values, .. }, Node::Leaf { keys, values, .. }) => {
+                keys.insert(0, left_keys.pop().expect("the sibling has a key to spare"));
+                values.insert(0, left_values.pop().expect("the sibling has a value to spare"));
+                separators[position] = keys[0].clone();
+            },
+            (Node::Internal { keys: left_keys, children: left_children }, Node::Internal { keys, children }) => {
+                let moved_up = left_keys.pop().expect("the sibling has a key to spare");
+                keys.insert(0, mem::replace(&mut separators[position], moved_up));
+                children.insert(0, left_children.pop().expect("the sibling has a child to spare"));
+            },
+            _ => unreachable!("siblings are at the same depth")
+        }
+        self.put_children(parent, position, left, right);
+    }
+
+    /// Move the first key of the child after the input position over to the end of the child at
+    /// it, through the parent's separator between them.
+    ///
+    /// * `parent`: index of the internal Node.
+    /// * `position`: position of the child receiving a key.
+    fn rotate_left(&mut self, parent: usize, position: usize) {
+        let (mut left, mut right) = self.take_children(parent, position);
+        let Node::Internal { keys: separators, .. } = &mut self.nodes[parent] else {
+            unreachable!("only internal Nodes have children");
+        };
+        match (&mut left, &mut right) {
+            (Node::Leaf { keys, values, .. }, Node::Leaf { keys: right_keys, values: right_values, .. }) => {
+                keys.push(right_keys.remove(0));
+                values.push(right_values.remove(0));
+                separators[position] = right_keys[0].clone();
+            },
+            (Node::Internal { keys, children }, Node::Internal { keys: right_keys, children: right_children }) => {
+                let moved_up = right_keys.remove(0);
+                keys.push(mem::replace(&mut separators[position], moved_up));
+                children.push(right_children.remove(0));
+            },
+            _ => unreachable!("siblings are at the same depth")
+        }
+        self.put_children(parent, position, left, right);
+    }
+
+    /// Merge the child after the input position into the child at it, removing their separator
+    /// from the parent and freeing the right child's slot. Merged leaves take over the right
+    /// leaf's link; merged internal Nodes take the separator down between their keys.
+    ///
+    /// * `parent`: index of the internal Node.
+    /// * `position`: position of the left child of the pair.
+    fn merge_children(&mut self, parent: usize, position: usize) {
+        let (mut left, right) = self.take_children(parent, position);
+        let Node::Internal { keys: separators, children } = &mut self.nodes[parent] else {
+            unreachable!("only internal Nodes have children");
+        };
+        let separator = separators.remove(position);
+        let right_index = children.remove(position + 1);
+        let left_index = children[position];
+        match (&mut left, right) {
+            (Node::Leaf { keys, values, next }, Node::Leaf { keys: right_keys, values: right_values, next: right_next }) => {
+                keys.extend(right_keys);
+                values.extend(right_values);
+                *next = right_next;
+            },
+            (Node::Internal { keys, children }, Node::Internal { keys: right_keys, children: right_children }) => {
+                keys.push(separator);
+                keys.extend(right_keys);
+                children.extend(right_children);
+            },
+            _ => unreachable!("siblings are at the same depth")
+        }
+        self.nodes[left_index] = left;
+        self.release(right_index);
+    }
+
+    /// Take the children at the input position of the parent and the one after it out of their
+    /// slots, so both can be changed alongside the parent. They must be put back with
+    /// put_children (or released).
+    ///
+    /// * `parent`: index of the internal Node.
+    /// * `position`: position of the left child of the pair.
+    fn take_children(&mut self, parent: usize, position: usize) -> (Node<K, V>, Node<K, V>) {
+        let Node::Internal { children, .. } = &self.nodes[parent] else {
+            unreachable!("only internal Nodes have children");
+        };
+        let (left, right) = (children[position], children[position + 1]);
+        (mem::replace(&mut self.nodes[left], Node::Free), mem::replace(&mut self.nodes[right], Node::Free))
+    }
+
+    /// Put back children taken out with take_children.
+    ///
+    /// * `parent`: index of the internal Node.
+    /// * `position`: position of the left child of the pair.
+    /// * `left`: the left child.
+    /// * `right`: the right child.
+    fn put_children(&mut self, parent: usize, position: usize, left: Node<K, V>, right: Node<K, V>) {
+        let Node::Internal { children, .. } = &self.nodes[parent] else {
+            unreachable!("only internal Nodes have children");
+        };
+        let (left_index, right_index) = (children[position], children[position + 1]);
+        self.nodes[left_index] = left;
+        self.nodes[right_index] = right;
+    }
+
+    /// Return the number of keys in the Node at the input index.
+    ///
+    /// * `index`: index of the Node.
+    fn key_count(&self, index: usize) -> usize {
+        match &self.nodes[index] {
+            Node::Internal { keys, .. } | Node::Leaf { keys, .. } => keys.len(),
+            Node::Free => 0
+        }
+    }
+
+    /// Store a Node in a Free slot (or a new one) and return its index.
+    ///
+    /// * `node`: Node to be stored.
+    fn allocate(&mut self, node: Node<K, V>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Mark the slot at the input index Free, dropping the Node in it, so it can be reused.
+    ///
+    /// * `index`: index of the slot.
+    fn release(&mut self, index: usize) {
+        self.nodes[index] = Node::Free;
+        self.free.push(index);
+    }
+}
+
+/// Binary search sorted keys for the input key.
+///
+/// * `keys`: sorted keys of a leaf.
+/// * `key`: key to look for - any borrowed form of the tree's key type.
+fn search<K, Q>(keys: &[K], key: &Q) -> Result<usize, usize> where K: Borrow<Q>, Q: Ord + ?Sized {
+    keys.binary_search_by(|stored| stored.borrow().cmp(key))
+}
+
+/// Return the position of the child of an internal Node whose subtree the input key belongs in:
+/// after every separator less than or equal to it.
+///
+/// * `separators`: the internal Node's keys.
+/// * `key`: key to route - any borrowed form of the tree's key type.
+fn child_position<K, Q>(separators: &[K], key: &Q) -> usize where K: Borrow<Q>, Q: Ord + ?Sized {
+    separators.partition_point(|separator| separator.borrow() <= key)
+}
+
+impl<K, V, const B: usize> Default for BPlusTree<K, V, B> where K: Ord + Clone {
+    fn default() -> Self {
+        BPlusTree::new()
+    }
+}
+
+impl<K, V, const B: usize> fmt::Debug for BPlusTree<K, V, B> where K: Ord + Clone + fmt::Debug, V: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Insert each key-value pair in turn, so later values replace earlier ones for the same key.
+impl<K, V, const B: usize> Extend<(K, V)> for BPlusTree<K, V, B> where K: Ord + Clone {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, const B: usize> FromIterator<(K, V)> for BPlusTree<K, V, B> where K: Ord + Clone {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BPlusTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<'a, K, V, const B: usize> IntoIterator for &'a BPlusTree<K, V, B> where K: Ord + Clone {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Range<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+// Iterator implementation for Range struct
+impl<'a, K, V> Iterator for Range<'a, K, V> where K: Ord {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Node::Leaf { keys, values, next } = &self.nodes[self.leaf?] else {
+                unreachable!("the leaf list only links leaves");
+            };
+            let Some(key) = keys.get(self.index) else {
+                self.leaf = *next;
+                self.index = 0;
+                continue;
+            };
+            let past_end = match &self.end {
+                Bound::Included(end) => key > end,
+                Bound::Excluded(end) => key >= end,
+                Bound::Unbounded => false
+            };
+            if past_end {
+                self.leaf = None;
+                return None;
+            }
+            self.index += 1;
+            return Some((key, &values[self.index - 1]));
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_bplus_tree() -> BPlusTree<u32, u32, 2> {
+    let mut tree: BPlusTree<u32, u32, 2> = BPlusTree::new();
+    for key in [50, 20, 80, 10, 30, 60, 90, 40, 70] {
+        tree.insert(key, key * 10);
+    }
+    tree
+}
+
+/// Check every B+ tree invariant - Node sizes, separators bounding their subtrees, every leaf at
+/// the same depth, the leaf list visiting every pair in order, and no slot leaked or shared.
+#[cfg(test)]
+fn check_invariants<K: Ord + Clone + fmt::Debug, V, const B: usize>(tree: &BPlusTree<K, V, B>) {
+    let mut leaves_in_order = Vec::new();
+    let mut reachable = 0;
+    // (index, depth, lower bound, upper bound) still to visit, leftmost on top
+    let mut stack: Vec<(usize, usize, Option<&K>, Option<&K>)> = vec![(tree.root, 1, None, None)];
+    while let Some((index, depth, lower, upper)) = stack.pop() {
+        reachable += 1;
+        let is_root = index == tree.root;
+        let keys = match &tree.nodes[index] {
+            Node::Internal { keys, children } => {
+                assert_eq!(children.len(), keys.len() + 1);
+                assert!(!keys.is_empty());
+                for position in (0..children.len()).rev() {
+                    let child_lower = if position == 0 { lower } else { Some(&keys[position - 1]) };
+                    stack.push((children[position], depth + 1, child_lower, keys.get(position).or(upper)));
+                }
+                keys
+            },
+            Node::Leaf { keys, values, .. } => {
+                assert_eq!(keys.len(), values.len());
+                assert_eq!(depth, tree.height());
+                leaves_in_order.push(index);
+                keys
+            },
+            Node::Free => panic!("slot {} is Free but linked into the tree", index)
+        };
+        assert!(keys.len() < 2 * B);
+        assert!(is_root || keys.len() >= B - 1);
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(lower.is_none_or(|lower| keys.iter().all(|key| lower <= key)));
+        assert!(upper.is_none_or(|upper| keys.iter().all(|key| key < upper)));
+    }
+    assert_eq!(reachable + tree.free.len(), tree.nodes.len());
+
+    // following the links visits the leaves in the same order as the tree does
+    let mut linked = Vec::new();
+    let mut leaf = Some(tree.first_leaf);
+    while let Some(index) = leaf {
+        linked.push(index);
+        let Node::Leaf { next, .. } = &tree.nodes[index] else {
+            panic!("slot {} is linked into the leaf list but isn't a leaf", index);
+        };
+        leaf = *next;
+    }
+    assert_eq!(linked, leaves_in_order);
+    assert_eq!(tree.iter().count(), tree.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bplus_tree_works() {
+        let mut tree = setup_bplus_tree();
+        check_invariants(&tree);
+        assert_eq!(tree.len(), 9);
+        assert!(tree.height() > 1);
+        assert_eq!(tree.get(&30), Some(&300));
+        assert_eq!(tree.get(&35), None);
+        assert!(tree.contains_key(&90));
+
+        assert_eq!(tree.insert(30, 333), Some(300));
+        *tree.get_mut(&40).unwrap() += 1;
+        assert_eq!(tree.get(&40), Some(&401));
+        assert_eq!(tree.len(), 9);
+        assert_eq!(tree.first_key_value(), Some((&10, &100)));
+
+        assert_eq!(tree.remove(&50), Some(500));
+        assert_eq!(tree.remove(&50), None);
+        check_invariants(&tree);
+        assert_eq!(tree.iter().map(|(key, _)| *key).collect::<Vec<_>>(), vec![10, 20, 30, 40, 60, 70, 80, 90]);
+
+        let empty: BPlusTree<u32, u32> = BPlusTree::new();
+        assert_eq!(empty.first_key_value(), None);
+        assert_eq!(empty.range(1..5).next(), None);
+    }
+
+    #[test]
+    fn bplus_tree_range_scans() {
+        let tree: BPlusTree<u32, u32, 2> = (0..100).map(|key| (key * 2, key)).collect();
+        check_invariants(&tree);
+        let keys = |range: (Bound<u32>, Bound<u32>)| tree.range(range).map(|(key, _)| *key).collect::<Vec<_>>();
+
+        assert_eq!(tree.range(10..16).map(|(key, _)| *key).collect::<Vec<_>>(), vec![10, 12, 14]);
+        assert_eq!(tree.range(11..=16).map(|(key, _)| *key).collect::<Vec<_>>(), vec![12, 14, 16]);
+        assert_eq!(tree.range(190..).map(|(key, _)| *key).collect::<Vec<_>>(), vec![190, 192, 194, 196, 198]);
+        assert_eq!(tree.range(..4).map(|(key, _)| *key).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(keys((Bound::Excluded(10), Bound::Included(14))), vec![12, 14]);
+        assert_eq!(tree.range(..).count(), 100);
+        assert_eq!(tree.range(500..).count(), 0);
+        assert_eq!(tree.range(7..7).count(), 0);
+        assert_eq!(keys((Bound::Included(20), Bound::Excluded(10))), Vec::<u32>::new());
+
+        // a scan crosses many leaves, each reached by its link rather than from the root
+        assert_eq!(tree.range(3..197).count(), 97);
+    }
+
+    #[test]
+    fn bplus_tree_rebalances_on_remove() {
+        let mut tree: BPlusTree<u32, u32, 2> = (0..200).map(|key| (key, key)).collect();
+        let height = tree.height();
+        let slots = tree.nodes.len();
+
+        // removing from the front rotates and merges leaves into their left siblings
+        for key in 0..150 {
+            assert_eq!(tree.remove(&key), Some(key));
+            check_invariants(&tree);
+        }
+        assert!(tree.height() < height);
+        assert!(!tree.free.is_empty());
+
+        // freed slots are reused by later splits
+        for key in 0..150 {
+            tree.insert(key, key);
+        }
+        check_invariants(&tree);
+        assert!(tree.nodes.len() <= slots + tree.height());
+        for key in (0..200).rev() {
+            assert_eq!(tree.remove(&key), Some(key));
+        }
+        check_invariants(&tree);
+        assert!(tree.is_empty());
+        assert_eq!(tree.height(), 1);
+    }
+
+    #[test]
+    fn bplus_tree_matches_std_btree_map() {
+        fn random_operations<const B: usize>() {
+            let mut tree: BPlusTree<u32, u32, B> = BPlusTree::new();
+            let mut expected = std::collections::BTreeMap::new();
+            let mut seed: u64 = 0x2545_F491_4F6C_DD1D ^ B as u64;
+            let mut next_random = move || {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            };
+            for step in 0..4000 {
+                let random = next_random();
+                let key = (random % 500) as u32;
+                if random.is_multiple_of(3) {
+                    assert_eq!(tree.remove(&key), expected.remove(&key));
+                } else {
+                    assert_eq!(tree.insert(key, step), expected.insert(key, step));
+                }
+                if step % 100 == 0 {
+                    check_invariants(&tree);
+                    let start = (next_random() % 500) as u32;
+                    let end = start + (next_random() % 100) as u32;
+                    assert!(tree.range(start..end).eq(expected.range(start..end)));
+                    assert!(tree.range(start..=end).eq(expected.range(start..=end)));
+                }
+            }
+            check_invariants(&tree);
+            assert!(tree.iter().eq(expected.iter()));
+        }
+        random_operations::<2>();
+        random_operations::<3>();
+        random_operations::<DEFAULT_ORDER>();
+    }
+
+    #[test]
+    fn bplus_tree_traits() {
+        let tree: BPlusTree<String, u32> = [("b".to_string(), 2), ("a".to_string(), 1)].into_iter().collect();
+        assert_eq!(format!("{:?}", tree), "{\"a\": 1, \"b\": 2}");
+        assert_eq!(tree.get("a"), Some(&1));
+        assert_eq!((&tree).into_iter().count(), 2);
+        let mut tree = tree;
+        assert_eq!(tree.remove("b"), Some(2));
+        assert_eq!(BPlusTree::<u32, u32>::default().len(), 0);
+    }
+}
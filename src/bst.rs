@@ -9,10 +9,13 @@
 // [x] BinarySearchTree::print_inorder
 // [x] BinarySearchTree::print_preorder
 // [x] BinarySearchTree::print_postorder
-// [ ] BinarySearchTree::height
+// [x] BinarySearchTree::height
+// [x] BinarySearchTree::depth_of
 //
 
 use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
 use crate::linked_list;
 
 
@@ -23,413 +26,2540 @@ use crate::linked_list;
 ///
 /// * `root`: An Option-wrapped reference to the root Node of the binary search tree.
 ///         This will be None if there are zero nodes in this tree.
+/// * `size`: The number of Nodes in this tree. Maintained by add_value and remove_value so it
+///         doesn't need to be counted by traversal.
+/// * `duplicate_policy`: What add_value does with a value equal to one already in the tree. Set
+///         on construction; see DuplicatePolicy.
+///
+/// Cloning a BinarySearchTree deep-copies every Node, without recursion (see Node::fold_postorder).
+/// Its Debug output shows the nested Node structure rather than just the values - formatted
+/// recursively, one level of nesting per level of the tree, so Debug-printing a very deep tree
+/// (e.g. hundreds of thousands of Nodes built from sorted input) can overflow the stack. Print
+/// the values from iter() instead for trees that may be degenerate.
+#[derive(Debug)]
 pub struct BinarySearchTree<T: Ord> {
-    root: Option<Box<Node<T>>>
+    root: Option<Box<Node<T>>>,
+    size: usize,
+    duplicate_policy: DuplicatePolicy
 }
 
 
-/// A Node in a BinarySearchTree struct.
+/// A Node in a BinarySearchTree struct (also used by SplayTree and BinarySearchTreeBy).
 ///
-/// * `value`: Value held in this Node - must be comparable with < or > (implement Ord trait)
+/// * `value`: Value held in this Node - must be comparable with < or > (implement Ord trait), or
+///         be ordered by the comparator of the BinarySearchTreeBy struct holding it
 /// * `left_branch`: Option-wrapped reference to another Node, which should contain a Node with
 ///         lesser value. Initializes as None.
 /// * `right_branch`: Option-wrapped reference to another Node, which should contain a Node with
 ///         greater value. Initializes as None.
-pub struct Node<T: Ord> {
-    value: T,
-    left_branch: Option<Box<Node<T>>>,
-    right_branch: Option<Box<Node<T>>>
+/// * `subtree_size`: Number of Nodes in the subtree rooted at this Node (including itself).
+///         Initializes as 1 and is kept up to date by insertion and removal, so order statistics
+///         (kth_smallest, rank) can be answered by walking a single path.
+/// * `count`: Number of times this Node's value has been added to the tree. Initializes as 1, and
+///         only goes higher in trees using DuplicatePolicy::Count.
+///
+/// Cloning a Node deep-copies its subtree without recursion; Debug formats it recursively (see
+/// BinarySearchTree).
+#[derive(Debug)]
+pub struct Node<T> {
+    pub(crate) value: T,
+    pub(crate) left_branch: Option<Box<Node<T>>>,
+    pub(crate) right_branch: Option<Box<Node<T>>>,
+    pub(crate) subtree_size: usize,
+    pub(crate) count: usize
+}
+
+
+/// Enum for what a BinarySearchTree struct does when a value is added that is equal (==) to a
+/// value already in the tree.
+///
+/// * `Ignore`: Discard the new value. The default.
+/// * `Replace`: Replace the stored value with the new value (for values that are equal by Ord but
+///         otherwise differ).
+/// * `Count`: Keep the stored value and count the new one as another occurrence of it, so the
+///         tree acts as a multiset. remove_value then takes away one occurrence at a time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum DuplicatePolicy {
+    #[default]
+    Ignore,
+    Replace,
+    Count
 }
 
 
 /// Enum for traversal node order options on binary trees.
-enum TreeTraversalOrders {
-    Inorder, Preorder, Postorder
+///
+/// Levelorder is breadth-first: the root, then every Node at depth 1 from left to right, then every
+/// Node at depth 2, and so on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TreeTraversalOrders {
+    Inorder, Preorder, Postorder, Levelorder
+}
+
+
+/// Summary of the shape of a BinarySearchTree struct, returned by BinarySearchTree::stats.
+///
+/// * `height`: height of the tree (see BinarySearchTree::height).
+/// * `size`: number of Nodes in the tree.
+/// * `leaf_count`: number of Nodes with no children.
+/// * `internal_count`: number of Nodes with at least one child.
+/// * `fill_factor`: size divided by the number of Nodes in a perfect tree of the same height
+///         (2^height - 1): 1.0 for a perfect tree, approaching 0 as the tree degenerates. 1.0 for
+///         an empty tree.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TreeStats {
+    pub(crate) height: usize,
+    pub(crate) size: usize,
+    pub(crate) leaf_count: usize,
+    pub(crate) internal_count: usize,
+    pub(crate) fill_factor: f64
+}
+
+
+/// Enum for the ways a BinarySearchTree struct can be found to be broken by
+/// BinarySearchTree::check_invariants. Nodes are identified by their zero-based position in
+/// inorder order.
+///
+/// * `OutOfOrder`: The Node's value is not greater than the value of the Node before it.
+/// * `SubtreeSizeMismatch`: The Node's subtree_size is not one more than the sum of its branches'
+///         subtree sizes.
+/// * `InvalidCount`: The Node's count is 0, or above 1 in a tree not using DuplicatePolicy::Count.
+/// * `SizeMismatch`: The tree's size member doesn't match the number of Nodes in the tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum BstInvariantError {
+    OutOfOrder { position: usize },
+    SubtreeSizeMismatch { position: usize, recorded: usize, actual: usize },
+    InvalidCount { position: usize, count: usize },
+    SizeMismatch { recorded: usize, actual: usize }
+}
+
+
+/// Iterator yielding references to the values in a BinarySearchTree struct, in inorder (ascending)
+/// order. Created by BinarySearchTree::iter.
+///
+/// Implements DoubleEndedIterator, so the tree can be walked from largest to smallest value with
+/// rev(), or from both ends at once.
+///
+/// * `front_stack`: Nodes on the path to the next smallest value not yet yielded, smallest on top.
+/// * `back_stack`: Nodes on the path to the next largest value not yet yielded, largest on top.
+/// * `remaining`: Number of values not yet yielded from either end. The two stacks share Nodes, so
+///         this count is what stops them from yielding the same value twice once they meet.
+pub struct InorderIter<'a, T> {
+    front_stack: Vec<&'a Node<T>>,
+    back_stack: Vec<&'a Node<T>>,
+    remaining: usize
+}
+
+
+/// Iterator yielding references to the values in a BinarySearchTree struct, in preorder order
+/// (each Node before the Nodes in its branches). Created by BinarySearchTree::preorder_iter.
+///
+/// * `stack`: Nodes whose values have yet to be yielded, with the next Node to visit on top. A
+///         Node's right child is pushed under its left child, so the left branch is visited first.
+/// * `remaining`: Number of values not yet yielded.
+pub struct PreorderIter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+    remaining: usize
+}
+
+
+/// Iterator yielding references to the values in a BinarySearchTree struct, in postorder order
+/// (each Node after the Nodes in its branches). Created by BinarySearchTree::postorder_iter.
+///
+/// * `stack`: Nodes whose values have yet to be yielded, with the next Node to visit on top, each
+///         paired with whether its children have already been pushed above it. A Node is only
+///         yielded once it comes back to the top with its children pushed (and yielded).
+/// * `remaining`: Number of values not yet yielded.
+pub struct PostorderIter<'a, T> {
+    stack: Vec<(&'a Node<T>, bool)>,
+    remaining: usize
+}
+
+
+/// Iterator yielding references to the values in a BinarySearchTree struct in the inorder order of
+/// its mirror image (see BinarySearchTree::mirror) - right branch, Node, left branch - which is
+/// descending order. Created by BinarySearchTree::mirrored_view, without modifying the tree.
+///
+/// * `stack`: Nodes whose values have yet to be yielded, with the next Node to visit on top.
+pub struct MirroredView<'a, T> {
+    stack: Vec<&'a Node<T>>
+}
+
+
+/// Iterator yielding references to the values in a BinarySearchTree struct that fall within a
+/// range, in inorder (ascending) order. Created by BinarySearchTree::range.
+///
+/// Subtrees that lie entirely below the start of the range are never pushed onto the stack, and
+/// iteration stops at the first value past the end of the range.
+///
+/// * `stack`: Nodes whose values have yet to be yielded (possibly ending past the range), with the
+///         next Node to visit on top.
+/// * `bounds`: the range of values to yield.
+pub struct RangeIter<'a, T: Ord, R: RangeBounds<T>> {
+    stack: Vec<&'a Node<T>>,
+    bounds: R
+}
+
+
+/// Iterator yielding mutable references to the values in a BinarySearchTree struct, in inorder
+/// (ascending) order. Created by BinarySearchTree::iter_mut.
+///
+/// * `stack`: Nodes whose values have yet to be yielded, with the next Node to visit on top. Each
+///         Node is split into a mutable reference to its value and one to its right branch, as
+///         its left branch has already been borrowed (and pushed) separately.
+pub struct InorderIterMut<'a, T: Ord> {
+    stack: Vec<(&'a mut T, Option<&'a mut Node<T>>)>
+}
+
+
+/// Iterator yielding owned values taken out of a BinarySearchTree struct, in inorder (ascending)
+/// order. Created by BinarySearchTree::drain.
+///
+/// The iterator owns the Nodes it has yet to visit; each Node is dismantled as its value is yielded,
+/// with its right branch taking its place on the stack.
+///
+/// * `stack`: Nodes whose values have yet to be yielded, with the next Node to visit on top. Every
+///         Node on the stack has already had its left branch taken and pushed above it.
+/// * `remaining`: Number of values not yet yielded.
+pub struct DrainIter<T: Ord> {
+    stack: Vec<Box<Node<T>>>,
+    remaining: usize
 }
 
 
 // Method implementation for BinarySearchTree struct
 impl<T> BinarySearchTree<T> where T: Ord {
     
-    /// Return a new, empty BinarySearchTree struct
-    fn new() -> BinarySearchTree<T> {
+    /// Return a new, empty BinarySearchTree struct that ignores duplicate values.
+    pub(crate) fn new() -> BinarySearchTree<T> {
+        BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Ignore)
+    }
+
+    /// Return a new, empty BinarySearchTree struct that handles duplicate values according to the
+    ///     input policy.
+    ///
+    /// * `duplicate_policy`: variant of DuplicatePolicy enum to use for values added to this tree.
+    fn with_duplicate_policy(duplicate_policy: DuplicatePolicy) -> BinarySearchTree<T> {
+        BinarySearchTree {
+            root: None,
+            size: 0,
+            duplicate_policy
+        }
+    }
+
+    /// Return a new BinarySearchTree struct holding clones of the values in a sorted slice,
+    ///     arranged as a perfectly balanced tree. See from_sorted_iter.
+    ///
+    /// * `values`: slice of values sorted in ascending order.
+    fn from_sorted_slice(values: &[T]) -> BinarySearchTree<T> where T: Clone {
+        BinarySearchTree::from_sorted_iter(values.iter().cloned())
+    }
+
+    /// Return a new BinarySearchTree struct holding the values from a sorted iterator, arranged as
+    ///     a perfectly balanced tree: the middle value becomes the root, and the values on either
+    ///     side of it become its left and right subtrees, built the same way.
+    ///
+    /// Adding sorted values one by one with add_value would instead build a chain of right
+    ///     children with O(n) height (in O(n^2) time); this takes O(n) time for O(log n) height.
+    ///
+    /// Equal consecutive values are only added once, like with add_value.
+    ///
+    /// Panics if the values are not sorted in ascending order.
+    ///
+    /// * `values`: anything that can be iterated over to get values in ascending order.
+    fn from_sorted_iter<I: IntoIterator<Item = T>>(values: I) -> BinarySearchTree<T> {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.dedup();
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]), "values must be sorted in ascending order");
+
+        let size = values.len();
         BinarySearchTree {
-            root: None
+            root: Node::build_balanced(&mut values.into_iter(), size),
+            size,
+            duplicate_policy: DuplicatePolicy::default()
         }
     }
 
+    /// Return the number of values held in this BinarySearchTree struct. Occurrences of the same
+    ///     value counted under DuplicatePolicy::Count only count once - see count_of.
+    pub(crate) fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Return whether this BinarySearchTree struct holds no values.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
     /// Find whether a value is present in a BinarySearchTree struct.
     ///
     /// * `value`: value to be searched for.
     fn find_value(&self, value: &T) -> bool {
-        if self.root.is_some() {
-            self.root.as_ref().unwrap().find_value(value)
-        } else {
-            false
-        }
+        self.find_by(|node_value| value.cmp(node_value)).is_some()
     }
 
-    /// Add a Node to this BinarySearchTree struct.
+    /// Return the number of occurrences of the input value in this tree: 0 or 1, or possibly higher
+    ///     for trees using DuplicatePolicy::Count.
     ///
-    /// Accomplish this (if there is a root node) by beginning a recursive call to evaluate the new
-    /// value against the current node's value, starting with the root member Node.
+    /// * `value`: value to be counted.
+    fn count_of(&self, value: &T) -> usize {
+        self.find_node_by(|node_value| value.cmp(node_value)).map_or(0, |node| node.count)
+    }
+
+    /// Search this tree with a comparison closure instead of a value of type T, and return a
+    ///     reference to the value it matches. This allows searching by part of a value (e.g. by
+    ///     key alone, for values that are key-value pairs ordered by key).
     ///
-    /// It should be noted - if this value is evaluated as Ordering::Equal (== operator) to another Node's
-    /// value in this tree, this value will be discarded without a Node being added.
+    /// Walks down from the root in a loop, going left or right depending on how the target
+    ///     compares to each Node's value, so only the search path is visited and a degenerate tree
+    ///     can't overflow the stack.
     ///
-    /// * `value`: The value to be added into the binary search tree.
-    fn add_value(&mut self, value: T) {
-        match &mut self.root {
-            Some(boxed_node) => {
-                boxed_node.add_value_as_child(value);
-            },
-            None => {
-                self.root = Some(Box::new(Node::new(value)));
-            }
-        }
+    /// * `compare`: closure returning how the target compares to the value of the Node passed
+    ///         in - Ordering::Less if the target would be in its left branch. Must be consistent
+    ///         with the ordering of T.
+    pub(crate) fn find_by<F: Fn(&T) -> Ordering>(&self, compare: F) -> Option<&T> {
+        self.find_node_by(compare).map(|node| &node.value)
     }
 
-    /// Find input value in the BinarySearchTree (using Ordering::Equal (== operator)) and remove
-    ///     it (and its enclosing Node).
+    /// Same as find_by, but return a mutable reference to the matched value.
     ///
-    /// Node::remove_value_if_child is a recursive method that consumes the calling Node
-    ///     struct and returns a new allocated Box to be assigned in place.
+    /// It should be noted - the mutation must not change how the value is ordered (see iter_mut).
     ///
-    /// * `value`: Value to be removed from the binary search tree.
-    fn remove_value(&mut self, value: T) {
-        if self.root.is_some() {
-            self.root = self.root.take().unwrap().remove_value_if_child(&value);
-        }
+    /// * `compare`: closure returning how the target compares to the value of the Node passed in.
+    pub(crate) fn find_by_mut<F: Fn(&T) -> Ordering>(&mut self, compare: F) -> Option<&mut T> {
+        self.find_node_by_mut(compare).map(|node| &mut node.value)
     }
 
-    /// Create and return a vector containing references to the values held by Nodes in this
-    /// BinarySearchTree struct.
+    /// Same as find_by, but return a reference to the Node holding the matched value.
     ///
-    /// * `order`: A variant of TreeTraversalOrders enum that determines the orders of the value
-    /// references in the returned vector
-    fn collectpeek_traversal_values(&self, order: TreeTraversalOrders) -> Vec<&T> {
-        let mut list = Vec::new();
-        match order {
-            TreeTraversalOrders::Inorder => { Node::collectpeek_inorder(&self.root, &mut list); },
-            TreeTraversalOrders::Preorder => { Node::collectpeek_preorder(&self.root, &mut list); },
-            TreeTraversalOrders::Postorder => { Node::collectpeek_postorder(&self.root, &mut list); }
-        };
-        list
+    /// * `compare`: closure returning how the target compares to the value of the Node passed in.
+    fn find_node_by<F: Fn(&T) -> Ordering>(&self, compare: F) -> Option<&Node<T>> {
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            match compare(&node.value) {
+                Ordering::Less => { opt_node = node.left_branch.as_deref(); },
+                Ordering::Greater => { opt_node = node.right_branch.as_deref(); },
+                Ordering::Equal => { return Some(node); }
+            }
+        }
+        None
     }
 
-    /// Experimental version of previous method collectpeek_traversal_values_cratell that uses this
-    /// crate's LinkedList struct instead of Vec. 
-    ///
-    /// Not fully implemented - LinkedList is less tested and therefore less reliable than standard library Vecs.
+    /// Same as find_by, but return a mutable reference to the Node holding the matched value.
     ///
-    /// A speed test should be created between this method and the previous method on
-    ///     TreeTraversalOrders::Inorder.
+    /// * `compare`: closure returning how the target compares to the value of the Node passed in.
+    fn find_node_by_mut<F: Fn(&T) -> Ordering>(&mut self, compare: F) -> Option<&mut Node<T>> {
+        let mut opt_node = self.root.as_deref_mut();
+        while let Some(node) = opt_node {
+            match compare(&node.value) {
+                Ordering::Less => { opt_node = node.left_branch.as_deref_mut(); },
+                Ordering::Greater => { opt_node = node.right_branch.as_deref_mut(); },
+                Ordering::Equal => { return Some(node); }
+            }
+        }
+        None
+    }
+
+    /// Return the height of this BinarySearchTree struct: the number of Nodes on the longest path
+    ///     from the root Node down to a leaf Node.
     ///
-    /// * `order`: A variant of TreeTraversalOrders enum that determines the orders of the value
-    /// references in the returned vector
-    fn collectpeek_traversal_values_cratell(&self, order: TreeTraversalOrders) -> linked_list::LinkedList<&T> {
-        let mut list = linked_list::LinkedList::new();
-        match order {
-            TreeTraversalOrders::Inorder => { Node::collectpeek_inorder_cratell(&self.root, &mut list); },
-            TreeTraversalOrders::Preorder => { todo!() },
-            TreeTraversalOrders::Postorder => { todo!() }
-        };
-        list
+    /// An empty tree has height 0 and a tree with only a root Node has height 1. A tree built from
+    ///     sorted input degenerates into a chain, so its height is equal to its number of Nodes.
+    fn height(&self) -> usize {
+        Node::height(&self.root)
+    }
+
+    /// Return the diameter of this BinarySearchTree struct: the number of edges on the longest path
+    ///     between any two Nodes. The path doesn't have to pass through the root. See
+    ///     shape_metrics.
+    fn diameter(&self) -> usize {
+        self.shape_metrics().0
     }
-}
 
+    /// Return the width of the widest level of this BinarySearchTree struct: the greatest number of
+    ///     Nodes at the same depth. See shape_metrics.
+    fn max_width(&self) -> usize {
+        self.shape_metrics().1
+    }
 
-// Method implementations for Node struct in a BinarySearchTree struct.
-impl<T> Node<T> where T: Ord {
+    /// Return the diameter and maximum width of this BinarySearchTree struct, computed together in
+    ///     a single postorder pass (see Node::measure_shape).
+    fn shape_metrics(&self) -> (usize, usize) {
+        let mut diameter = 0;
+        let mut level_widths = Vec::new();
+        Node::measure_shape(&self.root, 0, &mut diameter, &mut level_widths);
+        (diameter, level_widths.into_iter().max().unwrap_or(0))
+    }
 
-    /// Return a new Node struct with the value T.
+    /// Return the number of leaf Nodes (Nodes with no children) in this BinarySearchTree struct.
+    fn count_leaves(&self) -> usize {
+        self.preorder_nodes()
+            .filter(|node| node.left_branch.is_none() && node.right_branch.is_none())
+            .count()
+    }
+
+    /// Return the number of internal Nodes (Nodes with at least one child) in this
+    ///     BinarySearchTree struct. Every Node is either a leaf or internal.
+    fn count_internal(&self) -> usize {
+        self.size - self.count_leaves()
+    }
+
+    /// Return a TreeStats struct summarizing the shape of this BinarySearchTree struct, e.g. to
+    ///     compare how balanced trees built different ways are.
+    fn stats(&self) -> TreeStats {
+        let height = self.height();
+        let leaf_count = self.count_leaves();
+        let fill_factor = if height == 0 {
+            1.0
+        } else {
+            self.size as f64 / (2f64.powi(height as i32) - 1.0)
+        };
+        TreeStats {
+            height,
+            size: self.size,
+            leaf_count,
+            internal_count: self.size - leaf_count,
+            fill_factor
+        }
+    }
+
+    /// Return an iterator of references to the Nodes (rather than values) in this BinarySearchTree
+    ///     struct, in preorder order, walking them with an explicit stack.
+    fn preorder_nodes(&self) -> impl Iterator<Item = &Node<T>> {
+        let mut stack: Vec<&Node<T>> = self.root.as_deref().into_iter().collect();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.right_branch.as_deref());
+            stack.extend(node.left_branch.as_deref());
+            Some(node)
+        })
+    }
+
+    /// Return the values of the Nodes visited when searching for the input value, from the root
+    ///     down to the Node holding it, or None if the value is not in the tree.
     ///
-    /// * `value`: Value to be stored in the Node.
-    fn new(value: T) -> Node<T> {
-        Node {
-            value,
-            left_branch: None,
-            right_branch: None
+    /// * `value`: value to be searched for.
+    fn path_to(&self, value: &T) -> Option<Vec<&T>> {
+        let mut path = Vec::new();
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            path.push(&node.value);
+            match value.cmp(&node.value) {
+                Ordering::Less => { opt_node = node.left_branch.as_deref(); },
+                Ordering::Greater => { opt_node = node.right_branch.as_deref(); },
+                Ordering::Equal => { return Some(path); }
+            }
         }
+        None
     }
 
-    /// Return a reference to the value held in this Struct.
+    /// Return whether this BinarySearchTree struct is height-balanced: at every Node, the heights
+    ///     of the two branches differ by at most 1 (the AVL tree condition). Such a tree has
+    ///     O(log n) height; when this returns false, rebalance can be called to restore it.
     ///
-    fn peek_value(&self) -> &T {
-        &self.value
+    /// Stops at the first unbalanced subtree found.
+    fn is_balanced(&self) -> bool {
+        Node::balanced_height(&self.root).is_some()
     }
 
-    /// 1. Check if Node's value == input value
-    /// 2. If no: if left_branch is some, call this method on an immutable ref of left child
-    /// 3. If still no: if right_branch is some, call this method on an immutable ref of right
-    ///    child.
+    /// Return the balance factor of every Node in this BinarySearchTree struct, in inorder
+    ///     (ascending) order, as (value reference, depth, balance factor) tuples.
     ///
-    /// Return whether value was found.
+    /// The balance factor of a Node is the height of its right branch minus the height of its left
+    ///     branch: positive for right-heavy Nodes, negative for left-heavy Nodes. The tree is
+    ///     balanced (see is_balanced) if every factor is -1, 0 or 1.
     ///
-    /// * `value`: Value to be checked; whether it is contained in this Node or any of this Node's
-    /// children branch Nodes.
+    /// Heights are computed bottom-up in a single pass, so this costs O(n) rather than computing
+    ///     the height of every subtree separately.
+    fn balance_factors(&self) -> Vec<(&T, usize, isize)> {
+        let mut report = Vec::with_capacity(self.size);
+        Node::collect_balance_factors(&self.root, 0, &mut report);
+        report
+    }
+
+    /// Return the depth of the Node holding the input value (the number of edges between the root
+    ///     Node and that Node), or None if the value is not present in the tree.
     ///
-    fn find_value(&self, value: &T) -> bool {
-        let mut value_found: bool = &self.value == value;
+    /// The root Node has depth 0.
+    ///
+    /// * `value`: value whose Node depth should be found.
+    fn depth_of(&self, value: &T) -> Option<usize> {
+        self.root.as_ref().and_then(|boxed_node| boxed_node.depth_of(value, 0))
+    }
 
-        if !value_found && self.left_branch.is_some() {
-            value_found = self.left_branch.as_ref().unwrap().find_value(value);
+    /// Return a reference to the smallest value in this tree that is greater than the input value,
+    ///     or None if there is no such value. The input value doesn't need to be in the tree.
+    ///
+    /// Walks down from the root: every time the search path turns left, the Node it turns at is
+    ///     the best candidate found so far.
+    ///
+    /// * `value`: value to find the successor of.
+    fn successor(&self, value: &T) -> Option<&T> {
+        let mut candidate = None;
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            if &node.value > value {
+                candidate = Some(&node.value);
+                opt_node = node.left_branch.as_deref();
+            } else {
+                opt_node = node.right_branch.as_deref();
+            }
         }
+        candidate
+    }
 
-        if !value_found && self.right_branch.is_some() {
-            value_found = self.right_branch.as_ref().unwrap().find_value(value);
+    /// Return a reference to the largest value in this tree that is less than the input value, or
+    ///     None if there is no such value. The input value doesn't need to be in the tree.
+    ///
+    /// Walks down from the root: every time the search path turns right, the Node it turns at is
+    ///     the best candidate found so far.
+    ///
+    /// * `value`: value to find the predecessor of.
+    fn predecessor(&self, value: &T) -> Option<&T> {
+        let mut candidate = None;
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            if &node.value < value {
+                candidate = Some(&node.value);
+                opt_node = node.right_branch.as_deref();
+            } else {
+                opt_node = node.left_branch.as_deref();
+            }
         }
+        candidate
+    }
 
-        value_found
+    /// Return a reference to the largest value in this tree that is less than or equal to the input
+    ///     value, or None if every value in the tree is greater.
+    ///
+    /// Same as predecessor, except that a Node holding an equal value ends the search.
+    ///
+    /// * `value`: value to find the floor of.
+    fn floor(&self, value: &T) -> Option<&T> {
+        let mut candidate = None;
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            match node.value.cmp(value) {
+                Ordering::Equal => { return Some(&node.value); },
+                Ordering::Less => {
+                    candidate = Some(&node.value);
+                    opt_node = node.right_branch.as_deref();
+                },
+                Ordering::Greater => {
+                    opt_node = node.left_branch.as_deref();
+                }
+            }
+        }
+        candidate
     }
 
-    /// Add a child Node to this Node with the input value.
+    /// Return a reference to the smallest value in this tree that is greater than or equal to the
+    ///     input value, or None if every value in the tree is less.
+    ///
+    /// Same as successor, except that a Node holding an equal value ends the search.
     ///
-    /// In binary search trees, if a new value to be added is less than a parent node's value, it
-    /// should be added as a left-branch child of the parent node. If the new value is greater, it
-    /// should be added as the right-branch child of the parent node. If the branch node already
-    /// exists, the same operation should be done on the corresponding branch child node - recursively
-    /// invoke this method on that branch child node with the same value.
+    /// * `value`: value to find the ceiling of.
+    fn ceil(&self, value: &T) -> Option<&T> {
+        let mut candidate = None;
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            match node.value.cmp(value) {
+                Ordering::Equal => { return Some(&node.value); },
+                Ordering::Greater => {
+                    candidate = Some(&node.value);
+                    opt_node = node.left_branch.as_deref();
+                },
+                Ordering::Less => {
+                    opt_node = node.right_branch.as_deref();
+                }
+            }
+        }
+        candidate
+    }
+
+    /// Return a reference to the value with k smaller values in this tree (k = 0 is the smallest
+    ///     value), or None if the tree holds k or fewer values.
     ///
-    /// Needs a mutable self reference so it can assign to left_branch/right_branch members.
+    /// Uses the subtree sizes held by each Node to pick a branch at each step, so this costs
+    ///     O(height) rather than a traversal.
     ///
-    /// * `value`: value to be held by the child Node to be added to this Node.
-    fn add_value_as_child(&mut self, value: T) {
-        match value.cmp(&self.value) {
-            Ordering::Less => {
-                if let Some(boxed_node) = &mut self.left_branch {
-                    boxed_node.add_value_as_child(value);
-                } else {
-                    self.left_branch = Some(Box::new(Node::new(value)));
+    /// * `k`: zero-based position of the value in ascending order.
+    fn kth_smallest(&self, k: usize) -> Option<&T> {
+        let mut k = k;
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            let left_size = Node::subtree_size(&node.left_branch);
+            match k.cmp(&left_size) {
+                Ordering::Less => { opt_node = node.left_branch.as_deref(); },
+                Ordering::Equal => { return Some(&node.value); },
+                Ordering::Greater => {
+                    k -= left_size + 1;
+                    opt_node = node.right_branch.as_deref();
                 }
-            },
-            Ordering::Greater => {
-                if let Some(boxed_node) = &mut self.right_branch {
-                    boxed_node.add_value_as_child(value);
-                } else  {
-                    self.right_branch = Some(Box::new(Node::new(value)));
+            }
+        }
+        None
+    }
+
+    /// Return the number of values in this tree that are less than the input value. The input
+    ///     value doesn't need to be in the tree; if it is, this is its zero-based position in
+    ///     ascending order, so kth_smallest(rank(value)) finds it again.
+    ///
+    /// Costs O(height): every time the search path turns right, the Node it turns at and its
+    ///     whole left branch are counted as less than the input value.
+    ///
+    /// * `value`: value to find the rank of.
+    fn rank(&self, value: &T) -> usize {
+        let mut rank = 0;
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            match value.cmp(&node.value) {
+                Ordering::Less => { opt_node = node.left_branch.as_deref(); },
+                Ordering::Equal => { return rank + Node::subtree_size(&node.left_branch); },
+                Ordering::Greater => {
+                    rank += Node::subtree_size(&node.left_branch) + 1;
+                    opt_node = node.right_branch.as_deref();
                 }
-            },
-            Ordering::Equal => ()
-        }
-    }
-
-    /// If this Node's value member matches the input value (== operator), remove self from tree by
-    ///     calling remove_self_from_tree and replacing the current Node in place with its return value.
-    ///     See remove_self_from_tree documentation on return value of this operation.
-    ///
-    /// It should be noted: the calling Node struct is consumed by this method if self's value members
-    ///     matches the search value.
-    /// 
-    /// Otherwise, if input value member is less than Node's value member, compare it to
-    ///     left_branch and assign left_branch member to be the return val of this function invoked
-    ///     on the left_branch Node (if it is not None - if it is None, do nothing because this
-    ///     value cannot be found in the tree)
-    ///
-    /// If this Node's value member is greater than Node's value member, compare it to right_branch
-    ///     and assign right_branch member to be the return val of this function invoked on the
-    ///     right_branch Node (if it is not None (see note above))
-    ///     
-    /// In both cases where this Node's value is not equal to the input value, return a new
-    ///     allocation of this Node in the heap (Box) because we consume the original.
-    ///
-    /// * `value`: Value to be removed from the Node or its children branches.
-    fn remove_value_if_child(mut self, value: &T) -> Option<Box<Node<T>>> {
-        match value.cmp(&self.value) {
-            Ordering::Less if self.left_branch.is_some() => {
-                self.left_branch = self.left_branch.unwrap().remove_value_if_child(value);
-            },
-            Ordering::Greater if self.right_branch.is_some() => {
-                self.right_branch = self.right_branch.unwrap().remove_value_if_child(value);
-            },
-            Ordering::Equal => { return self.remove_self_from_tree(); },
-            _ => ()
-        };
-        Some(Box::new(self))
+            }
+        }
+        rank
     }
 
-    /// This self-consume method is run on a pointer to a Node - either from a parent or from the
-    ///     BinarySearchTree struct - from the remove_value_if_child method, which assigns the
-    ///     return value of this method to "paint over" the previous reference to this Node struct.
+    /// Add a Node to this BinarySearchTree struct.
     ///
-    /// This method fundamentally therefore controls the logic of what should take a deleted Node's
-    ///     place in a BinarySearchTree struct, depending on its available children.
+    /// Accomplish this (if there is a root node) by walking down from the root member Node in a
+    /// loop, evaluating the new value against each Node's value to pick its left or right branch,
+    /// until reaching an empty branch where the new Node is placed. Every Node passed on the way
+    /// gains a descendant, so its subtree_size is incremented.
     ///
-    /// If this Node has no children: replace self with None.
+    /// It should be noted - if this value is evaluated as Ordering::Equal (== operator) to another Node's
+    /// value in this tree, no Node is added: depending on this tree's duplicate_policy, the value
+    /// is discarded, replaces the stored value, or increments the stored value's count.
     ///
-    /// If this Node has only right child or only left child: replace self with that child.
+    /// * `value`: The value to be added into the binary search tree.
+    pub(crate) fn add_value(&mut self, value: T) {
+        // check first, so subtree sizes are only incremented when a Node will be added
+        let duplicate_policy = self.duplicate_policy;
+        if let Some(node) = self.find_node_by_mut(|node_value| value.cmp(node_value)) {
+            match duplicate_policy {
+                DuplicatePolicy::Ignore => (),
+                DuplicatePolicy::Replace => { node.value = value; },
+                DuplicatePolicy::Count => { node.count += 1; }
+            }
+            return;
+        }
+
+        let mut link = &mut self.root;
+        while let Some(node) = link {
+            node.subtree_size += 1;
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &mut node.left_branch,
+                _ => &mut node.right_branch
+            };
+        }
+        *link = Some(Box::new(Node::new(value)));
+        self.size += 1;
+    }
+
+    /// Find input value in the BinarySearchTree (using Ordering::Equal (== operator)) and remove
+    ///     it (and its enclosing Node).
     ///
-    /// If this Node has two children:
-    /// 1. Find the smallest Node that is a child of this node's right child. From here on 
-    ///     we will call the Node that we find NodeB, and self NodeA.
-    ///     NodeA: { valueA }
-    ///     NodeB: { valueB } (valueB < valueA)
+    /// Return the value that was stored in the tree, or None if no equal value was found.
     ///
-    /// 2. Swap that node's value (valueB) with the value in self (valueA).
-    ///     NodeA: { valueB }
-    ///     NodeB: { valueA }
+    /// In trees using DuplicatePolicy::Count, a value counted more than once only has its count
+    ///     decremented, and the input value is handed back instead of the stored one.
     ///
-    /// 3. Recursively call down this child's right-child-branch (the branch of NodeA that 
-    ///     terminates in NodeB) to delete the node with valueA, to make sure NodeB's parent
-    ///     Node also sets its reference to NodeB as None.
+    /// * `value`: Value to be removed from the binary search tree.
+    fn remove_value(&mut self, value: T) -> Option<T> {
+        if let Some(node) = self.find_node_by_mut(|node_value| value.cmp(node_value)) {
+            if node.count > 1 {
+                node.count -= 1;
+                return Some(value);
+            }
+        }
+        self.remove_by(|node_value| value.cmp(node_value))
+    }
+
+    /// Remove the stored value equal to the input value from this tree and return it, or None if
+    ///     no equal value was found. Unlike remove_value, the value handed back is always the one
+    ///     that was stored in the tree, and no value needs to be given up to find it.
     ///
-    fn remove_self_from_tree(mut self) -> Option<Box<Node<T>>> {
-        let left_child_exists = self.left_branch.is_some();
-        let right_child_exists = self.right_branch.is_some();
+    /// In trees using DuplicatePolicy::Count, the value is removed along with all of its counted
+    ///     occurrences.
+    ///
+    /// * `value`: value equal to the one to be taken out of the tree.
+    fn take(&mut self, value: &T) -> Option<T> {
+        self.remove_by(|node_value| value.cmp(node_value))
+    }
 
-        if !left_child_exists {
-            if !right_child_exists {    // self has no children
-                return None;
-            } else {                    // self has only right child
-                return self.right_branch;
+    /// Add the input value to this tree, replacing the stored equal value if there is one, and
+    ///     return the replaced value (regardless of duplicate_policy). Return None if no equal
+    ///     value was stored, in which case a new Node is added as by add_value.
+    ///
+    /// In trees using DuplicatePolicy::Count, the replaced value's count is kept.
+    ///
+    /// * `value`: value to be placed in the tree.
+    fn replace(&mut self, value: T) -> Option<T> {
+        match self.find_by_mut(|node_value| value.cmp(node_value)) {
+            Some(stored_value) => Some(std::mem::replace(stored_value, value)),
+            None => {
+                self.add_value(value);
+                None
             }
         }
-        if !right_child_exists {        // self has only left child
-            return self.left_branch;
+    }
+
+    /// Remove the value matched by a comparison closure (see find_by) from this tree, and return
+    ///     it, or None if nothing matched.
+    ///
+    /// Walks down from the root member Node in a loop to the branch holding the Node to remove,
+    ///     decrementing the subtree_size of every Node passed on the way. That Node is then
+    ///     replaced in place by the return value of Node::remove_self_from_tree.
+    ///
+    /// * `compare`: closure returning how the target compares to the value of the Node passed in.
+    pub(crate) fn remove_by<F: Fn(&T) -> Ordering>(&mut self, compare: F) -> Option<T> {
+        // check first, so subtree sizes are only decremented when a Node will be removed
+        self.find_by(&compare)?;
+
+        // the target is in the tree, so the search path can't end on an empty branch
+        let mut link = &mut self.root;
+        while compare(&link.as_ref().unwrap().value) != Ordering::Equal {
+            let node = link.as_mut().unwrap();
+            node.subtree_size -= 1;
+            link = match compare(&node.value) {
+                Ordering::Less => &mut node.left_branch,
+                _ => &mut node.right_branch
+            };
         }
 
-        let mutref_right_branch = self.right_branch.as_deref_mut().unwrap();
-        
-        // Get a new Box pointer containing a mutable ref (from self) to the min child. This Box
-        //  goes out of scope and is dumped at the end of this method, but that's okay because we
-        //  only need it for the std::mem::swap call.
-        let node_with_new_value = mutref_right_branch.find_minimum_child_below();
+        let (replacement, removed_value) = link.take().unwrap().remove_self_from_tree();
+        *link = replacement;
+        self.size -= 1;
+        Some(removed_value)
+    }
 
-        std::mem::swap(&mut self.value, &mut node_with_new_value.value);
-        
-        mutref_right_branch.drop_misaligned_child();
+    /// Remove the smallest value from this BinarySearchTree struct and return it, or None if the
+    ///     tree is empty. Together with add_value, this lets the tree be used as a priority queue.
+    ///
+    /// Costs O(height): the value is at the end of the chain of left children from the root.
+    ///
+    /// In trees using DuplicatePolicy::Count, the value is removed along with all of its counted
+    ///     occurrences, as only one T is stored for them.
+    fn pop_min(&mut self) -> Option<T> {
+        self.root.as_ref()?;
+        let minimum_node = Node::take_minimum(&mut self.root);
+        self.size -= 1;
+        Some(minimum_node.value)
+    }
 
-        Some(Box::new(self))
+    /// Remove the largest value from this BinarySearchTree struct and return it, or None if the
+    ///     tree is empty. See pop_min.
+    fn pop_max(&mut self) -> Option<T> {
+        self.root.as_ref()?;
+        let maximum_node = Node::take_maximum(&mut self.root);
+        self.size -= 1;
+        Some(maximum_node.value)
     }
 
-    /// Recursively return a new Box pointer (mutable) to the smallest child below self.
-    /// Helper method to remove_self_from_tree method.
+    /// Rebalance this BinarySearchTree struct in place with the Day-Stout-Warren algorithm, so its
+    ///     height becomes the minimum possible for its size. Existing Nodes are relinked by
+    ///     rotations; no values are moved or reallocated.
     ///
-    fn find_minimum_child_below(&mut self) -> &mut Node<T> {
-        if self.left_branch.is_some() {
-            self.find_minimum_child_below()
-        } else {
-            self
+    /// 1. Flatten the tree into a "vine": a chain of right children in ascending order, by rotating
+    ///     right at every Node that has a left child.
+    ///
+    /// 2. Rotate left at every other Node down the vine, enough times that the number of Nodes
+    ///     left in the vine is one less than a power of two (this fills the bottom level).
+    ///
+    /// 3. Repeatedly rotate left at every other Node down the remaining vine, halving its length
+    ///     each pass, until the vine is a single Node: the root of a balanced tree.
+    ///
+    /// Runs in O(n) time with O(1) extra space, and no recursion.
+    fn rebalance(&mut self) {
+        self.flatten_to_vine();
+
+        // largest size of a complete tree that fits: one less than a power of two
+        let mut vine_length = (1 << (self.size + 1).ilog2()) - 1;
+        if vine_length != self.size {
+            // fewer Nodes than a complete tree of this height: first rotate the overflow out
+            self.compress_vine(self.size - vine_length);
+        }
+        while vine_length > 1 {
+            vine_length /= 2;
+            self.compress_vine(vine_length);
         }
     }
 
-    /// Recursively go through left-children of calling Node; remove the reference in the parent if
-    /// the child's value is greater than the parent's (should only happen in process of deleting a
-    /// node that has two child Nodes)
-    ///
-    fn drop_misaligned_child(&mut self) {
-        if let Some(left_child) = &self.left_branch {
-            if self.value < left_child.value {
-                self.left_branch = None;
+    /// Turn this tree into a chain of right children (step 1 of rebalance), by rotating right at
+    ///     each Node until it has no left child, then moving on to its right child.
+    fn flatten_to_vine(&mut self) {
+        let mut link = &mut self.root;
+        while link.is_some() {
+            if link.as_ref().unwrap().left_branch.is_some() {
+                let node = link.take().unwrap();
+                *link = Some(node.rotate_right());
             } else {
-                self.drop_misaligned_child();
+                link = &mut link.as_mut().unwrap().right_branch;
             }
         }
     }
 
-    /// Assign to a Vec (using a mutable reference to it) node value references of this Node and
-    /// its branch-children Nodes, using inorder traversal, recursively calling this method.
+    /// Rotate left at the top of the vine and then at every other Node below it, the input number
+    ///     of times (steps 2 and 3 of rebalance). Each rotation moves a Node off the vine, to
+    ///     become the left child of the Node below it.
     ///
-    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
-    ///         Node's branch members
-    /// * `list`: mutable references to the Vec where Node value references should be added.
-    fn collectpeek_inorder<'a>(
-        opt_node: &'a Option<Box<Node<T>>>,
-        list: &mut Vec<&'a T>
-    ) {
-        if let Some(boxed_node) = opt_node {
-            Node::collectpeek_inorder(&boxed_node.left_branch, list);
-            list.push(&boxed_node.value);
-            Node::collectpeek_inorder(&boxed_node.right_branch, list);
+    /// * `count`: number of rotations to do. The vine must have at least twice this many Nodes.
+    fn compress_vine(&mut self, count: usize) {
+        let mut link = &mut self.root;
+        for _ in 0..count {
+            let node = link.take().unwrap();
+            *link = Some(node.rotate_left());
+            link = &mut link.as_mut().unwrap().right_branch;
         }
     }
 
-    /// Assign to a LinkedList (from this crate) (using a mutable reference to it) node value references of this Node and
-    /// its branch-children Nodes, using inorder traversal, recursively calling this method.
+    /// Create and return a vector containing references to the values held by Nodes in this
+    /// BinarySearchTree struct.
     ///
-    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
-    ///         Node's branch members
-    /// * `list`: mutable references to the LinkedList where Node value references should be added.
-    fn collectpeek_inorder_cratell<'a>(
-        opt_node: &'a Option<Box<Node<T>>>, 
-        list: &mut linked_list::LinkedList<&'a T>
-    ) {
-        if let Some(boxed_node) = opt_node {
-            Node::collectpeek_inorder_cratell(&boxed_node.left_branch, list);
-            list.add_value(&boxed_node.value);
-            Node::collectpeek_inorder_cratell(&boxed_node.right_branch, list);
-        }
-    }
-
+    /// * `order`: A variant of TreeTraversalOrders enum that determines the orders of the value
+    /// references in the returned vector
+    fn collectpeek_traversal_values(&self, order: TreeTraversalOrders) -> Vec<&T> {
+        let mut list = Vec::new();
+        match order {
+            TreeTraversalOrders::Inorder => { Node::collectpeek_inorder(&self.root, &mut list); },
+            TreeTraversalOrders::Preorder => { Node::collectpeek_preorder(&self.root, &mut list); },
+            TreeTraversalOrders::Postorder => { Node::collectpeek_postorder(&self.root, &mut list); },
+            TreeTraversalOrders::Levelorder => { Node::collectpeek_levelorder(&self.root, &mut list); }
+        };
+        list
+    }
+
+    /// Call the input closure on a reference to each value held by Nodes in this BinarySearchTree
+    /// struct, in the input order, without collecting the values first.
+    ///
+    /// Inorder, preorder and postorder traversals recurse down the tree and allocate nothing;
+    /// levelorder traversal needs a queue holding at most two levels of Nodes at a time.
+    ///
+    /// * `order`: A variant of TreeTraversalOrders enum that determines the order the closure is
+    ///         called on values in.
+    /// * `visit`: closure to call on each value.
+    fn traverse_with<F: FnMut(&T)>(&self, order: TreeTraversalOrders, mut visit: F) {
+        match order {
+            TreeTraversalOrders::Inorder => { Node::visit_inorder(&self.root, &mut visit); },
+            TreeTraversalOrders::Preorder => { Node::visit_preorder(&self.root, &mut visit); },
+            TreeTraversalOrders::Postorder => { Node::visit_postorder(&self.root, &mut visit); },
+            TreeTraversalOrders::Levelorder => { Node::visit_levelorder(&self.root, &mut visit); }
+        }
+    }
+
+    /// Create and return a vector of vectors containing references to the values held by Nodes in
+    /// this BinarySearchTree struct, grouped by depth: the first vector holds the root value, the
+    /// second the values at depth 1, and so on, each from left to right.
+    fn levels(&self) -> Vec<Vec<&T>> {
+        Node::collectpeek_levels(&self.root)
+    }
+
+    /// Return whether this tree and the other tree have the same structure: a Node in every
+    ///     position where the other tree has a Node, and nowhere else. Values are not compared.
+    ///
+    /// Use == to compare contents regardless of shape instead.
+    ///
+    /// * `other`: the BinarySearchTree struct to compare shapes with.
+    fn same_shape(&self, other: &BinarySearchTree<T>) -> bool {
+        Node::same_shape(&self.root, &other.root)
+    }
+
+    /// Return an iterator of references to the values in this BinarySearchTree struct, in inorder
+    ///     (ascending) order. Call rev() on it to get values in descending order.
+    pub(crate) fn iter(&self) -> InorderIter<'_, T> {
+        InorderIter::new(self.root.as_deref(), self.size)
+    }
+
+    /// Return an iterator of references to the values in this BinarySearchTree struct, in preorder
+    ///     order. Values are visited lazily with an explicit stack of O(height) Nodes, unlike
+    ///     collectpeek_traversal_values which recurses and collects every value up front.
+    fn preorder_iter(&self) -> PreorderIter<'_, T> {
+        PreorderIter::new(self.root.as_deref(), self.size)
+    }
+
+    /// Return an iterator of references to the values in this BinarySearchTree struct, in
+    ///     postorder order. See preorder_iter.
+    fn postorder_iter(&self) -> PostorderIter<'_, T> {
+        PostorderIter::new(self.root.as_deref(), self.size)
+    }
+
+    /// Return an iterator of references to the values in this BinarySearchTree struct in the
+    ///     inorder order of its mirror image - that is, in descending order - without mirroring
+    ///     the tree itself. See MirroredView.
+    fn mirrored_view(&self) -> MirroredView<'_, T> {
+        let mut view = MirroredView { stack: Vec::new() };
+        view.push_right_branches(self.root.as_deref());
+        view
+    }
+
+    /// Swap the left and right branches of every Node in this BinarySearchTree struct, turning the
+    ///     tree into its mirror image: inorder traversal then visits values in descending order.
+    ///
+    /// It should be noted - a mirrored tree is no longer a valid binary search tree under Ord, so
+    ///     searching, adding and removing values (and check_invariants) must wait until it has been
+    ///     mirrored back by calling this method again. Traversals, height and shape methods work as
+    ///     usual. To only read values in mirrored order, use mirrored_view instead.
+    ///
+    /// Swaps the branches of Nodes taken off a worklist, so degenerate trees can be mirrored
+    ///     without overflowing the call stack.
+    fn mirror(&mut self) {
+        let mut worklist: Vec<&mut Node<T>> = self.root.as_deref_mut().into_iter().collect();
+        while let Some(node) = worklist.pop() {
+            std::mem::swap(&mut node.left_branch, &mut node.right_branch);
+            worklist.extend(node.left_branch.as_deref_mut());
+            worklist.extend(node.right_branch.as_deref_mut());
+        }
+    }
+
+    /// Return an iterator of references to the values in this BinarySearchTree struct that fall
+    ///     within the input range, in inorder (ascending) order.
+    ///
+    /// Only subtrees that can intersect the range are visited, so this costs O(height + k) for k
+    ///     values in range rather than a full traversal.
+    ///
+    /// * `bounds`: range of values to iterate over, e.g. `3..7`, `..=5` or `(Bound::Excluded(2),
+    ///         Bound::Unbounded)`.
+    fn range<R: RangeBounds<T>>(&self, bounds: R) -> RangeIter<'_, T, R> {
+        let mut stack = Vec::new();
+        let mut opt_node = self.root.as_deref();
+
+        // descend to the smallest value in range, only keeping Nodes that are not below the range
+        while let Some(node) = opt_node {
+            if RangeIter::is_after_start(&bounds, &node.value) {
+                stack.push(node);
+                opt_node = node.left_branch.as_deref();
+            } else {
+                opt_node = node.right_branch.as_deref();
+            }
+        }
+        RangeIter { stack, bounds }
+    }
+
+    /// Return an iterator of mutable references to the values in this BinarySearchTree struct, in
+    ///     inorder (ascending) order.
+    ///
+    /// It should be noted - the tree is only a valid binary search tree while every value keeps its
+    ///     ordering relative to the other values. Mutations through this iterator must only change
+    ///     parts of a value that don't affect that ordering (e.g. the value in a key-value pair whose
+    ///     keys are unique). Changing the ordering of a value leaves it in the wrong branch, after
+    ///     which searches, insertions and removals may silently miss values.
+    pub(crate) fn iter_mut(&mut self) -> InorderIterMut<'_, T> {
+        let mut iter = InorderIterMut { stack: Vec::new() };
+        iter.push_left_branches(self.root.as_deref_mut());
+        iter
+    }
+
+    /// Remove every value from this BinarySearchTree struct, and return an iterator yielding them
+    ///     by value in inorder (ascending) order. The tree is left empty (keeping its
+    ///     DuplicatePolicy), even if the iterator is dropped before it is used up.
+    ///
+    /// Each value is yielded once - occurrences counted under DuplicatePolicy::Count are not
+    ///     repeated, as T isn't required to be Clone.
+    fn drain(&mut self) -> DrainIter<T> {
+        let mut iter = DrainIter { stack: Vec::new(), remaining: self.size };
+        iter.push_left_branches(self.root.take());
+        self.size = 0;
+        iter
+    }
+
+    /// Consume this BinarySearchTree struct and return a vector of its values in inorder
+    ///     (ascending) order. See drain.
+    fn into_sorted_vec(mut self) -> Vec<T> {
+        self.drain().collect()
+    }
+
+    /// Move every value from another BinarySearchTree struct into this one, leaving this tree
+    ///     perfectly balanced.
+    ///
+    /// Rather than adding the other tree's values one at a time, both trees are drained into
+    ///     ascending sequences of detached Nodes, merged like in merge sort, and relinked into a
+    ///     balanced tree - O(n + m) time, reusing the existing Node allocations.
+    ///
+    /// Values in both trees are handled by this tree's duplicate_policy, as if the other tree's
+    ///     value were being added: it is discarded, replaces this tree's value, or has its count
+    ///     added to this tree's count.
+    ///
+    /// * `other`: the BinarySearchTree struct to take values from.
+    fn merge(&mut self, mut other: BinarySearchTree<T>) {
+        let duplicate_policy = self.duplicate_policy;
+        let mut other_drain = other.drain();
+        let other_nodes = std::iter::from_fn(|| other_drain.next_node())
+            .map(|mut node| {
+                if duplicate_policy != DuplicatePolicy::Count {
+                    node.count = 1;
+                }
+                node
+            });
+        self.merge_nodes(other_nodes);
+    }
+
+    /// Add many values to this BinarySearchTree struct at once, with the same result as adding them
+    ///     one by one with add_value in the order given, except that the tree ends up perfectly
+    ///     balanced.
+    ///
+    /// For batches large enough that adding values one by one would cost more than rebuilding
+    ///     (n * log2(size) >= size, for n new values), the values are sorted, grouped into one
+    ///     detached Node per distinct value, and merged with the tree's own Nodes as in merge -
+    ///     O(size + n log n) no matter how skewed the tree is. Smaller batches are added one by one.
+    ///
+    /// * `values`: anything that can be iterated over to get values, in any order.
+    fn insert_many<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        let mut values: Vec<T> = values.into_iter().collect();
+        let log_size = (usize::BITS - self.size.leading_zeros()) as usize;
+        if values.len() * log_size < self.size {
+            for value in values {
+                self.add_value(value);
+            }
+            return;
+        }
+
+        // stable sort, so equal values stay in the order they were given
+        values.sort();
+        let duplicate_policy = self.duplicate_policy;
+        let mut new_nodes: Vec<Box<Node<T>>> = Vec::new();
+        for value in values {
+            match new_nodes.last_mut() {
+                Some(last_node) if last_node.value == value => match duplicate_policy {
+                    DuplicatePolicy::Ignore => (),
+                    DuplicatePolicy::Replace => { last_node.value = value; },
+                    DuplicatePolicy::Count => { last_node.count += 1; }
+                },
+                _ => new_nodes.push(Box::new(Node::new(value)))
+            }
+        }
+        self.merge_nodes(new_nodes.into_iter());
+    }
+
+    /// Merge this tree's Nodes with other detached Nodes, both in ascending order, like the merge
+    ///     step of merge sort, and relink them all into a perfectly balanced tree. Helper method to
+    ///     merge and insert_many methods.
+    ///
+    /// A value in both is handled by this tree's duplicate_policy, as if the other Node's value were
+    ///     being added.
+    ///
+    /// * `other_nodes`: iterator yielding detached Nodes (with no branches) in ascending order of
+    ///         value, with no two equal values.
+    fn merge_nodes<I: Iterator<Item = Box<Node<T>>>>(&mut self, other_nodes: I) {
+        let duplicate_policy = self.duplicate_policy;
+        let mut own_drain = self.drain();
+        let mut own_nodes = std::iter::from_fn(|| own_drain.next_node()).peekable();
+        let mut other_nodes = other_nodes.peekable();
+
+        let mut merged = Vec::new();
+        loop {
+            let next_node = match (own_nodes.peek(), other_nodes.peek()) {
+                (Some(own_node), Some(other_node)) => match own_node.value.cmp(&other_node.value) {
+                    Ordering::Less => own_nodes.next(),
+                    Ordering::Greater => other_nodes.next(),
+                    Ordering::Equal => {
+                        let mut own_node = own_nodes.next().unwrap();
+                        let other_node = other_nodes.next().unwrap();
+                        match duplicate_policy {
+                            DuplicatePolicy::Ignore => (),
+                            DuplicatePolicy::Replace => { own_node.value = other_node.value; },
+                            DuplicatePolicy::Count => { own_node.count += other_node.count; }
+                        }
+                        Some(own_node)
+                    }
+                },
+                (Some(_), None) => own_nodes.next(),
+                (None, _) => other_nodes.next()
+            };
+            match next_node {
+                Some(node) => merged.push(node),
+                None => break
+            }
+        }
+
+        self.size = merged.len();
+        self.root = Node::link_balanced(&mut merged.into_iter(), self.size);
+    }
+
+    /// Return a new BinarySearchTree struct holding clones of the values in this tree, the other
+    ///     tree, or both. See combine_sorted.
+    ///
+    /// * `other`: the BinarySearchTree struct to take the union with.
+    fn union(&self, other: &BinarySearchTree<T>) -> BinarySearchTree<T> where T: Clone {
+        self.combine_sorted(other, true, true, true)
+    }
+
+    /// Return a new BinarySearchTree struct holding clones of the values in both this tree and the
+    ///     other tree. See combine_sorted.
+    ///
+    /// * `other`: the BinarySearchTree struct to take the intersection with.
+    fn intersection(&self, other: &BinarySearchTree<T>) -> BinarySearchTree<T> where T: Clone {
+        self.combine_sorted(other, false, true, false)
+    }
+
+    /// Return a new BinarySearchTree struct holding clones of the values in this tree that are not
+    ///     in the other tree. See combine_sorted.
+    ///
+    /// * `other`: the BinarySearchTree struct whose values are left out.
+    fn difference(&self, other: &BinarySearchTree<T>) -> BinarySearchTree<T> where T: Clone {
+        self.combine_sorted(other, true, false, false)
+    }
+
+    /// Walk this tree and the other tree in inorder order side by side, like the merge step of
+    ///     merge sort, and return a new, perfectly balanced BinarySearchTree struct holding clones
+    ///     of the values selected by the flags. Costs O(n + m) rather than a search per value.
+    ///
+    /// Values are treated as a set: each value is kept once, from this tree if it is in both. The
+    ///     new tree has this tree's duplicate_policy, but counts are not carried over.
+    ///
+    /// * `other`: the BinarySearchTree struct to combine with.
+    /// * `keep_own_only`: whether to keep values only in this tree.
+    /// * `keep_both`: whether to keep values in both trees.
+    /// * `keep_other_only`: whether to keep values only in the other tree.
+    fn combine_sorted(
+        &self,
+        other: &BinarySearchTree<T>,
+        keep_own_only: bool,
+        keep_both: bool,
+        keep_other_only: bool
+    ) -> BinarySearchTree<T> where T: Clone {
+        let mut own_values = self.iter().peekable();
+        let mut other_values = other.iter().peekable();
+        let mut combined = Vec::new();
+
+        loop {
+            let (value, keep) = match (own_values.peek(), other_values.peek()) {
+                (Some(own_value), Some(other_value)) => match own_value.cmp(other_value) {
+                    Ordering::Less => (own_values.next().unwrap(), keep_own_only),
+                    Ordering::Greater => (other_values.next().unwrap(), keep_other_only),
+                    Ordering::Equal => {
+                        other_values.next();
+                        (own_values.next().unwrap(), keep_both)
+                    }
+                },
+                (Some(_), None) => (own_values.next().unwrap(), keep_own_only),
+                (None, Some(_)) => (other_values.next().unwrap(), keep_other_only),
+                (None, None) => break
+            };
+            if keep {
+                combined.push(value.clone());
+            }
+        }
+
+        let size = combined.len();
+        BinarySearchTree {
+            root: Node::build_balanced(&mut combined.into_iter(), size),
+            size,
+            duplicate_policy: self.duplicate_policy
+        }
+    }
+
+    /// Check that this BinarySearchTree struct is internally consistent, returning the first problem
+    ///     found in inorder order. Meant for debugging and for tests to call after every mutation.
+    ///
+    /// Checks that values are in strictly ascending inorder order, that every Node's subtree_size
+    ///     and count are consistent, and that the tree's size matches its number of Nodes.
+    ///
+    /// Walks the tree with an explicit stack, so degenerate trees can be checked without
+    ///     overflowing the call stack. Each subtree_size is checked against the recorded sizes of
+    ///     its branches, which (with every Node checked) is enough to make every recorded size
+    ///     correct.
+    fn check_invariants(&self) -> Result<(), BstInvariantError> {
+        let mut stack: Vec<&Node<T>> = Vec::new();
+        let mut opt_node = self.root.as_deref();
+        let mut previous_value: Option<&T> = None;
+        let mut position = 0;
+
+        loop {
+            while let Some(node) = opt_node {
+                stack.push(node);
+                opt_node = node.left_branch.as_deref();
+            }
+            let Some(node) = stack.pop() else { break };
+
+            if previous_value.is_some_and(|previous_value| *previous_value >= node.value) {
+                return Err(BstInvariantError::OutOfOrder { position });
+            }
+            let actual = 1 + Node::subtree_size(&node.left_branch) + Node::subtree_size(&node.right_branch);
+            if node.subtree_size != actual {
+                return Err(BstInvariantError::SubtreeSizeMismatch { position, recorded: node.subtree_size, actual });
+            }
+            if node.count == 0 || (node.count > 1 && self.duplicate_policy != DuplicatePolicy::Count) {
+                return Err(BstInvariantError::InvalidCount { position, count: node.count });
+            }
+
+            previous_value = Some(&node.value);
+            position += 1;
+            opt_node = node.right_branch.as_deref();
+        }
+
+        if self.size != position {
+            return Err(BstInvariantError::SizeMismatch { recorded: self.size, actual: position });
+        }
+        Ok(())
+    }
+
+    /// Return a Graphviz DOT digraph of the Node structure of this BinarySearchTree struct, e.g. to
+    ///     render with `dot -Tsvg`.
+    ///
+    /// Nodes are named by their preorder position and labelled with their value (and their count,
+    ///     if above 1). A Node with only one child gets a point-shaped placeholder for the missing
+    ///     child, so left and right children are drawn on the correct sides.
+    ///
+    /// Walks the tree with an explicit stack, so degenerate trees can be exported without
+    ///     overflowing the call stack.
+    fn to_dot(&self) -> String where T: fmt::Display {
+        let mut dot = String::from("digraph BinarySearchTree {\n");
+        let mut stack: Vec<(&Node<T>, usize)> = self.root.as_deref().map(|node| (node, 0)).into_iter().collect();
+        let mut next_id = 1;
+
+        while let Some((node, id)) = stack.pop() {
+            let label = node.value.to_string().replace('\\', "\\\\").replace('"', "\\\"");
+            if node.count > 1 {
+                dot.push_str(&format!("    node{} [label=\"{} (x{})\"];\n", id, label, node.count));
+            } else {
+                dot.push_str(&format!("    node{} [label=\"{}\"];\n", id, label));
+            }
+            if node.left_branch.is_none() && node.right_branch.is_none() {
+                continue;
+            }
+
+            // push the right child first so the left subtree is named (and written) first
+            let mut children = Vec::new();
+            for (branch, side) in [(&node.left_branch, "left"), (&node.right_branch, "right")] {
+                match branch {
+                    Some(child) => {
+                        dot.push_str(&format!("    node{} -> node{};\n", id, next_id));
+                        children.push((child.as_ref(), next_id));
+                        next_id += 1;
+                    },
+                    None => {
+                        dot.push_str(&format!("    node{}_{} [shape=point];\n", id, side));
+                        dot.push_str(&format!("    node{} -> node{}_{};\n", id, id, side));
+                    }
+                }
+            }
+            stack.extend(children.into_iter().rev());
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Return a sideways drawing of the Node structure of this BinarySearchTree struct, one value
+    ///     per line, in the style of the `tree` command with ASCII line characters:
+    ///
+    /// ```text
+    /// 4
+    /// |-- 2
+    /// |   |-- 1
+    /// |   `-- 3
+    /// `-- 6
+    ///     |-- 5
+    ///     `-- (none)
+    /// ```
+    ///
+    /// Each Node's left child is drawn above its right child. A Node with only one child shows
+    ///     `(none)` in place of the missing child, so the two sides can be told apart. Values
+    ///     counted more than once (DuplicatePolicy::Count) are followed by their count.
+    ///
+    /// Walks the tree with an explicit stack, so degenerate trees can be drawn without overflowing
+    ///     the call stack.
+    fn render_ascii(&self) -> String where T: fmt::Display {
+        let mut rendered = String::new();
+        // (Node to draw, what to write before it on its line, what to indent its children with)
+        let mut stack: Vec<(Option<&Node<T>>, String, String)> = Vec::new();
+        if let Some(root) = self.root.as_deref() {
+            stack.push((Some(root), String::new(), String::new()));
+        }
+
+        while let Some((opt_node, line_prefix, child_prefix)) = stack.pop() {
+            rendered.push_str(&line_prefix);
+            let Some(node) = opt_node else {
+                rendered.push_str("(none)\n");
+                continue;
+            };
+            if node.count > 1 {
+                rendered.push_str(&format!("{} (x{})\n", node.value, node.count));
+            } else {
+                rendered.push_str(&format!("{}\n", node.value));
+            }
+
+            if node.left_branch.is_some() || node.right_branch.is_some() {
+                stack.push((node.right_branch.as_deref(), format!("{}`-- ", child_prefix), format!("{}    ", child_prefix)));
+                stack.push((node.left_branch.as_deref(), format!("{}|-- ", child_prefix), format!("{}|   ", child_prefix)));
+            }
+        }
+        rendered
+    }
+
+    /// Experimental version of previous method collectpeek_traversal_values_cratell that uses this
+    /// crate's LinkedList struct instead of Vec. 
+    ///
+    /// Preorder and postorder values are collected from the lazy PreorderIter and PostorderIter
+    /// iterators rather than recursively.
+    ///
+    /// A speed test should be created between this method and the previous method on
+    ///     TreeTraversalOrders::Inorder.
+    ///
+    /// * `order`: A variant of TreeTraversalOrders enum that determines the orders of the value
+    /// references in the returned vector
+    fn collectpeek_traversal_values_cratell(&self, order: TreeTraversalOrders) -> linked_list::LinkedList<&T> {
+        let mut list = linked_list::LinkedList::new();
+        match order {
+            TreeTraversalOrders::Inorder => { Node::collectpeek_inorder_cratell(&self.root, &mut list); },
+            TreeTraversalOrders::Preorder => {
+                for value in self.preorder_iter() {
+                    list.add_value(value);
+                }
+            },
+            TreeTraversalOrders::Postorder => {
+                for value in self.postorder_iter() {
+                    list.add_value(value);
+                }
+            },
+            TreeTraversalOrders::Levelorder => { Node::visit_levelorder(&self.root, &mut |value| list.add_value(value)); }
+        };
+        list
+    }
+}
+
+
+impl<T> Default for BinarySearchTree<T> where T: Ord {
+    fn default() -> Self {
+        BinarySearchTree::new()
+    }
+}
+
+/// Two trees are equal if they hold equal values (with equal counts), regardless of the shape they
+/// are arranged in.
+impl<T> PartialEq for BinarySearchTree<T> where T: Ord {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+            && self.iter().zip(other.iter())
+                .all(|(value, other_value)| value == other_value && self.count_of(value) == other.count_of(value))
+    }
+}
+
+impl<T> Eq for BinarySearchTree<T> where T: Ord {}
+
+
+// Deep-copy every Node without recursion (see Node::fold_postorder), so cloning a degenerate tree
+// can't overflow the stack.
+impl<T> Clone for BinarySearchTree<T> where T: Ord + Clone {
+    fn clone(&self) -> Self {
+        BinarySearchTree {
+            root: Node::clone_subtree(&self.root),
+            size: self.size,
+            duplicate_policy: self.duplicate_policy
+        }
+    }
+}
+
+
+impl<T> Clone for Node<T> where T: Clone {
+    fn clone(&self) -> Self {
+        Node {
+            value: self.value.clone(),
+            left_branch: Node::clone_subtree(&self.left_branch),
+            right_branch: Node::clone_subtree(&self.right_branch),
+            subtree_size: self.subtree_size,
+            count: self.count
+        }
+    }
+}
+
+
+// Dropping a Box<Node> drops its branches recursively, one stack frame per level, so the default
+// drop of a degenerate tree (e.g. built from sorted input) can overflow the stack. Instead, unlink
+// every Node's children onto a worklist before the Node itself is dropped, so each Node is dropped
+// without any branches.
+impl<T> Drop for BinarySearchTree<T> where T: Ord {
+    fn drop(&mut self) {
+        Node::drop_iteratively(self.root.take());
+    }
+}
+
+
+// Serialize a BinarySearchTree struct as the sequence of its values in inorder (ascending) order,
+// so the format doesn't depend on the tree's shape. Each value is serialized once, and the
+// duplicate_policy isn't serialized.
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for BinarySearchTree<T> where T: Ord + serde::Serialize {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+// Deserialize a BinarySearchTree struct from a sequence of values in ascending order, rebuilding it
+// perfectly balanced (see from_sorted_iter) with the default DuplicatePolicy. Unsorted input is an
+// error rather than a panic.
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for BinarySearchTree<T> where T: Ord + serde::Deserialize<'de> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values: Vec<T> = Vec::deserialize(deserializer)?;
+        if !values.windows(2).all(|pair| pair[0] <= pair[1]) {
+            return Err(serde::de::Error::custom("values must be sorted in ascending order"));
+        }
+        Ok(BinarySearchTree::from_sorted_iter(values))
+    }
+}
+
+
+impl fmt::Display for BstInvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BstInvariantError::OutOfOrder { position } => {
+                write!(f, "node {} is not greater than the node before it", position)
+            },
+            BstInvariantError::SubtreeSizeMismatch { position, recorded, actual } => {
+                write!(f, "node {} has subtree_size {} but its subtree holds {} nodes", position, recorded, actual)
+            },
+            BstInvariantError::InvalidCount { position, count } => {
+                write!(f, "node {} has invalid count {}", position, count)
+            },
+            BstInvariantError::SizeMismatch { recorded, actual } => {
+                write!(f, "tree has size {} but holds {} nodes", recorded, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BstInvariantError {}
+
+
+// Method implementations for Node struct in a BinarySearchTree struct.
+impl<T> Node<T> {
+
+    /// Return a new Node struct with the value T.
+    ///
+    /// * `value`: Value to be stored in the Node.
+    pub(crate) fn new(value: T) -> Node<T> {
+        Node {
+            value,
+            left_branch: None,
+            right_branch: None,
+            subtree_size: 1,
+            count: 1
+        }
+    }
+
+    /// Return a reference to the value held in this Struct.
+    ///
+    fn peek_value(&self) -> &T {
+        &self.value
+    }
+
+    /// Recursively compare the shapes of two subtrees rooted in Option-wrapped Node references.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference in the first tree.
+    /// * `other_opt_node`: Option-wrapped Node reference in the same position of the second tree.
+    fn same_shape(opt_node: &Option<Box<Node<T>>>, other_opt_node: &Option<Box<Node<T>>>) -> bool {
+        match (opt_node, other_opt_node) {
+            (Some(boxed_node), Some(other_boxed_node)) => {
+                Node::same_shape(&boxed_node.left_branch, &other_boxed_node.left_branch)
+                    && Node::same_shape(&boxed_node.right_branch, &other_boxed_node.right_branch)
+            },
+            (None, None) => true,
+            _ => false
+        }
+    }
+
+    /// Recursively build a perfectly balanced subtree out of the next `count` values of an iterator
+    ///     of ascending values, returning its root.
+    ///
+    /// Values are taken in inorder order: first the left subtree is built from the first half of
+    ///     the values, then the next value is taken for the root, then the right subtree is built
+    ///     from the rest. This way the values never need to be indexed into.
+    ///
+    /// * `values`: iterator yielding values in ascending order, with at least `count` remaining.
+    /// * `count`: number of values to build the subtree from.
+    pub(crate) fn build_balanced<I: Iterator<Item = T>>(values: &mut I, count: usize) -> Option<Box<Node<T>>> {
+        if count == 0 {
+            return None;
+        }
+        let left_count = count / 2;
+        let left_branch = Node::build_balanced(values, left_count);
+        let mut node = Node::new(values.next().expect("fewer values than count"));
+        node.left_branch = left_branch;
+        node.right_branch = Node::build_balanced(values, count - left_count - 1);
+        node.subtree_size = count;
+        Some(Box::new(node))
+    }
+
+    /// Same as build_balanced, but link together existing detached Nodes (with no branches) instead
+    ///     of creating new ones, so their allocations and counts are kept.
+    ///
+    /// * `nodes`: iterator yielding detached Nodes in ascending order of value, with at least
+    ///         `count` remaining.
+    /// * `count`: number of Nodes to build the subtree from.
+    fn link_balanced<I: Iterator<Item = Box<Node<T>>>>(nodes: &mut I, count: usize) -> Option<Box<Node<T>>> {
+        if count == 0 {
+            return None;
+        }
+        let left_count = count / 2;
+        let left_branch = Node::link_balanced(nodes, left_count);
+        let mut node = nodes.next().expect("fewer nodes than count");
+        node.left_branch = left_branch;
+        node.right_branch = Node::link_balanced(nodes, count - left_count - 1);
+        node.subtree_size = count;
+        Some(node)
+    }
+
+    /// Drop the subtree rooted in an Option-wrapped Node without recursion: Nodes are taken off a
+    ///     worklist one at a time, and their branches are moved onto the worklist before the Node
+    ///     is dropped.
+    ///
+    /// * `opt_node`: Option-wrapped Node at the top of the subtree to be dropped.
+    pub(crate) fn drop_iteratively(opt_node: Option<Box<Node<T>>>) {
+        let mut worklist: Vec<Box<Node<T>>> = opt_node.into_iter().collect();
+        while let Some(mut node) = worklist.pop() {
+            worklist.extend(node.left_branch.take());
+            worklist.extend(node.right_branch.take());
+        }
+    }
+
+    /// Return a deep copy of the subtree rooted in an Option-wrapped Node reference, built bottom-up
+    ///     by fold_postorder so it doesn't recurse.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    pub(crate) fn clone_subtree(opt_node: &Option<Box<Node<T>>>) -> Option<Box<Node<T>>> where T: Clone {
+        Node::fold_postorder(opt_node, || None, |node, _, left_branch, right_branch| {
+            Some(Box::new(Node {
+                value: node.value.clone(),
+                left_branch,
+                right_branch,
+                subtree_size: node.subtree_size,
+                count: node.count
+            }))
+        })
+    }
+
+    /// Return the number of Nodes in the subtree rooted in an Option-wrapped Node reference (0 if
+    ///     there is no Node).
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    pub(crate) fn subtree_size(opt_node: &Option<Box<Node<T>>>) -> usize {
+        opt_node.as_ref().map_or(0, |boxed_node| boxed_node.subtree_size)
+    }
+
+    /// Recompute this Node's subtree_size from the subtree sizes of its branches. Must be called
+    ///     whenever a branch member is reassigned to a subtree with a different number of Nodes.
+    pub(crate) fn update_subtree_size(&mut self) {
+        self.subtree_size = 1 + Node::subtree_size(&self.left_branch) + Node::subtree_size(&self.right_branch);
+    }
+
+    /// Rotate the subtree rooted at this Node to the right, returning its new root: this Node's
+    ///     left child takes its place, this Node becomes that child's right child, and that child's
+    ///     old right branch becomes this Node's left branch. Inorder order is preserved.
+    ///
+    /// ```text
+    ///      self            left
+    ///      /  \            /  \
+    ///   left   c   =>     a   self
+    ///   /  \                  /  \
+    ///  a    b                b    c
+    /// ```
+    ///
+    /// Panics if this Node has no left child.
+    pub(crate) fn rotate_right(mut self: Box<Self>) -> Box<Node<T>> {
+        let mut new_root = self.left_branch.take().expect("can't rotate right without a left child");
+        self.left_branch = new_root.right_branch.take();
+        self.update_subtree_size();
+        new_root.right_branch = Some(self);
+        new_root.update_subtree_size();
+        new_root
+    }
+
+    /// Rotate the subtree rooted at this Node to the left, returning its new root. Mirror image of
+    ///     rotate_right.
+    ///
+    /// Panics if this Node has no right child.
+    pub(crate) fn rotate_left(mut self: Box<Self>) -> Box<Node<T>> {
+        let mut new_root = self.right_branch.take().expect("can't rotate left without a right child");
+        self.right_branch = new_root.left_branch.take();
+        self.update_subtree_size();
+        new_root.left_branch = Some(self);
+        new_root.update_subtree_size();
+        new_root
+    }
+
+    /// Compute the height of the subtree rooted in an Option-wrapped Node reference: 1 + the
+    ///     greater height of its two branches, or 0 if there is no Node.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    pub(crate) fn height(opt_node: &Option<Box<Node<T>>>) -> usize {
+        Node::fold_postorder(opt_node, || 0, |_, _, left_height, right_height| 1 + left_height.max(right_height))
+    }
+
+    /// Combine the results for the two branches of every Node in the subtree rooted in an
+    ///     Option-wrapped Node reference into a result for the Node, in postorder, and return the
+    ///     result for the whole subtree. Missing branches get the result of `empty`.
+    ///
+    /// Uses a stack of Nodes still to be entered or finished instead of recursion, so any depth
+    ///     of tree can be folded - this is what height, measure_shape and Clone are built on.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `empty`: closure returning the result for a missing Node.
+    /// * `combine`: closure taking a Node, its depth (below opt_node's Node) and the results for its
+    ///         left and right branches, and returning the result for the Node.
+    pub(crate) fn fold_postorder<'a, R>(
+        opt_node: &'a Option<Box<Node<T>>>,
+        mut empty: impl FnMut() -> R,
+        mut combine: impl FnMut(&'a Node<T>, usize, R, R) -> R
+    ) -> R {
+        // Enter pushes the Node's branches to be folded first; Finish combines their results
+        enum Step<'a, T> {
+            Enter(&'a Option<Box<Node<T>>>, usize),
+            Finish(&'a Node<T>, usize)
+        }
+        let mut steps = vec![Step::Enter(opt_node, 0)];
+        let mut results: Vec<R> = Vec::new();
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Enter(None, _) => results.push(empty()),
+                Step::Enter(Some(boxed_node), depth) => {
+                    steps.push(Step::Finish(boxed_node, depth));
+                    steps.push(Step::Enter(&boxed_node.right_branch, depth + 1));
+                    steps.push(Step::Enter(&boxed_node.left_branch, depth + 1));
+                },
+                Step::Finish(node, depth) => {
+                    let right = results.pop().expect("the right branch was folded");
+                    let left = results.pop().expect("the left branch was folded");
+                    results.push(combine(node, depth, left, right));
+                }
+            }
+        }
+        results.pop().expect("the subtree was folded")
+    }
+
+    /// Measure the subtree rooted in an Option-wrapped Node reference in postorder, returning its
+    ///     height.
+    ///
+    /// The longest path that turns at this Node goes down to the deepest Node of each branch, so
+    ///     its length in edges is the sum of the branches' heights; the diameter is the longest of
+    ///     these over every Node. Each Node also counts itself towards the width of its level.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `depth`: depth of the Node in the tree it belongs to.
+    /// * `diameter`: mutable reference to the longest path length found so far.
+    /// * `level_widths`: mutable reference to the number of Nodes found so far at each depth.
+    fn measure_shape(
+        opt_node: &Option<Box<Node<T>>>,
+        depth: usize,
+        diameter: &mut usize,
+        level_widths: &mut Vec<usize>
+    ) -> usize {
+        Node::fold_postorder(opt_node, || 0, |_, node_depth, left_height, right_height| {
+            *diameter = (*diameter).max(left_height + right_height);
+            let level = depth + node_depth;
+            if level_widths.len() <= level {
+                level_widths.resize(level + 1, 0);
+            }
+            level_widths[level] += 1;
+            1 + left_height.max(right_height)
+        })
+    }
+
+    /// Recursively compute the height of the subtree rooted in an Option-wrapped Node reference
+    ///     (see height), or return None as soon as any Node in it is found to have branches whose
+    ///     heights differ by more than 1.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    fn balanced_height(opt_node: &Option<Box<Node<T>>>) -> Option<usize> {
+        let Some(boxed_node) = opt_node else {
+            return Some(0);
+        };
+        let left_height = Node::balanced_height(&boxed_node.left_branch)?;
+        let right_height = Node::balanced_height(&boxed_node.right_branch)?;
+        if left_height.abs_diff(right_height) > 1 {
+            return None;
+        }
+        Some(1 + left_height.max(right_height))
+    }
+
+    /// Assign to a Vec (using a mutable reference to it) a (value reference, depth, balance factor)
+    ///     tuple for this Node and its branch-children Nodes in inorder order, recursively calling
+    ///     this method. Return the height of the subtree rooted in the Option-wrapped Node.
+    ///
+    /// A Node's balance factor is only known once its right branch has been visited, so its tuple
+    ///     is pushed with a placeholder factor and filled in afterwards.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `depth`: depth of the Node in the tree it belongs to.
+    /// * `report`: mutable reference to the Vec to push tuples to.
+    fn collect_balance_factors<'a>(
+        opt_node: &'a Option<Box<Node<T>>>,
+        depth: usize,
+        report: &mut Vec<(&'a T, usize, isize)>
+    ) -> usize {
+        let Some(boxed_node) = opt_node else {
+            return 0;
+        };
+        let left_height = Node::collect_balance_factors(&boxed_node.left_branch, depth + 1, report);
+        let index = report.len();
+        report.push((&boxed_node.value, depth, 0));
+        let right_height = Node::collect_balance_factors(&boxed_node.right_branch, depth + 1, report);
+        report[index].2 = right_height as isize - left_height as isize;
+        1 + left_height.max(right_height)
+    }
+
+    /// Follow the search path for the input value down from this Node, counting the edges
+    ///     traversed. Return the count if the value is found, otherwise None.
+    ///
+    /// * `value`: Value to be searched for below (and including) this Node.
+    /// * `depth`: depth of this Node in the tree it belongs to.
+    fn depth_of(&self, value: &T, depth: usize) -> Option<usize> where T: Ord {
+        let mut node = self;
+        let mut depth = depth;
+        loop {
+            let next_branch = match value.cmp(&node.value) {
+                Ordering::Less => &node.left_branch,
+                Ordering::Greater => &node.right_branch,
+                Ordering::Equal => { return Some(depth); }
+            };
+            node = next_branch.as_deref()?;
+            depth += 1;
+        }
+    }
+
+    /// This self-consume method is run on the Node to be removed - taken out of its parent or out of
+    ///     the BinarySearchTree struct - from the remove_value method, which assigns the first
+    ///     return value of this method to "paint over" the previous reference to this Node struct.
+    ///     The second return value is the value this Node held.
+    ///
+    /// This method fundamentally therefore controls the logic of what should take a deleted Node's
+    ///     place in a BinarySearchTree struct, depending on its available children.
+    ///
+    /// If this Node has no children: replace self with None.
+    ///
+    /// If this Node has only right child or only left child: replace self with that child.
+    ///
+    /// If this Node has two children:
+    /// 1. Remove the smallest Node below this Node's right child (the inorder successor of this
+    ///     Node's value) from the right branch, taking its value out of it. That Node has no left
+    ///     child, so removing it is one of the cases above.
+    ///
+    /// 2. Put the successor value in place of this Node's value. Every value in the left branch is
+    ///     still smaller than it, and every value left in the right branch is still larger.
+    ///
+    /// 3. Keep this Node (with the successor value) in place, and return the value it held before.
+    ///
+    pub(crate) fn remove_self_from_tree(mut self) -> (Option<Box<Node<T>>>, T) {
+        let left_child_exists = self.left_branch.is_some();
+        let right_child_exists = self.right_branch.is_some();
+
+        if !left_child_exists {
+            if !right_child_exists {    // self has no children
+                return (None, self.value);
+            } else {                    // self has only right child
+                return (self.right_branch, self.value);
+            }
+        }
+        if !right_child_exists {        // self has only left child
+            return (self.left_branch, self.value);
+        }
+
+        let successor_node = Node::take_minimum(&mut self.right_branch);
+        self.subtree_size -= 1;
+
+        // the successor's count moves along with its value
+        self.count = successor_node.count;
+        let self_value = std::mem::replace(&mut self.value, successor_node.value);
+        (Some(Box::new(self)), self_value)
+    }
+
+    /// Remove the Node holding the smallest value in a non-empty subtree (the end of its chain of
+    ///     left children) and return that Node. Helper method to remove_self_from_tree and
+    ///     BinarySearchTree::pop_min methods.
+    ///
+    /// Walks the chain of left children in a loop, decrementing the subtree_size of every Node
+    ///     passed; the smallest Node has no left child, so it is replaced by its right branch.
+    ///
+    /// * `link`: mutable reference to the Option-wrapped Node at the top of the subtree - can be
+    ///         called directly on a Node's branch members. Must not be None.
+    fn take_minimum(link: &mut Option<Box<Node<T>>>) -> Box<Node<T>> {
+        let mut link = link;
+        while link.as_ref().unwrap().left_branch.is_some() {
+            let node = link.as_mut().unwrap();
+            node.subtree_size -= 1;
+            link = &mut node.left_branch;
+        }
+        let mut minimum_node = link.take().unwrap();
+        *link = minimum_node.right_branch.take();
+        minimum_node
+    }
+
+    /// Remove the Node holding the largest value in a non-empty subtree (the end of its chain of
+    ///     right children) and return that Node. Mirror image of take_minimum.
+    ///
+    /// * `link`: mutable reference to the Option-wrapped Node at the top of the subtree - can be
+    ///         called directly on a Node's branch members. Must not be None.
+    fn take_maximum(link: &mut Option<Box<Node<T>>>) -> Box<Node<T>> {
+        let mut link = link;
+        while link.as_ref().unwrap().right_branch.is_some() {
+            let node = link.as_mut().unwrap();
+            node.subtree_size -= 1;
+            link = &mut node.right_branch;
+        }
+        let mut maximum_node = link.take().unwrap();
+        *link = maximum_node.left_branch.take();
+        maximum_node
+    }
+
+    /// Assign to a Vec (using a mutable reference to it) node value references of this Node and
+    /// its branch-children Nodes, using inorder traversal, recursively calling this method.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `list`: mutable references to the Vec where Node value references should be added.
+    pub(crate) fn collectpeek_inorder<'a>(
+        opt_node: &'a Option<Box<Node<T>>>,
+        list: &mut Vec<&'a T>
+    ) {
+        if let Some(boxed_node) = opt_node {
+            Node::collectpeek_inorder(&boxed_node.left_branch, list);
+            list.push(&boxed_node.value);
+            Node::collectpeek_inorder(&boxed_node.right_branch, list);
+        }
+    }
+
+    /// Assign to a LinkedList (from this crate) (using a mutable reference to it) node value references of this Node and
+    /// its branch-children Nodes, using inorder traversal, recursively calling this method.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `list`: mutable references to the LinkedList where Node value references should be added.
+    fn collectpeek_inorder_cratell<'a>(
+        opt_node: &'a Option<Box<Node<T>>>, 
+        list: &mut linked_list::LinkedList<&'a T>
+    ) {
+        if let Some(boxed_node) = opt_node {
+            Node::collectpeek_inorder_cratell(&boxed_node.left_branch, list);
+            list.add_value(&boxed_node.value);
+            Node::collectpeek_inorder_cratell(&boxed_node.right_branch, list);
+        }
+    }
+
+    /// Assign to a Vec (using a mutable reference to it) node value references of this Node and
+    /// its branch-children Nodes, using preorder traversal, recursively calling this method.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `list`: mutable references to the Vec where Node value references should be added.
+    pub(crate) fn collectpeek_preorder<'a>(
+        opt_node: &'a Option<Box<Node<T>>>, 
+        list: &mut Vec<&'a T>
+    ) {
+        if let Some(boxed_node) = opt_node {
+            list.push(&boxed_node.value);
+            Node::collectpeek_preorder(&boxed_node.left_branch, list);
+            Node::collectpeek_preorder(&boxed_node.right_branch, list);
+        }
+    }
+
     /// Assign to a Vec (using a mutable reference to it) node value references of this Node and
-    /// its branch-children Nodes, using preorder traversal, recursively calling this method.
+    /// its branch-children Nodes, using levelorder (breadth-first) traversal (see
+    /// visit_levelorder).
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `list`: mutable references to the Vec where Node value references should be added.
+    pub(crate) fn collectpeek_levelorder<'a>(
+        opt_node: &'a Option<Box<Node<T>>>,
+        list: &mut Vec<&'a T>
+    ) {
+        Node::visit_levelorder(opt_node, &mut |value| list.push(value));
+    }
+
+    /// Call a closure on node value references of this Node and its branch-children Nodes, using
+    /// inorder traversal, recursively calling this method.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `visit`: mutable reference to the closure to call on each value.
+    fn visit_inorder<'a, F: FnMut(&'a T)>(opt_node: &'a Option<Box<Node<T>>>, visit: &mut F) {
+        if let Some(boxed_node) = opt_node {
+            Node::visit_inorder(&boxed_node.left_branch, visit);
+            visit(&boxed_node.value);
+            Node::visit_inorder(&boxed_node.right_branch, visit);
+        }
+    }
+
+    /// Call a closure on node value references of this Node and its branch-children Nodes, using
+    /// preorder traversal, recursively calling this method.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `visit`: mutable reference to the closure to call on each value.
+    fn visit_preorder<'a, F: FnMut(&'a T)>(opt_node: &'a Option<Box<Node<T>>>, visit: &mut F) {
+        if let Some(boxed_node) = opt_node {
+            visit(&boxed_node.value);
+            Node::visit_preorder(&boxed_node.left_branch, visit);
+            Node::visit_preorder(&boxed_node.right_branch, visit);
+        }
+    }
+
+    /// Call a closure on node value references of this Node and its branch-children Nodes, using
+    /// postorder traversal, recursively calling this method.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `visit`: mutable reference to the closure to call on each value.
+    fn visit_postorder<'a, F: FnMut(&'a T)>(opt_node: &'a Option<Box<Node<T>>>, visit: &mut F) {
+        if let Some(boxed_node) = opt_node {
+            Node::visit_postorder(&boxed_node.left_branch, visit);
+            Node::visit_postorder(&boxed_node.right_branch, visit);
+            visit(&boxed_node.value);
+        }
+    }
+
+    /// Call a closure on node value references of this Node and its branch-children Nodes, using
+    /// levelorder (breadth-first) traversal.
+    ///
+    /// Uses this crate's LinkedList struct as a queue of Nodes waiting to be visited: each Node
+    /// dequeued has the closure called on its value and its children enqueued.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `visit`: mutable reference to the closure to call on each value.
+    fn visit_levelorder<'a, F: FnMut(&'a T)>(opt_node: &'a Option<Box<Node<T>>>, visit: &mut F) {
+        let mut queue: linked_list::LinkedList<&'a Node<T>> = linked_list::LinkedList::new();
+        if let Some(boxed_node) = opt_node {
+            queue.add_value(boxed_node);
+        }
+        while let Some(node) = queue.dequeue_value() {
+            visit(&node.value);
+            for child in [&node.left_branch, &node.right_branch].into_iter().flatten() {
+                queue.add_value(child);
+            }
+        }
+    }
+
+    /// Return node value references of this Node and its branch-children Nodes grouped by depth
+    /// (relative to this Node), using levelorder (breadth-first) traversal.
+    ///
+    /// Like collectpeek_levelorder, but each Node is queued along with its depth, and a new group
+    /// is started whenever a Node is dequeued from a deeper level than the last one.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    pub(crate) fn collectpeek_levels(opt_node: &Option<Box<Node<T>>>) -> Vec<Vec<&T>> {
+        let mut levels: Vec<Vec<&T>> = Vec::new();
+        let mut queue: linked_list::LinkedList<(&Node<T>, usize)> = linked_list::LinkedList::new();
+        if let Some(boxed_node) = opt_node {
+            queue.add_value((boxed_node, 0));
+        }
+        while let Some((node, depth)) = queue.dequeue_value() {
+            if depth == levels.len() {
+                levels.push(Vec::new());
+            }
+            levels[depth].push(&node.value);
+            for child in [&node.left_branch, &node.right_branch].into_iter().flatten() {
+                queue.add_value((child, depth + 1));
+            }
+        }
+        levels
+    }
+
+    /// Assign to a Vec (using a mutable reference to it) node value references of this Node and
+    /// its branch-children Nodes, using postorder traversal, recursively calling this method.
     ///
     /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
     ///         Node's branch members
     /// * `list`: mutable references to the Vec where Node value references should be added.
-    fn collectpeek_preorder<'a>(
+    pub(crate) fn collectpeek_postorder<'a>(
         opt_node: &'a Option<Box<Node<T>>>, 
         list: &mut Vec<&'a T>
     ) {
         if let Some(boxed_node) = opt_node {
+            Node::collectpeek_postorder(&boxed_node.left_branch, list);
+            Node::collectpeek_postorder(&boxed_node.right_branch, list);
             list.push(&boxed_node.value);
-            Node::collectpeek_preorder(&boxed_node.left_branch, list);
-            Node::collectpeek_preorder(&boxed_node.right_branch, list);
         }
     }
+}
+
+
+// Method implementations for InorderIter struct.
+impl<'a, T> InorderIter<'a, T> {
+
+    /// Return a new InorderIter struct over the subtree below (and including) the input Node.
+    ///
+    /// * `root`: Option-wrapped reference to the root Node of the subtree to iterate over.
+    /// * `size`: number of Nodes in the subtree.
+    pub(crate) fn new(root: Option<&'a Node<T>>, size: usize) -> InorderIter<'a, T> {
+        let mut iter = InorderIter {
+            front_stack: Vec::new(),
+            back_stack: Vec::new(),
+            remaining: size
+        };
+        iter.push_left_branches(root);
+        iter.push_right_branches(root);
+        iter
+    }
+
+    /// Push the input Node and then its chain of left children onto the front stack, so that the
+    ///     smallest value not yet yielded ends up on top.
+    ///
+    /// * `opt_node`: Option-wrapped reference to the Node at the top of the chain.
+    fn push_left_branches(&mut self, mut opt_node: Option<&'a Node<T>>) {
+        while let Some(node) = opt_node {
+            self.front_stack.push(node);
+            opt_node = node.left_branch.as_deref();
+        }
+    }
+
+    /// Push the input Node and then its chain of right children onto the back stack, so that the
+    ///     largest value not yet yielded ends up on top.
+    ///
+    /// * `opt_node`: Option-wrapped reference to the Node at the top of the chain.
+    fn push_right_branches(&mut self, mut opt_node: Option<&'a Node<T>>) {
+        while let Some(node) = opt_node {
+            self.back_stack.push(node);
+            opt_node = node.right_branch.as_deref();
+        }
+    }
+}
+
+impl<'a, T> Iterator for InorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.front_stack.pop()?;
+        self.push_left_branches(node.right_branch.as_deref());
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for InorderIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.back_stack.pop()?;
+        self.push_right_branches(node.left_branch.as_deref());
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for InorderIter<'a, T> {}
+
+
+// Method implementations for PreorderIter struct.
+impl<'a, T> PreorderIter<'a, T> {
+
+    /// Return a new PreorderIter struct over the subtree below (and including) the input Node.
+    ///
+    /// * `root`: Option-wrapped reference to the root Node of the subtree to iterate over.
+    /// * `size`: number of Nodes in the subtree.
+    pub(crate) fn new(root: Option<&'a Node<T>>, size: usize) -> PreorderIter<'a, T> {
+        PreorderIter { stack: root.into_iter().collect(), remaining: size }
+    }
+}
+
+impl<'a, T> Iterator for PreorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.right_branch.as_deref());
+        self.stack.extend(node.left_branch.as_deref());
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for PreorderIter<'a, T> {}
+
+
+// Method implementations for PostorderIter struct.
+impl<'a, T> PostorderIter<'a, T> {
+
+    /// Return a new PostorderIter struct over the subtree below (and including) the input Node.
+    ///
+    /// * `root`: Option-wrapped reference to the root Node of the subtree to iterate over.
+    /// * `size`: number of Nodes in the subtree.
+    pub(crate) fn new(root: Option<&'a Node<T>>, size: usize) -> PostorderIter<'a, T> {
+        PostorderIter { stack: root.map(|node| (node, false)).into_iter().collect(), remaining: size }
+    }
+}
+
+impl<'a, T> Iterator for PostorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, children_pushed) = self.stack.pop()?;
+            if children_pushed {
+                self.remaining -= 1;
+                return Some(&node.value);
+            }
+            self.stack.push((node, true));
+            self.stack.extend(node.right_branch.as_deref().map(|child| (child, false)));
+            self.stack.extend(node.left_branch.as_deref().map(|child| (child, false)));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for PostorderIter<'a, T> {}
+
+
+// Method implementations for MirroredView struct.
+impl<'a, T> MirroredView<'a, T> {
+
+    /// Push the input Node and then its chain of right children onto the stack, so that the
+    ///     largest value not yet yielded ends up on top.
+    ///
+    /// * `opt_node`: Option-wrapped reference to the Node at the top of the chain.
+    fn push_right_branches(&mut self, mut opt_node: Option<&'a Node<T>>) {
+        while let Some(node) = opt_node {
+            self.stack.push(node);
+            opt_node = node.right_branch.as_deref();
+        }
+    }
+}
+
+impl<'a, T> Iterator for MirroredView<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_right_branches(node.left_branch.as_deref());
+        Some(&node.value)
+    }
+}
+
+
+// Method implementations for RangeIter struct.
+impl<'a, T, R> RangeIter<'a, T, R> where T: Ord, R: RangeBounds<T> {
+
+    /// Return whether the input value is not below the start bound of the range.
+    ///
+    /// * `bounds`: range to check the start bound of.
+    /// * `value`: value to be checked.
+    fn is_after_start(bounds: &R, value: &T) -> bool {
+        match bounds.start_bound() {
+            Bound::Included(start) => value >= start,
+            Bound::Excluded(start) => value > start,
+            Bound::Unbounded => true
+        }
+    }
+
+    /// Return whether the input value is not above the end bound of the range.
+    ///
+    /// * `bounds`: range to check the end bound of.
+    /// * `value`: value to be checked.
+    fn is_before_end(bounds: &R, value: &T) -> bool {
+        match bounds.end_bound() {
+            Bound::Included(end) => value <= end,
+            Bound::Excluded(end) => value < end,
+            Bound::Unbounded => true
+        }
+    }
+}
+
+impl<'a, T, R> Iterator for RangeIter<'a, T, R> where T: Ord, R: RangeBounds<T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        if !RangeIter::<T, R>::is_before_end(&self.bounds, &node.value) {
+            // every remaining value is larger still
+            self.stack.clear();
+            return None;
+        }
+
+        // the right branch is entirely above this Node's value, so entirely after the start bound
+        let mut opt_node = node.right_branch.as_deref();
+        while let Some(right_node) = opt_node {
+            self.stack.push(right_node);
+            opt_node = right_node.left_branch.as_deref();
+        }
+        Some(&node.value)
+    }
+}
+
+
+// Method implementations for InorderIterMut struct.
+impl<'a, T> InorderIterMut<'a, T> where T: Ord {
+
+    /// Push the input Node and then its chain of left children onto the stack, so that the
+    ///     smallest value not yet yielded ends up on top.
+    ///
+    /// * `opt_node`: Option-wrapped mutable reference to the Node at the top of the chain.
+    fn push_left_branches(&mut self, mut opt_node: Option<&'a mut Node<T>>) {
+        while let Some(node) = opt_node {
+            // destructure to borrow each member separately
+            let Node { value, left_branch, right_branch, .. } = node;
+            self.stack.push((value, right_branch.as_deref_mut()));
+            opt_node = left_branch.as_deref_mut();
+        }
+    }
+}
+
+impl<'a, T> Iterator for InorderIterMut<'a, T> where T: Ord {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, right_branch) = self.stack.pop()?;
+        self.push_left_branches(right_branch);
+        Some(value)
+    }
+}
+
+
+// Method implementations for DrainIter struct.
+impl<T> DrainIter<T> where T: Ord {
+
+    /// Push the input Node and then its chain of left children onto the stack, detaching each left
+    ///     child from its parent so that the smallest value not yet yielded ends up on top.
+    ///
+    /// * `opt_node`: Option-wrapped Node at the top of the chain.
+    fn push_left_branches(&mut self, mut opt_node: Option<Box<Node<T>>>) {
+        while let Some(mut node) = opt_node {
+            opt_node = node.left_branch.take();
+            self.stack.push(node);
+        }
+    }
+
+    /// Return the Node holding the next smallest value, detached from both of its branches (its
+    ///     right branch takes its place on the stack).
+    fn next_node(&mut self) -> Option<Box<Node<T>>> {
+        let mut node = self.stack.pop()?;
+        self.push_left_branches(node.right_branch.take());
+        self.remaining -= 1;
+        Some(node)
+    }
+}
+
+impl<T> Iterator for DrainIter<T> where T: Ord {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_node().map(|node| node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for DrainIter<T> where T: Ord {}
+
+// Nodes left on the stack still hold their right branches, so drop them the same way as
+// BinarySearchTree does.
+impl<T> Drop for DrainIter<T> where T: Ord {
+    fn drop(&mut self) {
+        for node in self.stack.drain(..) {
+            Node::drop_iteratively(Some(node));
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_bst() -> BinarySearchTree<u32> {
+    let mut bst: BinarySearchTree<u32> = BinarySearchTree::new();
+    bst.add_value(4);
+    bst.add_value(2);
+    bst.add_value(6);
+    bst.add_value(1);
+    bst.add_value(3);
+    bst.add_value(5);
+    bst
+    //      4
+    //     / \
+    //   2    6
+    //  /\   /
+    // 1 3  5
+}
+
+/// Build a chain of Nodes holding 0..n where each Node is the right child of the one before, as
+/// if n sorted values had been added one by one (linked directly, to avoid O(n^2) insertion).
+fn setup_degenerate_bst(n: u32) -> BinarySearchTree<u32> {
+    let mut root = None;
+    for value in (0..n).rev() {
+        let mut node = Node::new(value);
+        node.right_branch = root;
+        node.subtree_size = (n - value) as usize;
+        root = Some(Box::new(node));
+    }
+    BinarySearchTree { root, size: n as usize, duplicate_policy: DuplicatePolicy::Ignore }
+}
+
+#[cfg (test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bst_can_be_created_and_added_to()  {
+        let bst = setup_bst();
+        assert!(&bst.root.is_some());
+        assert_eq!(bst.root.as_ref().unwrap().value, 4);
+    }
+
+    #[test]
+    fn bst_height_can_be_evaluated() {
+        let bst = setup_bst();
+        assert_eq!(bst.height(), 3);
+        assert_eq!(BinarySearchTree::<u32>::new().height(), 0);
+
+        let mut degenerate_bst: BinarySearchTree<u32> = BinarySearchTree::new();
+        for i in 0..10 {
+            degenerate_bst.add_value(i);
+        }
+        assert_eq!(degenerate_bst.height(), 10);
+    }
+
+    #[test]
+    fn bst_depth_of_value_can_be_found() {
+        let bst = setup_bst();
+        assert_eq!(bst.depth_of(&4), Some(0));
+        assert_eq!(bst.depth_of(&6), Some(1));
+        assert_eq!(bst.depth_of(&3), Some(2));
+        assert_eq!(bst.depth_of(&88), None);
+    }
+
+    #[test]
+    fn bst_can_be_built_balanced_from_sorted_values() {
+        let values: Vec<u32> = (1..=7).collect();
+        let bst = BinarySearchTree::from_sorted_slice(&values);
+        assert_eq!(bst.len(), 7);
+        assert_eq!(bst.height(), 3);
+        assert_eq!(bst.root.as_ref().unwrap().value, 4);
+        assert_eq!(bst.iter().collect::<Vec<_>>(), values.iter().collect::<Vec<_>>());
+        assert_eq!(bst.kth_smallest(5), Some(&6));
+
+        let bst = BinarySearchTree::from_sorted_iter((0..1000).chain(std::iter::once(999)));
+        assert_eq!(bst.len(), 1000);
+        assert_eq!(bst.height(), 10);
+        assert_eq!(bst.rank(&500), 500);
+
+        assert!(BinarySearchTree::<u32>::from_sorted_slice(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn bst_cannot_be_built_from_unsorted_values() {
+        BinarySearchTree::from_sorted_slice(&[1, 3, 2]);
+    }
+
+    #[test]
+    fn bst_can_be_rebalanced_in_place() {
+        let mut bst = setup_degenerate_bst(100_000);
+        bst.rebalance();
+        assert_eq!(bst.height(), 17);
+        assert_eq!(bst.len(), 100_000);
+        assert_eq!(bst.root.as_ref().unwrap().subtree_size, 100_000);
+        assert_eq!(bst.kth_smallest(12_345), Some(&12_345));
+        assert!(bst.iter().copied().eq(0..100_000));
+
+        for size in [0, 1, 2, 6, 7, 8] {
+            let mut bst: BinarySearchTree<usize> = BinarySearchTree::new();
+            for value in (0..size).rev() {
+                bst.add_value(value);
+            }
+            bst.rebalance();
+            assert_eq!(bst.height(), (size + 1).next_power_of_two().trailing_zeros() as usize);
+            assert!(bst.iter().copied().eq(0..size));
+        }
+    }
+
+    #[test]
+    fn bst_len_is_tracked() {
+        let mut bst = setup_bst();
+        assert_eq!(bst.len(), 6);
+        assert!(!bst.is_empty());
+
+        bst.add_value(4);
+        assert_eq!(bst.len(), 6);
+
+        bst.remove_value(88);
+        assert_eq!(bst.len(), 6);
+
+        bst.remove_value(1);
+        bst.remove_value(6);
+        assert_eq!(bst.len(), 4);
+
+        assert!(BinarySearchTree::<u32>::new().is_empty());
+    }
+
+    #[test]
+    fn bst_can_be_cloned_deeply() {
+        let bst = setup_bst();
+        let mut cloned_bst = bst.clone();
+        cloned_bst.remove_value(6);
+        cloned_bst.add_value(7);
+
+        assert_eq!(bst.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6]);
+        assert_eq!(cloned_bst.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &7]);
+        assert_eq!(cloned_bst.len(), 6);
+    }
+
+    #[test]
+    fn bst_debug_shows_structure() {
+        let mut bst: BinarySearchTree<u32> = BinarySearchTree::default();
+        assert!(bst.is_empty());
+        assert_eq!(format!("{:?}", bst), "BinarySearchTree { root: None, size: 0, duplicate_policy: Ignore }");
+
+        bst.add_value(2);
+        bst.add_value(1);
+        assert_eq!(
+            format!("{:?}", bst),
+            "BinarySearchTree { root: Some(Node { value: 2, left_branch: Some(Node { value: 1, \
+                left_branch: None, right_branch: None, subtree_size: 1, count: 1 }), right_branch: None, \
+                subtree_size: 2, count: 1 }), size: 2, duplicate_policy: Ignore }"
+        );
+    }
+
+    /// Value that is ordered by its first member only, so equal values can still differ.
+    #[derive(Debug)]
+    struct KeyedValue(u32, char);
+
+    impl PartialEq for KeyedValue {
+        fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+    }
+
+    impl Eq for KeyedValue {}
+
+    impl PartialOrd for KeyedValue {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+    }
 
-    /// Assign to a Vec (using a mutable reference to it) node value references of this Node and
-    /// its branch-children Nodes, using postorder traversal, recursively calling this method.
-    ///
-    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
-    ///         Node's branch members
-    /// * `list`: mutable references to the Vec where Node value references should be added.
-    fn collectpeek_postorder<'a>(
-        opt_node: &'a Option<Box<Node<T>>>, 
-        list: &mut Vec<&'a T>
-    ) {
-        if let Some(boxed_node) = opt_node {
-            Node::collectpeek_postorder(&boxed_node.left_branch, list);
-            Node::collectpeek_postorder(&boxed_node.right_branch, list);
-            list.push(&boxed_node.value);
+    impl Ord for KeyedValue {
+        fn cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+    }
+
+    #[test]
+    fn bst_duplicates_can_replace_stored_values() {
+        let mut bst = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Replace);
+        bst.add_value(KeyedValue(1, 'a'));
+        bst.add_value(KeyedValue(1, 'b'));
+        assert_eq!(bst.len(), 1);
+        assert_eq!(bst.iter().next().unwrap().1, 'b');
+
+        let mut bst = BinarySearchTree::new();
+        bst.add_value(KeyedValue(1, 'a'));
+        bst.add_value(KeyedValue(1, 'b'));
+        assert_eq!(bst.len(), 1);
+        assert_eq!(bst.iter().next().unwrap().1, 'a');
+        assert_eq!(bst.count_of(&KeyedValue(1, 'z')), 1);
+    }
+
+    #[test]
+    fn bst_stored_values_can_be_taken_and_replaced() {
+        let mut bst = BinarySearchTree::new();
+        bst.add_value(KeyedValue(2, 'a'));
+        bst.add_value(KeyedValue(1, 'b'));
+
+        assert_eq!(bst.replace(KeyedValue(2, 'c')).map(|value| value.1), Some('a'));
+        assert_eq!(bst.replace(KeyedValue(3, 'd')), None);
+        assert_eq!(bst.len(), 3);
+        assert_eq!(bst.take(&KeyedValue(2, ' ')).map(|value| value.1), Some('c'));
+        assert_eq!(bst.take(&KeyedValue(2, ' ')), None);
+        assert_eq!(bst.iter().map(|value| value.1).collect::<String>(), "bd");
+
+        let mut bst = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Count);
+        for value in [5, 5, 5] {
+            bst.add_value(value);
         }
+        assert_eq!(bst.replace(5), Some(5));
+        assert_eq!(bst.count_of(&5), 3);
+        assert_eq!(bst.take(&5), Some(5));
+        assert!(bst.is_empty());
     }
-}
 
+    #[test]
+    fn bst_duplicates_can_be_counted_as_a_multiset() {
+        let mut bst: BinarySearchTree<u32> = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Count);
+        for value in [4, 2, 4, 6, 4, 2] {
+            bst.add_value(value);
+        }
+        assert_eq!(bst.len(), 3);
+        assert_eq!(bst.count_of(&4), 3);
+        assert_eq!(bst.count_of(&2), 2);
+        assert_eq!(bst.count_of(&5), 0);
 
-////////////////////////////////////////////////////////////////////////////
-//  TESTS
+        assert_eq!(bst.remove_value(4), Some(4));
+        assert_eq!(bst.count_of(&4), 2);
+        assert_eq!(bst.len(), 3);
+        bst.remove_value(4);
+        bst.remove_value(4);
+        assert!(!bst.find_value(&4));
+        assert_eq!(bst.len(), 2);
 
-fn setup_bst() -> BinarySearchTree<u32> {
-    let mut bst: BinarySearchTree<u32> = BinarySearchTree::new();
-    bst.add_value(4);
-    bst.add_value(2);
-    bst.add_value(6);
-    bst.add_value(1);
-    bst.add_value(3);
-    bst.add_value(5);
-    bst
-    //      4
-    //     / \
-    //   2    6
-    //  /\   /
-    // 1 3  5
-}
+        let mut other_bst: BinarySearchTree<u32> = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Count);
+        for value in [2, 6] {
+            other_bst.add_value(value);
+        }
+        assert!(bst != other_bst);
+        other_bst.add_value(2);
+        assert!(bst == other_bst);
+    }
 
-#[cfg (test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn bst_counts_move_with_values_on_two_child_deletion() {
+        let mut bst: BinarySearchTree<u32> = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Count);
+        for value in [4, 2, 6, 6] {
+            bst.add_value(value);
+        }
+        assert_eq!(bst.remove_value(4), Some(4));
+        assert_eq!(bst.root.as_ref().unwrap().value, 6);
+        assert_eq!(bst.count_of(&6), 2);
+    }
 
     #[test]
-    fn bst_can_be_created_and_added_to()  {
+    fn bst_equality_compares_contents() {
         let bst = setup_bst();
-        assert!(&bst.root.is_some());
-        assert_eq!(bst.root.unwrap().value, 4);
+        let mut chain_bst: BinarySearchTree<u32> = BinarySearchTree::new();
+        for i in 1..=6 {
+            chain_bst.add_value(i);
+        }
+        assert!(bst == chain_bst);
+        assert!(!bst.same_shape(&chain_bst));
+
+        chain_bst.remove_value(6);
+        assert!(bst != chain_bst);
     }
 
-    // #[test]
-    fn bst_height_can_be_evaluated() {
+    #[test]
+    fn bst_shapes_can_be_compared() {
         let bst = setup_bst();
+        let mut other_bst: BinarySearchTree<u32> = BinarySearchTree::new();
+        for i in [40, 20, 60, 10, 30, 50] {
+            other_bst.add_value(i);
+        }
+        assert!(bst.same_shape(&other_bst));
+        assert!(bst != other_bst);
+
+        other_bst.add_value(70);
+        assert!(!bst.same_shape(&other_bst));
     }
 
     #[test]
@@ -441,10 +2571,111 @@ mod tests {
     }
 
     #[test]
-    fn bst_can_delete_nodes() {
+    fn bst_shape_metrics_can_be_computed() {
+        let bst = setup_bst();
+        assert_eq!(bst.diameter(), 4);
+        assert_eq!(bst.max_width(), 3);
+
+        let empty: BinarySearchTree<u32> = BinarySearchTree::new();
+        assert_eq!(empty.shape_metrics(), (0, 0));
+        assert_eq!(setup_degenerate_bst(10).shape_metrics(), (9, 1));
+
+        // longest path not passing through the root
+        let mut bst = BinarySearchTree::new();
+        for value in [10, 4, 11, 2, 6, 1, 3, 7, 8, 0] {
+            bst.add_value(value);
+        }
+        // 0-1-2-4-6-7-8 turns at 4: 6 edges, while the longest path through the root has 5
+        assert_eq!(bst.diameter(), 6);
+        assert_eq!(bst.max_width(), 3);
+    }
+
+    #[test]
+    fn bst_node_statistics_can_be_computed() {
+        let bst = setup_bst();
+        assert_eq!(bst.count_leaves(), 3);
+        assert_eq!(bst.count_internal(), 3);
+        assert_eq!(
+            bst.stats(),
+            TreeStats { height: 3, size: 6, leaf_count: 3, internal_count: 3, fill_factor: 6.0 / 7.0 }
+        );
+
+        let stats = setup_degenerate_bst(8).stats();
+        assert_eq!((stats.leaf_count, stats.internal_count), (1, 7));
+        assert_eq!(stats.fill_factor, 8.0 / 255.0);
+
+        let stats = BinarySearchTree::<u32>::new().stats();
+        assert_eq!((stats.height, stats.size, stats.leaf_count, stats.fill_factor), (0, 0, 0, 1.0));
+        assert_eq!(BinarySearchTree::from_sorted_iter(0..15).stats().fill_factor, 1.0);
+    }
+
+    #[test]
+    fn bst_search_path_can_be_found() {
+        let bst = setup_bst();
+        assert_eq!(bst.path_to(&4), Some(vec![&4]));
+        assert_eq!(bst.path_to(&3), Some(vec![&4, &2, &3]));
+        assert_eq!(bst.path_to(&5), Some(vec![&4, &6, &5]));
+        assert_eq!(bst.path_to(&7), None);
+        assert_eq!(BinarySearchTree::new().path_to(&1), None);
+    }
+
+    #[test]
+    fn bst_successor_and_predecessor_can_be_found() {
+        let bst = setup_bst();
+        assert_eq!(bst.successor(&3), Some(&4));
+        assert_eq!(bst.successor(&4), Some(&5));
+        assert_eq!(bst.successor(&0), Some(&1));
+        assert_eq!(bst.successor(&6), None);
+        assert_eq!(bst.predecessor(&5), Some(&4));
+        assert_eq!(bst.predecessor(&4), Some(&3));
+        assert_eq!(bst.predecessor(&88), Some(&6));
+        assert_eq!(bst.predecessor(&1), None);
+    }
+
+    #[test]
+    fn bst_floor_and_ceil_can_be_found() {
+        let mut bst: BinarySearchTree<u32> = BinarySearchTree::new();
+        for deadline in [40, 20, 60, 10, 30, 50] {
+            bst.add_value(deadline);
+        }
+        assert_eq!(bst.floor(&30), Some(&30));
+        assert_eq!(bst.floor(&35), Some(&30));
+        assert_eq!(bst.floor(&99), Some(&60));
+        assert_eq!(bst.floor(&5), None);
+        assert_eq!(bst.ceil(&30), Some(&30));
+        assert_eq!(bst.ceil(&35), Some(&40));
+        assert_eq!(bst.ceil(&5), Some(&10));
+        assert_eq!(bst.ceil(&61), None);
+    }
+
+    #[test]
+    fn bst_order_statistics_can_be_queried() {
         let mut bst = setup_bst();
+        assert_eq!(bst.kth_smallest(0), Some(&1));
+        assert_eq!(bst.kth_smallest(3), Some(&4));
+        assert_eq!(bst.kth_smallest(5), Some(&6));
+        assert_eq!(bst.kth_smallest(6), None);
+        assert_eq!(bst.rank(&1), 0);
+        assert_eq!(bst.rank(&5), 4);
+        assert_eq!(bst.rank(&88), 6);
+
         bst.remove_value(1);
         bst.remove_value(2);
+        bst.add_value(7);
+        assert_eq!(bst.kth_smallest(0), Some(&3));
+        assert_eq!(bst.kth_smallest(4), Some(&7));
+        assert_eq!(bst.rank(&6), 3);
+        for (k, value) in bst.iter().enumerate() {
+            assert_eq!(bst.rank(value), k);
+        }
+    }
+
+    #[test]
+    fn bst_can_delete_nodes() {
+        let mut bst = setup_bst();
+        assert_eq!(bst.remove_value(1), Some(1));
+        assert_eq!(bst.remove_value(2), Some(2));
+        assert_eq!(bst.remove_value(2), None);
         let mut list_iter = bst.collectpeek_traversal_values(TreeTraversalOrders::Inorder).into_iter();
         assert_eq!(list_iter.next(), Some(&3));
         assert_eq!(list_iter.next(), Some(&4));
@@ -452,6 +2683,481 @@ mod tests {
         assert_eq!(list_iter.next(), Some(&6));
     }
 
+    #[test]
+    fn bst_can_be_iterated_in_both_directions() {
+        let bst = setup_bst();
+        assert_eq!(bst.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6]);
+        assert_eq!(bst.iter().rev().collect::<Vec<_>>(), vec![&6, &5, &4, &3, &2, &1]);
+
+        let mut iter = bst.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        assert!(BinarySearchTree::<u32>::new().iter().next_back().is_none());
+    }
+
+    #[test]
+    fn bst_values_can_be_taken_out_in_order() {
+        let bst = setup_bst();
+        assert_eq!(bst.into_sorted_vec(), vec![1, 2, 3, 4, 5, 6]);
+
+        let mut bst = setup_bst();
+        let mut drain = bst.drain();
+        assert_eq!(drain.len(), 6);
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.next(), Some(2));
+        assert_eq!(drain.len(), 4);
+        drop(drain);
+        assert!(bst.is_empty());
+        assert!(bst.root.is_none());
+
+        bst.add_value(7);
+        assert_eq!(bst.drain().collect::<Vec<_>>(), vec![7]);
+        assert_eq!(bst.into_sorted_vec(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn bst_can_absorb_another_tree() {
+        let mut bst = setup_bst();
+        let mut other = BinarySearchTree::new();
+        for value in [9, 3, 8, 0, 7] {
+            other.add_value(value);
+        }
+        bst.merge(other);
+        assert_eq!(bst.len(), 10);
+        assert_eq!(bst.iter().copied().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+        assert_eq!(bst.height(), 4);
+        assert_eq!(bst.root.as_ref().unwrap().subtree_size, 10);
+
+        bst.merge(BinarySearchTree::new());
+        assert_eq!(bst.len(), 10);
+        let mut empty = BinarySearchTree::new();
+        empty.merge(bst.clone());
+        assert_eq!(empty, bst);
+    }
+
+    #[test]
+    fn bst_merge_follows_duplicate_policy() {
+        let mut bst = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Count);
+        let mut other = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Count);
+        for value in [1, 2, 2] {
+            bst.add_value(value);
+        }
+        for value in [2, 3, 3, 3] {
+            other.add_value(value);
+        }
+        bst.merge(other.clone());
+        assert_eq!(bst.len(), 3);
+        assert_eq!([bst.count_of(&1), bst.count_of(&2), bst.count_of(&3)], [1, 3, 3]);
+
+        // counts aren't carried into trees that don't count duplicates
+        let mut bst = setup_bst();
+        bst.merge(other);
+        assert_eq!(bst.count_of(&3), 1);
+
+        let mut bst = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Replace);
+        bst.add_value(KeyedValue(1, 'a'));
+        let mut other = BinarySearchTree::new();
+        other.add_value(KeyedValue(1, 'b'));
+        bst.merge(other);
+        assert_eq!(bst.iter().next().unwrap().1, 'b');
+    }
+
+    #[test]
+    fn bst_invariants_hold_after_every_mutation() {
+        // pseudo-random sequence of values (linear congruential generator) so failures reproduce
+        let mut state: u32 = 12345;
+        let mut next_value = || {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 16) % 64
+        };
+
+        for duplicate_policy in [DuplicatePolicy::Ignore, DuplicatePolicy::Replace, DuplicatePolicy::Count] {
+            let mut bst = BinarySearchTree::with_duplicate_policy(duplicate_policy);
+            for step in 0..2000 {
+                if step % 3 == 2 {
+                    bst.remove_value(next_value());
+                } else {
+                    bst.add_value(next_value());
+                }
+                assert_eq!(bst.check_invariants(), Ok(()), "after step {}", step);
+                if step % 500 == 499 {
+                    bst.rebalance();
+                    assert_eq!(bst.check_invariants(), Ok(()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bst_invariant_violations_are_reported() {
+        let mut bst = setup_bst();
+        assert_eq!(bst.check_invariants(), Ok(()));
+        assert_eq!(setup_degenerate_bst(1000).check_invariants(), Ok(()));
+
+        bst.size = 5;
+        assert_eq!(bst.check_invariants(), Err(BstInvariantError::SizeMismatch { recorded: 5, actual: 6 }));
+        bst.size = 6;
+
+        bst.root.as_mut().unwrap().count = 2;
+        assert_eq!(bst.check_invariants(), Err(BstInvariantError::InvalidCount { position: 3, count: 2 }));
+        bst.root.as_mut().unwrap().count = 1;
+
+        bst.root.as_mut().unwrap().subtree_size = 7;
+        assert_eq!(
+            bst.check_invariants(),
+            Err(BstInvariantError::SubtreeSizeMismatch { position: 3, recorded: 7, actual: 6 })
+        );
+        assert_eq!(
+            bst.check_invariants().unwrap_err().to_string(),
+            "node 3 has subtree_size 7 but its subtree holds 6 nodes"
+        );
+        bst.root.as_mut().unwrap().subtree_size = 6;
+
+        for value in bst.iter_mut() {
+            if *value == 5 {
+                *value = 9;
+            }
+        }
+        assert_eq!(bst.check_invariants(), Err(BstInvariantError::OutOfOrder { position: 5 }));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bst_can_be_serialized_and_deserialized() {
+        let bst = setup_degenerate_bst(7);
+        let json = serde_json::to_string(&bst).unwrap();
+        assert_eq!(json, "[0,1,2,3,4,5,6]");
+
+        let loaded: BinarySearchTree<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, bst);
+        assert_eq!(loaded.height(), 3);
+        assert_eq!(loaded.check_invariants(), Ok(()));
+
+        assert!(serde_json::from_str::<BinarySearchTree<u32>>("[2,1]").is_err());
+        assert_eq!(serde_json::from_str::<BinarySearchTree<u32>>("[]").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn bst_can_be_exported_to_dot() {
+        let mut bst = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Count);
+        for value in [2, 1, 3, 4, 3] {
+            bst.add_value(value);
+        }
+        assert_eq!(
+            bst.to_dot(),
+            concat!(
+                "digraph BinarySearchTree {\n",
+                "    node0 [label=\"2\"];\n",
+                "    node0 -> node1;\n",
+                "    node0 -> node2;\n",
+                "    node1 [label=\"1\"];\n",
+                "    node2 [label=\"3 (x2)\"];\n",
+                "    node2_left [shape=point];\n",
+                "    node2 -> node2_left;\n",
+                "    node2 -> node3;\n",
+                "    node3 [label=\"4\"];\n",
+                "}\n"
+            )
+        );
+        assert_eq!(BinarySearchTree::<u32>::new().to_dot(), "digraph BinarySearchTree {\n}\n");
+
+        let mut quoted = BinarySearchTree::new();
+        quoted.add_value(String::from("say \"hi\""));
+        assert!(quoted.to_dot().contains(r#"[label="say \"hi\""]"#));
+    }
+
+    #[test]
+    fn bst_can_be_rendered_as_ascii() {
+        let bst = setup_bst();
+        assert_eq!(
+            bst.render_ascii(),
+            concat!(
+                "4\n",
+                "|-- 2\n",
+                "|   |-- 1\n",
+                "|   `-- 3\n",
+                "`-- 6\n",
+                "    |-- 5\n",
+                "    `-- (none)\n"
+            )
+        );
+        assert_eq!(BinarySearchTree::<u32>::new().render_ascii(), "");
+
+        let mut bst = BinarySearchTree::with_duplicate_policy(DuplicatePolicy::Count);
+        for value in [1, 2, 2] {
+            bst.add_value(value);
+        }
+        assert_eq!(bst.render_ascii(), "1\n|-- (none)\n`-- 2 (x2)\n");
+    }
+
+    #[test]
+    fn bst_balance_can_be_checked() {
+        let bst = setup_bst();
+        assert!(bst.is_balanced());
+        assert_eq!(
+            bst.balance_factors(),
+            vec![(&1, 2, 0), (&2, 1, 0), (&3, 2, 0), (&4, 0, 0), (&5, 2, 0), (&6, 1, -1)]
+        );
+        assert!(BinarySearchTree::<u32>::new().is_balanced());
+        assert!(BinarySearchTree::<u32>::new().balance_factors().is_empty());
+
+        let mut bst = setup_degenerate_bst(4);
+        assert!(!bst.is_balanced());
+        assert_eq!(bst.balance_factors(), vec![(&0, 0, 3), (&1, 1, 2), (&2, 2, 1), (&3, 3, 0)]);
+        bst.rebalance();
+        assert!(bst.is_balanced());
+
+        // balanced at the root, but not below it
+        let mut bst = BinarySearchTree::new();
+        for value in [4, 2, 6, 1, 7, 0, 8] {
+            bst.add_value(value);
+        }
+        assert!(!bst.is_balanced());
+        assert_eq!(bst.balance_factors()[3], (&4, 0, 0));
+    }
+
+    #[test]
+    fn bst_set_operations_can_be_computed() {
+        let bst = setup_bst();
+        let other = BinarySearchTree::from_sorted_slice(&[0, 2, 4, 7, 8]);
+        let values = |tree: BinarySearchTree<u32>| tree.into_sorted_vec();
+
+        assert_eq!(values(bst.union(&other)), vec![0, 1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(values(bst.intersection(&other)), vec![2, 4]);
+        assert_eq!(values(bst.difference(&other)), vec![1, 3, 5, 6]);
+        assert_eq!(values(other.difference(&bst)), vec![0, 7, 8]);
+
+        let union = bst.union(&other);
+        assert_eq!(union.check_invariants(), Ok(()));
+        assert!(union.is_balanced());
+
+        let empty = BinarySearchTree::new();
+        assert_eq!(bst.union(&empty), bst);
+        assert!(bst.intersection(&empty).is_empty());
+        assert_eq!(empty.difference(&bst).len(), 0);
+    }
+
+    #[test]
+    fn bst_values_can_be_inserted_in_bulk() {
+        for duplicate_policy in [DuplicatePolicy::Ignore, DuplicatePolicy::Replace, DuplicatePolicy::Count] {
+            let batch = [KeyedValue(5, 'a'), KeyedValue(1, 'b'), KeyedValue(5, 'c'), KeyedValue(3, 'd'), KeyedValue(2, 'e')];
+            let mut bulk = BinarySearchTree::with_duplicate_policy(duplicate_policy);
+            let mut one_by_one = BinarySearchTree::with_duplicate_policy(duplicate_policy);
+            for value in [KeyedValue(3, 'x'), KeyedValue(4, 'y')] {
+                bulk.add_value(KeyedValue(value.0, value.1));
+                one_by_one.add_value(value);
+            }
+            bulk.insert_many(batch.iter().map(|value| KeyedValue(value.0, value.1)));
+            for value in batch {
+                one_by_one.add_value(value);
+            }
+
+            assert_eq!(bulk.check_invariants(), Ok(()));
+            assert_eq!(bulk, one_by_one);
+            assert_eq!(
+                bulk.iter().map(|value| value.1).collect::<String>(),
+                one_by_one.iter().map(|value| value.1).collect::<String>()
+            );
+            assert!(bulk.is_balanced());
+        }
+    }
+
+    #[test]
+    fn bst_bulk_insertion_rebuilds_skewed_trees() {
+        let mut bst = setup_degenerate_bst(10_000);
+        bst.insert_many((10_000..20_000).rev());
+        assert_eq!(bst.len(), 20_000);
+        assert_eq!(bst.height(), 15);
+        assert_eq!(bst.check_invariants(), Ok(()));
+
+        // small batches are added one by one
+        bst.insert_many([20_000, 5]);
+        assert_eq!(bst.len(), 20_001);
+        assert_eq!(bst.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn bst_can_be_mirrored() {
+        let mut bst = setup_bst();
+        assert_eq!(bst.mirrored_view().collect::<Vec<_>>(), vec![&6, &5, &4, &3, &2, &1]);
+
+        bst.mirror();
+        //      4
+        //     / \
+        //    6   2
+        //     \  /\
+        //     5 3  1
+        assert_eq!(bst.collectpeek_traversal_values(TreeTraversalOrders::Inorder), vec![&6, &5, &4, &3, &2, &1]);
+        assert_eq!(bst.collectpeek_traversal_values(TreeTraversalOrders::Preorder), vec![&4, &6, &5, &2, &3, &1]);
+        assert_eq!(bst.check_invariants(), Err(BstInvariantError::OutOfOrder { position: 1 }));
+
+        bst.mirror();
+        assert!(bst.same_shape(&setup_bst()));
+        assert_eq!(bst.check_invariants(), Ok(()));
+        assert!(bst.find_value(&5));
+
+        let mut bst = setup_degenerate_bst(1_000_000);
+        bst.mirror();
+        assert_eq!(bst.root.as_ref().unwrap().right_branch.as_ref().map(|node| node.value), None);
+        assert_eq!(bst.mirrored_view().next(), Some(&0));
+    }
+
+    #[test]
+    fn bst_can_be_queried_by_range() {
+        let bst = setup_bst();
+        assert_eq!(bst.range(2..5).collect::<Vec<_>>(), vec![&2, &3, &4]);
+        assert_eq!(bst.range(2..=5).collect::<Vec<_>>(), vec![&2, &3, &4, &5]);
+        assert_eq!(bst.range(..3).collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(bst.range(5..).collect::<Vec<_>>(), vec![&5, &6]);
+        assert_eq!(bst.range(..).count(), 6);
+        assert_eq!(
+            bst.range((Bound::Excluded(1), Bound::Excluded(4))).collect::<Vec<_>>(),
+            vec![&2, &3]
+        );
+        assert_eq!(bst.range(7..10).next(), None);
+        assert_eq!(bst.range(3..3).next(), None);
+    }
+
+    #[test]
+    fn bst_values_can_be_mutated_inorder() {
+        let mut bst: BinarySearchTree<(u32, char)> = BinarySearchTree::new();
+        bst.add_value((2, 'b'));
+        bst.add_value((1, 'a'));
+        bst.add_value((3, 'c'));
+        for value in bst.iter_mut() {
+            value.1 = value.1.to_ascii_uppercase();
+        }
+        let list = bst.collectpeek_traversal_values(TreeTraversalOrders::Inorder);
+        assert_eq!(list, vec![&(1, 'A'), &(2, 'B'), &(3, 'C')]);
+
+        let mut empty_bst: BinarySearchTree<u32> = BinarySearchTree::new();
+        assert!(empty_bst.iter_mut().next().is_none());
+    }
+
+    #[test]
+    fn bst_extreme_values_can_be_popped() {
+        let mut bst = setup_bst();
+        assert_eq!(bst.pop_min(), Some(1));
+        assert_eq!(bst.pop_max(), Some(6));
+        assert_eq!(bst.pop_max(), Some(5));
+        assert_eq!(bst.check_invariants(), Ok(()));
+        assert_eq!(bst.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+
+        // ascending order out of a priority queue
+        for value in [9, 0, 7] {
+            bst.add_value(value);
+        }
+        let mut popped = Vec::new();
+        while let Some(value) = bst.pop_min() {
+            popped.push(value);
+            assert_eq!(bst.check_invariants(), Ok(()));
+        }
+        assert_eq!(popped, vec![0, 2, 3, 4, 7, 9]);
+        assert!(bst.is_empty());
+        assert_eq!(bst.pop_max(), None);
+
+        let mut bst = setup_degenerate_bst(1000);
+        assert_eq!(bst.pop_max(), Some(999));
+        assert_eq!(bst.pop_min(), Some(0));
+        assert_eq!(bst.root.as_ref().unwrap().subtree_size, 998);
+    }
+
+    #[test]
+    fn bst_can_delete_nodes_with_two_children() {
+        let mut bst = setup_bst();
+        bst.add_value(7);
+        assert_eq!(bst.remove_value(4), Some(4));
+        assert_eq!(bst.root.as_ref().unwrap().value, 5);
+        assert_eq!(bst.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &5, &6, &7]);
+
+        assert_eq!(bst.remove_value(2), Some(2));
+        assert_eq!(bst.remove_value(5), Some(5));
+        assert_eq!(bst.iter().collect::<Vec<_>>(), vec![&1, &3, &6, &7]);
+        assert_eq!(bst.len(), 4);
+        assert_eq!(bst.kth_smallest(3), Some(&7));
+        assert_eq!(bst.rank(&6), 2);
+    }
+
+    #[test]
+    fn bst_operations_do_not_overflow_on_degenerate_trees() {
+        let n = 1_000_000;
+        let mut bst = setup_degenerate_bst(n);
+        assert!(bst.find_value(&(n - 1)));
+        assert!(!bst.find_value(&n));
+
+        bst.add_value(n);
+        assert_eq!(bst.rank(&n), n as usize);
+        assert_eq!(bst.remove_value(n - 1), Some(n - 1));
+        assert_eq!(bst.len(), n as usize);
+
+        for value in 0..(n / 2) {
+            assert_eq!(bst.remove_value(value), Some(value));
+        }
+        assert_eq!(bst.len(), (n / 2) as usize);
+    }
+
+    #[test]
+    fn bst_deep_trees_can_be_dropped() {
+        let n = 1_000_000;
+        drop(setup_degenerate_bst(n));
+
+        // a chain of left children, and a drain dropped part way down it
+        let mut root = None;
+        for value in 0..n {
+            let mut node = Node::new(value);
+            node.left_branch = root;
+            node.subtree_size = (value + 1) as usize;
+            root = Some(Box::new(node));
+        }
+        let mut bst = BinarySearchTree { root, size: n as usize, duplicate_policy: DuplicatePolicy::Ignore };
+        assert_eq!(bst.check_invariants(), Ok(()));
+        let mut drain = bst.drain();
+        assert_eq!(drain.next(), Some(0));
+        drop(drain);
+
+        let mut bst = setup_degenerate_bst(n);
+        bst.drain().nth(10);
+    }
+
+    #[test]
+    fn bst_deep_trees_can_be_cloned_and_measured() {
+        let n = 1_000_000;
+        let bst = setup_degenerate_bst(n);
+        let cloned_bst = bst.clone();
+        drop(bst);
+        assert_eq!(cloned_bst.len(), n as usize);
+        assert_eq!(cloned_bst.root.as_ref().unwrap().subtree_size, n as usize);
+        assert_eq!(cloned_bst.height(), n as usize);
+        assert_eq!(cloned_bst.shape_metrics(), (n as usize - 1, 1));
+        assert_eq!(cloned_bst.depth_of(&(n - 1)), Some(n as usize - 1));
+        assert_eq!(cloned_bst.depth_of(&n), None);
+        assert_eq!(cloned_bst.iter().nth(500_000), Some(&500_000));
+    }
+
+    #[test]
+    fn node_rotations_preserve_order_and_sizes() {
+        let mut bst = setup_bst();
+        let root = bst.root.take().unwrap().rotate_right();
+        assert_eq!(root.value, 2);
+        assert_eq!(root.subtree_size, 6);
+        assert_eq!(root.right_branch.as_ref().unwrap().subtree_size, 4);
+        bst.root = Some(root);
+        assert_eq!(bst.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6]);
+
+        let root = bst.root.take().unwrap().rotate_left();
+        assert_eq!(root.value, 4);
+        bst.root = Some(root);
+        assert!(bst.same_shape(&setup_bst()));
+    }
+
     #[test]
     fn bst_can_be_traversed_inorder() {
         let bst = setup_bst();
@@ -477,6 +3183,44 @@ mod tests {
         assert_eq!(list.dequeue_value(), Some(&6));
     }
 
+    #[test]
+    fn bst_can_be_traversed_with_cratell_in_every_order() {
+        let bst = setup_bst();
+        for order in [
+            TreeTraversalOrders::Inorder,
+            TreeTraversalOrders::Preorder,
+            TreeTraversalOrders::Postorder,
+            TreeTraversalOrders::Levelorder
+        ] {
+            let mut list = bst.collectpeek_traversal_values_cratell(order);
+            for value in bst.collectpeek_traversal_values(order) {
+                assert_eq!(list.dequeue_value(), Some(value));
+            }
+            assert_eq!(list.dequeue_value(), None);
+        }
+    }
+
+    #[test]
+    fn bst_can_be_traversed_lazily() {
+        let bst = setup_bst();
+        assert_eq!(bst.preorder_iter().collect::<Vec<_>>(), bst.collectpeek_traversal_values(TreeTraversalOrders::Preorder));
+        assert_eq!(bst.postorder_iter().collect::<Vec<_>>(), bst.collectpeek_traversal_values(TreeTraversalOrders::Postorder));
+
+        let mut preorder = bst.preorder_iter();
+        assert_eq!(preorder.len(), 6);
+        assert_eq!(preorder.next(), Some(&4));
+        assert_eq!(preorder.len(), 5);
+        assert_eq!(bst.postorder_iter().next(), Some(&1));
+        assert_eq!(BinarySearchTree::<u32>::new().postorder_iter().next(), None);
+
+        // no recursion, so degenerate trees can be walked
+        let n = 1_000_000;
+        let bst = setup_degenerate_bst(n);
+        assert_eq!(bst.preorder_iter().last(), Some(&(n - 1)));
+        assert_eq!(bst.postorder_iter().next(), Some(&(n - 1)));
+        assert_eq!(bst.postorder_iter().count(), n as usize);
+    }
+
     #[test]
     fn bst_can_be_traversed_preorder() {
         let bst = setup_bst();
@@ -490,6 +3234,41 @@ mod tests {
         assert_eq!(list_iter.next(), Some(&5));
     }
 
+    #[test]
+    fn bst_can_be_traversed_levelorder() {
+        let bst = setup_bst();
+        let list = bst.collectpeek_traversal_values(TreeTraversalOrders::Levelorder);
+        assert_eq!(list, vec![&4, &2, &6, &1, &3, &5]);
+        assert!(BinarySearchTree::<u32>::new().collectpeek_traversal_values(TreeTraversalOrders::Levelorder).is_empty());
+    }
+
+    #[test]
+    fn bst_can_be_traversed_with_closure() {
+        let bst = setup_bst();
+        for order in [
+            TreeTraversalOrders::Inorder,
+            TreeTraversalOrders::Preorder,
+            TreeTraversalOrders::Postorder,
+            TreeTraversalOrders::Levelorder
+        ] {
+            let mut visited = Vec::new();
+            bst.traverse_with(order, |value| visited.push(*value));
+            let collected: Vec<u32> = bst.collectpeek_traversal_values(order).into_iter().copied().collect();
+            assert_eq!(visited, collected);
+        }
+
+        let mut sum = 0;
+        bst.traverse_with(TreeTraversalOrders::Inorder, |value| sum += value);
+        assert_eq!(sum, 21);
+    }
+
+    #[test]
+    fn bst_values_can_be_grouped_by_level() {
+        let bst = setup_bst();
+        assert_eq!(bst.levels(), vec![vec![&4], vec![&2, &6], vec![&1, &3, &5]]);
+        assert!(BinarySearchTree::<u32>::new().levels().is_empty());
+    }
+
     #[test]
     fn bst_can_be_traversed_postorder() {
         let bst = setup_bst();
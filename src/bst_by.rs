@@ -0,0 +1,226 @@
+//! Binary search tree ordered by a comparator closure instead of the Ord trait, built on the Node
+//!     struct from bst.rs. Values that don't implement Ord (like f64), or that need an ordering
+//!     other than their Ord one (descending, case-insensitive...), can be stored.
+
+use std::cmp::Ordering;
+use crate::bst::{ InorderIter, Node };
+
+
+/// A binary search tree struct containing pointers to Node structs, ordered by a comparator.
+///
+/// * `root`: An Option-wrapped reference to the root Node of the binary search tree.
+///         This will be None if there are zero nodes in this tree.
+/// * `size`: The number of Nodes in this tree.
+/// * `compare`: Comparator closure returning how its first argument is ordered relative to its
+///         second. Every comparison made by this tree goes through it, so it must be a total
+///         order (consistent, like Ord::cmp) for the tree to stay valid.
+pub struct BinarySearchTreeBy<T, F: Fn(&T, &T) -> Ordering> {
+    root: Option<Box<Node<T>>>,
+    size: usize,
+    compare: F
+}
+
+
+// Method implementation for BinarySearchTreeBy struct
+impl<T, F> BinarySearchTreeBy<T, F> where F: Fn(&T, &T) -> Ordering {
+
+    /// Return a new, empty BinarySearchTreeBy struct ordered by the input comparator.
+    ///
+    /// * `compare`: comparator closure, e.g. `|a: &f64, b: &f64| a.total_cmp(b)` or
+    ///         `|a: &u32, b: &u32| b.cmp(a)` for descending order.
+    fn new(compare: F) -> BinarySearchTreeBy<T, F> {
+        BinarySearchTreeBy {
+            root: None,
+            size: 0,
+            compare
+        }
+    }
+
+    /// Return the number of values held in this BinarySearchTreeBy struct.
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Return whether this BinarySearchTreeBy struct holds no values.
+    fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Return the height of this BinarySearchTreeBy struct (see BinarySearchTree::height).
+    fn height(&self) -> usize {
+        Node::height(&self.root)
+    }
+
+    /// Return a reference to the stored value the comparator considers equal to the input value,
+    ///     if there is one.
+    ///
+    /// * `value`: value to be searched for.
+    fn get(&self, value: &T) -> Option<&T> {
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            match (self.compare)(value, &node.value) {
+                Ordering::Less => { opt_node = node.left_branch.as_deref(); },
+                Ordering::Greater => { opt_node = node.right_branch.as_deref(); },
+                Ordering::Equal => { return Some(&node.value); }
+            }
+        }
+        None
+    }
+
+    /// Find whether a value is present in this BinarySearchTreeBy struct, according to its
+    ///     comparator.
+    ///
+    /// * `value`: value to be searched for.
+    fn find_value(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    /// Add a Node holding the input value to this BinarySearchTreeBy struct, walking down from the
+    ///     root in a loop like BinarySearchTree::add_value.
+    ///
+    /// If the comparator finds an equal value already in the tree, no Node is added and the input
+    ///     value is discarded, as BinarySearchTree::add_value does by default.
+    ///
+    /// * `value`: The value to be added into the binary search tree.
+    fn add_value(&mut self, value: T) {
+        // check first, so subtree sizes are only incremented when a Node will be added
+        if self.find_value(&value) {
+            return;
+        }
+
+        let mut link = &mut self.root;
+        while let Some(node) = link {
+            node.subtree_size += 1;
+            link = match (self.compare)(&value, &node.value) {
+                Ordering::Less => &mut node.left_branch,
+                _ => &mut node.right_branch
+            };
+        }
+        *link = Some(Box::new(Node::new(value)));
+        self.size += 1;
+    }
+
+    /// Find the stored value the comparator considers equal to the input value and remove it
+    ///     (and its enclosing Node), like BinarySearchTree::remove_by.
+    ///
+    /// Return the value that was stored in the tree, or None if no equal value was found.
+    ///
+    /// * `value`: Value to be removed from the binary search tree.
+    fn remove_value(&mut self, value: &T) -> Option<T> {
+        // check first, so subtree sizes are only decremented when a Node will be removed
+        if !self.find_value(value) {
+            return None;
+        }
+
+        // the target is in the tree, so the search path can't end on an empty branch
+        let mut link = &mut self.root;
+        while (self.compare)(value, &link.as_ref().unwrap().value) != Ordering::Equal {
+            let node = link.as_mut().unwrap();
+            node.subtree_size -= 1;
+            link = match (self.compare)(value, &node.value) {
+                Ordering::Less => &mut node.left_branch,
+                _ => &mut node.right_branch
+            };
+        }
+
+        let (replacement, removed_value) = link.take().unwrap().remove_self_from_tree();
+        *link = replacement;
+        self.size -= 1;
+        Some(removed_value)
+    }
+
+    /// Return an iterator of references to the values in this BinarySearchTreeBy struct, in the
+    ///     comparator's ascending order. Call rev() on it to get values in descending order.
+    fn iter(&self) -> InorderIter<'_, T> {
+        InorderIter::new(self.root.as_deref(), self.size)
+    }
+}
+
+// Drop the Nodes the same way as BinarySearchTree does, so a degenerate tree (e.g. built from
+// input sorted by the comparator) doesn't overflow the stack.
+impl<T, F> Drop for BinarySearchTreeBy<T, F> where F: Fn(&T, &T) -> Ordering {
+    fn drop(&mut self) {
+        Node::drop_iteratively(self.root.take());
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_descending_bst() -> BinarySearchTreeBy<u32, impl Fn(&u32, &u32) -> Ordering> {
+    let mut bst = BinarySearchTreeBy::new(|a: &u32, b: &u32| b.cmp(a));
+    for value in [4, 2, 6, 1, 3, 5] {
+        bst.add_value(value);
+    }
+    bst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bst_by_orders_by_comparator() {
+        let bst = setup_descending_bst();
+        assert_eq!(bst.len(), 6);
+        assert_eq!(bst.height(), 3);
+        assert_eq!(bst.iter().copied().collect::<Vec<_>>(), vec![6, 5, 4, 3, 2, 1]);
+        assert!(bst.find_value(&3));
+        assert!(!bst.find_value(&7));
+    }
+
+    #[test]
+    fn bst_by_can_hold_values_without_ord() {
+        let mut bst = BinarySearchTreeBy::new(|a: &f64, b: &f64| a.total_cmp(b));
+        for value in [2.5, -1.0, 3.25, 0.0] {
+            bst.add_value(value);
+        }
+        assert_eq!(bst.iter().copied().collect::<Vec<_>>(), vec![-1.0, 0.0, 2.5, 3.25]);
+        assert_eq!(bst.remove_value(&2.5), Some(2.5));
+        assert_eq!(bst.remove_value(&2.5), None);
+        assert_eq!(bst.len(), 3);
+    }
+
+    #[test]
+    fn bst_by_treats_comparator_equal_values_as_duplicates() {
+        let mut bst = BinarySearchTreeBy::new(|a: &String, b: &String| {
+            a.to_lowercase().cmp(&b.to_lowercase())
+        });
+        for word in ["banana", "Apple", "cherry"] {
+            bst.add_value(String::from(word));
+        }
+        bst.add_value(String::from("BANANA"));
+        assert_eq!(bst.len(), 3);
+        assert_eq!(bst.get(&String::from("apple")), Some(&String::from("Apple")));
+        assert_eq!(bst.iter().map(String::as_str).collect::<Vec<_>>(), vec!["Apple", "banana", "cherry"]);
+
+        assert_eq!(bst.remove_value(&String::from("CHERRY")), Some(String::from("cherry")));
+        assert_eq!(bst.iter().rev().map(String::as_str).collect::<Vec<_>>(), vec!["banana", "Apple"]);
+    }
+
+    #[test]
+    fn bst_by_can_delete_nodes_with_two_children() {
+        let mut bst = setup_descending_bst();
+        assert_eq!(bst.remove_value(&4), Some(4));
+        assert_eq!(bst.remove_value(&2), Some(2));
+        assert_eq!(bst.iter().copied().collect::<Vec<_>>(), vec![6, 5, 3, 1]);
+        assert_eq!(bst.root.as_ref().unwrap().subtree_size, 4);
+    }
+
+    #[test]
+    fn bst_by_deep_trees_can_be_dropped() {
+        // a chain of left children, as descending insertion into an ascending tree would build
+        let n = 1_000_000;
+        let mut root = None;
+        for value in 0..n {
+            let mut node = Node::new(value);
+            node.left_branch = root;
+            node.subtree_size = (value + 1) as usize;
+            root = Some(Box::new(node));
+        }
+        let bst = BinarySearchTreeBy { root, size: n as usize, compare: |a: &u32, b: &u32| a.cmp(b) };
+        assert_eq!(bst.iter().next(), Some(&0));
+        drop(bst);
+    }
+}
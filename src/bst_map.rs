@@ -0,0 +1,215 @@
+//! Ordered key-value map built on the BinarySearchTree struct from bst.rs. Each key-value pair is
+//!     stored in the tree as a single value that is ordered by its key alone.
+
+use std::cmp::Ordering;
+use crate::bst::BinarySearchTree;
+
+
+/// A key-value pair stored in a BstMap struct's tree. Compares by key only, so the tree holds at
+/// most one Entry per key and can be searched by key.
+///
+/// * `key`: Key this Entry is ordered by.
+/// * `value`: Value associated with the key - free to be mutated in place as it doesn't affect
+///         ordering.
+struct Entry<K: Ord, V> {
+    key: K,
+    value: V
+}
+
+impl<K: Ord, V> PartialEq for Entry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Ord, V> Eq for Entry<K, V> {}
+
+impl<K: Ord, V> PartialOrd for Entry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for Entry<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+
+/// A map from keys to values, kept in key order in a BinarySearchTree struct.
+///
+/// * `tree`: BinarySearchTree holding one Entry per key.
+pub struct BstMap<K: Ord, V> {
+    tree: BinarySearchTree<Entry<K, V>>
+}
+
+
+// Method implementation for BstMap struct
+impl<K, V> BstMap<K, V> where K: Ord {
+
+    /// Return a new, empty BstMap struct
+    fn new() -> BstMap<K, V> {
+        BstMap {
+            tree: BinarySearchTree::new()
+        }
+    }
+
+    /// Return the number of key-value pairs held in this BstMap struct.
+    fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Return whether this BstMap struct holds no key-value pairs.
+    fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Associate the input value with the input key in this map.
+    ///
+    /// If the key was already present, its value is replaced in place (the key is kept) and the
+    ///     old value is returned. Otherwise a new Entry is added to the tree and None is returned.
+    ///
+    /// * `key`: key to associate the value with.
+    /// * `value`: value to store.
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.tree.find_by_mut(|entry| key.cmp(&entry.key)) {
+            Some(entry) => Some(std::mem::replace(&mut entry.value, value)),
+            None => {
+                self.tree.add_value(Entry { key, value });
+                None
+            }
+        }
+    }
+
+    /// Return a reference to the value associated with the input key, if there is one.
+    ///
+    /// * `key`: key to look up.
+    fn get(&self, key: &K) -> Option<&V> {
+        self.tree.find_by(|entry| key.cmp(&entry.key)).map(|entry| &entry.value)
+    }
+
+    /// Return a mutable reference to the value associated with the input key, if there is one.
+    ///
+    /// * `key`: key to look up.
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.tree.find_by_mut(|entry| key.cmp(&entry.key)).map(|entry| &mut entry.value)
+    }
+
+    /// Return whether a value is associated with the input key.
+    ///
+    /// * `key`: key to look up.
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Remove the input key from this map, and return the value that was associated with it.
+    ///
+    /// * `key`: key to remove.
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.tree.remove_by(|entry| key.cmp(&entry.key)).map(|entry| entry.value)
+    }
+
+    /// Return an iterator of (key, value) reference pairs in ascending key order. It can be
+    ///     reversed for descending key order.
+    fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+        self.tree.iter().map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Return an iterator of (key, mutable value reference) pairs in ascending key order. Keys
+    ///     can't be mutated, so the map stays ordered.
+    fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.tree.iter_mut().map(|entry| (&entry.key, &mut entry.value))
+    }
+
+    /// Return an iterator of references to the keys in this map, in ascending order.
+    fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Return an iterator of references to the values in this map, in ascending key order.
+    fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> Default for BstMap<K, V> where K: Ord {
+    fn default() -> Self {
+        BstMap::new()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_bst_map() -> BstMap<u32, &'static str> {
+    let mut map: BstMap<u32, &'static str> = BstMap::new();
+    map.insert(4, "four");
+    map.insert(2, "two");
+    map.insert(6, "six");
+    map.insert(1, "one");
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bst_map_can_be_inserted_into_and_read() {
+        let map = setup_bst_map();
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get(&2), Some(&"two"));
+        assert_eq!(map.get(&3), None);
+        assert!(map.contains_key(&6));
+        assert!(!BstMap::<u32, u32>::new().contains_key(&6));
+    }
+
+    #[test]
+    fn bst_map_insert_replaces_existing_values() {
+        let mut map = setup_bst_map();
+        assert_eq!(map.insert(2, "deux"), Some("two"));
+        assert_eq!(map.insert(3, "trois"), None);
+        assert_eq!(map.get(&2), Some(&"deux"));
+        assert_eq!(map.len(), 5);
+    }
+
+    #[test]
+    fn bst_map_values_can_be_mutated() {
+        let mut map: BstMap<char, u32> = BstMap::new();
+        for c in "hello world".chars() {
+            match map.get_mut(&c) {
+                Some(count) => { *count += 1; },
+                None => { map.insert(c, 1); }
+            }
+        }
+        assert_eq!(map.get(&'l'), Some(&3));
+        assert_eq!(map.get(&'o'), Some(&2));
+
+        for (_, count) in map.iter_mut() {
+            *count *= 10;
+        }
+        assert_eq!(map.get(&'h'), Some(&10));
+    }
+
+    #[test]
+    fn bst_map_can_remove_keys() {
+        let mut map = setup_bst_map();
+        assert_eq!(map.remove(&4), Some("four"));
+        assert_eq!(map.remove(&4), None);
+        assert_eq!(map.len(), 3);
+        assert!(!map.contains_key(&4));
+        assert_eq!(map.keys().collect::<Vec<_>>(), vec![&1, &2, &6]);
+    }
+
+    #[test]
+    fn bst_map_iterates_in_key_order() {
+        let map = setup_bst_map();
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(&1, &"one"), (&2, &"two"), (&4, &"four"), (&6, &"six")]
+        );
+        assert_eq!(map.values().rev().collect::<Vec<_>>(), vec![&"six", &"four", &"two", &"one"]);
+    }
+}
@@ -0,0 +1,670 @@
+//! Ordered key-value map stored in a B-tree. Each node holds a sorted run of keys in a Vec,
+//!     so a lookup touches O(log n) nodes and binary searches a small contiguous array in each -
+//!     the cache-friendly counterpart to the one-key-per-node BinarySearchTree in bst.rs.
+//!
+//! The order of the tree is the const generic B (its minimum degree): every node but the root
+//!     holds between B - 1 and 2B - 1 keys, and every internal node has one more child than it
+//!     has keys. Insertion splits full nodes and deletion merges or rebalances underfull ones on
+//!     the way down, so both finish in a single pass from the root.
+//!
+//! ```
+//! use rust_datastructures_algorithms::btree::BTreeMap;
+//!
+//! // a 2-3-4 tree
+//! let mut map: BTreeMap<u32, &str, 2> = BTreeMap::new();
+//! map.insert(3, "three");
+//! map.insert(1, "one");
+//! map.insert(2, "two");
+//! assert_eq!(map.get(&2), Some(&"two"));
+//! assert_eq!(map.remove(&1), Some("one"));
+//! assert_eq!(map.keys().collect::<Vec<_>>(), vec![&2, &3]);
+//! ```
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem;
+
+
+/// Minimum degree used when none is given: nodes hold 5 to 11 keys.
+pub const DEFAULT_ORDER: usize = 6;
+
+
+/// A B-tree map struct holding the root Node.
+///
+/// * `root`: Root Node - an empty leaf when the map is empty. Unlike other Nodes it may hold
+///         fewer than B - 1 keys.
+/// * `length`: Number of key-value pairs in the map.
+pub struct BTreeMap<K, V, const B: usize = DEFAULT_ORDER> {
+    root: Node<K, V, B>,
+    length: usize
+}
+
+
+/// Node in a BTreeMap struct.
+///
+/// * `keys`: Sorted keys held by this Node - at most 2B - 1 of them.
+/// * `values`: Value for each key, at the same index.
+/// * `children`: Child Nodes - empty for a leaf, otherwise one more than there are keys. Every
+///         key in `children[i]` is less than `keys[i]`, which is less than every key in
+///         `children[i + 1]`.
+struct Node<K, V, const B: usize> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    children: Vec<Node<K, V, B>>
+}
+
+
+/// Iterator yielding references to the key-value pairs in a BTreeMap struct, in key order.
+/// Created by BTreeMap::iter.
+///
+/// * `stack`: Path of Nodes from the root to the Node holding the next key, each with the index
+///         of the next of its keys to be yielded.
+/// * `remaining`: Number of key-value pairs not yet yielded.
+pub struct Iter<'a, K, V, const B: usize> {
+    stack: Vec<(&'a Node<K, V, B>, usize)>,
+    remaining: usize
+}
+
+
+// Method implementations for BTreeMap struct
+impl<K, V, const B: usize> BTreeMap<K, V, B> where K: Ord {
+
+    /// Return a new, empty BTreeMap struct. Fails to compile if B is less than 2.
+    pub fn new() -> BTreeMap<K, V, B> {
+        const { assert!(B >= 2, "a B-tree's minimum degree must be at least 2") };
+        BTreeMap {
+            root: Node::new(),
+            length: 0
+        }
+    }
+
+    /// Return the number of key-value pairs in this BTreeMap.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this BTreeMap holds no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the number of levels of Nodes in this BTreeMap (1 for a lone root). Every leaf is
+    /// at the same depth.
+    pub fn height(&self) -> usize {
+        let mut height = 1;
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            height += 1;
+            node = child;
+        }
+        height
+    }
+
+    /// Insert a key-value pair into the map. If the key was already in the map, replace its value
+    /// and return the old one (the key itself is not replaced).
+    ///
+    /// A full root is split first, growing the tree by a level. Then on the way down every full
+    ///     child is split before it is entered, so there is always room in the leaf reached.
+    ///
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.root.is_full() {
+            let old_root = mem::replace(&mut self.root, Node::new());
+            self.root.children.push(old_root);
+            self.root.split_child(0);
+        }
+        let old_value = self.root.insert_nonfull(key, value);
+        if old_value.is_none() {
+            self.length += 1;
+        }
+        old_value
+    }
+
+    /// Get a reference to the value associated with the input key (if the key is in the map).
+    ///
+    /// * `key`: key to look up - any borrowed form of the map's key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: Ord + ?Sized {
+        let mut node = &self.root;
+        loop {
+            match node.search(key) {
+                Ok(index) => return Some(&node.values[index]),
+                Err(index) => node = node.children.get(index)?
+            }
+        }
+    }
+
+    /// Get a mutable reference to the value associated with the input key (if the key is in the
+    /// map).
+    ///
+    /// * `key`: key to look up - any borrowed form of the map's key type.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q>, Q: Ord + ?Sized {
+        let mut node = &mut self.root;
+        loop {
+            match node.search(key) {
+                Ok(index) => return Some(&mut node.values[index]),
+                Err(index) => node = node.children.get_mut(index)?
+            }
+        }
+    }
+
+    /// Return whether the input key is in the map.
+    ///
+    /// * `key`: key to look for - any borrowed form of the map's key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>, Q: Ord + ?Sized {
+        self.get(key).is_some()
+    }
+
+    /// Remove the input key from the map and return the value that was associated with it (if the
+    /// key was in the map).
+    ///
+    /// * `key`: key to be removed - any borrowed form of the map's key type.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q>, Q: Ord + ?Sized {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Remove the input key from the map and return the stored key and its value (if the key was
+    /// in the map).
+    ///
+    /// On the way down, every child about to be entered that has only B - 1 keys is given another
+    ///     first - borrowed from a sibling through the parent, or by merging with a sibling - so
+    ///     a key can always be taken out of the Node it is found in. If that empties the root,
+    ///     its only child becomes the new root and the tree shrinks by a level.
+    ///
+    /// * `key`: key to be removed - any borrowed form of the map's key type.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)> where K: Borrow<Q>, Q: Ord + ?Sized {
+        let removed = self.root.remove(key);
+        if self.root.keys.is_empty() {
+            if let Some(only_child) = self.root.children.pop() {
+                self.root = only_child;
+            }
+        }
+        if removed.is_some() {
+            self.length -= 1;
+        }
+        removed
+    }
+
+    /// Return references to the smallest key and its value (if the map is not empty).
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            node = child;
+        }
+        Some((node.keys.first()?, node.values.first()?))
+    }
+
+    /// Return references to the largest key and its value (if the map is not empty).
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        while let Some(child) = node.children.last() {
+            node = child;
+        }
+        Some((node.keys.last()?, node.values.last()?))
+    }
+
+    /// Return an iterator of references to the key-value pairs in this BTreeMap, in key order.
+    pub fn iter(&self) -> Iter<'_, K, V, B> {
+        let mut iter = Iter {
+            stack: Vec::new(),
+            remaining: self.length
+        };
+        iter.push_leftmost_path(&self.root);
+        iter
+    }
+
+    /// Return an iterator of references to the keys in this BTreeMap, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Return an iterator of references to the values in this BTreeMap, in key order.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K, V, const B: usize> Default for BTreeMap<K, V, B> where K: Ord {
+    fn default() -> Self {
+        BTreeMap::new()
+    }
+}
+
+impl<K, V, const B: usize> fmt::Debug for BTreeMap<K, V, B> where K: Ord + fmt::Debug, V: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Insert each key-value pair in turn, so later values replace earlier ones for the same key.
+impl<K, V, const B: usize> Extend<(K, V)> for BTreeMap<K, V, B> where K: Ord {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, const B: usize> FromIterator<(K, V)> for BTreeMap<K, V, B> where K: Ord {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = BTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V, const B: usize> IntoIterator for &'a BTreeMap<K, V, B> where K: Ord {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+// Method implementations for Node struct
+impl<K, V, const B: usize> Node<K, V, B> where K: Ord {
+
+    /// Return a new, empty leaf Node.
+    fn new() -> Node<K, V, B> {
+        Node {
+            keys: Vec::with_capacity(2 * B - 1),
+            values: Vec::with_capacity(2 * B - 1),
+            children: Vec::new()
+        }
+    }
+
+    /// Return whether this Node has no children.
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Return whether this Node holds the most keys a Node can (2B - 1).
+    fn is_full(&self) -> bool {
+        self.keys.len() == 2 * B - 1
+    }
+
+    /// Binary search this Node's keys. Return Ok with the index of the input key, or Err with the
+    /// index of the child whose subtree it would be in.
+    ///
+    /// * `key`: key to look for - any borrowed form of the map's key type.
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize> where K: Borrow<Q>, Q: Ord + ?Sized {
+        self.keys.binary_search_by(|stored| stored.borrow().cmp(key))
+    }
+
+    /// Split the full child at the input index in two around its median key, which moves up into
+    /// this Node between the two halves. Each half is left with B - 1 keys.
+    ///
+    /// * `index`: index of the full child.
+    fn split_child(&mut self, index: usize) {
+        let child = &mut self.children[index];
+        let mut right = Node::new();
+        right.keys.extend(child.keys.drain(B..));
+        right.values.extend(child.values.drain(B..));
+        if !child.is_leaf() {
+            right.children.extend(child.children.drain(B..));
+        }
+        let median_key = child.keys.pop().expect("a full Node has a median key");
+        let median_value = child.values.pop().expect("a full Node has a median value");
+        self.keys.insert(index, median_key);
+        self.values.insert(index, median_value);
+        self.children.insert(index + 1, right);
+    }
+
+    /// Insert a key-value pair below this Node, which must not be full, and return the old value
+    /// if the key was already there. Full children are split before being entered.
+    ///
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    fn insert_nonfull(&mut self, key: K, value: V) -> Option<V> {
+        let mut node = self;
+        loop {
+            let mut index = match node.search(&key) {
+                Ok(index) => return Some(mem::replace(&mut node.values[index], value)),
+                Err(index) => index
+            };
+            if node.is_leaf() {
+                node.keys.insert(index, key);
+                node.values.insert(index, value);
+                return None;
+            }
+            if node.children[index].is_full() {
+                node.split_child(index);
+                // the median that moved up may be the key, or decide which half it belongs in
+                match key.cmp(&node.keys[index]) {
+                    Ordering::Less => {},
+                    Ordering::Equal => return Some(mem::replace(&mut node.values[index], value)),
+                    Ordering::Greater => index += 1
+                }
+            }
+            node = &mut node.children[index];
+        }
+    }
+
+    /// Remove the input key from below this Node and return the stored key and its value (if the
+    /// key is there). This Node must have at least B keys, unless it is the root.
+    ///
+    /// A key found in a leaf is just removed. A key found in an internal Node is replaced by its
+    ///     predecessor or successor taken from a child with keys to spare, or if neither child
+    ///     has any, the two children are merged around the key and it is removed from the merge.
+    ///
+    /// * `key`: key to be removed - any borrowed form of the map's key type.
+    fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)> where K: Borrow<Q>, Q: Ord + ?Sized {
+        match self.search(key) {
+            Ok(index) if self.is_leaf() => Some((self.keys.remove(index), self.values.remove(index))),
+            Ok(index) => {
+                let replacement = if self.children[index].keys.len() >= B {
+                    self.children[index].pop_last()
+                } else if self.children[index + 1].keys.len() >= B {
+                    self.children[index + 1].pop_first()
+                } else {
+                    self.merge_children(index);
+                    return self.children[index].remove(key);
+                };
+                let (new_key, new_value) = replacement;
+                Some((mem::replace(&mut self.keys[index], new_key), mem::replace(&mut self.values[index], new_value)))
+            },
+            Err(_) if self.is_leaf() => None,
+            Err(index) => {
+                let index = self.fill_child(index);
+                self.children[index].remove(key)
+            }
+        }
+    }
+
+    /// Remove and return the largest key below this Node and its value. This Node must have at
+    /// least B keys, or be a leaf with at least one.
+    fn pop_last(&mut self) -> (K, V) {
+        if self.is_leaf() {
+            let key = self.keys.pop().expect("the Node has a key to spare");
+            let value = self.values.pop().expect("the Node has a value to spare");
+            return (key, value);
+        }
+        let index = self.fill_child(self.children.len() - 1);
+        self.children[index].pop_last()
+    }
+
+    /// Remove and return the smallest key below this Node and its value. This Node must have at
+    /// least B keys, or be a leaf with at least one.
+    fn pop_first(&mut self) -> (K, V) {
+        if self.is_leaf() {
+            return (self.keys.remove(0), self.values.remove(0));
+        }
+        let index = self.fill_child(0);
+        self.children[index].pop_first()
+    }
+
+    /// Make sure the child at the input index has at least B keys before it is entered: rotate a
+    /// key in from a sibling that has one to spare, or else merge the child with a sibling. Return
+    /// the index of the Node now holding the child's keys (one less if it merged into its left
+    /// sibling).
+    ///
+    /// * `index`: index of the child about to be entered.
+    fn fill_child(&mut self, index: usize) -> usize {
+        if self.children[index].keys.len() >= B {
+            return index;
+        }
+        if index > 0 && self.children[index - 1].keys.len() >= B {
+            self.rotate_right(index - 1);
+            index
+        } else if index + 1 < self.children.len() && self.children[index + 1].keys.len() >= B {
+            self.rotate_left(index);
+            index
+        } else if index + 1 < self.children.len() {
+            self.merge_children(index);
+            index
+        } else {
+            self.merge_children(index - 1);
+            index - 1
+        }
+    }
+
+    /// Move the largest key of the child at the input index up into this Node, and the key of this
+    /// Node between that child and the next down into the start of the next child.
+    ///
+    /// * `index`: index of the child giving up a key.
+    fn rotate_right(&mut self, index: usize) {
+        let (left_half, right_half) = self.children.split_at_mut(index + 1);
+        let (left, right) = (&mut left_half[index], &mut right_half[0]);
+        let key = mem::replace(&mut self.keys[index], left.keys.pop().expect("the sibling has a key to spare"));
+        let value = mem::replace(&mut self.values[index], left.values.pop().expect("the sibling has a value to spare"));
+        right.keys.insert(0, key);
+        right.values.insert(0, value);
+        if let Some(child) = left.children.pop() {
+            right.children.insert(0, child);
+        }
+    }
+
+    /// Move the smallest key of the child after the input index up into this Node, and the key of
+    /// this Node between the two children down onto the end of the child at the index.
+    ///
+    /// * `index`: index of the child receiving a key.
+    fn rotate_left(&mut self, index: usize) {
+        let (left_half, right_half) = self.children.split_at_mut(index + 1);
+        let (left, right) = (&mut left_half[index], &mut right_half[0]);
+        let key = mem::replace(&mut self.keys[index], right.keys.remove(0));
+        let value = mem::replace(&mut self.values[index], right.values.remove(0));
+        left.keys.push(key);
+        left.values.push(value);
+        if !right.is_leaf() {
+            left.children.push(right.children.remove(0));
+        }
+    }
+
+    /// Merge the child after the input index into the child at it, with the key of this Node
+    /// between them moving down into the middle. Both children must have B - 1 keys, so the merge
+    /// is full.
+    ///
+    /// * `index`: index of the left child of the pair.
+    fn merge_children(&mut self, index: usize) {
+        let right = self.children.remove(index + 1);
+        let key = self.keys.remove(index);
+        let value = self.values.remove(index);
+        let left = &mut self.children[index];
+        left.keys.push(key);
+        left.values.push(value);
+        left.keys.extend(right.keys);
+        left.values.extend(right.values);
+        left.children.extend(right.children);
+    }
+}
+
+
+// Method implementations for Iter struct
+impl<'a, K, V, const B: usize> Iter<'a, K, V, B> {
+
+    /// Push the input Node and the first child of each Node below it onto the stack, down to a
+    /// leaf - the path to the smallest key below the Node.
+    ///
+    /// * `node`: Node to start from.
+    fn push_leftmost_path(&mut self, node: &'a Node<K, V, B>) {
+        let mut node = node;
+        loop {
+            self.stack.push((node, 0));
+            match node.children.first() {
+                Some(child) => node = child,
+                None => return
+            }
+        }
+    }
+}
+
+// Iterator implementation for Iter struct
+impl<'a, K, V, const B: usize> Iterator for Iter<'a, K, V, B> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, index) = self.stack.last_mut()?;
+            let node: &'a Node<K, V, B> = node;
+            if *index == node.keys.len() {
+                self.stack.pop();
+                continue;
+            }
+            let key_index = *index;
+            *index += 1;
+            // everything in the child after this key comes before the Node's next key
+            if let Some(child) = node.children.get(key_index + 1) {
+                self.push_leftmost_path(child);
+            }
+            self.remaining -= 1;
+            return Some((&node.keys[key_index], &node.values[key_index]));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V, const B: usize> ExactSizeIterator for Iter<'a, K, V, B> {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_btree_map() -> BTreeMap<u32, String, 2> {
+    let mut map: BTreeMap<u32, String, 2> = BTreeMap::new();
+    for key in [50, 20, 80, 10, 30, 60, 90, 40, 70] {
+        map.insert(key, key.to_string());
+    }
+    map
+}
+
+/// Check every B-tree invariant - sorted keys within and between Nodes, key counts, child counts
+/// and all leaves at the same depth - and that the map's length matches.
+#[cfg(test)]
+fn check_invariants<K: Ord, V, const B: usize>(map: &BTreeMap<K, V, B>) {
+    fn check<K: Ord, V, const B: usize>(node: &Node<K, V, B>, is_root: bool, lower: Option<&K>, upper: Option<&K>, depth: usize, leaf_depth: &mut Option<usize>) -> usize {
+        assert_eq!(node.keys.len(), node.values.len());
+        assert!(node.keys.len() < 2 * B);
+        assert!(is_root || node.keys.len() >= B - 1);
+        assert!(node.keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(lower.is_none_or(|lower| node.keys.first().is_none_or(|first| lower < first)));
+        assert!(upper.is_none_or(|upper| node.keys.last().is_none_or(|last| last < upper)));
+        if node.is_leaf() {
+            assert_eq!(*leaf_depth.get_or_insert(depth), depth);
+            return node.keys.len();
+        }
+        assert_eq!(node.children.len(), node.keys.len() + 1);
+        let mut count = node.keys.len();
+        for (index, child) in node.children.iter().enumerate() {
+            let child_lower = if index == 0 { lower } else { Some(&node.keys[index - 1]) };
+            let child_upper = node.keys.get(index).or(upper);
+            count += check(child, false, child_lower, child_upper, depth + 1, leaf_depth);
+        }
+        count
+    }
+    let mut leaf_depth = None;
+    assert_eq!(check(&map.root, true, None, None, 1, &mut leaf_depth), map.len());
+    assert_eq!(leaf_depth, Some(map.height()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn btree_map_works() {
+        let mut map = setup_btree_map();
+        check_invariants(&map);
+        assert_eq!(map.len(), 9);
+        assert_eq!(map.get(&30).map(String::as_str), Some("30"));
+        assert_eq!(map.get(&35), None);
+        assert!(map.contains_key(&90));
+
+        assert_eq!(map.insert(30, "thirty".to_string()), Some("30".to_string()));
+        map.get_mut(&40).unwrap().push('!');
+        assert_eq!(map.get(&40).map(String::as_str), Some("40!"));
+        assert_eq!(map.len(), 9);
+
+        assert_eq!(map.first_key_value(), Some((&10, &"10".to_string())));
+        assert_eq!(map.last_key_value(), Some((&90, &"90".to_string())));
+        assert_eq!(map.remove(&50), Some("50".to_string()));
+        assert_eq!(map.remove(&50), None);
+        assert_eq!(map.remove_entry(&10), Some((10, "10".to_string())));
+        check_invariants(&map);
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![20, 30, 40, 60, 70, 80, 90]);
+
+        let empty: BTreeMap<u32, u32> = BTreeMap::new();
+        assert_eq!(empty.first_key_value(), None);
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.height(), 1);
+    }
+
+    #[test]
+    fn btree_map_splits_and_merges() {
+        // a 2-3-4 tree of 1..=15 inserted in order: splits keep every Node between 1 and 3 keys
+        let mut map: BTreeMap<u32, u32, 2> = (1..=15).map(|key| (key, key)).collect();
+        check_invariants(&map);
+        assert_eq!(map.height(), 3);
+
+        let mut large: BTreeMap<u32, u32, 2> = (0..1000).map(|key| (key, key)).collect();
+        check_invariants(&large);
+        // a Node with k keys has k + 1 >= 2 children, so height is at most log2(n + 1) levels
+        assert!(large.height() <= 10);
+
+        // removing keys merges and rotates Nodes back into shape, shrinking the tree
+        for key in 1..=14 {
+            assert_eq!(map.remove(&key), Some(key));
+            check_invariants(&map);
+        }
+        assert_eq!(map.height(), 1);
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&15, &15)]);
+        assert_eq!(map.remove(&15), Some(15));
+        assert!(map.is_empty());
+
+        for key in (0..1000).rev().step_by(3) {
+            assert_eq!(large.remove(&key), Some(key));
+        }
+        check_invariants(&large);
+        assert_eq!(large.len(), 666);
+    }
+
+    #[test]
+    fn btree_map_matches_std_btree_map() {
+        fn random_operations<const B: usize>() {
+            let mut map: BTreeMap<u32, u32, B> = BTreeMap::new();
+            let mut expected = std::collections::BTreeMap::new();
+            let mut seed: u64 = 0x9E37_79B9_7F4A_7C15 ^ B as u64;
+            for step in 0..4000 {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                let key = (seed % 500) as u32;
+                if seed.is_multiple_of(3) {
+                    assert_eq!(map.remove(&key), expected.remove(&key));
+                } else {
+                    assert_eq!(map.insert(key, step), expected.insert(key, step));
+                }
+                if step % 200 == 0 {
+                    check_invariants(&map);
+                }
+            }
+            check_invariants(&map);
+            assert!(map.iter().eq(expected.iter()));
+            assert_eq!(map.iter().len(), expected.len());
+            assert_eq!(map.first_key_value(), expected.first_key_value());
+            assert_eq!(map.last_key_value(), expected.last_key_value());
+        }
+        random_operations::<2>();
+        random_operations::<3>();
+        random_operations::<DEFAULT_ORDER>();
+        random_operations::<32>();
+    }
+
+    #[test]
+    fn btree_map_traits() {
+        let map: BTreeMap<&str, u32> = [("b", 2), ("a", 1), ("b", 3)].into_iter().collect();
+        assert_eq!(format!("{:?}", map), "{\"a\": 1, \"b\": 3}");
+        assert_eq!((&map).into_iter().map(|(_, value)| value).sum::<u32>(), 4);
+        assert_eq!(map.values().collect::<Vec<_>>(), vec![&1, &3]);
+
+        let mut words: BTreeMap<String, usize> = BTreeMap::default();
+        words.insert("hello".to_string(), 5);
+        assert_eq!(words.get("hello"), Some(&5));
+        assert_eq!(words.remove("hello"), Some(5));
+    }
+}
@@ -0,0 +1,318 @@
+//! Circular singly linked list implementation that works using std::rc::Rc and interior
+//!     mutability with RefCell, like linked_list.rs. The tail Node's next member points back to
+//!     the head Node, so the list can be rotated in O(1) without moving any values.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+
+/// A circular linked list struct containing a "pointer" to its tail Node.
+///
+/// Every Node is owned by the strong next member of the Node before it - including the head Node,
+/// which is owned by the tail Node. That makes the Nodes a reference cycle, which Rc can't free on
+/// its own: Drop breaks the cycle explicitly before dropping the Nodes.
+///
+/// Only the tail is stored, as the head is always the tail's next Node.
+///
+/// * `tail`: An Option-wrapped reference to the last Node (None if the list is empty).
+/// * `length`: Number of Nodes in the list.
+pub struct CircularLinkedList<T> {
+    tail: Option<Rc<RefCell<Node<T>>>>,
+    length: usize
+}
+
+
+/// Node in a CircularLinkedList struct.
+///
+/// * `value`: T must be of type that matches the CircularLinkedList struct this Node is placed in
+/// * `next`: Option holding an Rc to the next Node - always Some while the Node is in a list (a
+///         single Node's next member points to itself); only None while the cycle is broken.
+pub struct Node<T> {
+    value: T,
+    next: Option<Rc<RefCell<Node<T>>>>
+}
+
+
+/// Iterator yielding references to the values in a CircularLinkedList struct, from head to tail,
+/// once each: iteration is bounded by the list's length instead of stopping at a missing next
+/// Node. Created by CircularLinkedList::iter.
+///
+/// * `next`: reference to the Node holding the next value to be yielded.
+/// * `remaining`: Number of values not yet yielded.
+pub struct Iter<'a, T> {
+    next: Option<&'a RefCell<Node<T>>>,
+    remaining: usize
+}
+
+
+// Method implementations for CircularLinkedList struct
+impl<T> CircularLinkedList<T> {
+
+    /// Return a new, empty CircularLinkedList struct
+    pub fn new() -> CircularLinkedList<T> {
+        CircularLinkedList {
+            tail: None,
+            length: 0
+        }
+    }
+
+    /// Return the number of values in this CircularLinkedList.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this CircularLinkedList holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Add a Node containing value T to the end of the list, between the tail and the head, and
+    /// make it the tail.
+    ///
+    /// * `value`: T to be stored in a new Node at the tail of the list.
+    pub fn add_value(&mut self, value: T) {
+        let new_node = self.link_after_tail(value);
+        self.tail = Some(new_node);
+    }
+
+    /// Add a Node containing value T to the front of the list, between the tail and the head, and
+    /// leave the tail in place - so the new Node is the head.
+    ///
+    /// * `value`: T to be stored in a new Node at the head of the list.
+    pub fn push_front(&mut self, value: T) {
+        let new_node = self.link_after_tail(value);
+        if self.tail.is_none() {
+            self.tail = Some(new_node);
+        }
+    }
+
+    /// Get a reference to the value in the head Node (if the list is not empty).
+    pub fn peek_head_value(&self) -> Option<&T> {
+        // SAFETY: the list holds the head Node, and is borrowed for the returned reference
+        Some(unsafe { &Node::get_unguarded(self.head()?).value })
+    }
+
+    /// Get a reference to the value in the tail Node (if the list is not empty).
+    pub fn peek_tail_value(&self) -> Option<&T> {
+        // SAFETY: as for peek_head_value
+        Some(unsafe { &Node::get_unguarded(self.tail.as_deref()?).value })
+    }
+
+    /// Remove the head Node from the list and return its value (unless the list is empty). The
+    /// tail Node's next member is pointed past it, to the new head.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let tail = self.tail.as_ref()?;
+        let head = tail.borrow_mut().next.take().expect("Nodes in a list always have a next Node");
+        if Rc::ptr_eq(&head, tail) {
+            // the only Node pointed to itself - dropping the list's reference leaves just head
+            self.tail = None;
+        } else {
+            let new_head = head.borrow_mut().next.take();
+            tail.borrow_mut().next = new_head;
+        }
+        self.length -= 1;
+
+        // Nodes are private to the list, so no other strong reference to head exists
+        match Rc::try_unwrap(head) {
+            Ok(node_refcell) => Some(node_refcell.into_inner().value),
+            Err(_) => unreachable!("Nodes are only strongly referenced by the list")
+        }
+    }
+
+    /// Rotate the list by one Node: the head Node becomes the tail, and the Node after it becomes
+    /// the head. O(1) - only the tail member moves.
+    pub fn rotate(&mut self) {
+        if let Some(tail) = self.tail.take() {
+            self.tail = tail.borrow().next.clone();
+        }
+    }
+
+    /// Rotate the list by the input number of Nodes (see rotate). Rotating by len() leaves the
+    /// list unchanged, so only steps % len() Nodes are walked.
+    ///
+    /// * `steps`: number of Nodes to move from the front of the list to the back.
+    pub fn rotate_by(&mut self, steps: usize) {
+        if self.length == 0 {
+            return;
+        }
+        for _ in 0..steps % self.length {
+            self.rotate();
+        }
+    }
+
+    /// Return an iterator of references to the values in this CircularLinkedList, from head to
+    /// tail, yielding each value once.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head(),
+            remaining: self.length
+        }
+    }
+
+    /// Return a reference to the head Node - the tail Node's next Node - borrowed from self.
+    fn head(&self) -> Option<&RefCell<Node<T>>> {
+        // SAFETY: the list holds the tail Node, and is borrowed for the returned reference
+        let tail = unsafe { Node::get_unguarded(self.tail.as_deref()?) };
+        tail.next.as_deref()
+    }
+
+    /// Link a new Node containing value T in between the tail and the head, and return an Rc
+    /// clone of it. If the list is empty, the new Node's next member points to itself, but the
+    /// tail member isn't set - callers decide whether the new Node is the tail.
+    ///
+    /// * `value`: T to be stored in the new Node.
+    fn link_after_tail(&mut self, value: T) -> Rc<RefCell<Node<T>>> {
+        let new_node = Rc::new(RefCell::new(Node { value, next: None }));
+        let head = match &self.tail {
+            Some(tail) => tail.borrow_mut().next.replace(Rc::clone(&new_node)),
+            None => Some(Rc::clone(&new_node))
+        };
+        new_node.borrow_mut().next = head;
+        self.length += 1;
+        new_node
+    }
+}
+
+impl<T> Default for CircularLinkedList<T> {
+    fn default() -> Self {
+        CircularLinkedList::new()
+    }
+}
+
+// Break the cycle at the tail, then drop Nodes one at a time from the head, so long lists don't
+// drop their next chain recursively.
+impl<T> Drop for CircularLinkedList<T> {
+    fn drop(&mut self) {
+        let Some(tail) = self.tail.take() else {
+            return;
+        };
+        let mut opt_node = tail.borrow_mut().next.take();
+        while let Some(node_rc) = opt_node {
+            opt_node = node_rc.borrow_mut().next.take();
+        }
+    }
+}
+
+
+// Method implementations for Node struct
+impl<T> Node<T> {
+
+    /// Return a reference to the Node in the input RefCell without a guard, like linked_list's
+    /// Iter does.
+    ///
+    /// # Safety
+    /// The Node mustn't be mutably borrowed while the reference exists. That holds for any Node in
+    /// a list borrowed for as long, since only the list's &mut self methods mutably borrow Nodes.
+    ///
+    /// * `node_ref`: RefCell-wrapped Node to be borrowed.
+    unsafe fn get_unguarded(node_ref: &RefCell<Node<T>>) -> &Node<T> {
+        // SAFETY: guaranteed by the caller
+        unsafe { node_ref.try_borrow_unguarded() }.expect("Nodes aren't mutably borrowed while the list is borrowed")
+    }
+}
+
+
+// Iterator implementation for Iter struct
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node_ref = self.next.take()?;
+        // SAFETY: the CircularLinkedList borrowed for 'a holds the Node
+        let node = unsafe { Node::get_unguarded(node_ref) };
+        self.next = node.next.as_deref();
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_circular_linked_list() -> CircularLinkedList<u32> {
+    let mut cll: CircularLinkedList<u32> = CircularLinkedList::new();
+    cll.add_value(2);
+    cll.add_value(4);
+    cll.add_value(6);
+    cll
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circular_linked_list_works() {
+        let mut cll = setup_circular_linked_list();
+        cll.push_front(0);
+        assert_eq!(cll.len(), 4);
+        assert_eq!(cll.peek_head_value(), Some(&0));
+        assert_eq!(cll.peek_tail_value(), Some(&6));
+        assert_eq!(cll.iter().collect::<Vec<_>>(), vec![&0, &2, &4, &6]);
+
+        assert_eq!(cll.pop_front(), Some(0));
+        assert_eq!(cll.pop_front(), Some(2));
+        assert_eq!(cll.pop_front(), Some(4));
+        assert_eq!(cll.peek_head_value(), Some(&6));
+        assert_eq!(cll.pop_front(), Some(6));
+        assert_eq!(cll.pop_front(), None);
+        assert!(cll.is_empty());
+        assert_eq!(cll.iter().next(), None);
+
+        cll.push_front(1);
+        assert_eq!(cll.peek_tail_value(), Some(&1));
+    }
+
+    #[test]
+    fn circular_linked_list_can_be_rotated() {
+        let mut cll = setup_circular_linked_list();
+        cll.rotate();
+        assert_eq!(cll.iter().collect::<Vec<_>>(), vec![&4, &6, &2]);
+        assert_eq!(cll.peek_tail_value(), Some(&2));
+
+        cll.rotate_by(5);
+        assert_eq!(cll.iter().collect::<Vec<_>>(), vec![&2, &4, &6]);
+        cll.add_value(8);
+        assert_eq!(cll.iter().collect::<Vec<_>>(), vec![&2, &4, &6, &8]);
+
+        let mut empty: CircularLinkedList<u32> = CircularLinkedList::new();
+        empty.rotate();
+        empty.rotate_by(3);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn circular_linked_list_drop_breaks_the_cycle() {
+        let counter = Rc::new(());
+        let mut cll = CircularLinkedList::new();
+        for _ in 0..3 {
+            cll.add_value(Rc::clone(&counter));
+        }
+        cll.rotate();
+        assert_eq!(Rc::strong_count(&counter), 4);
+        drop(cll);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        // a single Node points to itself
+        let mut single = CircularLinkedList::new();
+        single.add_value(Rc::clone(&counter));
+        drop(single);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let mut long_cll = CircularLinkedList::new();
+        for value in 0..500_000u32 {
+            long_cll.add_value(value);
+        }
+        drop(long_cll);
+    }
+}
@@ -0,0 +1,273 @@
+//! Count-min sketch: approximate counts of how often each item occurs in a stream, in a fixed
+//!     number of counters however many distinct items there are. The counters form d rows of w;
+//!     each item is hashed to one counter per row, and counting it adds to all d. An item's count
+//!     is estimated by the smallest of its d counters - never too low, and too high only by the
+//!     counts of other items sharing all of them.
+//!
+//! CountMinSketch::new picks the width w and depth d from error bounds ε and δ: w = e / ε and
+//!     d = ln(1 / δ). Then every estimate is at most ε N too high (for N the total of all
+//!     counts) with probability at least 1 - δ. As in the bloom_filter module, the d counters come
+//!     from two hashes by double hashing, with hashers using fixed keys.
+//!
+//! Sketches with the same dimensions can be merged by adding their counters, which gives the
+//!     sketch of both streams together - so streams can be counted separately and combined.
+//!
+//! ```
+//! use rust_datastructures_algorithms::count_min_sketch::CountMinSketch;
+//!
+//! let mut words = CountMinSketch::new(0.01, 0.01);
+//! for word in "the cat and the dog and the bird".split(' ') {
+//!     words.increment(word);
+//! }
+//! assert!(words.estimate("the") >= 3);
+//! assert_eq!(words.total(), 8);
+//!
+//! let mut more = CountMinSketch::new(0.01, 0.01);
+//! more.add("the", 10);
+//! let merged = words.merge(&more).unwrap();
+//! assert!(merged.estimate("the") >= 13);
+//! ```
+
+use std::f64::consts::E;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use crate::bloom_filter::item_hashes;
+
+
+/// A count-min sketch struct.
+///
+/// * `counters`: The counters, row after row - row i is `counters[i * width..(i + 1) * width]`.
+/// * `width`: Number of counters per row (w).
+/// * `depth`: Number of rows (d).
+/// * `total`: Sum of all counts added (N).
+/// * `marker`: Marker for the item type, which is only hashed, never stored.
+pub struct CountMinSketch<T: Hash + ?Sized> {
+    counters: Vec<u64>,
+    width: usize,
+    depth: usize,
+    total: u64,
+    marker: PhantomData<fn(&T)>
+}
+
+
+// Method implementations for CountMinSketch struct
+impl<T> CountMinSketch<T> where T: Hash + ?Sized {
+
+    /// Return a new, empty CountMinSketch struct sized so that each estimate is at most the input
+    /// fraction of the total count too high, except with the input probability. Panics if either
+    /// isn't strictly between 0 and 1.
+    ///
+    /// * `epsilon`: error bound as a fraction of the total count (ε), e.g. 0.001.
+    /// * `delta`: probability of an estimate exceeding the error bound (δ), e.g. 0.01.
+    pub fn new(epsilon: f64, delta: f64) -> CountMinSketch<T> {
+        assert!(epsilon > 0.0 && epsilon < 1.0, "epsilon must be between 0 and 1, not {}", epsilon);
+        assert!(delta > 0.0 && delta < 1.0, "delta must be between 0 and 1, not {}", delta);
+        let width = (E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil().max(1.0) as usize;
+        CountMinSketch::with_dimensions(width, depth)
+    }
+
+    /// Return a new, empty CountMinSketch struct with the input numbers of counters per row and
+    /// rows. Panics if either is 0.
+    ///
+    /// * `width`: number of counters per row (w).
+    /// * `depth`: number of rows (d).
+    pub fn with_dimensions(width: usize, depth: usize) -> CountMinSketch<T> {
+        assert!(width > 0 && depth > 0, "a count-min sketch needs at least one row and column");
+        CountMinSketch { counters: vec![0; width * depth], width, depth, total: 0, marker: PhantomData }
+    }
+
+    /// Return the number of counters per row (w).
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Return the number of rows (d).
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Return the sum of all counts added (N).
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Return whether nothing has been counted.
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Count one occurrence of an item.
+    ///
+    /// * `item`: item to be counted.
+    pub fn increment(&mut self, item: &T) {
+        self.add(item, 1);
+    }
+
+    /// Count the input number of occurrences of an item. Counters saturate at u64::MAX rather than
+    /// overflowing.
+    ///
+    /// * `item`: item to be counted.
+    /// * `count`: number of occurrences.
+    pub fn add(&mut self, item: &T, count: u64) {
+        for counter in self.counter_indexes(item) {
+            self.counters[counter] = self.counters[counter].saturating_add(count);
+        }
+        self.total = self.total.saturating_add(count);
+    }
+
+    /// Return an estimate of how many times the item has been counted: the smallest of its
+    /// counters. Never less than the true count.
+    ///
+    /// * `item`: item to look up.
+    pub fn estimate(&self, item: &T) -> u64 {
+        self.counter_indexes(item).map(|counter| self.counters[counter]).min().expect("depth is at least 1")
+    }
+
+    /// Reset every count to 0.
+    pub fn clear(&mut self) {
+        self.counters.fill(0);
+        self.total = 0;
+    }
+
+    /// Return the sketch of everything counted by this sketch or the input one, found by adding
+    /// their counters. Returns None if the sketches have different dimensions.
+    ///
+    /// * `other`: the other sketch.
+    pub fn merge(&self, other: &CountMinSketch<T>) -> Option<CountMinSketch<T>> {
+        if self.width != other.width || self.depth != other.depth {
+            return None;
+        }
+        Some(CountMinSketch {
+            counters: self.counters.iter().zip(&other.counters).map(|(left, right)| left.saturating_add(*right)).collect(),
+            width: self.width,
+            depth: self.depth,
+            total: self.total.saturating_add(other.total),
+            marker: PhantomData
+        })
+    }
+
+    /// Return the index of the input item's counter in each row, by double hashing.
+    ///
+    /// * `item`: item to find the counters of.
+    fn counter_indexes(&self, item: &T) -> impl Iterator<Item = usize> {
+        let (first, second) = item_hashes(item);
+        let width = self.width;
+        (0..self.depth).map(move |row| {
+            let column = first.wrapping_add((row as u64).wrapping_mul(second)) % width as u64;
+            row * width + column as usize
+        })
+    }
+}
+
+impl<T> Clone for CountMinSketch<T> where T: Hash + ?Sized {
+    fn clone(&self) -> Self {
+        CountMinSketch {
+            counters: self.counters.clone(),
+            width: self.width,
+            depth: self.depth,
+            total: self.total,
+            marker: PhantomData
+        }
+    }
+}
+
+impl<T> PartialEq for CountMinSketch<T> where T: Hash + ?Sized {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.depth == other.depth && self.counters == other.counters
+    }
+}
+
+impl<T> Eq for CountMinSketch<T> where T: Hash + ?Sized {}
+
+impl<T> Extend<T> for CountMinSketch<T> where T: Hash {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.increment(&item);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_count_min_sketch() -> CountMinSketch<u32> {
+    let mut sketch = CountMinSketch::new(0.01, 0.01);
+    // item i occurs i times
+    for item in 1..=100 {
+        sketch.add(&item, u64::from(item));
+    }
+    sketch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_min_sketch_works() {
+        let mut sketch = setup_count_min_sketch();
+        // w = e / 0.01, d = ln(1 / 0.01)
+        assert_eq!(sketch.width(), 272);
+        assert_eq!(sketch.depth(), 5);
+        assert_eq!(sketch.total(), 5050);
+        assert!((1..=100).all(|item| sketch.estimate(&item) >= u64::from(item)));
+        // 100 items in 272 columns mostly get a counter of their own in some row
+        assert!((1..=100).filter(|item| sketch.estimate(item) == u64::from(*item)).count() > 90);
+        assert!(sketch.estimate(&1000) <= 50);
+
+        sketch.increment(&7);
+        assert!(sketch.estimate(&7) >= 8);
+        sketch.clear();
+        assert!(sketch.is_empty());
+        assert_eq!(sketch.estimate(&100), 0);
+    }
+
+    #[test]
+    fn count_min_sketch_error_bound() {
+        let (epsilon, delta) = (0.005, 0.01);
+        let mut sketch = CountMinSketch::new(epsilon, delta);
+        // a skewed stream: item i occurs about 1 / (i + 1) as often as item 0
+        let mut counts = vec![0u64; 2000];
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for _ in 0..50_000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let item = ((counts.len() as f64).powf((seed % 10_000) as f64 / 10_000.0) - 1.0) as usize;
+            counts[item] += 1;
+            sketch.increment(&item);
+        }
+        let bound = (epsilon * sketch.total() as f64) as u64;
+        let mut over_bound = 0;
+        for (item, count) in counts.iter().enumerate() {
+            let estimate = sketch.estimate(&item);
+            assert!(estimate >= *count);
+            if estimate > count + bound {
+                over_bound += 1;
+            }
+        }
+        assert!(over_bound as f64 <= delta * counts.len() as f64, "{} over the bound", over_bound);
+    }
+
+    #[test]
+    fn count_min_sketch_merge() {
+        let mut left = CountMinSketch::with_dimensions(64, 4);
+        let mut right = CountMinSketch::with_dimensions(64, 4);
+        let mut whole = CountMinSketch::with_dimensions(64, 4);
+        for item in 0..500u32 {
+            let half = if item % 3 == 0 { &mut left } else { &mut right };
+            half.increment(&(item % 40));
+            whole.increment(&(item % 40));
+        }
+        // merging gives exactly the sketch of both streams together
+        let merged = left.merge(&right).unwrap();
+        assert!(merged == whole);
+        assert_eq!(merged.total(), 500);
+        assert!((0..40u32).all(|item| merged.estimate(&item) >= left.estimate(&item).max(right.estimate(&item))));
+
+        assert!(left.merge(&CountMinSketch::with_dimensions(64, 5)).is_none());
+        assert!(left.merge(&CountMinSketch::with_dimensions(32, 4)).is_none());
+    }
+}
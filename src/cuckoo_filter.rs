@@ -0,0 +1,357 @@
+//! Cuckoo filter: an approximate set like the bloom_filter module's BloomFilter, but one that
+//!     supports deleting items. Instead of bits, it stores a short fingerprint of each item in
+//!     one of two candidate buckets. The second bucket is the first XOR a hash of the
+//!     fingerprint, so either bucket can be found from the other and the fingerprint alone.
+//!
+//! When both buckets are full, a random fingerprint is kicked out to its other bucket, which may
+//!     kick out another, and so on (as in cuckoo hashing). If that chain runs past a limit, the
+//!     fingerprint left without a place is kept aside as a victim rather than lost; the filter
+//!     then counts as full and refuses new items until something is removed. With buckets of 4
+//!     fingerprints, the filter typically fills to over 95% before that happens.
+//!
+//! An item is reported present if its fingerprint is in either bucket, so false positives happen
+//!     at a rate of about 2b / 2^f for buckets of b fingerprints of f bits. Only remove items
+//!     that were inserted - removing one that wasn't could remove another item's fingerprint.
+//!
+//! ```
+//! use rust_datastructures_algorithms::cuckoo_filter::CuckooFilter;
+//!
+//! let mut filter = CuckooFilter::new(1000);
+//! assert!(filter.insert("apple"));
+//! assert!(filter.insert("banana"));
+//! assert!(filter.contains("apple"));
+//! assert!(filter.remove("apple"));
+//! assert!(!filter.contains("apple"));
+//! assert_eq!(filter.len(), 1);
+//! ```
+
+use std::hash::Hash;
+use std::marker::PhantomData;
+use crate::bloom_filter::item_hashes;
+use crate::rng::XorShift64;
+
+
+/// Number of fingerprints per bucket used by CuckooFilter::new.
+pub const DEFAULT_BUCKET_SIZE: usize = 4;
+
+/// Number of bits per fingerprint used by CuckooFilter::new.
+pub const DEFAULT_FINGERPRINT_BITS: u32 = 16;
+
+/// Most fingerprints kicked out by one insertion before the filter counts as full.
+const MAX_KICKS: usize = 500;
+
+/// Fraction of slots new aims to fill at its capacity - a little under the load buckets of 4
+/// reach in practice.
+const TARGET_LOAD: f64 = 0.9;
+
+
+/// A cuckoo filter struct.
+///
+/// * `slots`: Fingerprints, bucket after bucket - bucket i is `slots[i * bucket_size..(i + 1) *
+///         bucket_size]`. 0 marks an empty slot, so no fingerprint is 0.
+/// * `bucket_count`: Number of buckets - a power of two, so bucket indexes can be masked.
+/// * `bucket_size`: Number of fingerprints per bucket (b).
+/// * `fingerprint_bits`: Number of bits per fingerprint (f), from 1 to 16.
+/// * `victim`: Bucket index and fingerprint left without a slot after a chain of kicks ran too long
+///         (None if there is none). The filter refuses new items while there is one.
+/// * `length`: Number of fingerprints stored, including the victim.
+/// * `rng`: Xorshift generator choosing which fingerprint to kick out.
+/// * `marker`: Marker for the item type, which is only hashed, never stored.
+pub struct CuckooFilter<T: Hash + ?Sized> {
+    slots: Vec<u16>,
+    bucket_count: usize,
+    bucket_size: usize,
+    fingerprint_bits: u32,
+    victim: Option<(usize, u16)>,
+    length: usize,
+    rng: XorShift64,
+    marker: PhantomData<fn(&T)>
+}
+
+
+// Method implementations for CuckooFilter struct
+impl<T> CuckooFilter<T> where T: Hash + ?Sized {
+
+    /// Return a new, empty CuckooFilter struct with room for about the input number of items,
+    /// using buckets of 4 fingerprints of 16 bits (a false positive rate of about 0.01%).
+    ///
+    /// * `capacity`: number of items the filter should hold.
+    pub fn new(capacity: usize) -> CuckooFilter<T> {
+        let buckets = (capacity as f64 / (DEFAULT_BUCKET_SIZE as f64 * TARGET_LOAD)).ceil() as usize;
+        CuckooFilter::with_parameters(buckets.max(1).next_power_of_two(), DEFAULT_BUCKET_SIZE, DEFAULT_FINGERPRINT_BITS)
+    }
+
+    /// Return a new, empty CuckooFilter struct with the input parameters. Panics if the bucket
+    /// count isn't a power of two, the bucket size is 0, or the fingerprint size isn't from 1 to
+    /// 16 bits.
+    ///
+    /// * `bucket_count`: number of buckets - a power of two.
+    /// * `bucket_size`: number of fingerprints per bucket (b). Larger buckets allow a higher load
+    ///         but raise the false positive rate.
+    /// * `fingerprint_bits`: number of bits per fingerprint (f). Each extra bit halves the false
+    ///         positive rate.
+    pub fn with_parameters(bucket_count: usize, bucket_size: usize, fingerprint_bits: u32) -> CuckooFilter<T> {
+        assert!(bucket_count.is_power_of_two(), "bucket count must be a power of two, not {}", bucket_count);
+        assert!(bucket_size > 0, "buckets must hold at least one fingerprint");
+        assert!((1..=16).contains(&fingerprint_bits), "fingerprints must be 1 to 16 bits, not {}", fingerprint_bits);
+        CuckooFilter {
+            slots: vec![0; bucket_count * bucket_size],
+            bucket_count,
+            bucket_size,
+            fingerprint_bits,
+            victim: None,
+            length: 0,
+            rng: XorShift64::new(),
+            marker: PhantomData
+        }
+    }
+
+    /// Return the number of items stored.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether no items are stored.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the number of fingerprint slots (buckets times bucket size).
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Return the fraction of fingerprint slots in use.
+    pub fn load_factor(&self) -> f64 {
+        self.length as f64 / self.slots.len() as f64
+    }
+
+    /// Return whether the filter is full: a chain of kicks ran too long, and new items will be
+    /// refused until one is removed.
+    pub fn is_full(&self) -> bool {
+        self.victim.is_some()
+    }
+
+    /// Insert an item, returning whether it was stored (false only if the filter is full).
+    /// Inserting the same item twice stores its fingerprint twice, so it must be removed twice.
+    ///
+    /// The fingerprint goes into whichever of its two buckets has a free slot. If neither does, a
+    ///     random fingerprint in one of them is swapped out and moved to its own other bucket,
+    ///     repeating up to MAX_KICKS times; the fingerprint still without a slot becomes the
+    ///     victim.
+    ///
+    /// * `item`: item to be inserted.
+    pub fn insert(&mut self, item: &T) -> bool {
+        if self.victim.is_some() {
+            return false;
+        }
+        let (bucket, fingerprint) = self.bucket_and_fingerprint(item);
+        self.length += 1;
+        self.place(bucket, fingerprint);
+        true
+    }
+
+    /// Return whether the item may have been inserted: false means it definitely wasn't (or was
+    /// removed), and true is wrong for a small fraction of items.
+    ///
+    /// * `item`: item to look for.
+    pub fn contains(&self, item: &T) -> bool {
+        let (first, fingerprint) = self.bucket_and_fingerprint(item);
+        let second = self.alternate_bucket(first, fingerprint);
+        self.bucket(first).contains(&fingerprint)
+            || self.bucket(second).contains(&fingerprint)
+            || self.victim.is_some_and(|(bucket, victim)| victim == fingerprint && (bucket == first || bucket == second))
+    }
+
+    /// Remove one copy of an item's fingerprint, returning whether one was found. Only remove
+    /// items that were inserted: removing an item that wasn't, but shares a fingerprint and bucket
+    /// with one that was, removes the other item instead.
+    ///
+    /// * `item`: item to be removed.
+    pub fn remove(&mut self, item: &T) -> bool {
+        let (first, fingerprint) = self.bucket_and_fingerprint(item);
+        let second = self.alternate_bucket(first, fingerprint);
+        if let Some((bucket, victim)) = self.victim {
+            if victim == fingerprint && (bucket == first || bucket == second) {
+                self.victim = None;
+                self.length -= 1;
+                return true;
+            }
+        }
+        for bucket in [first, second] {
+            let start = bucket * self.bucket_size;
+            if let Some(slot) = self.slots[start..start + self.bucket_size].iter().position(|stored| *stored == fingerprint) {
+                self.slots[start + slot] = 0;
+                self.length -= 1;
+                // the freed slot may give the victim a home
+                if let Some((bucket, victim)) = self.victim.take() {
+                    self.place(bucket, victim);
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Remove every item.
+    pub fn clear(&mut self) {
+        self.slots.fill(0);
+        self.victim = None;
+        self.length = 0;
+    }
+
+    /// Store a fingerprint in the input bucket or its alternate, kicking out others as needed, and
+    /// leaving the last one kicked out as the victim if the chain runs past MAX_KICKS.
+    ///
+    /// * `bucket`: one of the fingerprint's buckets.
+    /// * `fingerprint`: fingerprint to be stored.
+    fn place(&mut self, bucket: usize, fingerprint: u16) {
+        let alternate = self.alternate_bucket(bucket, fingerprint);
+        if self.try_store(bucket, fingerprint) || self.try_store(alternate, fingerprint) {
+            return;
+        }
+        let mut bucket = if self.rng.next_u64() & 1 == 0 { bucket } else { alternate };
+        let mut fingerprint = fingerprint;
+        for _ in 0..MAX_KICKS {
+            let slot = bucket * self.bucket_size + (self.rng.next_u64() % self.bucket_size as u64) as usize;
+            fingerprint = std::mem::replace(&mut self.slots[slot], fingerprint);
+            bucket = self.alternate_bucket(bucket, fingerprint);
+            if self.try_store(bucket, fingerprint) {
+                return;
+            }
+        }
+        self.victim = Some((bucket, fingerprint));
+    }
+
+    /// Store a fingerprint in a free slot of the input bucket, returning whether there was one.
+    ///
+    /// * `bucket`: index of the bucket.
+    /// * `fingerprint`: fingerprint to be stored.
+    fn try_store(&mut self, bucket: usize, fingerprint: u16) -> bool {
+        let start = bucket * self.bucket_size;
+        match self.slots[start..start + self.bucket_size].iter().position(|stored| *stored == 0) {
+            Some(slot) => {
+                self.slots[start + slot] = fingerprint;
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Return the fingerprints (and empty slots) in the input bucket.
+    ///
+    /// * `bucket`: index of the bucket.
+    fn bucket(&self, bucket: usize) -> &[u16] {
+        &self.slots[bucket * self.bucket_size..(bucket + 1) * self.bucket_size]
+    }
+
+    /// Return an item's first bucket and its fingerprint, from two independent hashes. A
+    /// fingerprint of 0 is made 1, since 0 marks empty slots.
+    ///
+    /// * `item`: item to hash.
+    fn bucket_and_fingerprint(&self, item: &T) -> (usize, u16) {
+        let (first, second) = item_hashes(item);
+        let fingerprint = (second >> (64 - self.fingerprint_bits)) as u16;
+        ((first as usize) & (self.bucket_count - 1), fingerprint.max(1))
+    }
+
+    /// Return a fingerprint's other bucket: the input bucket XOR a hash of the fingerprint, so
+    /// applying it to either bucket gives the other.
+    ///
+    /// * `bucket`: one of the fingerprint's buckets.
+    /// * `fingerprint`: the fingerprint.
+    fn alternate_bucket(&self, bucket: usize, fingerprint: u16) -> usize {
+        // MurmurHash2's multiplier spreads the fingerprint's bits over the word
+        let hash = u64::from(fingerprint).wrapping_mul(0x5BD1_E995);
+        (bucket ^ hash as usize) & (self.bucket_count - 1)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_cuckoo_filter() -> CuckooFilter<u32> {
+    let mut filter = CuckooFilter::new(1000);
+    for item in 0..1000 {
+        filter.insert(&(item * 3));
+    }
+    filter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuckoo_filter_works() {
+        let mut filter = setup_cuckoo_filter();
+        assert_eq!(filter.len(), 1000);
+        assert_eq!(filter.slot_count(), 4 * 512);
+        assert!(!filter.is_full());
+        assert!((0..1000).all(|item| filter.contains(&(item * 3))));
+
+        // removing half leaves the other half, and the removed ones are gone
+        for item in (0..1000).step_by(2) {
+            assert!(filter.remove(&(item * 3)));
+        }
+        assert_eq!(filter.len(), 500);
+        assert!((1..1000).step_by(2).all(|item| filter.contains(&(item * 3))));
+        assert!((0..1000).step_by(2).filter(|item| filter.contains(&(item * 3))).count() < 5);
+        assert!(!filter.remove(&1_000_000));
+
+        // a duplicate is stored twice and must be removed twice
+        assert!(filter.insert(&3));
+        assert!(filter.remove(&3));
+        assert!(filter.contains(&3));
+        assert!(filter.remove(&3));
+        assert!(!filter.contains(&3));
+
+        filter.clear();
+        assert!(filter.is_empty());
+        assert!(!filter.contains(&9));
+    }
+
+    #[test]
+    fn cuckoo_filter_false_positive_rate() {
+        // 8 bit fingerprints in buckets of 4: about 2 * 4 / 256 = 3%
+        let mut filter: CuckooFilter<u64> = CuckooFilter::with_parameters(1024, 4, 8);
+        for item in 0..3500 {
+            assert!(filter.insert(&item));
+        }
+        let false_positives = (1_000_000..1_100_000u64).filter(|item| filter.contains(item)).count();
+        let measured = false_positives as f64 / 100_000.0;
+        assert!(measured < 2.0 * 4.0 / 256.0, "measured {}", measured);
+        assert!((0..3500).all(|item| filter.contains(&item)));
+    }
+
+    #[test]
+    fn cuckoo_filter_fills_up_without_losing_items() {
+        let mut filter: CuckooFilter<u64> = CuckooFilter::with_parameters(256, 4, 16);
+        let mut stored = 0;
+        while filter.insert(&stored) {
+            stored += 1;
+        }
+        // the insertion that filled the filter still stored its item (or the one it kicked out)
+        assert!(filter.is_full());
+        assert_eq!(filter.len(), stored as usize);
+        assert!(filter.load_factor() > 0.9, "load factor {}", filter.load_factor());
+        assert!((0..stored).all(|item| filter.contains(&item)));
+        assert!(!filter.insert(&u64::MAX));
+
+        // removing items makes room again, and the victim moves into the freed slots
+        for item in 0..20 {
+            assert!(filter.remove(&item));
+        }
+        assert!(!filter.is_full());
+        assert_eq!(filter.len(), stored as usize - 20);
+        assert!((20..stored).all(|item| filter.contains(&item)));
+        assert!(filter.insert(&0));
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn cuckoo_filter_bucket_count_must_be_power_of_two() {
+        CuckooFilter::<u32>::with_parameters(100, 4, 12);
+    }
+}
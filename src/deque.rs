@@ -0,0 +1,431 @@
+//! Double-ended queue backed by a growable circular buffer (ring buffer): values can be pushed
+//!     and popped at both ends in O(1) (amortized for pushes), and read by index from the front.
+//!     queue.rs's Queue is built on it, and ring_buffer.rs's fixed-size RingBuffer shares its
+//!     index wrapping (wrap_index).
+//!
+//! ```
+//! use rust_datastructures_algorithms::deque::Deque;
+//!
+//! // maximum of each window of 3 values, with a monotonic deque of indices
+//! let values = [1, 3, -1, -3, 5, 3, 6, 7];
+//! let mut indices: Deque<usize> = Deque::new();
+//! let mut maximums = Vec::new();
+//! for (i, value) in values.iter().enumerate() {
+//!     while indices.back().is_some_and(|&back| values[back] <= *value) {
+//!         indices.pop_back();
+//!     }
+//!     indices.push_back(i);
+//!     if indices[0] + 3 <= i {
+//!         indices.pop_front();
+//!     }
+//!     if i >= 2 {
+//!         maximums.push(values[indices[0]]);
+//!     }
+//! }
+//! assert_eq!(maximums, vec![3, 3, 5, 5, 6, 7]);
+//! ```
+
+use std::ops::{ Index, IndexMut };
+
+
+/// Capacity the buffer grows to on the first push.
+const MIN_CAPACITY: usize = 4;
+
+
+/// A double-ended queue struct holding its values in a circular buffer.
+///
+/// The values are the `length` slots starting at index `front`, wrapping from the end of the
+/// buffer back to index 0. Empty slots hold None. push_front moves the front index back one slot
+/// (wrapping to the end of the buffer), push_back fills the slot after the last value.
+///
+/// * `buffer`: Slots of the circular buffer - its length is the deque's capacity.
+/// * `front`: Index of the slot holding the front value.
+/// * `length`: Number of values in the deque.
+pub struct Deque<T> {
+    buffer: Vec<Option<T>>,
+    front: usize,
+    length: usize
+}
+
+
+/// Iterator yielding references to the values in a Deque struct, from front to back. Implements
+/// DoubleEndedIterator, so it can be reversed to go from back to front. Created by Deque::iter.
+///
+/// * `deque`: the Deque struct being iterated over.
+/// * `front_offset`: Position (from the front of the deque) of the next value to be yielded from
+///         the front.
+/// * `back_offset`: Position one past the next value to be yielded from the back.
+pub struct Iter<'a, T> {
+    deque: &'a Deque<T>,
+    front_offset: usize,
+    back_offset: usize
+}
+
+
+/// Iterator yielding the values in a Deque struct by value, from front to back, consuming the
+/// deque. Created by Deque::into_iter (from the IntoIterator trait).
+///
+/// * `deque`: the Deque being consumed; each step pops its front value.
+pub struct IntoIter<T> {
+    deque: Deque<T>
+}
+
+
+// Method implementations for Deque struct
+impl<T> Deque<T> {
+
+    /// Return a new, empty Deque struct. Nothing is allocated until the first push.
+    pub fn new() -> Deque<T> {
+        Deque {
+            buffer: Vec::new(),
+            front: 0,
+            length: 0
+        }
+    }
+
+    /// Return a new, empty Deque struct with room for the input number of values before it needs
+    /// to grow.
+    ///
+    /// * `capacity`: number of values to allocate room for.
+    pub fn with_capacity(capacity: usize) -> Deque<T> {
+        let mut deque = Deque::new();
+        deque.buffer.resize_with(capacity, || None);
+        deque
+    }
+
+    /// Return the number of values in this Deque.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this Deque holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the number of values this Deque can hold before it needs to grow.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Add a value to the front of the deque, growing the buffer first if it is full.
+    ///
+    /// * `value`: value to be pushed.
+    pub fn push_front(&mut self, value: T) {
+        if self.length == self.buffer.len() {
+            self.grow();
+        }
+        self.front = wrap_index(self.front + self.buffer.len() - 1, self.buffer.len());
+        self.buffer[self.front] = Some(value);
+        self.length += 1;
+    }
+
+    /// Add a value to the back of the deque, growing the buffer first if it is full.
+    ///
+    /// * `value`: value to be pushed.
+    pub fn push_back(&mut self, value: T) {
+        if self.length == self.buffer.len() {
+            self.grow();
+        }
+        let back = self.slot_index(self.length);
+        self.buffer[back] = Some(value);
+        self.length += 1;
+    }
+
+    /// Remove the value at the front of the deque and return it (unless the deque is empty).
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+        let value = self.buffer[self.front].take();
+        self.front = self.slot_index(1);
+        self.length -= 1;
+        value
+    }
+
+    /// Remove the value at the back of the deque and return it (unless the deque is empty).
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+        self.length -= 1;
+        let back = self.slot_index(self.length);
+        self.buffer[back].take()
+    }
+
+    /// Get a reference to the value at the front of the deque (if the deque is not empty).
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Get a reference to the value at the back of the deque (if the deque is not empty).
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.length.checked_sub(1)?)
+    }
+
+    /// Return a reference to the value at the input position from the front, or None if the
+    /// index is not less than len().
+    ///
+    /// * `index`: position of the value, counting from 0 at the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.length {
+            return None;
+        }
+        self.buffer[self.slot_index(index)].as_ref()
+    }
+
+    /// Return a mutable reference to the value at the input position from the front (see get).
+    ///
+    /// * `index`: position of the value, counting from 0 at the front.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.length {
+            return None;
+        }
+        let slot = self.slot_index(index);
+        self.buffer[slot].as_mut()
+    }
+
+    /// Return an iterator of references to the values in this Deque, from front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            deque: self,
+            front_offset: 0,
+            back_offset: self.length
+        }
+    }
+
+    /// Return the index of the slot the input number of places after the front slot, wrapping
+    /// around the end of the buffer.
+    ///
+    /// * `offset`: number of places after the front slot, less than or equal to the capacity.
+    fn slot_index(&self, offset: usize) -> usize {
+        wrap_index(self.front + offset, self.buffer.len())
+    }
+
+    /// Double the capacity of the buffer (to at least MIN_CAPACITY). The values are moved to a new
+    /// buffer in order, starting at index 0, so they no longer wrap around.
+    fn grow(&mut self) {
+        let new_capacity = (self.buffer.len() * 2).max(MIN_CAPACITY);
+        let mut new_buffer: Vec<Option<T>> = Vec::with_capacity(new_capacity);
+        for offset in 0..self.length {
+            let index = self.slot_index(offset);
+            new_buffer.push(self.buffer[index].take());
+        }
+        new_buffer.resize_with(new_capacity, || None);
+        self.buffer = new_buffer;
+        self.front = 0;
+    }
+}
+
+/// Return the input index into a circular buffer of the input capacity, wrapped around the end of
+/// the buffer back to the start. Cheaper than % for the indexes circular buffers use, which are
+/// never a full lap past the end.
+///
+/// * `index`: slot index, less than twice the capacity.
+/// * `capacity`: number of slots in the buffer.
+pub(crate) fn wrap_index(index: usize, capacity: usize) -> usize {
+    if index >= capacity {
+        index - capacity
+    } else {
+        index
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Deque::new()
+    }
+}
+
+// Indexing from the front of the deque; panics if the index is not less than len(), like Vec.
+impl<T> Index<usize> for Deque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let length = self.length;
+        self.get(index).unwrap_or_else(|| panic!("index {} is out of bounds for a deque of length {}", index, length))
+    }
+}
+
+impl<T> IndexMut<usize> for Deque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let length = self.length;
+        self.get_mut(index).unwrap_or_else(|| panic!("index {} is out of bounds for a deque of length {}", index, length))
+    }
+}
+
+// Push each value onto the back, in order.
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+// Iterator implementations for Iter struct
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front_offset == self.back_offset {
+            return None;
+        }
+        let value = self.deque.get(self.front_offset);
+        self.front_offset += 1;
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back_offset - self.front_offset;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front_offset == self.back_offset {
+            return None;
+        }
+        self.back_offset -= 1;
+        self.deque.get(self.back_offset)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+
+// Iterator implementations for IntoIter struct
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len(), Some(self.deque.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_deque() -> Deque<u32> {
+    let mut deque: Deque<u32> = Deque::new();
+    deque.push_back(2);
+    deque.push_back(4);
+    deque.push_front(0);
+    deque
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deque_works() {
+        let mut deque = setup_deque();
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque.front(), Some(&0));
+        assert_eq!(deque.back(), Some(&4));
+        assert_eq!(deque.pop_back(), Some(4));
+        assert_eq!(deque.pop_front(), Some(0));
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.back(), None);
+        assert!(deque.is_empty());
+
+        deque.push_front(1);
+        assert_eq!(deque.back(), Some(&1));
+    }
+
+    #[test]
+    fn deque_wraps_around_at_both_ends_and_grows() {
+        let mut deque = setup_deque();
+        // push_front wrapped the front to the end of the buffer
+        assert_eq!(deque.capacity(), 4);
+        assert_eq!(deque.front, 3);
+        deque.push_front(100);
+        assert_eq!(deque.capacity(), 4);
+
+        deque.push_back(6);
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(deque.iter().collect::<Vec<_>>(), vec![&100, &0, &2, &4, &6]);
+        assert_eq!(deque.iter().rev().collect::<Vec<_>>(), vec![&6, &4, &2, &0, &100]);
+
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(&100));
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&0, &2, &4]);
+    }
+
+    #[test]
+    fn deque_can_be_indexed() {
+        let mut deque = setup_deque();
+        assert_eq!(deque[0], 0);
+        assert_eq!(deque[2], 4);
+        deque[1] = 20;
+        assert_eq!(deque.get(1), Some(&20));
+        assert_eq!(deque.get(3), None);
+        if let Some(value) = deque.get_mut(0) {
+            *value += 1;
+        }
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![1, 20, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of bounds for a deque of length 3")]
+    fn deque_index_panics_past_the_end() {
+        let deque = setup_deque();
+        let _ = deque[3];
+    }
+
+    #[test]
+    fn deque_can_be_extended_and_consumed_from_both_ends() {
+        let mut deque: Deque<u32> = (1..=3).collect();
+        deque.extend([4, 5]);
+        let mut into_iter = deque.into_iter();
+        assert_eq!(into_iter.next_back(), Some(5));
+        assert_eq!(into_iter.len(), 4);
+        assert_eq!(into_iter.collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+}
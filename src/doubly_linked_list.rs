@@ -0,0 +1,405 @@
+//! Doubly linked list implementation that works using std::rc::{ Rc, Weak }, and interior
+//!     mutability with RefCell, like linked_list.rs. Each Node also holds a Weak reference back to
+//!     the Node before it, so the list can be removed from and iterated at both ends, and a Node
+//!     can be unlinked in O(1) given a handle to it.
+
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use std::ptr;
+
+
+/// A doubly linked list struct containing "pointers" to Node structs.
+///
+/// Forward links (head, and each Node's next member) are strong Rc references that own the Nodes;
+/// backward links (tail, and each Node's prev member) are Weak references, so no two Nodes own
+/// each other and no reference cycle keeps Nodes alive.
+///
+/// * `head`: An Option-wrapped reference to the first Node.
+/// * `tail`: Weak reference to the last Node (upgrade returns None if the list is empty).
+/// * `length`: Number of Nodes in the list.
+/// * `owner`: Allocation identifying this list. NodeHandles hold a Weak reference to it, so
+///         remove_node can tell its own handles from another list's.
+pub struct DoublyLinkedList<T> {
+    head: Option<Rc<RefCell<Node<T>>>>,
+    tail: Weak<RefCell<Node<T>>>,
+    length: usize,
+    owner: Rc<()>
+}
+
+
+/// Node in a DoublyLinkedList struct.
+///
+/// * `value`: T must be of type that matches the DoublyLinkedList struct this Node is placed in
+/// * `next`: Option holding an Rc to the next Node - the only strong reference to it.
+/// * `prev`: Weak reference to the previous Node (upgrade returns None for the head Node).
+pub struct Node<T> {
+    value: T,
+    next: Option<Rc<RefCell<Node<T>>>>,
+    prev: Weak<RefCell<Node<T>>>
+}
+
+
+/// Handle to a Node in a DoublyLinkedList struct, returned when a value is added. Passing it back
+/// to DoublyLinkedList::remove_node removes that Node in O(1), without searching.
+///
+/// Holds Weak references, so it doesn't keep the Node alive once the Node is removed or the list
+/// is dropped. The Weak reference to the list's owner member keeps that allocation (though not
+/// the list) alive, so a later list can't be allocated at the same address and mistaken for it.
+///
+/// * `node`: Weak reference to the Node.
+/// * `owner`: Weak reference to the owner member of the list the Node was added to.
+pub struct NodeHandle<T> {
+    node: Weak<RefCell<Node<T>>>,
+    owner: Weak<()>
+}
+
+
+/// Iterator yielding references to the values in a DoublyLinkedList struct, from head to tail.
+/// Implements DoubleEndedIterator, so it can be reversed to go from tail to head. Created by
+/// DoublyLinkedList::iter.
+///
+/// * `front`: reference to the Node holding the next value to be yielded from the front.
+/// * `back`: reference to the Node holding the next value to be yielded from the back.
+/// * `remaining`: Number of values not yet yielded from either end. Stops the two ends from
+///         yielding the same value twice once they meet.
+pub struct Iter<'a, T> {
+    front: Option<&'a RefCell<Node<T>>>,
+    back: Option<&'a RefCell<Node<T>>>,
+    remaining: usize
+}
+
+
+// Method implementations for DoublyLinkedList struct
+impl<T> DoublyLinkedList<T> {
+
+    /// Return a new, empty DoublyLinkedList struct
+    pub fn new() -> DoublyLinkedList<T> {
+        DoublyLinkedList {
+            head: None,
+            tail: Weak::new(),
+            length: 0,
+            owner: Rc::new(())
+        }
+    }
+
+    /// Return the number of values in this DoublyLinkedList.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this DoublyLinkedList holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Add a Node containing value T to the end of the list, and return a handle to it.
+    ///
+    /// * `value`: T to be stored in a new Node at the tail of the list.
+    pub fn add_value(&mut self, value: T) -> NodeHandle<T> {
+        let new_node = Node::new_ref_wrapped(value);
+        match self.tail.upgrade() {
+            Some(old_tail) => {
+                new_node.borrow_mut().prev = Rc::downgrade(&old_tail);
+                old_tail.borrow_mut().next = Some(Rc::clone(&new_node));
+            },
+            None => {
+                self.head = Some(Rc::clone(&new_node));
+            }
+        }
+        self.tail = Rc::downgrade(&new_node);
+        self.length += 1;
+        NodeHandle { node: Rc::downgrade(&new_node), owner: Rc::downgrade(&self.owner) }
+    }
+
+    /// Add a Node containing value T to the front of the list, and return a handle to it.
+    ///
+    /// * `value`: T to be stored in a new Node at the head of the list.
+    pub fn push_front(&mut self, value: T) -> NodeHandle<T> {
+        let new_node = Node::new_ref_wrapped(value);
+        match self.head.take() {
+            Some(old_head) => {
+                old_head.borrow_mut().prev = Rc::downgrade(&new_node);
+                new_node.borrow_mut().next = Some(old_head);
+            },
+            None => {
+                self.tail = Rc::downgrade(&new_node);
+            }
+        }
+        let handle = NodeHandle { node: Rc::downgrade(&new_node), owner: Rc::downgrade(&self.owner) };
+        self.head = Some(new_node);
+        self.length += 1;
+        handle
+    }
+
+    /// Get a reference to the value in the head Node (if the list is not empty).
+    pub fn peek_head_value(&self) -> Option<&T> {
+        // SAFETY: the list holds the head Node, and is borrowed for the returned reference
+        Some(unsafe { &Node::get_unguarded(self.head.as_deref()?).value })
+    }
+
+    /// Get a reference to the value in the tail Node (if the list is not empty).
+    pub fn peek_tail_value(&self) -> Option<&T> {
+        // SAFETY: the list holds the tail Node (as its predecessor's next member, or the head
+        //      member), and is borrowed for the returned reference
+        unsafe { Node::upgrade_ref(&self.tail).map(|node_ref| &Node::get_unguarded(node_ref).value) }
+    }
+
+    /// Remove the head Node from the list and return its value (unless the list is empty).
+    pub fn dequeue_value(&mut self) -> Option<T> {
+        let head = self.head.clone()?;
+        self.unlink(head)
+    }
+
+    /// Remove the tail Node from the list and return its value (unless the list is empty).
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail.upgrade()?;
+        self.unlink(tail)
+    }
+
+    /// Remove the Node the input handle refers to from the list in O(1), and return its value.
+    ///     Return None, leaving the list unchanged, if the Node has already been removed or the
+    ///     handle was returned by another list.
+    ///
+    /// * `handle`: handle returned by add_value or push_front.
+    pub fn remove_node(&mut self, handle: NodeHandle<T>) -> Option<T> {
+        if !ptr::eq(handle.owner.as_ptr(), Rc::as_ptr(&self.owner)) {
+            return None;
+        }
+        let node_rc = handle.node.upgrade()?;
+        self.unlink(node_rc)
+    }
+
+    /// Return an iterator of references to the values in this list, from head to tail. Call rev()
+    ///     on it to iterate from tail to head.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.head.as_deref(),
+            // SAFETY: as for peek_tail_value
+            back: unsafe { Node::upgrade_ref(&self.tail) },
+            remaining: self.length
+        }
+    }
+
+    /// Unlink a Node in this list from its neighbours, joining them to each other (or updating head
+    ///     or tail if the Node was at either end), and return its value. Returns None only if
+    ///     something besides the list and node_rc holds a strong reference to the Node, which the
+    ///     list's private members rule out.
+    ///
+    /// * `node_rc`: Rc clone of a Node in this list.
+    fn unlink(&mut self, node_rc: Rc<RefCell<Node<T>>>) -> Option<T> {
+        let (prev, next) = {
+            let mut node = node_rc.borrow_mut();
+            (node.prev.upgrade(), node.next.take())
+        };
+        match &next {
+            Some(next_rc) => { next_rc.borrow_mut().prev = prev.as_ref().map_or(Weak::new(), Rc::downgrade); },
+            None => { self.tail = prev.as_ref().map_or(Weak::new(), Rc::downgrade); }
+        }
+        // dropping the previous Node's (or head's) strong reference leaves node_rc as the only one
+        match prev {
+            Some(prev_rc) => { prev_rc.borrow_mut().next = next; },
+            None => { self.head = next; }
+        }
+        self.length -= 1;
+
+        Rc::into_inner(node_rc).map(|node_refcell| node_refcell.into_inner().value)
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        DoublyLinkedList::new()
+    }
+}
+
+// Drop Nodes one at a time from the head, so long lists don't drop their next chain recursively.
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        let mut opt_node = self.head.take();
+        while let Some(node_rc) = opt_node {
+            opt_node = node_rc.borrow_mut().next.take();
+        }
+    }
+}
+
+
+// Method implementations for Node struct
+impl<T> Node<T> {
+
+    /// Return a new Node with the value T, wrapped in Rc<RefCell>> so Weak references to it can be
+    /// made for its neighbour's prev member and the list's tail member.
+    ///
+    /// * `value`: Value to be stored in the Node.
+    fn new_ref_wrapped(value: T) -> Rc<RefCell<Node<T>>> {
+        Rc::new(RefCell::new(Node {
+            value,
+            next: None,
+            prev: Weak::new()
+        }))
+    }
+
+    /// Return a reference to the Node in the input RefCell without a guard, like linked_list's
+    /// Iter does.
+    ///
+    /// # Safety
+    /// The Node mustn't be mutably borrowed while the reference exists. That holds for any Node in
+    /// a list borrowed for as long, since only the list's &mut self methods mutably borrow Nodes.
+    ///
+    /// * `node_ref`: RefCell-wrapped Node to be borrowed.
+    unsafe fn get_unguarded(node_ref: &RefCell<Node<T>>) -> &Node<T> {
+        // SAFETY: guaranteed by the caller
+        unsafe { node_ref.try_borrow_unguarded() }.expect("Nodes aren't mutably borrowed while the list is borrowed")
+    }
+
+    /// Return a reference to the RefCell-wrapped Node a Weak reference points to (unless it has
+    /// been dropped), borrowed for as long as the Weak reference is. Weak::upgrade returns an Rc
+    /// instead, which only lives as long as the caller keeps it.
+    ///
+    /// # Safety
+    /// Something else must hold a strong reference to the Node for as long as the Weak reference
+    /// is borrowed - e.g. the Node's list, if the Weak reference is a member of it or its Nodes.
+    ///
+    /// * `weak`: Weak reference to the Node.
+    unsafe fn upgrade_ref(weak: &Weak<RefCell<Node<T>>>) -> Option<&RefCell<Node<T>>> {
+        weak.upgrade()?;
+        // SAFETY: the Node exists, and the caller guarantees it outlives the borrow of weak
+        Some(unsafe { &*weak.as_ptr() })
+    }
+}
+
+
+// Iterator implementations for Iter struct
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node_ref = self.front.take()?;
+        // SAFETY: the DoublyLinkedList borrowed for 'a holds the Node
+        let node = unsafe { Node::get_unguarded(node_ref) };
+        self.front = node.next.as_deref();
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node_ref = self.back.take()?;
+        // SAFETY: the DoublyLinkedList borrowed for 'a holds the Node and the one before it
+        let node = unsafe { Node::get_unguarded(node_ref) };
+        self.back = unsafe { Node::upgrade_ref(&node.prev) };
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_doubly_linked_list() -> DoublyLinkedList<u32> {
+    let mut dll: DoublyLinkedList<u32> = DoublyLinkedList::new();
+    dll.add_value(2);
+    dll.add_value(4);
+    dll.add_value(6);
+    dll
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubly_linked_list_works() {
+        let mut dll = setup_doubly_linked_list();
+        dll.push_front(0);
+        assert_eq!(dll.len(), 4);
+        assert_eq!(dll.peek_head_value(), Some(&0));
+        assert_eq!(dll.peek_tail_value(), Some(&6));
+        assert_eq!(dll.iter().collect::<Vec<_>>(), vec![&0, &2, &4, &6]);
+    }
+
+    #[test]
+    fn pop_back_works() {
+        let mut dll = setup_doubly_linked_list();
+        assert_eq!(dll.pop_back(), Some(6));
+        assert_eq!(dll.peek_tail_value(), Some(&4));
+        assert_eq!(dll.dequeue_value(), Some(2));
+        assert_eq!(dll.pop_back(), Some(4));
+        assert!(dll.is_empty());
+        assert_eq!(dll.pop_back(), None);
+        assert_eq!(dll.peek_head_value(), None);
+
+        // the emptied list can be refilled from either end
+        dll.add_value(8);
+        dll.push_front(7);
+        assert_eq!(dll.iter().collect::<Vec<_>>(), vec![&7, &8]);
+    }
+
+    #[test]
+    fn iter_works_in_both_directions() {
+        let dll = setup_doubly_linked_list();
+        assert_eq!(dll.iter().rev().collect::<Vec<_>>(), vec![&6, &4, &2]);
+
+        let mut iter = dll.iter();
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn nodes_can_be_removed_by_handle() {
+        let mut dll = setup_doubly_linked_list();
+        let middle = dll.push_front(1);
+        let tail = dll.add_value(9);
+        let head = dll.push_front(0);
+        let middle_again = NodeHandle { node: middle.node.clone(), owner: middle.owner.clone() };
+
+        assert_eq!(dll.remove_node(middle), Some(1));
+        assert_eq!(dll.remove_node(middle_again), None);
+        assert_eq!(dll.remove_node(tail), Some(9));
+        assert_eq!(dll.remove_node(head), Some(0));
+        assert_eq!(dll.len(), 3);
+        assert_eq!(dll.iter().collect::<Vec<_>>(), vec![&2, &4, &6]);
+        assert_eq!(dll.iter().rev().collect::<Vec<_>>(), vec![&6, &4, &2]);
+    }
+
+    #[test]
+    fn handles_from_another_list_are_rejected() {
+        let mut dll = setup_doubly_linked_list();
+        let mut other = DoublyLinkedList::new();
+        let foreign_head = other.add_value(1);
+        let foreign_tail = other.add_value(3);
+        let own = dll.push_front(0);
+
+        assert_eq!(dll.remove_node(foreign_head), None);
+        assert_eq!(dll.remove_node(foreign_tail), None);
+        assert_eq!(dll.iter().collect::<Vec<_>>(), vec![&0, &2, &4, &6]);
+        assert_eq!(other.iter().rev().collect::<Vec<_>>(), vec![&3, &1]);
+        assert_eq!(other.remove_node(own), None);
+        assert_eq!(other.len(), 2);
+    }
+
+    #[test]
+    fn long_lists_can_be_dropped() {
+        let mut dll = DoublyLinkedList::new();
+        for value in 0..1_000_000 {
+            dll.add_value(value);
+        }
+        drop(dll);
+    }
+}
@@ -0,0 +1,377 @@
+//! Two-dimensional range sums over a grid of numbers.
+//!
+//! FenwickTree2D is a Fenwick (binary indexed) tree of Fenwick trees: cell (i, j) of the tree
+//!     holds the sum of a block of the grid whose height and width are the lowest set bits of i
+//!     and j (counting from 1). Any prefix rectangle is the sum of O(log r * log c) blocks, and a
+//!     point update touches the same number, so it suits grids that change.
+//!
+//! PrefixSum2D is the static alternative for grids that don't change: it stores the sum of every
+//!     prefix rectangle, so a rectangle sum is four lookups, O(1).
+//!
+//! ```
+//! use rust_datastructures_algorithms::fenwick_tree::{ FenwickTree2D, PrefixSum2D };
+//!
+//! let grid = vec![
+//!     vec![1, 2, 3],
+//!     vec![4, 5, 6],
+//!     vec![7, 8, 9]
+//! ];
+//! let mut tree = FenwickTree2D::from_grid(&grid);
+//! // rows 1 and 2, columns 0 and 1
+//! assert_eq!(tree.rectangle_sum(1..3, 0..2), 24);
+//! tree.add(2, 0, 10);
+//! assert_eq!(tree.rectangle_sum(1.., ..2), 34);
+//!
+//! let sums = PrefixSum2D::new(&grid);
+//! assert_eq!(sums.rectangle_sum(..2, 1..), 16);
+//! ```
+
+use std::ops::{ RangeBounds, Sub };
+use crate::segment_tree::{ range_to_indexes, Number };
+
+
+/// A 2D Fenwick tree struct over a grid, for point updates and rectangle sums in
+/// O(log r * log c).
+///
+/// * `rows`: Number of rows in the grid (r).
+/// * `columns`: Number of columns in the grid (c).
+/// * `tree`: Block sums in row-major order, indexed from 1 in both dimensions as Fenwick trees
+///         are: the entry for (i, j) is the sum of grid rows i - lowbit(i) to i - 1 and columns
+///         j - lowbit(j) to j - 1, where lowbit(x) is the lowest set bit of x. Row and column 0
+///         are unused, so there are (r + 1) * (c + 1) entries.
+pub struct FenwickTree2D<T: Number> {
+    rows: usize,
+    columns: usize,
+    tree: Vec<T>
+}
+
+
+/// A 2D prefix sum table struct over a grid that won't change, for rectangle sums in O(1).
+///
+/// * `rows`: Number of rows in the grid (r).
+/// * `columns`: Number of columns in the grid (c).
+/// * `sums`: Prefix sums in row-major order: the entry for (i, j) is the sum of grid rows 0 to
+///         i - 1 and columns 0 to j - 1, so there are (r + 1) * (c + 1) entries.
+pub struct PrefixSum2D<T: Number> {
+    rows: usize,
+    columns: usize,
+    sums: Vec<T>
+}
+
+
+// Method implementations for FenwickTree2D struct
+impl<T> FenwickTree2D<T> where T: Number + Sub<Output = T> {
+
+    /// Return a new FenwickTree2D struct over a grid of zeroes.
+    ///
+    /// * `rows`: number of rows in the grid.
+    /// * `columns`: number of columns in the grid.
+    pub fn new(rows: usize, columns: usize) -> FenwickTree2D<T> {
+        FenwickTree2D { rows, columns, tree: vec![T::ZERO; (rows + 1) * (columns + 1)] }
+    }
+
+    /// Return a new FenwickTree2D struct over a copy of the input grid, built in O(r * c). Panics
+    /// if the rows aren't all the same length.
+    ///
+    /// Building is separable: each row of the table is first built as a 1D Fenwick tree (every
+    ///     entry adding itself into the entry lowbit(j) past it), then each column is in turn.
+    ///
+    /// * `grid`: the grid, as a Vec of rows.
+    pub fn from_grid(grid: &[Vec<T>]) -> FenwickTree2D<T> {
+        let (rows, columns) = grid_size(grid);
+        let mut fenwick = FenwickTree2D::new(rows, columns);
+        let width = columns + 1;
+        for (row, values) in grid.iter().enumerate() {
+            fenwick.tree[(row + 1) * width + 1..(row + 2) * width].copy_from_slice(values);
+        }
+        for row in 1..=rows {
+            for column in 1..=columns {
+                let parent = column + lowbit(column);
+                if parent <= columns {
+                    let value = fenwick.tree[row * width + column];
+                    fenwick.tree[row * width + parent] = fenwick.tree[row * width + parent] + value;
+                }
+            }
+        }
+        for row in 1..=rows {
+            let parent = row + lowbit(row);
+            if parent <= rows {
+                for column in 1..=columns {
+                    let value = fenwick.tree[row * width + column];
+                    fenwick.tree[parent * width + column] = fenwick.tree[parent * width + column] + value;
+                }
+            }
+        }
+        fenwick
+    }
+
+    /// Return the number of rows in the grid.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Return the number of columns in the grid.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Add the input amount to one cell of the grid, updating every block that covers it. Panics
+    /// if the cell is out of bounds.
+    ///
+    /// * `row`: row of the cell.
+    /// * `column`: column of the cell.
+    /// * `amount`: amount to be added.
+    pub fn add(&mut self, row: usize, column: usize, amount: T) {
+        self.check_cell(row, column);
+        let width = self.columns + 1;
+        let mut i = row + 1;
+        while i <= self.rows {
+            let mut j = column + 1;
+            while j <= self.columns {
+                self.tree[i * width + j] = self.tree[i * width + j] + amount;
+                j += lowbit(j);
+            }
+            i += lowbit(i);
+        }
+    }
+
+    /// Return the value of one cell of the grid. Panics if the cell is out of bounds.
+    ///
+    /// * `row`: row of the cell.
+    /// * `column`: column of the cell.
+    pub fn get(&self, row: usize, column: usize) -> T {
+        self.check_cell(row, column);
+        self.rectangle_sum(row..=row, column..=column)
+    }
+
+    /// Replace the value of one cell of the grid. Panics if the cell is out of bounds (or, for
+    /// unsigned types, if the value is less than the old one - add the difference instead).
+    ///
+    /// * `row`: row of the cell.
+    /// * `column`: column of the cell.
+    /// * `value`: the new value.
+    pub fn set(&mut self, row: usize, column: usize, value: T) {
+        let old_value = self.get(row, column);
+        self.add(row, column, value - old_value);
+    }
+
+    /// Return the sum of the cells in the input rows and columns (0 for an empty rectangle), by
+    /// inclusion-exclusion over four prefix sums. Panics if either range is out of bounds, like
+    /// slicing.
+    ///
+    /// * `rows`: range of rows, such as `start..end` or `..`.
+    /// * `columns`: range of columns.
+    pub fn rectangle_sum<R: RangeBounds<usize>, C: RangeBounds<usize>>(&self, rows: R, columns: C) -> T {
+        let (top, bottom) = range_to_indexes(rows, self.rows);
+        let (left, right) = range_to_indexes(columns, self.columns);
+        // added before subtracting, so unsigned sums never go below zero
+        self.prefix_sum(bottom, right) + self.prefix_sum(top, left) - self.prefix_sum(top, right)
+            - self.prefix_sum(bottom, left)
+    }
+
+    /// Return the sum of the cells in the first `rows` rows and first `columns` columns.
+    ///
+    /// * `rows`: number of rows in the prefix rectangle.
+    /// * `columns`: number of columns in the prefix rectangle.
+    fn prefix_sum(&self, rows: usize, columns: usize) -> T {
+        let width = self.columns + 1;
+        let mut sum = T::ZERO;
+        let mut i = rows;
+        while i > 0 {
+            let mut j = columns;
+            while j > 0 {
+                sum = sum + self.tree[i * width + j];
+                j -= lowbit(j);
+            }
+            i -= lowbit(i);
+        }
+        sum
+    }
+
+    /// Panic if the cell is out of bounds.
+    ///
+    /// * `row`: row of the cell.
+    /// * `column`: column of the cell.
+    fn check_cell(&self, row: usize, column: usize) {
+        assert!(
+            row < self.rows && column < self.columns,
+            "cell ({}, {}) out of bounds for a {} by {} grid", row, column, self.rows, self.columns
+        );
+    }
+}
+
+
+// Method implementations for PrefixSum2D struct
+impl<T> PrefixSum2D<T> where T: Number + Sub<Output = T> {
+
+    /// Return a new PrefixSum2D struct over the input grid, built in O(r * c). Panics if the rows
+    /// aren't all the same length.
+    ///
+    /// * `grid`: the grid, as a Vec of rows.
+    pub fn new(grid: &[Vec<T>]) -> PrefixSum2D<T> {
+        let (rows, columns) = grid_size(grid);
+        let width = columns + 1;
+        let mut sums = vec![T::ZERO; (rows + 1) * width];
+        for (row, values) in grid.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                let (i, j) = (row + 1, column + 1);
+                sums[i * width + j] = *value + sums[(i - 1) * width + j] + sums[i * width + j - 1]
+                    - sums[(i - 1) * width + j - 1];
+            }
+        }
+        PrefixSum2D { rows, columns, sums }
+    }
+
+    /// Return the number of rows in the grid.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Return the number of columns in the grid.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Return the sum of the cells in the input rows and columns (0 for an empty rectangle).
+    /// Panics if either range is out of bounds, like slicing.
+    ///
+    /// * `rows`: range of rows, such as `start..end` or `..`.
+    /// * `columns`: range of columns.
+    pub fn rectangle_sum<R: RangeBounds<usize>, C: RangeBounds<usize>>(&self, rows: R, columns: C) -> T {
+        let (top, bottom) = range_to_indexes(rows, self.rows);
+        let (left, right) = range_to_indexes(columns, self.columns);
+        let width = self.columns + 1;
+        self.sums[bottom * width + right] + self.sums[top * width + left] - self.sums[top * width + right]
+            - self.sums[bottom * width + left]
+    }
+}
+
+/// Return the lowest set bit of the input.
+///
+/// * `index`: a positive index.
+fn lowbit(index: usize) -> usize {
+    index & index.wrapping_neg()
+}
+
+/// Return the number of rows and columns of the input grid. Panics if the rows aren't all the
+/// same length.
+///
+/// * `grid`: the grid, as a Vec of rows.
+fn grid_size<T>(grid: &[Vec<T>]) -> (usize, usize) {
+    let columns = grid.first().map_or(0, Vec::len);
+    assert!(grid.iter().all(|row| row.len() == columns), "grid rows must all be the same length");
+    (grid.len(), columns)
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_grid() -> Vec<Vec<i64>> {
+    vec![
+        vec![3, 0, 1, 4, 2],
+        vec![5, 6, 3, 2, 1],
+        vec![1, 2, 0, 1, 5],
+        vec![4, 1, 0, 1, 7]
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the sum of a rectangle of the grid by adding up every cell in it.
+    fn naive_sum(grid: &[Vec<i64>], rows: std::ops::Range<usize>, columns: std::ops::Range<usize>) -> i64 {
+        grid[rows].iter().map(|row| row[columns.clone()].iter().sum::<i64>()).sum()
+    }
+
+    #[test]
+    fn fenwick_tree_2d_works() {
+        let grid = setup_grid();
+        let mut tree = FenwickTree2D::from_grid(&grid);
+        assert_eq!((tree.rows(), tree.columns()), (4, 5));
+        assert_eq!(tree.rectangle_sum(.., ..), 49);
+        assert_eq!(tree.rectangle_sum(1..3, 1..4), 14);
+        assert_eq!(tree.rectangle_sum(2..2, ..), 0);
+        assert_eq!(tree.get(3, 4), 7);
+
+        tree.add(1, 2, 10);
+        assert_eq!(tree.get(1, 2), 13);
+        assert_eq!(tree.rectangle_sum(1..3, 1..4), 24);
+        tree.set(0, 0, -3);
+        assert_eq!(tree.rectangle_sum(..1, ..1), -3);
+        assert_eq!(tree.rectangle_sum(.., ..), 53);
+
+        // building from a grid matches adding every cell to an empty tree
+        let mut added = FenwickTree2D::new(4, 5);
+        for (row, values) in grid.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                added.add(row, column, *value);
+            }
+        }
+        assert_eq!(added.tree, FenwickTree2D::from_grid(&grid).tree);
+
+        let empty: FenwickTree2D<f64> = FenwickTree2D::from_grid(&[]);
+        assert_eq!(empty.rectangle_sum(.., ..), 0.0);
+    }
+
+    #[test]
+    fn prefix_sum_2d_works() {
+        let grid = setup_grid();
+        let sums = PrefixSum2D::new(&grid);
+        assert_eq!((sums.rows(), sums.columns()), (4, 5));
+        for top in 0..=4 {
+            for bottom in top..=4 {
+                for left in 0..=5 {
+                    for right in left..=5 {
+                        assert_eq!(sums.rectangle_sum(top..bottom, left..right), naive_sum(&grid, top..bottom, left..right));
+                    }
+                }
+            }
+        }
+        assert_eq!(sums.rectangle_sum(3..=3, 4..=4), 7);
+
+        // unsigned sums are added before subtracting, so they never dip below zero
+        let unsigned: Vec<Vec<u32>> = vec![vec![0, 0], vec![0, 9]];
+        assert_eq!(PrefixSum2D::new(&unsigned).rectangle_sum(1.., 1..), 9);
+        assert_eq!(FenwickTree2D::from_grid(&unsigned).rectangle_sum(1.., 1..), 9);
+    }
+
+    #[test]
+    fn fenwick_tree_2d_matches_naive() {
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next_random = move |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % bound as u64) as usize
+        };
+        for (rows, columns) in [(1, 1), (3, 7), (8, 8), (13, 5)] {
+            let mut grid: Vec<Vec<i64>> = (0..rows)
+                .map(|_| (0..columns).map(|_| next_random(21) as i64 - 10).collect())
+                .collect();
+            let mut tree = FenwickTree2D::from_grid(&grid);
+            for _ in 0..200 {
+                let (row, column) = (next_random(rows), next_random(columns));
+                let amount = next_random(21) as i64 - 10;
+                grid[row][column] += amount;
+                tree.add(row, column, amount);
+
+                let top = next_random(rows + 1);
+                let bottom = top + next_random(rows - top + 1);
+                let left = next_random(columns + 1);
+                let right = left + next_random(columns - left + 1);
+                assert_eq!(tree.rectangle_sum(top..bottom, left..right), naive_sum(&grid, top..bottom, left..right));
+            }
+            let sums = PrefixSum2D::new(&grid);
+            assert_eq!(sums.rectangle_sum(.., ..), tree.rectangle_sum(.., ..));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn fenwick_tree_2d_add_out_of_bounds() {
+        let mut tree = FenwickTree2D::from_grid(&setup_grid());
+        tree.add(4, 0, 1);
+    }
+}
@@ -0,0 +1,709 @@
+//! Fibonacci heap implementation that works using std::rc::{ Rc, Weak }, and interior mutability
+//!     with RefCell. Insert, merge and decrease-key take O(1) amortized time and extract-min
+//!     takes O(log n) amortized, by deferring all restructuring to extract-min.
+//!
+//! The heap is a list of heap-ordered trees whose roots are kept in a circular doubly linked
+//!     "root list"; each Node's children form another circular doubly linked list. Lists can be
+//!     spliced together and Nodes cut out of them in O(1), which is what the heap relies on.
+//!
+//! ```
+//! use rust_datastructures_algorithms::fibonacci_heap::FibonacciHeap;
+//!
+//! let mut heap = FibonacciHeap::new();
+//! heap.insert(7);
+//! let nine = heap.insert(9);
+//! heap.insert(4);
+//! assert!(heap.decrease_key(&nine, 2));
+//! assert_eq!(heap.extract_min(), Some(2));
+//! assert_eq!(heap.extract_min(), Some(4));
+//! ```
+
+use std::rc::{Rc, Weak};
+use std::cell::{Ref, RefCell};
+use crate::pairing_heap::HeapTag;
+
+
+/// Shared, mutable reference to a Node - the type of every strong link in the heap.
+type Link<T> = Rc<RefCell<Node<T>>>;
+
+
+/// A Fibonacci heap struct containing a "pointer" to the root Node holding the smallest value.
+///
+/// Every list of Nodes (the root list, and each Node's children) is circular: each Node's right
+/// member is a strong reference to the next Node and its left member a Weak reference to the
+/// previous one. The strong right references form a cycle, which Drop breaks explicitly.
+///
+/// * `min`: An Option-wrapped reference to the root Node with the smallest value - the heap's
+///         entry point into the root list (None if the heap is empty).
+/// * `length`: Number of Nodes in the heap.
+/// * `counts`: Running totals of the restructuring work done so far (see OperationCounts).
+/// * `tag`: Identity of this heap, which NodeHandles are tagged with (see pairing_heap::HeapTag).
+pub struct FibonacciHeap<T: Ord> {
+    min: Option<Link<T>>,
+    length: usize,
+    counts: OperationCounts,
+    tag: Rc<HeapTag>
+}
+
+
+/// Node in a FibonacciHeap struct.
+///
+/// * `value`: T must be of type that matches the FibonacciHeap struct this Node is placed in
+/// * `parent`: Weak reference to the parent Node (upgrade returns None for roots).
+/// * `child`: Option holding an Rc to one of this Node's children - the entry point into its
+///         circular list of children.
+/// * `left`: Weak reference to the previous Node in this Node's circular list (itself if it is
+///         alone).
+/// * `right`: Option holding an Rc to the next Node in this Node's circular list (itself if it is
+///         alone). Only None while the heap is being dropped.
+/// * `degree`: Number of children.
+/// * `marked`: Whether this Node has lost a child since it last became a child itself. A marked
+///         Node that loses a second child is cut from its parent too (see cascading_cut).
+pub struct Node<T> {
+    value: T,
+    parent: Weak<RefCell<Node<T>>>,
+    child: Option<Link<T>>,
+    left: Weak<RefCell<Node<T>>>,
+    right: Option<Link<T>>,
+    degree: usize,
+    marked: bool
+}
+
+
+/// Handle to a Node in a FibonacciHeap struct, returned when a value is inserted. Passing it
+/// back to FibonacciHeap::decrease_key lowers that Node's value without searching for it.
+///
+/// Holds a Weak reference, so it doesn't keep the Node alive once the value is extracted or the
+/// heap is dropped.
+///
+/// * `node`: Weak reference to the Node.
+/// * `tag`: Tag of the heap the value was inserted into.
+pub struct NodeHandle<T> {
+    node: Weak<RefCell<Node<T>>>,
+    tag: Rc<HeapTag>
+}
+
+
+/// Running totals of the restructuring work a FibonacciHeap struct has done, for checking the
+/// amortized bounds: n inserts followed by any sequence of operations can only ever link as many
+/// trees as were inserted, and each cascading cut is paid for by an earlier mark.
+///
+/// * `links`: Number of times one root was made the child of another during consolidation.
+/// * `cuts`: Number of Nodes cut from their parent by decrease_key.
+/// * `cascading_cuts`: Number of marked Nodes cut from their parent after losing a second
+///         child.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OperationCounts {
+    pub links: usize,
+    pub cuts: usize,
+    pub cascading_cuts: usize
+}
+
+
+// Method implementations for FibonacciHeap struct
+impl<T> FibonacciHeap<T> where T: Ord {
+
+    /// Return a new, empty FibonacciHeap struct
+    pub fn new() -> FibonacciHeap<T> {
+        FibonacciHeap {
+            min: None,
+            length: 0,
+            counts: OperationCounts::default(),
+            tag: HeapTag::new()
+        }
+    }
+
+    /// Return the number of values in this FibonacciHeap.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this FibonacciHeap holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the restructuring work this FibonacciHeap has done so far.
+    pub fn counts(&self) -> OperationCounts {
+        self.counts
+    }
+
+    /// Add a value to the heap in O(1), as a new single-Node tree in the root list, and return a
+    /// handle to it.
+    ///
+    /// * `value`: value to be added.
+    pub fn insert(&mut self, value: T) -> NodeHandle<T> {
+        let new_node = Node::new_ref_wrapped(value);
+        let handle = NodeHandle { node: Rc::downgrade(&new_node), tag: Rc::clone(&self.tag) };
+        self.add_root(new_node);
+        self.length += 1;
+        handle
+    }
+
+    /// Move every value of the other FibonacciHeap into this one in O(1), by splicing the two
+    /// root lists together. Handles to values in the other heap stay valid, and now refer to this
+    /// heap.
+    ///
+    /// * `other`: FibonacciHeap whose values are moved into this one.
+    pub fn merge(&mut self, mut other: FibonacciHeap<T>) {
+        other.tag.meld_into(&self.tag);
+        if let Some(other_min) = other.min.take() {
+            self.add_root(other_min);
+            self.length += other.length;
+            self.counts.links += other.counts.links;
+            self.counts.cuts += other.counts.cuts;
+            self.counts.cascading_cuts += other.counts.cascading_cuts;
+            other.length = 0;
+        }
+    }
+
+    /// Return a RefCell guard of the smallest value (if the heap is not empty). The minimum Node
+    /// is borrowed until the guard is dropped.
+    pub fn peek_min(&self) -> Option<Ref<'_, T>> {
+        self.min.as_ref().map(|min| Ref::map(min.borrow(), |node| &node.value))
+    }
+
+    /// Remove the smallest value and return it (unless the heap is empty), in O(log n) amortized.
+    ///
+    /// The minimum Node's children are moved into the root list and the Node is removed from it.
+    ///     Then the root list is consolidated: roots of equal degree are linked (the larger root
+    ///     becomes a child of the smaller) until every root has a different degree, leaving
+    ///     O(log n) roots.
+    pub fn extract_min(&mut self) -> Option<T> {
+        let min = self.min.take()?;
+        let first_child = min.borrow_mut().child.take();
+        if let Some(first_child) = first_child {
+            for child in Node::ring_members(&first_child) {
+                let mut child_node = child.borrow_mut();
+                child_node.parent = Weak::new();
+                child_node.marked = false;
+            }
+            Node::splice(&min, &first_child);
+        }
+        let remaining_root = Node::remove_from_ring(&min);
+        // min is alone in its list - break its self-reference so it can be unwrapped
+        min.borrow_mut().right = None;
+        self.length -= 1;
+        if let Some(remaining_root) = remaining_root {
+            self.consolidate(remaining_root);
+        }
+
+        // handles only hold Weak references, so the heap held the only strong reference
+        match Rc::try_unwrap(min) {
+            Ok(node_refcell) => Some(node_refcell.into_inner().value),
+            Err(_) => unreachable!("Nodes are only strongly referenced by the heap")
+        }
+    }
+
+    /// Lower the value of the Node the input handle refers to, in O(1) amortized. If the Node is
+    /// now smaller than its parent, it is cut from its parent and moved into the root list, and
+    /// the parent is marked - or cut too, if it was already marked (see cascading_cut).
+    ///
+    /// Return false, leaving the heap unchanged, if the value was already extracted, the handle
+    /// came from another heap (that wasn't merged into this one) or the new value is greater than
+    /// the current one.
+    ///
+    /// * `handle`: handle returned by insert.
+    /// * `value`: new value, not greater than the current one.
+    pub fn decrease_key(&mut self, handle: &NodeHandle<T>, value: T) -> bool {
+        if !handle.tag.belongs_to(&self.tag) {
+            return false;
+        }
+        let Some(node_rc) = handle.node.upgrade() else {
+            return false;
+        };
+        if value > node_rc.borrow().value {
+            return false;
+        }
+        node_rc.borrow_mut().value = value;
+
+        let parent = node_rc.borrow().parent.upgrade();
+        if let Some(parent) = parent {
+            if node_rc.borrow().value < parent.borrow().value {
+                self.cut(&node_rc, &parent);
+                self.counts.cuts += 1;
+                self.cascading_cut(parent);
+            }
+        }
+        let is_new_min = self.min.as_ref().is_some_and(|min| node_rc.borrow().value < min.borrow().value);
+        if is_new_min {
+            self.min = Some(node_rc);
+        }
+        true
+    }
+
+    /// Consume this FibonacciHeap and return its values in ascending order, by extracting every
+    /// value.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.length);
+        while let Some(value) = self.extract_min() {
+            sorted.push(value);
+        }
+        sorted
+    }
+
+    /// Splice the circular list containing the input Node into the root list, and update min if
+    /// the Node is smaller. The Node should be the smallest in its list.
+    ///
+    /// * `node_rc`: Node whose list is to be added to the root list.
+    fn add_root(&mut self, node_rc: Link<T>) {
+        match &self.min {
+            Some(min) => {
+                Node::splice(min, &node_rc);
+                if node_rc.borrow().value < min.borrow().value {
+                    self.min = Some(node_rc);
+                }
+            },
+            None => {
+                self.min = Some(node_rc);
+            }
+        }
+    }
+
+    /// Link roots of equal degree until every root in the list containing the input root has a
+    /// different degree, then rebuild the root list from what is left and find the new minimum.
+    ///
+    /// * `start`: any root in the root list.
+    fn consolidate(&mut self, start: Link<T>) {
+        let roots = Node::ring_members(&start);
+        drop(start);
+        // roots_by_degree[d] holds the root with degree d found so far, if any
+        let mut roots_by_degree: Vec<Option<Link<T>>> = Vec::new();
+        for root in roots {
+            Node::make_ring_of_one(&root);
+            let mut tree = root;
+            loop {
+                let degree = tree.borrow().degree;
+                if degree >= roots_by_degree.len() {
+                    roots_by_degree.resize(degree + 1, None);
+                }
+                match roots_by_degree[degree].take() {
+                    Some(other_tree) => {
+                        tree = Node::link(tree, other_tree);
+                        self.counts.links += 1;
+                    },
+                    None => {
+                        roots_by_degree[degree] = Some(tree);
+                        break;
+                    }
+                }
+            }
+        }
+        for root in roots_by_degree.into_iter().flatten() {
+            self.add_root(root);
+        }
+    }
+
+    /// Cut the input Node out of its parent's list of children and move it into the root list,
+    /// unmarked.
+    ///
+    /// * `node_rc`: Node to be cut.
+    /// * `parent`: the Node's parent.
+    fn cut(&mut self, node_rc: &Link<T>, parent: &Link<T>) {
+        let remaining_sibling = Node::remove_from_ring(node_rc);
+        {
+            let mut parent_node = parent.borrow_mut();
+            if parent_node.child.as_ref().is_some_and(|child| Rc::ptr_eq(child, node_rc)) {
+                parent_node.child = remaining_sibling;
+            }
+            parent_node.degree -= 1;
+        }
+        {
+            let mut node = node_rc.borrow_mut();
+            node.parent = Weak::new();
+            node.marked = false;
+        }
+        self.add_root(Rc::clone(node_rc));
+    }
+
+    /// Walk up from a Node that just lost a child: if it isn't marked, mark it and stop; if it is
+    /// marked (this is its second lost child), cut it from its parent too and continue with the
+    /// parent. Roots are never marked.
+    ///
+    /// This keeps every Node's subtree size exponential in its degree, which bounds the degree
+    ///     (and so the number of roots after consolidation) by O(log n).
+    ///
+    /// * `node_rc`: Node that just lost a child.
+    fn cascading_cut(&mut self, mut node_rc: Link<T>) {
+        loop {
+            let Some(parent) = node_rc.borrow().parent.upgrade() else {
+                return;
+            };
+            if !node_rc.borrow().marked {
+                node_rc.borrow_mut().marked = true;
+                return;
+            }
+            self.cut(&node_rc, &parent);
+            self.counts.cascading_cuts += 1;
+            node_rc = parent;
+        }
+    }
+}
+
+impl<T> Default for FibonacciHeap<T> where T: Ord {
+    fn default() -> Self {
+        FibonacciHeap::new()
+    }
+}
+
+// Break every circular list and drop Nodes one at a time with a worklist, so the cycles are freed
+// and long lists aren't dropped recursively.
+impl<T> Drop for FibonacciHeap<T> where T: Ord {
+    fn drop(&mut self) {
+        let Some(min) = self.min.take() else {
+            return;
+        };
+        let mut worklist = Node::ring_members(&min);
+        drop(min);
+        while let Some(node_rc) = worklist.pop() {
+            let first_child = {
+                let mut node = node_rc.borrow_mut();
+                node.right = None;
+                node.child.take()
+            };
+            if let Some(first_child) = first_child {
+                worklist.extend(Node::ring_members(&first_child));
+            }
+        }
+    }
+}
+
+
+// Method implementations for Node struct
+impl<T> Node<T> where T: Ord {
+
+    /// Return a new Node with the value T, wrapped in Rc<RefCell>>, alone in its own circular list.
+    ///
+    /// * `value`: Value to be stored in the Node.
+    fn new_ref_wrapped(value: T) -> Link<T> {
+        let node_rc = Rc::new(RefCell::new(Node {
+            value,
+            parent: Weak::new(),
+            child: None,
+            left: Weak::new(),
+            right: None,
+            degree: 0,
+            marked: false
+        }));
+        Node::make_ring_of_one(&node_rc);
+        node_rc
+    }
+
+    /// Point the input Node's left and right members at itself, making it a circular list of one.
+    ///
+    /// * `node_rc`: Node to be reset.
+    fn make_ring_of_one(node_rc: &Link<T>) {
+        let mut node = node_rc.borrow_mut();
+        node.left = Rc::downgrade(node_rc);
+        node.right = Some(Rc::clone(node_rc));
+    }
+
+    /// Return Rc clones of every Node in the circular list containing the input Node, starting
+    /// with it and going right.
+    ///
+    /// * `start`: any Node in the list.
+    fn ring_members(start: &Link<T>) -> Vec<Link<T>> {
+        let mut members = vec![Rc::clone(start)];
+        let mut current = Node::right_of(start);
+        while !Rc::ptr_eq(&current, start) {
+            let next = Node::right_of(&current);
+            members.push(current);
+            current = next;
+        }
+        members
+    }
+
+    /// Join two separate circular lists into one in O(1): the list containing b is inserted
+    /// between a and the Node to its right.
+    ///
+    /// * `a`: Node in the first list.
+    /// * `b`: Node in the second list.
+    fn splice(a: &Link<T>, b: &Link<T>) {
+        let a_right = Node::right_of(a);
+        let b_left = b.borrow().left.upgrade().expect("Nodes in a list always have a left Node");
+        a.borrow_mut().right = Some(Rc::clone(b));
+        b.borrow_mut().left = Rc::downgrade(a);
+        b_left.borrow_mut().right = Some(Rc::clone(&a_right));
+        a_right.borrow_mut().left = Rc::downgrade(&b_left);
+    }
+
+    /// Remove the input Node from its circular list in O(1), leaving it in a list of its own, and
+    /// return another Node from the list it left (None if it was alone).
+    ///
+    /// * `node_rc`: Node to be removed.
+    fn remove_from_ring(node_rc: &Link<T>) -> Option<Link<T>> {
+        let right = Node::right_of(node_rc);
+        if Rc::ptr_eq(&right, node_rc) {
+            return None;
+        }
+        let left = node_rc.borrow().left.upgrade().expect("Nodes in a list always have a left Node");
+        left.borrow_mut().right = Some(Rc::clone(&right));
+        right.borrow_mut().left = Rc::downgrade(&left);
+        Node::make_ring_of_one(node_rc);
+        Some(right)
+    }
+
+    /// Make the root holding the larger value a child of the other root, and return the other
+    /// root. Both roots must be alone in their lists.
+    ///
+    /// * `a`: first root.
+    /// * `b`: second root, of the same degree.
+    fn link(a: Link<T>, b: Link<T>) -> Link<T> {
+        let (parent, child) = if b.borrow().value < a.borrow().value { (b, a) } else { (a, b) };
+        {
+            let mut child_node = child.borrow_mut();
+            child_node.parent = Rc::downgrade(&parent);
+            child_node.marked = false;
+        }
+        let existing_child = parent.borrow().child.clone();
+        match existing_child {
+            Some(existing_child) => Node::splice(&existing_child, &child),
+            None => { parent.borrow_mut().child = Some(child); }
+        }
+        parent.borrow_mut().degree += 1;
+        parent
+    }
+
+    /// Return an Rc clone of the Node to the right of the input Node.
+    ///
+    /// * `node_rc`: Node in a circular list.
+    fn right_of(node_rc: &Link<T>) -> Link<T> {
+        node_rc.borrow().right.clone().expect("Nodes in a list always have a right Node")
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_fibonacci_heap() -> FibonacciHeap<u32> {
+    let mut heap: FibonacciHeap<u32> = FibonacciHeap::new();
+    for value in [5, 3, 8, 1, 9, 2] {
+        heap.insert(value);
+    }
+    heap
+}
+
+/// Check heap order, parent links, degrees and that no root is marked, and return (number of
+/// roots, largest degree, number of marked Nodes, number of Nodes).
+#[cfg(test)]
+fn check_structure<T: Ord>(heap: &FibonacciHeap<T>) -> (usize, usize, usize, usize) {
+    let Some(min) = &heap.min else {
+        return (0, 0, 0, 0);
+    };
+    let roots = Node::ring_members(min);
+    let root_count = roots.len();
+    let (mut max_degree, mut marked, mut nodes) = (0, 0, 0);
+    for root in &roots {
+        assert!(root.borrow().value >= min.borrow().value);
+        assert!(!root.borrow().marked);
+        assert!(root.borrow().parent.upgrade().is_none());
+    }
+    let mut worklist = roots;
+    while let Some(node_rc) = worklist.pop() {
+        let node = node_rc.borrow();
+        nodes += 1;
+        max_degree = max_degree.max(node.degree);
+        marked += usize::from(node.marked);
+        let children = node.child.as_ref().map(Node::ring_members).unwrap_or_default();
+        assert_eq!(children.len(), node.degree);
+        for child in &children {
+            assert!(child.borrow().value >= node.value);
+            assert!(Rc::ptr_eq(&child.borrow().parent.upgrade().unwrap(), &node_rc));
+        }
+        worklist.extend(children);
+    }
+    assert_eq!(nodes, heap.length);
+    (root_count, max_degree, marked, nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the largest degree the Fibonacci heap analysis allows for n Nodes: log base phi of
+    ///     n (a Node of degree d has at least F(d + 2) >= phi^d Nodes in its subtree).
+    fn degree_bound(n: usize) -> usize {
+        ((n as f64).ln() / 1.618_034_f64.ln()).floor() as usize
+    }
+
+    #[test]
+    fn fibonacci_heap_works() {
+        let mut heap = setup_fibonacci_heap();
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.peek_min().as_deref(), Some(&1));
+        assert_eq!(heap.extract_min(), Some(1));
+        check_structure(&heap);
+        assert_eq!(heap.extract_min(), Some(2));
+        heap.insert(0);
+        assert_eq!(heap.extract_min(), Some(0));
+        check_structure(&heap);
+        assert_eq!(heap.into_sorted_vec(), vec![3, 5, 8, 9]);
+        assert_eq!(FibonacciHeap::<u32>::new().extract_min(), None);
+    }
+
+    #[test]
+    fn fibonacci_heaps_can_be_merged() {
+        let mut heap = setup_fibonacci_heap();
+        heap.extract_min();
+        let mut other = FibonacciHeap::new();
+        let handle = other.insert(10);
+        other.insert(4);
+        heap.merge(other);
+        heap.merge(FibonacciHeap::new());
+        assert_eq!(heap.len(), 7);
+        check_structure(&heap);
+
+        assert!(heap.decrease_key(&handle, 0));
+        assert_eq!(heap.peek_min().as_deref(), Some(&0));
+        assert_eq!(heap.into_sorted_vec(), vec![0, 2, 3, 4, 5, 8, 9]);
+    }
+
+    #[test]
+    fn fibonacci_heap_rejects_foreign_handles() {
+        let mut heap = setup_fibonacci_heap();
+        heap.extract_min();
+        let mut other = FibonacciHeap::new();
+        for value in 0..8 {
+            other.insert(value);
+        }
+        let foreign_child = other.insert(20);
+        // consolidate other so the handle's Node has a parent
+        assert_eq!(other.extract_min(), Some(0));
+
+        assert!(!heap.decrease_key(&foreign_child, 1));
+        assert_eq!(heap.len(), 5);
+        check_structure(&heap);
+        check_structure(&other);
+        assert_eq!(heap.into_sorted_vec(), vec![2, 3, 5, 8, 9]);
+
+        // once merged, the handle refers to the heap it was merged into
+        let mut merged = FibonacciHeap::new();
+        merged.merge(other);
+        assert!(merged.decrease_key(&foreign_child, 0));
+        assert_eq!(merged.extract_min(), Some(0));
+        check_structure(&merged);
+    }
+
+    #[test]
+    fn fibonacci_heap_consolidation_links_each_tree_once() {
+        let n = 1024;
+        let mut heap = FibonacciHeap::new();
+        for value in 0..n {
+            heap.insert(value);
+        }
+        // inserts are O(1): nothing is linked yet
+        assert_eq!(heap.counts(), OperationCounts::default());
+        assert_eq!(check_structure(&heap).0, n);
+
+        // the first extract_min links the other n - 1 roots into trees of distinct degrees
+        assert_eq!(heap.extract_min(), Some(0));
+        let (roots, max_degree, _, _) = check_structure(&heap);
+        assert_eq!(heap.counts().links, n - 1 - roots);
+        assert!(roots <= degree_bound(n) + 1);
+        assert!(max_degree <= degree_bound(n));
+
+        // every link removes a root, so there can never be more links than inserts
+        while heap.extract_min().is_some() {
+            assert!(heap.counts().links < n);
+        }
+    }
+
+    #[test]
+    fn fibonacci_heap_cascading_cuts() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<NodeHandle<u32>> = (0..9).map(|value| heap.insert(value * 10)).collect();
+        let thousand = heap.insert(1000);
+        // extracting 0 consolidates the other 9 roots into the root 10 and a tree of degree 3:
+        // 20 -> { 30, 60 -> { 70, 80 -> { 1000 } }, 40 -> { 50 } }
+        assert_eq!(heap.extract_min(), Some(0));
+        assert_eq!(check_structure(&heap), (2, 3, 0, 9));
+
+        // 1000 is cut from 80, which is marked
+        assert!(heap.decrease_key(&thousand, 15));
+        assert_eq!(heap.counts().cuts, 1);
+        assert_eq!(check_structure(&heap).2, 1);
+        // 70 is cut from 60, which is marked
+        assert!(heap.decrease_key(&handles[7], 4));
+        assert_eq!(check_structure(&heap).2, 2);
+        // 80 is cut from 60: it is unmarked as it becomes a root, and 60 has now lost two
+        // children, so it is cut from 20 as well - which stops there, as 20 is a root
+        assert!(heap.decrease_key(&handles[8], 3));
+        assert_eq!(heap.counts().cuts, 3);
+        assert_eq!(heap.counts().cascading_cuts, 1);
+        let (roots, _, marked, nodes) = check_structure(&heap);
+        assert_eq!((roots, marked, nodes), (6, 0, 9));
+
+        // a value that isn't lower, or was already extracted, is left alone
+        assert!(!heap.decrease_key(&handles[5], 55));
+        assert_eq!(heap.peek_min().as_deref(), Some(&3));
+        assert_eq!(heap.into_sorted_vec(), vec![3, 4, 10, 15, 20, 30, 40, 50, 60]);
+        assert!(handles[0].node.upgrade().is_none());
+    }
+
+    #[test]
+    fn fibonacci_heap_amortized_work_stays_bounded() {
+        let n = 2000;
+        let mut heap = FibonacciHeap::new();
+        let mut handles = Vec::new();
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next_random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for _ in 0..n {
+            handles.push(heap.insert(next_random() % 1_000_000 + 1_000_000));
+        }
+
+        let mut operations = n;
+        let mut extracts = 0;
+        let mut cut_attempts = 0;
+        for round in 0..n {
+            if round % 4 == 0 {
+                heap.extract_min();
+                extracts += 1;
+            } else {
+                let handle = &handles[next_random() as usize % handles.len()];
+                let new_value = next_random() % 1_000_000;
+                if heap.decrease_key(handle, new_value) {
+                    cut_attempts += 1;
+                }
+            }
+            operations += 1;
+            let (_, max_degree, marked, nodes) = check_structure(&heap);
+            assert!(max_degree <= degree_bound(nodes.max(1)));
+            // every cascading cut unmarks a Node, and each decrease_key marks at most one
+            assert!(heap.counts().cascading_cuts + marked <= cut_attempts);
+        }
+
+        // every link removes a root, and roots only come from inserts, cuts and the O(log n)
+        // children of each extracted minimum
+        let counts = heap.counts();
+        assert!(counts.cuts <= cut_attempts);
+        assert!(counts.links <= n + counts.cuts + counts.cascading_cuts + extracts * degree_bound(n));
+        assert!(counts.links + counts.cuts + counts.cascading_cuts <= 3 * operations);
+    }
+
+    #[test]
+    fn fibonacci_heap_drops_without_leaks() {
+        let counter = Rc::new(());
+        let mut heap = FibonacciHeap::new();
+        let mut handles = Vec::new();
+        for value in 0..100 {
+            handles.push(heap.insert((value, Rc::clone(&counter))));
+        }
+        heap.extract_min();
+        heap.decrease_key(&handles[50], (0, Rc::clone(&counter)));
+        drop(heap);
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert!(handles.iter().all(|handle| handle.node.upgrade().is_none()));
+
+        let mut long_heap = FibonacciHeap::new();
+        for value in 0..200_000u32 {
+            long_heap.insert(value);
+        }
+        drop(long_heap);
+    }
+}
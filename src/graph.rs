@@ -0,0 +1,604 @@
+//! Weighted directed graph in adjacency list representation, allowing parallel edges (any number
+//!     of edges between the same two nodes) and self-loops (edges from a node to itself).
+//!
+//! Nodes and edges are referred to by NodeId and EdgeId handles. Handle invalidation rules:
+//!     * Removing a node or edge invalidates only its own handle - and removing a node removes
+//!         its edges, invalidating theirs. Every other handle stays valid.
+//!     * Slots of removed nodes and edges are reused by later additions, but each handle also
+//!         records the slot's generation, which is bumped on removal, so a stale handle never
+//!         refers to a newer node or edge.
+//!     * Methods looking up a stale handle return None (or false, or an empty iterator), and
+//!         add_edge panics if either endpoint is stale.
+//!     * reverse keeps every handle, so ids from a graph are valid in its reverse.
+//!
+//! ```
+//! use rust_datastructures_algorithms::graph::DiGraph;
+//!
+//! let mut graph = DiGraph::new();
+//! let a = graph.add_node("a");
+//! let b = graph.add_node("b");
+//! let fast = graph.add_edge(a, b, 1);
+//! graph.add_edge(a, b, 5);
+//! graph.add_edge(b, b, 2);
+//! assert_eq!(graph.edges_between(a, b).count(), 2);
+//! assert_eq!(graph.find_edge(a, b), Some(fast));
+//!
+//! let reversed = graph.reverse();
+//! assert_eq!(reversed.outgoing(b).map(|edge| edge.target).collect::<Vec<_>>(), vec![a, a, b]);
+//! assert_eq!(graph.remove_node(b), Some("b"));
+//! assert_eq!(graph.edge(fast), None);
+//! ```
+
+use std::slice;
+
+
+/// Handle to a node in a DiGraph struct.
+///
+/// * `index`: Index of the node's slot.
+/// * `generation`: Generation of the slot when the node was added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId {
+    index: usize,
+    generation: u32
+}
+
+
+/// Handle to an edge in a DiGraph struct.
+///
+/// * `index`: Index of the edge's slot.
+/// * `generation`: Generation of the slot when the edge was added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeId {
+    index: usize,
+    generation: u32
+}
+
+
+/// A weighted directed graph struct, holding nodes and edges in slots referred to by handles.
+///
+/// * `nodes`: Slots holding each node's value and adjacency lists.
+/// * `edges`: Slots holding each edge's endpoints and weight.
+/// * `free_nodes`: Indexes of empty node slots.
+/// * `free_edges`: Indexes of empty edge slots.
+/// * `node_count`: Number of nodes in the graph.
+/// * `edge_count`: Number of edges in the graph.
+#[derive(Clone)]
+pub struct DiGraph<N, W> {
+    nodes: Vec<Slot<Node<N>>>,
+    edges: Vec<Slot<Edge<W>>>,
+    free_nodes: Vec<usize>,
+    free_edges: Vec<usize>,
+    node_count: usize,
+    edge_count: usize
+}
+
+
+/// Slot in a DiGraph struct.
+///
+/// * `generation`: Number of times the slot's contents have been removed.
+/// * `item`: The node or edge in the slot (None if it is empty).
+#[derive(Clone)]
+struct Slot<T> {
+    generation: u32,
+    item: Option<T>
+}
+
+
+/// Node in a DiGraph struct.
+///
+/// * `value`: Value held by the node.
+/// * `outgoing`: Edges leaving the node, in the order they were added.
+/// * `incoming`: Edges entering the node, in the order they were added. A self-loop is in both
+///         lists.
+#[derive(Clone)]
+struct Node<N> {
+    value: N,
+    outgoing: Vec<EdgeId>,
+    incoming: Vec<EdgeId>
+}
+
+
+/// Edge in a DiGraph struct.
+///
+/// * `source`: Node the edge leaves.
+/// * `target`: Node the edge enters.
+/// * `weight`: Weight of the edge.
+#[derive(Clone)]
+struct Edge<W> {
+    source: NodeId,
+    target: NodeId,
+    weight: W
+}
+
+
+/// An edge of a DiGraph struct as yielded by its iterators.
+///
+/// * `id`: Handle to the edge.
+/// * `source`: Node the edge leaves.
+/// * `target`: Node the edge enters.
+/// * `weight`: Reference to the edge's weight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EdgeRef<'a, W> {
+    pub id: EdgeId,
+    pub source: NodeId,
+    pub target: NodeId,
+    pub weight: &'a W
+}
+
+
+/// Iterator yielding the edges in one of a node's adjacency lists, in the order they were added.
+/// Created by DiGraph::outgoing and DiGraph::incoming.
+///
+/// * `ids`: Iterator over the handles in the list.
+/// * `edges`: The graph's edge slots.
+pub struct Edges<'a, W> {
+    ids: slice::Iter<'a, EdgeId>,
+    edges: &'a [Slot<Edge<W>>]
+}
+
+
+// Method implementations for DiGraph struct
+impl<N, W> DiGraph<N, W> {
+
+    /// Return a new, empty DiGraph struct.
+    pub fn new() -> DiGraph<N, W> {
+        DiGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            free_nodes: Vec::new(),
+            free_edges: Vec::new(),
+            node_count: 0,
+            edge_count: 0
+        }
+    }
+
+    /// Return the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Return the number of edges in the graph (counting each parallel edge and self-loop).
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Add a node holding the input value, with no edges, and return its handle.
+    ///
+    /// * `value`: value to be held by the node.
+    pub fn add_node(&mut self, value: N) -> NodeId {
+        let (index, generation) = allocate(&mut self.nodes, &mut self.free_nodes, Node {
+            value,
+            outgoing: Vec::new(),
+            incoming: Vec::new()
+        });
+        self.node_count += 1;
+        NodeId { index, generation }
+    }
+
+    /// Add an edge from the source node to the target node and return its handle. Edges already
+    /// between the two nodes are kept, and the source and target may be the same node. Panics if
+    /// either handle is stale.
+    ///
+    /// * `source`: node the edge leaves.
+    /// * `target`: node the edge enters.
+    /// * `weight`: weight of the edge.
+    pub fn add_edge(&mut self, source: NodeId, target: NodeId, weight: W) -> EdgeId {
+        assert!(self.contains_node(source), "source node {:?} is not in the graph", source);
+        assert!(self.contains_node(target), "target node {:?} is not in the graph", target);
+        let (index, generation) = allocate(&mut self.edges, &mut self.free_edges, Edge { source, target, weight });
+        let id = EdgeId { index, generation };
+        self.node_data_mut(source).expect("checked above").outgoing.push(id);
+        self.node_data_mut(target).expect("checked above").incoming.push(id);
+        self.edge_count += 1;
+        id
+    }
+
+    /// Return whether the input handle refers to a node in the graph.
+    ///
+    /// * `node`: handle to the node.
+    pub fn contains_node(&self, node: NodeId) -> bool {
+        self.node_data(node).is_some()
+    }
+
+    /// Return whether the input handle refers to an edge in the graph.
+    ///
+    /// * `edge`: handle to the edge.
+    pub fn contains_edge(&self, edge: EdgeId) -> bool {
+        self.edge_data(edge).is_some()
+    }
+
+    /// Return a reference to the value held by the node (None for a stale handle).
+    ///
+    /// * `node`: handle to the node.
+    pub fn node(&self, node: NodeId) -> Option<&N> {
+        self.node_data(node).map(|data| &data.value)
+    }
+
+    /// Return a mutable reference to the value held by the node (None for a stale handle).
+    ///
+    /// * `node`: handle to the node.
+    pub fn node_mut(&mut self, node: NodeId) -> Option<&mut N> {
+        self.node_data_mut(node).map(|data| &mut data.value)
+    }
+
+    /// Return the edge's endpoints and a reference to its weight (None for a stale handle).
+    ///
+    /// * `edge`: handle to the edge.
+    pub fn edge(&self, edge: EdgeId) -> Option<EdgeRef<'_, W>> {
+        self.edge_data(edge).map(|data| EdgeRef { id: edge, source: data.source, target: data.target, weight: &data.weight })
+    }
+
+    /// Return a mutable reference to the edge's weight (None for a stale handle).
+    ///
+    /// * `edge`: handle to the edge.
+    pub fn edge_weight_mut(&mut self, edge: EdgeId) -> Option<&mut W> {
+        slot_item_mut(&mut self.edges, edge.index, edge.generation).map(|data| &mut data.weight)
+    }
+
+    /// Return an iterator of the handles of every node, in slot order.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes.iter().enumerate()
+            .filter(|(_, slot)| slot.item.is_some())
+            .map(|(index, slot)| NodeId { index, generation: slot.generation })
+    }
+
+    /// Return an iterator of every edge, in slot order.
+    pub fn edges(&self) -> impl Iterator<Item = EdgeRef<'_, W>> {
+        self.edges.iter().enumerate().filter_map(|(index, slot)| {
+            let data = slot.item.as_ref()?;
+            let id = EdgeId { index, generation: slot.generation };
+            Some(EdgeRef { id, source: data.source, target: data.target, weight: &data.weight })
+        })
+    }
+
+    /// Return an iterator of the edges leaving the node, in the order they were added (empty for
+    /// a stale handle).
+    ///
+    /// * `node`: handle to the node.
+    pub fn outgoing(&self, node: NodeId) -> Edges<'_, W> {
+        let ids = self.node_data(node).map_or(&[][..], |data| &data.outgoing);
+        Edges { ids: ids.iter(), edges: &self.edges }
+    }
+
+    /// Return an iterator of the edges entering the node, in the order they were added (empty for
+    /// a stale handle).
+    ///
+    /// * `node`: handle to the node.
+    pub fn incoming(&self, node: NodeId) -> Edges<'_, W> {
+        let ids = self.node_data(node).map_or(&[][..], |data| &data.incoming);
+        Edges { ids: ids.iter(), edges: &self.edges }
+    }
+
+    /// Return an iterator of every edge from the source node to the target node, in the order
+    /// they were added. Scans the source's outgoing edges, so O(out-degree).
+    ///
+    /// * `source`: node the edges leave.
+    /// * `target`: node the edges enter.
+    pub fn edges_between(&self, source: NodeId, target: NodeId) -> impl Iterator<Item = EdgeRef<'_, W>> {
+        self.outgoing(source).filter(move |edge| edge.target == target)
+    }
+
+    /// Return the handle of the first edge added from the source node to the target node that is
+    /// still in the graph.
+    ///
+    /// * `source`: node the edge leaves.
+    /// * `target`: node the edge enters.
+    pub fn find_edge(&self, source: NodeId, target: NodeId) -> Option<EdgeId> {
+        self.edges_between(source, target).next().map(|edge| edge.id)
+    }
+
+    /// Remove the edge from the graph and return its weight (None for a stale handle). Only the
+    /// edge's own handle is invalidated. O(degree) of its endpoints.
+    ///
+    /// * `edge`: handle to the edge.
+    pub fn remove_edge(&mut self, edge: EdgeId) -> Option<W> {
+        let data = release(&mut self.edges, &mut self.free_edges, edge.index, edge.generation)?;
+        self.node_data_mut(data.source).expect("edges only join nodes in the graph").outgoing.retain(|id| *id != edge);
+        self.node_data_mut(data.target).expect("edges only join nodes in the graph").incoming.retain(|id| *id != edge);
+        self.edge_count -= 1;
+        Some(data.weight)
+    }
+
+    /// Remove the node and every edge leaving or entering it from the graph, and return its value
+    /// (None for a stale handle). The handles of the node and its edges are invalidated.
+    ///
+    /// * `node`: handle to the node.
+    pub fn remove_node(&mut self, node: NodeId) -> Option<N> {
+        let data = self.node_data(node)?;
+        // a self-loop is in both lists, so skip it the second time
+        let mut incident: Vec<EdgeId> = data.outgoing.clone();
+        incident.extend(data.incoming.iter().filter(|id| self.edges[id.index].item.as_ref().is_some_and(|edge| edge.source != node)));
+        for edge in incident {
+            self.remove_edge(edge);
+        }
+        let data = release(&mut self.nodes, &mut self.free_nodes, node.index, node.generation)?;
+        self.node_count -= 1;
+        Some(data.value)
+    }
+
+    /// Return the reverse of the graph: the same nodes, with every edge turned around. Handles
+    /// from this graph refer to the same nodes and (reversed) edges in the reverse. O(n + m).
+    pub fn reverse(&self) -> DiGraph<N, W> where N: Clone, W: Clone {
+        let mut reversed = self.clone();
+        for slot in &mut reversed.nodes {
+            if let Some(node) = &mut slot.item {
+                std::mem::swap(&mut node.outgoing, &mut node.incoming);
+            }
+        }
+        for slot in &mut reversed.edges {
+            if let Some(edge) = &mut slot.item {
+                std::mem::swap(&mut edge.source, &mut edge.target);
+            }
+        }
+        reversed
+    }
+
+    /// Return the node the handle refers to (None for a stale handle).
+    ///
+    /// * `node`: handle to the node.
+    fn node_data(&self, node: NodeId) -> Option<&Node<N>> {
+        slot_item(&self.nodes, node.index, node.generation)
+    }
+
+    /// Return the node the handle refers to, mutably (None for a stale handle).
+    ///
+    /// * `node`: handle to the node.
+    fn node_data_mut(&mut self, node: NodeId) -> Option<&mut Node<N>> {
+        slot_item_mut(&mut self.nodes, node.index, node.generation)
+    }
+
+    /// Return the edge the handle refers to (None for a stale handle).
+    ///
+    /// * `edge`: handle to the edge.
+    fn edge_data(&self, edge: EdgeId) -> Option<&Edge<W>> {
+        slot_item(&self.edges, edge.index, edge.generation)
+    }
+}
+
+/// Store an item in an empty slot (or a new one) and return the slot's index and generation.
+///
+/// * `slots`: the slots.
+/// * `free`: indexes of the empty slots.
+/// * `item`: item to be stored.
+fn allocate<T>(slots: &mut Vec<Slot<T>>, free: &mut Vec<usize>, item: T) -> (usize, u32) {
+    match free.pop() {
+        Some(index) => {
+            slots[index].item = Some(item);
+            (index, slots[index].generation)
+        },
+        None => {
+            slots.push(Slot { generation: 0, item: Some(item) });
+            (slots.len() - 1, 0)
+        }
+    }
+}
+
+/// Take the item out of a slot, if the handle's generation is current, bumping the generation so
+/// the handle goes stale.
+///
+/// * `slots`: the slots.
+/// * `free`: indexes of the empty slots.
+/// * `index`: index of the slot.
+/// * `generation`: generation recorded by the handle.
+fn release<T>(slots: &mut [Slot<T>], free: &mut Vec<usize>, index: usize, generation: u32) -> Option<T> {
+    let slot = slots.get_mut(index).filter(|slot| slot.generation == generation)?;
+    let item = slot.item.take()?;
+    slot.generation = slot.generation.wrapping_add(1);
+    free.push(index);
+    Some(item)
+}
+
+/// Return the item in a slot, if the handle's generation is current.
+///
+/// * `slots`: the slots.
+/// * `index`: index of the slot.
+/// * `generation`: generation recorded by the handle.
+fn slot_item<T>(slots: &[Slot<T>], index: usize, generation: u32) -> Option<&T> {
+    slots.get(index).filter(|slot| slot.generation == generation)?.item.as_ref()
+}
+
+/// Return the item in a slot mutably, if the handle's generation is current.
+///
+/// * `slots`: the slots.
+/// * `index`: index of the slot.
+/// * `generation`: generation recorded by the handle.
+fn slot_item_mut<T>(slots: &mut [Slot<T>], index: usize, generation: u32) -> Option<&mut T> {
+    slots.get_mut(index).filter(|slot| slot.generation == generation)?.item.as_mut()
+}
+
+impl<N, W> Default for DiGraph<N, W> {
+    fn default() -> Self {
+        DiGraph::new()
+    }
+}
+
+
+// Iterator implementation for Edges struct
+impl<'a, W> Iterator for Edges<'a, W> {
+    type Item = EdgeRef<'a, W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = *self.ids.next()?;
+        let edge = slot_item(self.edges, id.index, id.generation).expect("adjacency lists only hold live edges");
+        Some(EdgeRef { id, source: edge.source, target: edge.target, weight: &edge.weight })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+impl<W> ExactSizeIterator for Edges<'_, W> {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_graph() -> (DiGraph<char, u32>, Vec<NodeId>) {
+    let mut graph = DiGraph::new();
+    let nodes: Vec<NodeId> = "abcd".chars().map(|name| graph.add_node(name)).collect();
+    for (source, target, weight) in [(0, 1, 4), (0, 1, 2), (1, 2, 3), (2, 0, 1), (2, 2, 7), (2, 3, 5), (3, 3, 6)] {
+        graph.add_edge(nodes[source], nodes[target], weight);
+    }
+    (graph, nodes)
+}
+
+/// Check that the adjacency lists and edge endpoints agree, and the counts match the slots.
+#[cfg(test)]
+fn check_consistency<N, W>(graph: &DiGraph<N, W>) {
+    let edges: Vec<EdgeRef<'_, W>> = graph.edges().collect();
+    assert_eq!(edges.len(), graph.edge_count());
+    assert_eq!(graph.node_ids().count(), graph.node_count());
+    for edge in &edges {
+        assert!(graph.outgoing(edge.source).any(|outgoing| outgoing.id == edge.id));
+        assert!(graph.incoming(edge.target).any(|incoming| incoming.id == edge.id));
+    }
+    for node in graph.node_ids() {
+        assert!(graph.outgoing(node).all(|edge| edge.source == node));
+        assert!(graph.incoming(node).all(|edge| edge.target == node));
+    }
+    let listed: usize = graph.node_ids().map(|node| graph.outgoing(node).len()).sum();
+    assert_eq!(listed, edges.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the strongly connected components of the graph with Kosaraju's algorithm: order the
+    /// nodes by when a depth-first search finishes them, then search the reverse graph from each
+    /// in reverse finishing order - each search finds exactly one component.
+    fn strongly_connected_components<N: Clone, W: Clone>(graph: &DiGraph<N, W>) -> Vec<Vec<NodeId>> {
+        let mut finished = Vec::new();
+        let mut visited = std::collections::HashSet::new();
+        for start in graph.node_ids() {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut stack = vec![(start, graph.outgoing(start))];
+            while let Some((node, edges)) = stack.last_mut() {
+                match edges.next() {
+                    Some(edge) => {
+                        if visited.insert(edge.target) {
+                            stack.push((edge.target, graph.outgoing(edge.target)));
+                        }
+                    },
+                    None => {
+                        finished.push(*node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        let reversed = graph.reverse();
+        let mut assigned = std::collections::HashSet::new();
+        let mut components = Vec::new();
+        for start in finished.into_iter().rev() {
+            if !assigned.insert(start) {
+                continue;
+            }
+            let mut component = vec![start];
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for edge in reversed.outgoing(node) {
+                    if assigned.insert(edge.target) {
+                        component.push(edge.target);
+                        stack.push(edge.target);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn graph_works() {
+        let (mut graph, nodes) = setup_graph();
+        check_consistency(&graph);
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 7);
+        assert_eq!(graph.node(nodes[2]), Some(&'c'));
+        *graph.node_mut(nodes[3]).unwrap() = 'D';
+        assert_eq!(graph.node(nodes[3]), Some(&'D'));
+
+        // parallel edges are kept, in the order they were added
+        let weights: Vec<u32> = graph.edges_between(nodes[0], nodes[1]).map(|edge| *edge.weight).collect();
+        assert_eq!(weights, vec![4, 2]);
+        let first = graph.find_edge(nodes[0], nodes[1]).unwrap();
+        assert_eq!(*graph.edge(first).unwrap().weight, 4);
+        *graph.edge_weight_mut(first).unwrap() = 9;
+        assert_eq!(graph.edges_between(nodes[0], nodes[1]).map(|edge| *edge.weight).max(), Some(9));
+        assert_eq!(graph.find_edge(nodes[1], nodes[0]), None);
+
+        // a self-loop is both outgoing and incoming
+        let self_loop = graph.find_edge(nodes[2], nodes[2]).unwrap();
+        assert!(graph.outgoing(nodes[2]).any(|edge| edge.id == self_loop));
+        assert!(graph.incoming(nodes[2]).any(|edge| edge.id == self_loop));
+        assert_eq!(graph.outgoing(nodes[2]).len(), 3);
+        assert_eq!(graph.incoming(nodes[2]).len(), 2);
+
+        assert_eq!(graph.remove_edge(first), Some(9));
+        assert_eq!(graph.remove_edge(first), None);
+        assert_eq!(graph.edges_between(nodes[0], nodes[1]).count(), 1);
+        check_consistency(&graph);
+    }
+
+    #[test]
+    fn graph_remove_node_invalidates_handles() {
+        let (mut graph, nodes) = setup_graph();
+        let self_loop = graph.find_edge(nodes[2], nodes[2]).unwrap();
+        let into_c = graph.find_edge(nodes[1], nodes[2]).unwrap();
+        let kept = graph.find_edge(nodes[0], nodes[1]).unwrap();
+
+        assert_eq!(graph.remove_node(nodes[2]), Some('c'));
+        check_consistency(&graph);
+        assert_eq!(graph.node_count(), 3);
+        // the edges b->c, c->a, c->c and c->d went with it
+        assert_eq!(graph.edge_count(), 3);
+        assert!(!graph.contains_edge(self_loop) && !graph.contains_edge(into_c));
+        assert!(graph.contains_edge(kept));
+        assert_eq!(graph.incoming(nodes[0]).count(), 0);
+
+        // stale handles find nothing, even once their slots are reused
+        let e = graph.add_node('e');
+        assert_eq!(e.index, nodes[2].index);
+        assert_ne!(e, nodes[2]);
+        assert_eq!(graph.node(nodes[2]), None);
+        assert_eq!(graph.remove_node(nodes[2]), None);
+        assert_eq!(graph.outgoing(nodes[2]).count(), 0);
+        let reused = graph.add_edge(e, e, 8);
+        assert!(!graph.contains_edge(self_loop) && graph.contains_edge(reused));
+        assert_eq!(graph.edge(self_loop), None);
+        check_consistency(&graph);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in the graph")]
+    fn graph_add_edge_stale_node() {
+        let (mut graph, nodes) = setup_graph();
+        graph.remove_node(nodes[3]);
+        graph.add_edge(nodes[0], nodes[3], 1);
+    }
+
+    #[test]
+    fn graph_reverse() {
+        let (graph, nodes) = setup_graph();
+        let reversed = graph.reverse();
+        check_consistency(&reversed);
+        assert_eq!(reversed.edge_count(), graph.edge_count());
+        for edge in graph.edges() {
+            let turned = reversed.edge(edge.id).unwrap();
+            assert_eq!((turned.source, turned.target, turned.weight), (edge.target, edge.source, edge.weight));
+        }
+        assert_eq!(reversed.edges_between(nodes[1], nodes[0]).count(), 2);
+        assert_eq!(reversed.outgoing(nodes[3]).map(|edge| edge.target).collect::<Vec<_>>(), vec![nodes[2], nodes[3]]);
+        assert_eq!(reversed.reverse().edges().collect::<Vec<_>>(), graph.edges().collect::<Vec<_>>());
+
+        // a -> b -> c -> a is a cycle, and d only reaches itself
+        assert_eq!(strongly_connected_components(&graph), vec![vec![nodes[0], nodes[1], nodes[2]], vec![nodes[3]]]);
+    }
+}
@@ -0,0 +1,1255 @@
+//! Hash maps built from scratch on top of std's hashing (Hash, BuildHasher and the randomly
+//!     seeded RandomState).
+//!
+//! OpenHashMap uses open addressing: every key-value pair lives directly in one flat table of
+//!     slots, and collisions are resolved with linear probing - a key that hashes to an occupied
+//!     slot goes in the next free slot after it.
+//!
+//! ChainedHashMap uses separate chaining: the table is a list of buckets, each one of this
+//!     crate's LinkedList structs holding every key-value pair whose hash maps to it. Collisions
+//!     lengthen a bucket's chain instead of spilling into neighbouring slots.
+//!
+//! ```
+//! use rust_datastructures_algorithms::hash_map::OpenHashMap;
+//!
+//! let mut word_counts = OpenHashMap::new();
+//! for word in "the cat and the hat and the bat".split(' ') {
+//!     *word_counts.entry(word).or_insert(0) += 1;
+//! }
+//! assert_eq!(word_counts.get("the"), Some(&3));
+//! assert_eq!(word_counts.remove("and"), Some(2));
+//! assert_eq!(word_counts.len(), 4);
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{ BuildHasher, Hash };
+use std::mem;
+use crate::linked_list::{ self, LinkedList };
+
+
+/// Capacity the table grows to on the first insert. Capacities are always powers of two, so a
+/// hash can be reduced to a slot index with a mask.
+const MIN_CAPACITY: usize = 8;
+
+/// Maximum load factor, as a fraction: the table is resized before occupied slots plus
+/// tombstones would exceed 3/4 of it. Tombstones count because they lengthen probes just as much.
+const MAX_LOAD_NUMERATOR: usize = 3;
+const MAX_LOAD_DENOMINATOR: usize = 4;
+
+/// Number of buckets a ChainedHashMap struct starts with on the first insert. Bucket counts are
+/// always powers of two, like OpenHashMap capacities.
+const MIN_BUCKETS: usize = 8;
+
+
+/// Slot in an OpenHashMap struct's table.
+///
+/// * `Empty`: Never used since the table was last rebuilt - a probe reaching it can stop.
+/// * `Tombstone`: Held a key-value pair that was removed. A probe must carry on past it, as the
+///         key it is looking for may have been placed after it, but an insert can reuse it.
+/// * `Occupied`: Holds a key-value pair.
+enum Slot<K, V> {
+    Empty,
+    Tombstone,
+    Occupied(K, V)
+}
+
+
+/// A hash map struct using open addressing with linear probing.
+///
+/// A key is placed in the first Empty or Tombstone slot at or after the slot its hash maps to,
+/// wrapping around the end of the table. Lookups follow the same path until they find the key or
+/// reach an Empty slot. The load factor limit guarantees there is always an Empty slot, so probes
+/// always stop.
+///
+/// * `slots`: Slots of the table - its length is the map's capacity (0, or a power of two).
+/// * `length`: Number of key-value pairs in the map.
+/// * `tombstones`: Number of Tombstone slots.
+/// * `hash_builder`: Builds the hasher every key is hashed with.
+pub struct OpenHashMap<K, V, S = RandomState> {
+    slots: Vec<Slot<K, V>>,
+    length: usize,
+    tombstones: usize,
+    hash_builder: S
+}
+
+
+/// View into a single key's place in an OpenHashMap struct, which may be occupied or vacant.
+/// Created by OpenHashMap::entry.
+///
+/// * `Occupied`: The key is in the map.
+/// * `Vacant`: The key is not in the map.
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>)
+}
+
+
+/// View into an occupied slot of an OpenHashMap struct, part of the Entry enum.
+///
+/// * `slot`: The occupied slot.
+/// * `length`: The map's length, decremented if the entry is removed.
+/// * `tombstones`: The map's tombstone count, incremented if the entry is removed.
+pub struct OccupiedEntry<'a, K, V> {
+    slot: &'a mut Slot<K, V>,
+    length: &'a mut usize,
+    tombstones: &'a mut usize
+}
+
+
+/// View into the slot of an OpenHashMap struct that a key not in the map would be inserted
+/// into, part of the Entry enum. The map has already been grown if needed.
+///
+/// * `key`: Key the entry is for.
+/// * `slot`: Empty or Tombstone slot the key would be inserted into.
+/// * `length`: The map's length, incremented if a value is inserted.
+/// * `tombstones`: The map's tombstone count, decremented if a Tombstone slot is reused.
+pub struct VacantEntry<'a, K, V> {
+    key: K,
+    slot: &'a mut Slot<K, V>,
+    length: &'a mut usize,
+    tombstones: &'a mut usize
+}
+
+
+/// Iterator yielding references to the key-value pairs in an OpenHashMap struct, in table order
+/// (arbitrary, and changed by resizing). Created by OpenHashMap::iter.
+///
+/// * `slots`: Iterator over the slots of the table.
+/// * `remaining`: Number of key-value pairs not yet yielded.
+pub struct Iter<'a, K, V> {
+    slots: std::slice::Iter<'a, Slot<K, V>>,
+    remaining: usize
+}
+
+
+/// Iterator yielding references to the keys, and mutable references to the values, in an
+/// OpenHashMap struct, in table order. Created by OpenHashMap::iter_mut.
+///
+/// * `slots`: Iterator over the slots of the table.
+/// * `remaining`: Number of key-value pairs not yet yielded.
+pub struct IterMut<'a, K, V> {
+    slots: std::slice::IterMut<'a, Slot<K, V>>,
+    remaining: usize
+}
+
+
+/// Iterator yielding the key-value pairs of an OpenHashMap struct by value, in table order.
+/// Created by the OpenHashMap's into_iter.
+///
+/// * `slots`: Iterator over the slots of the table.
+/// * `remaining`: Number of key-value pairs not yet yielded.
+pub struct IntoIter<K, V> {
+    slots: std::vec::IntoIter<Slot<K, V>>,
+    remaining: usize
+}
+
+
+/// A hash map struct using separate chaining, with a LinkedList struct of key-value pairs as each
+/// bucket.
+///
+/// A key-value pair is appended to the bucket its key's hash maps to, and lookups walk that
+/// bucket's chain. The number of buckets doubles whenever the map would hold more pairs than
+/// buckets (a load factor of 1), so chains stay short on average however full the map gets -
+/// unlike open addressing, a chaining table never runs out of slots.
+///
+/// * `buckets`: Buckets of the table (none, or a power of two of them).
+/// * `length`: Number of key-value pairs in the map.
+/// * `hash_builder`: Builds the hasher every key is hashed with.
+pub struct ChainedHashMap<K, V, S = RandomState> {
+    buckets: Vec<LinkedList<(K, V)>>,
+    length: usize,
+    hash_builder: S
+}
+
+
+/// Iterator yielding references to the key-value pairs in a ChainedHashMap struct, bucket by
+/// bucket. Created by ChainedHashMap::iter.
+///
+/// * `buckets`: Iterator over the buckets not yet started.
+/// * `chain`: Iterator over the bucket currently being yielded from.
+/// * `remaining`: Number of key-value pairs not yet yielded.
+pub struct ChainedIter<'a, K, V> {
+    buckets: std::slice::Iter<'a, LinkedList<(K, V)>>,
+    chain: Option<linked_list::Iter<'a, (K, V)>>,
+    remaining: usize
+}
+
+
+// Method implementations for OpenHashMap struct
+impl<K, V> OpenHashMap<K, V, RandomState> where K: Hash + Eq {
+
+    /// Return a new, empty OpenHashMap struct hashing with a randomly seeded RandomState. Nothing
+    /// is allocated until the first insert.
+    pub fn new() -> OpenHashMap<K, V, RandomState> {
+        OpenHashMap::with_hasher(RandomState::new())
+    }
+
+    /// Return a new, empty OpenHashMap struct with room for at least the input number of
+    /// key-value pairs before it needs to grow.
+    ///
+    /// * `capacity`: number of key-value pairs to allocate room for.
+    pub fn with_capacity(capacity: usize) -> OpenHashMap<K, V, RandomState> {
+        let mut map = OpenHashMap::new();
+        map.reserve(capacity);
+        map
+    }
+}
+
+impl<K, V, S> OpenHashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+
+    /// Return a new, empty OpenHashMap struct hashing keys with hashers built by the input.
+    ///
+    /// * `hash_builder`: builds the hasher for each key.
+    pub fn with_hasher(hash_builder: S) -> OpenHashMap<K, V, S> {
+        OpenHashMap {
+            slots: Vec::new(),
+            length: 0,
+            tombstones: 0,
+            hash_builder
+        }
+    }
+
+    /// Return the number of key-value pairs in this OpenHashMap.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this OpenHashMap holds no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the number of key-value pairs this OpenHashMap can hold before it needs to grow.
+    pub fn capacity(&self) -> usize {
+        self.slots.len() * MAX_LOAD_NUMERATOR / MAX_LOAD_DENOMINATOR
+    }
+
+    /// Make sure the input number of further key-value pairs can be inserted without the table
+    /// being resized.
+    ///
+    /// * `additional`: number of key-value pairs about to be inserted.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.length + self.tombstones + additional;
+        if needed * MAX_LOAD_DENOMINATOR <= self.slots.len() * MAX_LOAD_NUMERATOR {
+            return;
+        }
+        // tombstones are dropped by the rebuild, so only the live pairs decide the new size. Keep
+        // them to at most half the new table, so a rebuild frees at least a quarter of it and the
+        // next one is far off
+        let live = self.length + additional;
+        let mut new_capacity = self.slots.len().max(MIN_CAPACITY);
+        while live * 2 > new_capacity {
+            new_capacity *= 2;
+        }
+        self.resize(new_capacity);
+    }
+
+    /// Insert a key-value pair into the map. If the key was already in the map, replace its value
+    /// and return the old one (the key itself is not replaced).
+    ///
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Get a reference to the value associated with the input key (if the key is in the map).
+    ///
+    /// * `key`: key to look up - any borrowed form of the map's key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    /// Get references to the stored key, and the value associated with it, matching the input
+    /// key (if the key is in the map).
+    ///
+    /// * `key`: key to look up - any borrowed form of the map's key type.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        match self.find(key) {
+            Ok(index) => match &self.slots[index] {
+                Slot::Occupied(stored_key, value) => Some((stored_key, value)),
+                _ => unreachable!("find only returns Ok for occupied slots")
+            },
+            Err(_) => None
+        }
+    }
+
+    /// Get a mutable reference to the value associated with the input key (if the key is in the
+    /// map).
+    ///
+    /// * `key`: key to look up - any borrowed form of the map's key type.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        match self.find(key) {
+            Ok(index) => match &mut self.slots[index] {
+                Slot::Occupied(_, value) => Some(value),
+                _ => unreachable!("find only returns Ok for occupied slots")
+            },
+            Err(_) => None
+        }
+    }
+
+    /// Return whether the input key is in the map.
+    ///
+    /// * `key`: key to look for - any borrowed form of the map's key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.find(key).is_ok()
+    }
+
+    /// Remove the input key from the map and return the value that was associated with it (if the
+    /// key was in the map).
+    ///
+    /// * `key`: key to be removed - any borrowed form of the map's key type.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Remove the input key from the map and return the stored key and its value (if the key was
+    /// in the map).
+    ///
+    /// * `key`: key to be removed - any borrowed form of the map's key type.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let index = self.find(key).ok()?;
+        let removed = remove_slot(&mut self.slots[index], &mut self.length, &mut self.tombstones);
+        Some(removed)
+    }
+
+    /// Return the entry for the input key, for inserting or updating its value in place with a
+    /// single lookup. The table is grown first if a new key would need it.
+    ///
+    /// * `key`: key the entry is for.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        self.reserve(1);
+        let index = self.find(&key);
+        let slot = match index {
+            Ok(index) | Err(index) => &mut self.slots[index]
+        };
+        let length = &mut self.length;
+        let tombstones = &mut self.tombstones;
+        match index {
+            Ok(_) => Entry::Occupied(OccupiedEntry { slot, length, tombstones }),
+            Err(_) => Entry::Vacant(VacantEntry { key, slot, length, tombstones })
+        }
+    }
+
+    /// Remove every key-value pair, keeping the table's allocation.
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            *slot = Slot::Empty;
+        }
+        self.length = 0;
+        self.tombstones = 0;
+    }
+
+    /// Return an iterator of references to the key-value pairs in this OpenHashMap, in arbitrary
+    /// order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots.iter(),
+            remaining: self.length
+        }
+    }
+
+    /// Return an iterator of references to the keys, and mutable references to the values, in
+    /// this OpenHashMap, in arbitrary order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: self.slots.iter_mut(),
+            remaining: self.length
+        }
+    }
+
+    /// Return an iterator of references to the keys in this OpenHashMap, in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Return an iterator of references to the values in this OpenHashMap, in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Return the index of the slot to start probing from for the input key.
+    ///
+    /// * `key`: key to be hashed.
+    fn home_index<Q>(&self, key: &Q) -> usize where Q: Hash + ?Sized {
+        (self.hash_builder.hash_one(key) as usize) & (self.slots.len() - 1)
+    }
+
+    /// Probe for the input key. Return Ok with the index of the slot holding it, or Err with the
+    /// index of the slot it should be inserted into - the first Tombstone passed on the way, or
+    /// else the Empty slot that ended the probe. Err(0) if the table has no slots.
+    ///
+    /// * `key`: key to look for - any borrowed form of the map's key type.
+    fn find<Q>(&self, key: &Q) -> Result<usize, usize> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        if self.slots.is_empty() {
+            return Err(0);
+        }
+        let mask = self.slots.len() - 1;
+        let mut index = self.home_index(key);
+        let mut first_tombstone = None;
+        loop {
+            match &self.slots[index] {
+                Slot::Empty => return Err(first_tombstone.unwrap_or(index)),
+                Slot::Tombstone => {
+                    first_tombstone.get_or_insert(index);
+                },
+                Slot::Occupied(stored_key, _) => {
+                    if stored_key.borrow() == key {
+                        return Ok(index);
+                    }
+                }
+            }
+            index = (index + 1) & mask;
+        }
+    }
+
+    /// Rebuild the table with the input number of slots, reinserting every key-value pair and
+    /// dropping every Tombstone.
+    ///
+    /// * `new_capacity`: number of slots in the new table - a power of two, large enough to keep
+    ///         the load factor under the limit.
+    fn resize(&mut self, new_capacity: usize) {
+        let mut new_slots = Vec::with_capacity(new_capacity);
+        new_slots.resize_with(new_capacity, || Slot::Empty);
+        let old_slots = mem::replace(&mut self.slots, new_slots);
+        self.tombstones = 0;
+        let mask = new_capacity - 1;
+        for slot in old_slots {
+            if let Slot::Occupied(key, value) = slot {
+                // every key is distinct and there are no tombstones, so take the first Empty slot
+                let mut index = self.home_index(&key);
+                while let Slot::Occupied(..) = self.slots[index] {
+                    index = (index + 1) & mask;
+                }
+                self.slots[index] = Slot::Occupied(key, value);
+            }
+        }
+    }
+}
+
+/// Take the key-value pair out of an occupied slot, leaving a Tombstone, and update the map's
+/// counts.
+///
+/// * `slot`: Occupied slot.
+/// * `length`: the map's length.
+/// * `tombstones`: the map's tombstone count.
+fn remove_slot<K, V>(slot: &mut Slot<K, V>, length: &mut usize, tombstones: &mut usize) -> (K, V) {
+    *length -= 1;
+    *tombstones += 1;
+    match mem::replace(slot, Slot::Tombstone) {
+        Slot::Occupied(key, value) => (key, value),
+        _ => unreachable!("only occupied slots are removed")
+    }
+}
+
+impl<K, V> Default for OpenHashMap<K, V, RandomState> where K: Hash + Eq {
+    fn default() -> Self {
+        OpenHashMap::new()
+    }
+}
+
+impl<K, V, S> Clone for OpenHashMap<K, V, S> where K: Clone, V: Clone, S: Clone {
+    fn clone(&self) -> Self {
+        OpenHashMap {
+            slots: self.slots.iter().map(|slot| match slot {
+                Slot::Empty => Slot::Empty,
+                Slot::Tombstone => Slot::Tombstone,
+                Slot::Occupied(key, value) => Slot::Occupied(key.clone(), value.clone())
+            }).collect(),
+            length: self.length,
+            tombstones: self.tombstones,
+            hash_builder: self.hash_builder.clone()
+        }
+    }
+}
+
+// Maps are equal if they hold the same key-value pairs, whatever order their tables hold them in.
+impl<K, V, S> PartialEq for OpenHashMap<K, V, S> where K: Hash + Eq, V: PartialEq, S: BuildHasher {
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length
+            && self.iter().all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K, V, S> Eq for OpenHashMap<K, V, S> where K: Hash + Eq, V: Eq, S: BuildHasher {}
+
+impl<K, V, S> fmt::Debug for OpenHashMap<K, V, S> where K: Hash + Eq + fmt::Debug, V: fmt::Debug, S: BuildHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Insert each key-value pair in turn, so later values replace earlier ones for the same key.
+impl<K, V, S> Extend<(K, V)> for OpenHashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for OpenHashMap<K, V, RandomState> where K: Hash + Eq {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OpenHashMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> IntoIterator for OpenHashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            slots: self.slots.into_iter(),
+            remaining: self.length
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a OpenHashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut OpenHashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+
+// Method implementations for ChainedHashMap struct
+impl<K, V> ChainedHashMap<K, V, RandomState> where K: Hash + Eq {
+
+    /// Return a new, empty ChainedHashMap struct hashing with a randomly seeded RandomState.
+    /// Nothing is allocated until the first insert.
+    pub fn new() -> ChainedHashMap<K, V, RandomState> {
+        ChainedHashMap::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> ChainedHashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+
+    /// Return a new, empty ChainedHashMap struct hashing keys with hashers built by the input.
+    ///
+    /// * `hash_builder`: builds the hasher for each key.
+    pub fn with_hasher(hash_builder: S) -> ChainedHashMap<K, V, S> {
+        ChainedHashMap {
+            buckets: Vec::new(),
+            length: 0,
+            hash_builder
+        }
+    }
+
+    /// Return the number of key-value pairs in this ChainedHashMap.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this ChainedHashMap holds no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the number of buckets in the table.
+    pub fn bucket_count(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Return the length of the longest chain in the table - the most key comparisons a lookup
+    /// can take.
+    pub fn longest_chain(&self) -> usize {
+        self.buckets.iter().map(LinkedList::len).max().unwrap_or(0)
+    }
+
+    /// Insert a key-value pair into the map. If the key was already in the map, replace its value
+    /// and return the old one (the key itself is not replaced).
+    ///
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(mem::replace(existing, value));
+        }
+        if self.length + 1 > self.buckets.len() {
+            self.resize((self.buckets.len() * 2).max(MIN_BUCKETS));
+        }
+        let index = self.bucket_index(&key);
+        self.buckets[index].add_value((key, value));
+        self.length += 1;
+        None
+    }
+
+    /// Get a reference to the value associated with the input key (if the key is in the map).
+    ///
+    /// * `key`: key to look up - any borrowed form of the map's key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        self.buckets[self.bucket_index(key)].iter()
+            .find(|(stored_key, _)| stored_key.borrow() == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Get a mutable reference to the value associated with the input key (if the key is in the
+    /// map).
+    ///
+    /// * `key`: key to look up - any borrowed form of the map's key type.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        let index = self.bucket_index(key);
+        self.buckets[index].iter_mut()
+            .find(|(stored_key, _)| stored_key.borrow() == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Return whether the input key is in the map.
+    ///
+    /// * `key`: key to look for - any borrowed form of the map's key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get(key).is_some()
+    }
+
+    /// Remove the input key from the map and return the value that was associated with it (if the
+    /// key was in the map).
+    ///
+    /// * `key`: key to be removed - any borrowed form of the map's key type.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        let index = self.bucket_index(key);
+        let bucket = &mut self.buckets[index];
+        let position = bucket.iter().position(|(stored_key, _)| stored_key.borrow() == key)?;
+        let (_, value) = bucket.remove_at(position)?;
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Return an iterator of references to the key-value pairs in this ChainedHashMap, in
+    /// arbitrary order.
+    pub fn iter(&self) -> ChainedIter<'_, K, V> {
+        ChainedIter {
+            buckets: self.buckets.iter(),
+            chain: None,
+            remaining: self.length
+        }
+    }
+
+    /// Return an iterator of references to the keys in this ChainedHashMap, in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Return an iterator of references to the values in this ChainedHashMap, in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Return the index of the bucket the input key belongs in. The table must have buckets.
+    ///
+    /// * `key`: key to be hashed.
+    fn bucket_index<Q>(&self, key: &Q) -> usize where Q: Hash + ?Sized {
+        (self.hash_builder.hash_one(key) as usize) & (self.buckets.len() - 1)
+    }
+
+    /// Rebuild the table with the input number of buckets, moving every key-value pair out of the
+    /// old chains and onto the end of its new bucket's chain.
+    ///
+    /// * `new_bucket_count`: number of buckets in the new table - a power of two.
+    fn resize(&mut self, new_bucket_count: usize) {
+        let mut new_buckets = Vec::with_capacity(new_bucket_count);
+        new_buckets.resize_with(new_bucket_count, LinkedList::new);
+        let old_buckets = mem::replace(&mut self.buckets, new_buckets);
+        for mut bucket in old_buckets {
+            while let Some((key, value)) = bucket.pop_front() {
+                let index = self.bucket_index(&key);
+                self.buckets[index].add_value((key, value));
+            }
+        }
+    }
+}
+
+impl<K, V> Default for ChainedHashMap<K, V, RandomState> where K: Hash + Eq {
+    fn default() -> Self {
+        ChainedHashMap::new()
+    }
+}
+
+impl<K, V, S> fmt::Debug for ChainedHashMap<K, V, S> where K: Hash + Eq + fmt::Debug, V: fmt::Debug, S: BuildHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Insert each key-value pair in turn, so later values replace earlier ones for the same key.
+impl<K, V, S> Extend<(K, V)> for ChainedHashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ChainedHashMap<K, V, RandomState> where K: Hash + Eq {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = ChainedHashMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a ChainedHashMap<K, V, S> where K: Hash + Eq, S: BuildHasher {
+    type Item = (&'a K, &'a V);
+    type IntoIter = ChainedIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+// Method implementations for Entry enum
+impl<'a, K, V> Entry<'a, K, V> {
+
+    /// Return a reference to the key this Entry is for.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key()
+        }
+    }
+
+    /// Return a mutable reference to the key's value, inserting the input value first if the key
+    /// is not in the map.
+    ///
+    /// * `default`: value to be inserted if the key is not in the map.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default)
+        }
+    }
+
+    /// Return a mutable reference to the key's value, inserting the result of the input function
+    /// first if the key is not in the map.
+    ///
+    /// * `default`: function called for the value to be inserted, only if the key is not in the
+    ///         map.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default())
+        }
+    }
+
+    /// Return a mutable reference to the key's value, inserting V's default value first if the
+    /// key is not in the map.
+    pub fn or_default(self) -> &'a mut V where V: Default {
+        self.or_insert_with(V::default)
+    }
+
+    /// Call the input function on the key's value if the key is in the map, and return the Entry.
+    ///
+    /// * `f`: function to modify the value with.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+
+// Method implementations for OccupiedEntry struct
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+
+    /// Return a reference to the stored key.
+    pub fn key(&self) -> &K {
+        self.pair().0
+    }
+
+    /// Return a reference to the key's value.
+    pub fn get(&self) -> &V {
+        self.pair().1
+    }
+
+    /// Return a mutable reference to the key's value.
+    pub fn get_mut(&mut self) -> &mut V {
+        match self.slot {
+            Slot::Occupied(_, value) => value,
+            _ => unreachable!("an OccupiedEntry's slot is always occupied")
+        }
+    }
+
+    /// Consume the entry and return a mutable reference to the key's value that lives as long as
+    /// the borrow of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        match self.slot {
+            Slot::Occupied(_, value) => value,
+            _ => unreachable!("an OccupiedEntry's slot is always occupied")
+        }
+    }
+
+    /// Replace the key's value and return the old one.
+    ///
+    /// * `value`: new value.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the key from the map and return its value.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Remove the key from the map and return the stored key and its value.
+    pub fn remove_entry(self) -> (K, V) {
+        remove_slot(self.slot, self.length, self.tombstones)
+    }
+
+    /// Return references to the stored key and its value.
+    fn pair(&self) -> (&K, &V) {
+        match &*self.slot {
+            Slot::Occupied(key, value) => (key, value),
+            _ => unreachable!("an OccupiedEntry's slot is always occupied")
+        }
+    }
+}
+
+
+// Method implementations for VacantEntry struct
+impl<'a, K, V> VacantEntry<'a, K, V> {
+
+    /// Return a reference to the key this entry is for.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Consume the entry and return the key it was for, without inserting it.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Insert the entry's key with the input value, and return a mutable reference to the value.
+    ///
+    /// * `value`: value to be associated with the key.
+    pub fn insert(self, value: V) -> &'a mut V {
+        if let Slot::Tombstone = self.slot {
+            *self.tombstones -= 1;
+        }
+        *self.length += 1;
+        *self.slot = Slot::Occupied(self.key, value);
+        match self.slot {
+            Slot::Occupied(_, value) => value,
+            _ => unreachable!("the slot was just filled")
+        }
+    }
+}
+
+
+// Iterator implementation for Iter struct
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.slots.by_ref() {
+            if let Slot::Occupied(key, value) = slot {
+                self.remaining -= 1;
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+// Iterator implementation for IterMut struct
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.slots.by_ref() {
+            if let Slot::Occupied(key, value) = slot {
+                self.remaining -= 1;
+                return Some((&*key, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+// Iterator implementation for IntoIter struct
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.slots.by_ref() {
+            if let Slot::Occupied(key, value) = slot {
+                self.remaining -= 1;
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+
+// Iterator implementation for ChainedIter struct
+impl<'a, K, V> Iterator for ChainedIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, value)) = self.chain.as_mut().and_then(Iterator::next) {
+                self.remaining -= 1;
+                return Some((key, value));
+            }
+            self.chain = Some(self.buckets.next()?.iter());
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ChainedIter<'a, K, V> {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_open_hash_map() -> OpenHashMap<String, u32> {
+    let mut map: OpenHashMap<String, u32> = OpenHashMap::new();
+    map.insert("one".to_string(), 1);
+    map.insert("two".to_string(), 2);
+    map.insert("three".to_string(), 3);
+    map
+}
+
+fn setup_chained_hash_map() -> ChainedHashMap<String, u32> {
+    let mut map: ChainedHashMap<String, u32> = ChainedHashMap::new();
+    map.insert("one".to_string(), 1);
+    map.insert("two".to_string(), 2);
+    map.insert("three".to_string(), 3);
+    map
+}
+
+/// BuildHasher whose hashers only produce the input number of distinct hashes, so keys collide
+/// far more than with a real hasher - for testing and benchmarking collision handling. With one
+/// distinct hash, every key collides.
+///
+/// * `0`: number of distinct hashes.
+#[cfg(test)]
+#[derive(Clone)]
+struct LimitedHashState(u64);
+
+#[cfg(test)]
+impl BuildHasher for LimitedHashState {
+    type Hasher = LimitedHasher;
+
+    fn build_hasher(&self) -> LimitedHasher {
+        LimitedHasher { state: 0, distinct_hashes: self.0 }
+    }
+}
+
+/// Hasher built by LimitedHashState: a simple multiplicative hash of the input bytes, reduced to
+/// one of distinct_hashes values.
+#[cfg(test)]
+struct LimitedHasher {
+    state: u64,
+    distinct_hashes: u64
+}
+
+#[cfg(test)]
+impl std::hash::Hasher for LimitedHasher {
+    fn finish(&self) -> u64 {
+        self.state % self.distinct_hashes
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state = self.state.wrapping_mul(31).wrapping_add(u64::from(*byte));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn open_hash_map_works() {
+        let mut map = setup_open_hash_map();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get("two"), Some(&2));
+        assert_eq!(map.get("four"), None);
+        assert!(map.contains_key("one"));
+
+        assert_eq!(map.insert("two".to_string(), 22), Some(2));
+        assert_eq!(map.len(), 3);
+        *map.get_mut("one").unwrap() += 10;
+        assert_eq!(map.get_key_value("one"), Some((&"one".to_string(), &11)));
+
+        assert_eq!(map.remove("three"), Some(3));
+        assert_eq!(map.remove("three"), None);
+        assert!(!map.contains_key("three"));
+        assert_eq!(map.len(), 2);
+
+        let mut pairs: Vec<(&String, &u32)> = map.iter().collect();
+        pairs.sort();
+        assert_eq!(pairs, vec![(&"one".to_string(), &11), (&"two".to_string(), &22)]);
+
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.get("one"), None);
+        assert_eq!(OpenHashMap::<u32, u32>::new().get(&1), None);
+    }
+
+    #[test]
+    fn open_hash_map_entry_api() {
+        let mut map = setup_open_hash_map();
+        *map.entry("one".to_string()).or_insert(0) += 1;
+        *map.entry("four".to_string()).or_insert(0) += 4;
+        assert_eq!(map.entry("five".to_string()).key(), "five");
+        map.entry("two".to_string()).and_modify(|value| *value *= 10).or_default();
+        map.entry("six".to_string()).and_modify(|value| *value *= 10).or_default();
+        assert_eq!(map.get("one"), Some(&2));
+        assert_eq!(map.get("two"), Some(&20));
+        assert_eq!(map.get("four"), Some(&4));
+        assert_eq!(map.get("five"), None);
+        assert_eq!(map.get("six"), Some(&0));
+
+        match map.entry("three".to_string()) {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.get(), &3);
+                assert_eq!(entry.insert(33), 3);
+                assert_eq!(entry.remove_entry(), ("three".to_string(), 33));
+            },
+            Entry::Vacant(_) => panic!("three is in the map")
+        }
+        match map.entry("three".to_string()) {
+            Entry::Occupied(_) => panic!("three was removed"),
+            Entry::Vacant(entry) => assert_eq!(entry.into_key(), "three")
+        }
+        assert_eq!(map.len(), 4);
+    }
+
+    #[test]
+    fn open_hash_map_resizes_and_reuses_tombstones() {
+        let mut map: OpenHashMap<u32, u32> = OpenHashMap::new();
+        assert_eq!(map.capacity(), 0);
+        for key in 0..1000 {
+            map.insert(key, key * 2);
+            assert!(map.len() <= map.capacity());
+        }
+        assert!(map.slots.len().is_power_of_two());
+        assert!((0..1000).all(|key| map.get(&key) == Some(&(key * 2))));
+
+        // removing leaves tombstones, which inserts reuse and resizing clears out
+        for key in (0..1000).step_by(2) {
+            assert_eq!(map.remove(&key), Some(key * 2));
+        }
+        assert_eq!(map.tombstones, 500);
+        let slots = map.slots.len();
+        for key in 1000..1400 {
+            map.insert(key, key * 2);
+        }
+        assert!(map.tombstones < 500);
+        assert!((map.length + map.tombstones) * MAX_LOAD_DENOMINATOR <= map.slots.len() * MAX_LOAD_NUMERATOR);
+        assert!(map.slots.len() <= slots * 2);
+        assert!((0..1000).all(|key| map.contains_key(&key) == (key % 2 == 1)));
+        assert!((1000..1400).all(|key| map.get(&key) == Some(&(key * 2))));
+        assert_eq!(map.len(), 900);
+
+        // churning a small map doesn't grow it without bound
+        let mut small: OpenHashMap<u32, u32> = OpenHashMap::with_capacity(4);
+        for key in 0..10_000 {
+            small.insert(key, key);
+            small.remove(&key);
+        }
+        assert!(small.is_empty());
+        assert_eq!(small.slots.len(), MIN_CAPACITY);
+    }
+
+    #[test]
+    fn open_hash_map_probes_past_collisions_and_tombstones() {
+        let mut map: OpenHashMap<u32, &str, LimitedHashState> = OpenHashMap::with_hasher(LimitedHashState(1));
+        for key in 0..5 {
+            map.insert(key, "value");
+        }
+        // every key hashes to the same slot, so removing one in the middle of the run must
+        // leave a Tombstone for lookups of the later keys to probe past
+        assert_eq!(map.remove(&2), Some("value"));
+        assert!((0..5).all(|key| map.contains_key(&key) == (key != 2)));
+        assert_eq!(map.insert(3, "updated"), Some("value"));
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.tombstones, 1);
+
+        // a new key takes the first Tombstone in its probe
+        map.insert(5, "value");
+        assert_eq!(map.tombstones, 0);
+        assert_eq!(map.get(&3), Some(&"updated"));
+        assert_eq!(map.len(), 5);
+    }
+
+    #[test]
+    fn open_hash_map_iterators_and_traits() {
+        let mut map: OpenHashMap<u32, u32> = (0..50).map(|key| (key, key)).collect();
+        assert_eq!(map.iter().len(), 50);
+        for (key, value) in &mut map {
+            *value += key;
+        }
+        let mut values: Vec<u32> = map.values().copied().collect();
+        values.sort();
+        assert_eq!(values, (0..50).map(|key| key * 2).collect::<Vec<_>>());
+        assert_eq!(map.keys().copied().max(), Some(49));
+
+        let clone = map.clone();
+        assert_eq!(clone, map);
+        map.insert(0, 1);
+        assert_ne!(clone, map);
+
+        let mut pairs: Vec<(u32, u32)> = map.into_iter().collect();
+        pairs.sort();
+        assert_eq!(pairs[0], (0, 1));
+        assert_eq!(pairs.len(), 50);
+
+        let small: OpenHashMap<&str, u32> = [("a", 1)].into_iter().collect();
+        assert_eq!(format!("{:?}", small), "{\"a\": 1}");
+    }
+
+    #[test]
+    fn chained_hash_map_works() {
+        let mut map = setup_chained_hash_map();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get("two"), Some(&2));
+        assert_eq!(map.get("four"), None);
+        assert_eq!(map.insert("two".to_string(), 22), Some(2));
+        *map.get_mut("one").unwrap() += 10;
+        assert_eq!(map.len(), 3);
+
+        assert_eq!(map.remove("three"), Some(3));
+        assert_eq!(map.remove("three"), None);
+        assert!(!map.contains_key("three"));
+        let mut pairs: Vec<(&String, &u32)> = map.iter().collect();
+        pairs.sort();
+        assert_eq!(pairs, vec![(&"one".to_string(), &11), (&"two".to_string(), &22)]);
+        assert_eq!(ChainedHashMap::<u32, u32>::new().remove(&1), None);
+
+        let small: ChainedHashMap<&str, u32> = [("a", 1), ("a", 2)].into_iter().collect();
+        assert_eq!(format!("{:?}", small), "{\"a\": 2}");
+    }
+
+    #[test]
+    fn chained_hash_map_grows_and_chains_collisions() {
+        let mut map: ChainedHashMap<u32, u32> = ChainedHashMap::new();
+        for key in 0..1000 {
+            map.insert(key, key * 2);
+            assert!(map.len() <= map.bucket_count());
+        }
+        assert!(map.bucket_count().is_power_of_two());
+        assert!((0..1000).all(|key| map.get(&key) == Some(&(key * 2))));
+        assert_eq!(map.iter().len(), 1000);
+        assert_eq!(map.values().map(|value| u64::from(*value)).sum::<u64>(), 999_000);
+
+        // every key collides, so they all share one chain, in insertion order
+        let mut colliding: ChainedHashMap<u32, u32, LimitedHashState> = ChainedHashMap::with_hasher(LimitedHashState(1));
+        colliding.extend((0..20).map(|key| (key, key)));
+        assert_eq!(colliding.longest_chain(), 20);
+        assert_eq!(colliding.remove(&10), Some(10));
+        assert_eq!(colliding.longest_chain(), 19);
+        assert!((0..20).all(|key| colliding.contains_key(&key) == (key != 10)));
+        assert_eq!(colliding.keys().copied().collect::<Vec<_>>(), (0..20).filter(|key| *key != 10).collect::<Vec<_>>());
+    }
+
+    /// Time inserting, looking up and removing keys in both hash maps (and std's HashMap), first
+    /// with a real hasher and then with keys crowded onto a few distinct hashes. Under heavy
+    /// collisions linear probing's runs merge into long clusters that every probe walks through,
+    /// while chaining only walks the keys that actually share a hash. Run with optimizations to
+    /// get meaningful numbers:
+    ///     cargo test --release hash_map_benchmark -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn hash_map_benchmark() {
+        const KEYS: u64 = 200_000;
+        const COLLIDING_KEYS: u64 = 20_000;
+        const DISTINCT_HASHES: u64 = 256;
+
+        fn time(name: &str, run: impl Fn() -> u64) {
+            let mut best = Duration::MAX;
+            for _ in 0..5 {
+                let start = Instant::now();
+                black_box(run());
+                best = best.min(start.elapsed());
+            }
+            println!("{:>32}: {:?}", name, best);
+        }
+
+        // insert every key, look each one up, then remove every key, returning a checksum
+        macro_rules! workload {
+            ($map:expr, $keys:expr) => {{
+                let mut map = $map;
+                for key in 0..$keys {
+                    map.insert(key, key);
+                }
+                let sum: u64 = (0..$keys).filter_map(|key| map.get(&key)).sum();
+                for key in 0..$keys {
+                    map.remove(&key);
+                }
+                sum
+            }};
+        }
+
+        time("OpenHashMap", || workload!(OpenHashMap::new(), KEYS));
+        time("ChainedHashMap", || workload!(ChainedHashMap::new(), KEYS));
+        time("std HashMap", || workload!(std::collections::HashMap::new(), KEYS));
+
+        let state = LimitedHashState(DISTINCT_HASHES);
+        time("OpenHashMap (colliding)", || workload!(OpenHashMap::with_hasher(state.clone()), COLLIDING_KEYS));
+        time("ChainedHashMap (colliding)", || workload!(ChainedHashMap::with_hasher(state.clone()), COLLIDING_KEYS));
+        time("std HashMap (colliding)", || workload!(std::collections::HashMap::with_hasher(state.clone()), COLLIDING_KEYS));
+    }
+}
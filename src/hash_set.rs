@@ -0,0 +1,477 @@
+//! Hash set built on the OpenHashMap struct from hash_map.rs: each value is stored as a key
+//!     mapped to (), so the set gets the map's hashing, probing and resizing for free.
+//!
+//! The set algebra methods (union, intersection, difference) return lazy iterators that walk
+//!     the sets as they are consumed, rather than building a new set up front.
+//!
+//! ```
+//! use rust_datastructures_algorithms::hash_set::HashSet;
+//!
+//! let evens: HashSet<u32> = (0..10).step_by(2).collect();
+//! let threes: HashSet<u32> = (0..10).step_by(3).collect();
+//!
+//! let mut both: Vec<&u32> = evens.intersection(&threes).collect();
+//! both.sort();
+//! assert_eq!(both, vec![&0, &6]);
+//! assert_eq!(evens.union(&threes).count(), 7);
+//! assert!(!evens.is_subset(&threes));
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{ BuildHasher, Hash };
+use crate::hash_map::{ self, OpenHashMap };
+
+
+/// A hash set struct: an OpenHashMap struct whose keys are the set's values.
+///
+/// * `map`: OpenHashMap holding each value as a key, mapped to ().
+pub struct HashSet<T, S = RandomState> {
+    map: OpenHashMap<T, (), S>
+}
+
+
+/// Iterator yielding references to the values in a HashSet struct, in arbitrary order. Created
+/// by HashSet::iter.
+///
+/// * `keys`: Iterator over the underlying map's key-value pairs.
+pub struct Iter<'a, T> {
+    keys: hash_map::Iter<'a, T, ()>
+}
+
+
+/// Iterator yielding the values of a HashSet struct by value, in arbitrary order. Created by the
+/// HashSet's into_iter.
+///
+/// * `keys`: Iterator over the underlying map's key-value pairs.
+pub struct IntoIter<T> {
+    keys: hash_map::IntoIter<T, ()>
+}
+
+
+/// Lazy iterator yielding references to the values in either of two HashSet structs, each value
+/// once. Created by HashSet::union.
+///
+/// * `first`: Iterator over every value in the first set.
+/// * `rest`: Iterator over the values in the second set that aren't in the first.
+pub struct Union<'a, T, S> {
+    first: Iter<'a, T>,
+    rest: Difference<'a, T, S>
+}
+
+
+/// Lazy iterator yielding references to the values in both of two HashSet structs. Created by
+/// HashSet::intersection.
+///
+/// * `smaller`: Iterator over the smaller of the two sets, so as few values as possible are
+///         looked up.
+/// * `larger`: The larger of the two sets, each value is looked up in.
+pub struct Intersection<'a, T, S> {
+    smaller: Iter<'a, T>,
+    larger: &'a HashSet<T, S>
+}
+
+
+/// Lazy iterator yielding references to the values in one HashSet struct that aren't in another.
+/// Created by HashSet::difference.
+///
+/// * `values`: Iterator over the set values are taken from.
+/// * `excluded`: The set whose values are skipped.
+pub struct Difference<'a, T, S> {
+    values: Iter<'a, T>,
+    excluded: &'a HashSet<T, S>
+}
+
+
+// Method implementations for HashSet struct
+impl<T> HashSet<T, RandomState> where T: Hash + Eq {
+
+    /// Return a new, empty HashSet struct hashing with a randomly seeded RandomState. Nothing is
+    /// allocated until the first insert.
+    pub fn new() -> HashSet<T, RandomState> {
+        HashSet { map: OpenHashMap::new() }
+    }
+
+    /// Return a new, empty HashSet struct with room for at least the input number of values
+    /// before it needs to grow.
+    ///
+    /// * `capacity`: number of values to allocate room for.
+    pub fn with_capacity(capacity: usize) -> HashSet<T, RandomState> {
+        HashSet { map: OpenHashMap::with_capacity(capacity) }
+    }
+}
+
+impl<T, S> HashSet<T, S> where T: Hash + Eq, S: BuildHasher {
+
+    /// Return a new, empty HashSet struct hashing values with hashers built by the input.
+    ///
+    /// * `hash_builder`: builds the hasher for each value.
+    pub fn with_hasher(hash_builder: S) -> HashSet<T, S> {
+        HashSet { map: OpenHashMap::with_hasher(hash_builder) }
+    }
+
+    /// Return the number of values in this HashSet.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return whether this HashSet holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Add a value to the set. Return whether it was added - false if an equal value was already
+    /// in the set, which is left in place.
+    ///
+    /// * `value`: value to be added.
+    pub fn insert(&mut self, value: T) -> bool {
+        match self.map.entry(value) {
+            hash_map::Entry::Occupied(_) => false,
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(());
+                true
+            }
+        }
+    }
+
+    /// Return whether the input value is in the set.
+    ///
+    /// * `value`: value to look for - any borrowed form of the set's value type.
+    pub fn contains<Q>(&self, value: &Q) -> bool where T: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.map.contains_key(value)
+    }
+
+    /// Get a reference to the value in the set equal to the input (if there is one).
+    ///
+    /// * `value`: value to look for - any borrowed form of the set's value type.
+    pub fn get<Q>(&self, value: &Q) -> Option<&T> where T: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.map.get_key_value(value).map(|(stored, _)| stored)
+    }
+
+    /// Remove the input value from the set. Return whether it was in the set.
+    ///
+    /// * `value`: value to be removed - any borrowed form of the set's value type.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool where T: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.map.remove(value).is_some()
+    }
+
+    /// Remove the value equal to the input from the set and return it (if it was in the set).
+    ///
+    /// * `value`: value to be removed - any borrowed form of the set's value type.
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T> where T: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.map.remove_entry(value).map(|(stored, _)| stored)
+    }
+
+    /// Remove every value, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Return an iterator of references to the values in this HashSet, in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { keys: self.map.iter() }
+    }
+
+    /// Return a lazy iterator of references to the values in this set or the other, each value
+    /// once: all of this set's values, then the other's values that aren't in this set.
+    ///
+    /// * `other`: set to take the union with.
+    pub fn union<'a>(&'a self, other: &'a HashSet<T, S>) -> Union<'a, T, S> {
+        Union {
+            first: self.iter(),
+            rest: other.difference(self)
+        }
+    }
+
+    /// Return a lazy iterator of references to the values in both this set and the other. The
+    /// smaller set is walked and each of its values looked up in the larger, so this takes time
+    /// proportional to the smaller set.
+    ///
+    /// * `other`: set to take the intersection with.
+    pub fn intersection<'a>(&'a self, other: &'a HashSet<T, S>) -> Intersection<'a, T, S> {
+        let (smaller, larger) = if self.len() <= other.len() { (self, other) } else { (other, self) };
+        Intersection {
+            smaller: smaller.iter(),
+            larger
+        }
+    }
+
+    /// Return a lazy iterator of references to the values in this set that aren't in the other.
+    ///
+    /// * `other`: set whose values are left out.
+    pub fn difference<'a>(&'a self, other: &'a HashSet<T, S>) -> Difference<'a, T, S> {
+        Difference {
+            values: self.iter(),
+            excluded: other
+        }
+    }
+
+    /// Return whether every value in this set is also in the other.
+    ///
+    /// * `other`: set that may contain this one.
+    pub fn is_subset(&self, other: &HashSet<T, S>) -> bool {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
+
+    /// Return whether every value in the other set is also in this one.
+    ///
+    /// * `other`: set that may be contained in this one.
+    pub fn is_superset(&self, other: &HashSet<T, S>) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Return whether this set and the other have no values in common.
+    ///
+    /// * `other`: set to compare with.
+    pub fn is_disjoint(&self, other: &HashSet<T, S>) -> bool {
+        self.intersection(other).next().is_none()
+    }
+}
+
+impl<T> Default for HashSet<T, RandomState> where T: Hash + Eq {
+    fn default() -> Self {
+        HashSet::new()
+    }
+}
+
+impl<T, S> Clone for HashSet<T, S> where T: Clone, S: Clone {
+    fn clone(&self) -> Self {
+        HashSet { map: self.map.clone() }
+    }
+}
+
+// Sets are equal if they hold the same values, whatever order their tables hold them in.
+impl<T, S> PartialEq for HashSet<T, S> where T: Hash + Eq, S: BuildHasher {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl<T, S> Eq for HashSet<T, S> where T: Hash + Eq, S: BuildHasher {}
+
+impl<T, S> fmt::Debug for HashSet<T, S> where T: Hash + Eq + fmt::Debug, S: BuildHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S> Extend<T> for HashSet<T, S> where T: Hash + Eq, S: BuildHasher {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|value| (value, ())));
+    }
+}
+
+impl<T> FromIterator<T> for HashSet<T, RandomState> where T: Hash + Eq {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = HashSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> IntoIterator for HashSet<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { keys: self.map.into_iter() }
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a HashSet<T, S> where T: Hash + Eq, S: BuildHasher {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+// Iterator implementation for Iter struct
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.keys.next().map(|(value, _)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+// Iterator implementation for IntoIter struct
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.keys.next().map(|(value, _)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+// Iterator implementation for Union struct
+impl<'a, T, S> Iterator for Union<'a, T, S> where T: Hash + Eq, S: BuildHasher {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.rest.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (first, _) = self.first.size_hint();
+        let (_, rest) = self.rest.size_hint();
+        (first, rest.map(|rest| first + rest))
+    }
+}
+
+// Iterator implementation for Intersection struct
+impl<'a, T, S> Iterator for Intersection<'a, T, S> where T: Hash + Eq, S: BuildHasher {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let larger = self.larger;
+        self.smaller.by_ref().find(|value| larger.contains(*value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.smaller.size_hint().1)
+    }
+}
+
+// Iterator implementation for Difference struct
+impl<'a, T, S> Iterator for Difference<'a, T, S> where T: Hash + Eq, S: BuildHasher {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let excluded = self.excluded;
+        self.values.by_ref().find(|value| !excluded.contains(*value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.values.size_hint().1)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_hash_set() -> HashSet<u32> {
+    let mut set: HashSet<u32> = HashSet::new();
+    set.insert(1);
+    set.insert(2);
+    set.insert(3);
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collect and sort an iterator's values, so sets can be compared in a fixed order.
+    fn sorted<'a>(values: impl Iterator<Item = &'a u32>) -> Vec<u32> {
+        let mut values: Vec<u32> = values.copied().collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn hash_set_works() {
+        let mut set = setup_hash_set();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&2));
+        assert!(!set.insert(2));
+        assert!(set.insert(4));
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.get(&4), Some(&4));
+
+        assert!(set.remove(&1));
+        assert!(!set.remove(&1));
+        assert_eq!(set.take(&2), Some(2));
+        assert_eq!(set.take(&2), None);
+        assert_eq!(sorted(set.iter()), vec![3, 4]);
+
+        let mut words: HashSet<String> = HashSet::new();
+        words.insert("cat".to_string());
+        assert!(words.contains("cat"));
+        assert!(words.remove("cat"));
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn hash_set_algebra() {
+        let set = setup_hash_set();
+        let other: HashSet<u32> = [2, 3, 4, 5].into_iter().collect();
+
+        assert_eq!(sorted(set.union(&other)), vec![1, 2, 3, 4, 5]);
+        assert_eq!(sorted(other.union(&set)), vec![1, 2, 3, 4, 5]);
+        assert_eq!(sorted(set.intersection(&other)), vec![2, 3]);
+        assert_eq!(sorted(other.intersection(&set)), vec![2, 3]);
+        assert_eq!(sorted(set.difference(&other)), vec![1]);
+        assert_eq!(sorted(other.difference(&set)), vec![4, 5]);
+
+        let empty: HashSet<u32> = HashSet::new();
+        assert_eq!(set.union(&empty).count(), 3);
+        assert_eq!(set.intersection(&empty).count(), 0);
+        assert_eq!(sorted(set.difference(&empty)), vec![1, 2, 3]);
+
+        let subset: HashSet<u32> = [2, 3].into_iter().collect();
+        assert!(subset.is_subset(&set));
+        assert!(subset.is_subset(&other));
+        assert!(!set.is_subset(&other));
+        assert!(empty.is_subset(&set));
+        assert!(set.is_superset(&subset));
+        assert!(set.is_subset(&set));
+        assert!(!set.is_disjoint(&other));
+        assert!(set.is_disjoint(&[7, 8].into_iter().collect()));
+    }
+
+    #[test]
+    fn hash_set_algebra_is_lazy() {
+        let set: HashSet<u32> = (0..1000).collect();
+        let other: HashSet<u32> = (500..1500).collect();
+
+        // nothing is computed until the iterator is consumed, and only as far as it is
+        let mut intersection = set.intersection(&other);
+        assert!(intersection.next().is_some_and(|value| (500..1000).contains(value)));
+        assert_eq!(intersection.count(), 499);
+
+        let mut union = set.union(&other);
+        assert_eq!(union.size_hint(), (1000, Some(2000)));
+        assert_eq!(union.by_ref().take(1000).count(), 1000);
+        assert!(union.all(|value| (1000..1500).contains(value)));
+
+        let collected: HashSet<u32> = set.difference(&other).copied().collect();
+        assert_eq!(collected, (0..500).collect());
+    }
+
+    #[test]
+    fn hash_set_traits() {
+        let mut set = setup_hash_set();
+        let clone = set.clone();
+        assert_eq!(clone, set);
+        set.extend([3, 4]);
+        assert_ne!(clone, set);
+        assert_eq!(set.len(), 4);
+        assert_eq!((&set).into_iter().len(), 4);
+
+        let mut values: Vec<u32> = set.into_iter().collect();
+        values.sort();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+
+        let single: HashSet<&str> = ["a"].into_iter().collect();
+        assert_eq!(format!("{:?}", single), "{\"a\"}");
+    }
+}
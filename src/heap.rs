@@ -0,0 +1,702 @@
+//! Binary heap implementations: a complete binary tree stored level by level in a Vec, where the
+//!     children of the value at index i are at indices 2i + 1 and 2i + 2. Every value is ordered
+//!     before its children, so the first value is always the one to pop next - the minimum for
+//!     MinHeap, the maximum for MaxHeap, and the first in comparator order for BinaryHeapBy.
+//!
+//! ```
+//! use rust_datastructures_algorithms::heap::MinHeap;
+//!
+//! let mut heap = MinHeap::from_vec(vec![5, 1, 8, 3]);
+//! heap.push(2);
+//! assert_eq!(heap.peek(), Some(&1));
+//! assert_eq!(heap.pop(), Some(1));
+//! assert_eq!(heap.pop(), Some(2));
+//! assert_eq!(heap.into_sorted_vec(), vec![3, 5, 8]);
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+
+/// A binary min-heap struct: pop always returns the smallest value.
+///
+/// * `values`: Vec holding the heap's complete binary tree, level by level - the root (smallest
+///         value) is at index 0.
+pub struct MinHeap<T: Ord> {
+    values: Vec<T>
+}
+
+
+/// A binary max-heap struct: pop always returns the largest value.
+///
+/// * `values`: Vec holding the heap's complete binary tree, level by level - the root (largest
+///         value) is at index 0.
+pub struct MaxHeap<T: Ord> {
+    values: Vec<T>
+}
+
+
+/// A binary heap struct ordered by a comparator closure instead of the Ord trait: pop always
+/// returns the value the comparator orders first. Values that don't implement Ord (like f64), or
+/// structs prioritized by one field, can be stored without a wrapper type.
+///
+/// ```
+/// use rust_datastructures_algorithms::heap::BinaryHeapBy;
+///
+/// struct Task { name: &'static str, priority: f64 }
+///
+/// // highest priority first
+/// let mut tasks = BinaryHeapBy::new(|a: &Task, b: &Task| b.priority.total_cmp(&a.priority));
+/// tasks.push(Task { name: "write", priority: 0.5 });
+/// tasks.push(Task { name: "deploy", priority: 0.9 });
+/// tasks.push(Task { name: "test", priority: 0.7 });
+/// assert_eq!(tasks.pop().map(|task| task.name), Some("deploy"));
+/// assert_eq!(tasks.pop().map(|task| task.name), Some("test"));
+/// ```
+///
+/// * `values`: Vec holding the heap's complete binary tree, level by level - the root (first
+///         value in comparator order) is at index 0.
+/// * `compare`: Comparator closure returning how its first argument is ordered relative to its
+///         second; values ordered Less are popped first. It must be a total order (consistent,
+///         like Ord::cmp) for the heap to stay valid.
+pub struct BinaryHeapBy<T, F: Fn(&T, &T) -> Ordering> {
+    values: Vec<T>,
+    compare: F
+}
+
+
+/// A binary min-heap of keys ordered by priority, that can look up any key's position in the
+/// heap - so a key's priority can be changed (e.g. a shorter distance found in Dijkstra's
+/// algorithm) in O(log n), rather than pushing a duplicate entry.
+///
+/// ```
+/// use rust_datastructures_algorithms::heap::IndexedPriorityQueue;
+///
+/// let mut queue = IndexedPriorityQueue::new();
+/// queue.push('a', 10);
+/// queue.push('b', 5);
+/// queue.decrease_key(&'a', 1);
+/// assert_eq!(queue.pop_min(), Some(('a', 1)));
+/// assert_eq!(queue.pop_min(), Some(('b', 5)));
+/// ```
+///
+/// * `entries`: Vec holding the heap's complete binary tree of (key, priority) pairs, level by
+///         level - the root (smallest priority) is at index 0.
+/// * `positions`: Map from each key to the index of its entry. Updated on every swap, so it is
+///         always in sync with entries.
+pub struct IndexedPriorityQueue<K: Hash + Eq + Clone, P: Ord> {
+    entries: Vec<(K, P)>,
+    positions: HashMap<K, usize>
+}
+
+
+// Method implementations for MinHeap struct
+impl<T> MinHeap<T> where T: Ord {
+
+    /// Return a new, empty MinHeap struct
+    pub fn new() -> MinHeap<T> {
+        MinHeap {
+            values: Vec::new()
+        }
+    }
+
+    /// Return a new MinHeap holding the values of the input Vec, rearranged in place in O(n) time.
+    ///
+    /// Heapifies bottom-up: every value with children is sifted down, starting from the last one,
+    ///     so each sift works on two subtrees that are already heaps. Most values are near the
+    ///     bottom and sift only a few levels, which is why this beats n pushes (O(n log n)).
+    ///
+    /// * `values`: Vec of values to be stored in the heap, in any order.
+    pub fn from_vec(mut values: Vec<T>) -> MinHeap<T> {
+        heapify(&mut values, &mut T::cmp);
+        MinHeap { values }
+    }
+
+    /// Return the number of values in this MinHeap.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return whether this MinHeap holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Add a value to the heap in O(log n): place it at the end of the bottom level, then sift it
+    /// up past any larger parents.
+    ///
+    /// * `value`: value to be added.
+    pub fn push(&mut self, value: T) {
+        self.values.push(value);
+        let last = self.values.len() - 1;
+        sift_up(&mut self.values, last, &mut T::cmp);
+    }
+
+    /// Remove the smallest value and return it (unless the heap is empty), in O(log n): move the
+    /// last value to the root, then sift it down past any smaller children.
+    pub fn pop(&mut self) -> Option<T> {
+        pop_root(&mut self.values, &mut T::cmp)
+    }
+
+    /// Get a reference to the smallest value (if the heap is not empty).
+    pub fn peek(&self) -> Option<&T> {
+        self.values.first()
+    }
+
+    /// Consume this MinHeap and return its values in ascending order, by popping every value.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.values.len());
+        while let Some(value) = self.pop() {
+            sorted.push(value);
+        }
+        sorted
+    }
+}
+
+impl<T> Default for MinHeap<T> where T: Ord {
+    fn default() -> Self {
+        MinHeap::new()
+    }
+}
+
+
+// Method implementations for MaxHeap struct
+impl<T> MaxHeap<T> where T: Ord {
+
+    /// Return a new, empty MaxHeap struct
+    pub fn new() -> MaxHeap<T> {
+        MaxHeap {
+            values: Vec::new()
+        }
+    }
+
+    /// Return a new MaxHeap holding the values of the input Vec, rearranged in place in O(n) time
+    /// (see MinHeap::from_vec).
+    ///
+    /// * `values`: Vec of values to be stored in the heap, in any order.
+    pub fn from_vec(mut values: Vec<T>) -> MaxHeap<T> {
+        heapify(&mut values, &mut reverse_cmp);
+        MaxHeap { values }
+    }
+
+    /// Return the number of values in this MaxHeap.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return whether this MaxHeap holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Add a value to the heap in O(log n) (see MinHeap::push).
+    ///
+    /// * `value`: value to be added.
+    pub fn push(&mut self, value: T) {
+        self.values.push(value);
+        let last = self.values.len() - 1;
+        sift_up(&mut self.values, last, &mut reverse_cmp);
+    }
+
+    /// Remove the largest value and return it (unless the heap is empty), in O(log n).
+    pub fn pop(&mut self) -> Option<T> {
+        pop_root(&mut self.values, &mut reverse_cmp)
+    }
+
+    /// Get a reference to the largest value (if the heap is not empty).
+    pub fn peek(&self) -> Option<&T> {
+        self.values.first()
+    }
+
+    /// Consume this MaxHeap and return its values in ascending order, like MinHeap's. Values are
+    /// popped largest first, so they are placed from the back of the Vec.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.values.len());
+        while let Some(value) = self.pop() {
+            sorted.push(value);
+        }
+        sorted.reverse();
+        sorted
+    }
+}
+
+impl<T> Default for MaxHeap<T> where T: Ord {
+    fn default() -> Self {
+        MaxHeap::new()
+    }
+}
+
+
+// Method implementations for BinaryHeapBy struct
+impl<T, F> BinaryHeapBy<T, F> where F: Fn(&T, &T) -> Ordering {
+
+    /// Return a new, empty BinaryHeapBy struct ordered by the input comparator.
+    ///
+    /// * `compare`: comparator closure, e.g. `|a: &f64, b: &f64| a.total_cmp(b)` to pop the
+    ///         smallest float first, or `|a: &Task, b: &Task| a.deadline.cmp(&b.deadline)` to pop
+    ///         by one field.
+    pub fn new(compare: F) -> BinaryHeapBy<T, F> {
+        BinaryHeapBy {
+            values: Vec::new(),
+            compare
+        }
+    }
+
+    /// Return a new BinaryHeapBy holding the values of the input Vec, rearranged in place in O(n)
+    /// time (see MinHeap::from_vec).
+    ///
+    /// * `values`: Vec of values to be stored in the heap, in any order.
+    /// * `compare`: comparator closure (see new).
+    pub fn from_vec(mut values: Vec<T>, compare: F) -> BinaryHeapBy<T, F> {
+        heapify(&mut values, &mut &compare);
+        BinaryHeapBy { values, compare }
+    }
+
+    /// Return the number of values in this BinaryHeapBy.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return whether this BinaryHeapBy holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Add a value to the heap in O(log n) (see MinHeap::push).
+    ///
+    /// * `value`: value to be added.
+    pub fn push(&mut self, value: T) {
+        self.values.push(value);
+        let last = self.values.len() - 1;
+        sift_up(&mut self.values, last, &mut &self.compare);
+    }
+
+    /// Remove the first value in comparator order and return it (unless the heap is empty), in
+    /// O(log n).
+    pub fn pop(&mut self) -> Option<T> {
+        pop_root(&mut self.values, &mut &self.compare)
+    }
+
+    /// Get a reference to the first value in comparator order (if the heap is not empty).
+    pub fn peek(&self) -> Option<&T> {
+        self.values.first()
+    }
+
+    /// Consume this BinaryHeapBy and return its values in comparator order, by popping every
+    /// value.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.values.len());
+        while let Some(value) = self.pop() {
+            sorted.push(value);
+        }
+        sorted
+    }
+}
+
+
+// Method implementations for IndexedPriorityQueue struct
+impl<K, P> IndexedPriorityQueue<K, P> where K: Hash + Eq + Clone, P: Ord {
+
+    /// Return a new, empty IndexedPriorityQueue struct
+    pub fn new() -> IndexedPriorityQueue<K, P> {
+        IndexedPriorityQueue {
+            entries: Vec::new(),
+            positions: HashMap::new()
+        }
+    }
+
+    /// Return the number of keys in this IndexedPriorityQueue.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return whether this IndexedPriorityQueue holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return whether the input key is in the queue.
+    ///
+    /// * `key`: key to look up.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// Return a reference to the priority of the input key, if it is in the queue.
+    ///
+    /// * `key`: key to look up.
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|&index| &self.entries[index].1)
+    }
+
+    /// Add a key with the input priority, in O(log n). If the key is already in the queue, its
+    /// priority is changed instead (see change_priority) and the old priority is returned.
+    ///
+    /// * `key`: key to be added.
+    /// * `priority`: priority of the key - smaller priorities are popped first.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if self.positions.contains_key(&key) {
+            return self.change_priority(&key, priority);
+        }
+        self.positions.insert(key.clone(), self.entries.len());
+        self.entries.push((key, priority));
+        self.sift_up(self.entries.len() - 1);
+        None
+    }
+
+    /// Get references to the key with the smallest priority, and its priority (if the queue is
+    /// not empty).
+    pub fn peek_min(&self) -> Option<(&K, &P)> {
+        self.entries.first().map(|(key, priority)| (key, priority))
+    }
+
+    /// Remove the key with the smallest priority and return it with its priority (unless the
+    /// queue is empty), in O(log n).
+    pub fn pop_min(&mut self) -> Option<(K, P)> {
+        self.remove_at(0)
+    }
+
+    /// Remove the input key from the queue wherever it is, and return its priority, in O(log n).
+    ///
+    /// * `key`: key to be removed.
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let index = *self.positions.get(key)?;
+        self.remove_at(index).map(|(_, priority)| priority)
+    }
+
+    /// Set the priority of a key that is already in the queue, in O(log n), and return its old
+    /// priority (or None if the key isn't in the queue). The entry is sifted up or down,
+    /// depending on whether the priority decreased or increased.
+    ///
+    /// * `key`: key whose priority changes.
+    /// * `priority`: new priority of the key.
+    pub fn change_priority(&mut self, key: &K, priority: P) -> Option<P> {
+        let index = *self.positions.get(key)?;
+        let old_priority = std::mem::replace(&mut self.entries[index].1, priority);
+        match self.entries[index].1.cmp(&old_priority) {
+            Ordering::Less => self.sift_up(index),
+            Ordering::Greater => self.sift_down(index),
+            Ordering::Equal => {}
+        }
+        Some(old_priority)
+    }
+
+    /// Lower the priority of a key that is already in the queue, in O(log n). The priority is only
+    /// changed if the new one is smaller - return whether it was.
+    ///
+    /// * `key`: key whose priority decreases.
+    /// * `priority`: new priority of the key.
+    pub fn decrease_key(&mut self, key: &K, priority: P) -> bool {
+        match self.positions.get(key) {
+            Some(&index) if priority < self.entries[index].1 => {
+                self.entries[index].1 = priority;
+                self.sift_up(index);
+                true
+            },
+            _ => false
+        }
+    }
+
+    /// Remove the entry at the input index: swap it with the last entry, pop it, and sift the
+    /// moved entry whichever way it needs to go.
+    ///
+    /// * `index`: index of the entry to be removed.
+    fn remove_at(&mut self, index: usize) -> Option<(K, P)> {
+        if index >= self.entries.len() {
+            return None;
+        }
+        let last = self.entries.len() - 1;
+        self.swap_entries(index, last);
+        let (key, priority) = self.entries.pop()?;
+        self.positions.remove(&key);
+        if index < self.entries.len() {
+            self.sift_down(index);
+            self.sift_up(index);
+        }
+        Some((key, priority))
+    }
+
+    /// Move the entry at the input index up the heap while its priority is smaller than its
+    /// parent's (see sift_up).
+    ///
+    /// * `index`: index of the entry to sift up.
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.entries[index].1 >= self.entries[parent].1 {
+                break;
+            }
+            self.swap_entries(index, parent);
+            index = parent;
+        }
+    }
+
+    /// Move the entry at the input index down the heap while a child has a smaller priority (see
+    /// sift_down).
+    ///
+    /// * `index`: index of the entry to sift down.
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            if left >= self.entries.len() {
+                break;
+            }
+            let right = left + 1;
+            let first_child = if right < self.entries.len() && self.entries[right].1 < self.entries[left].1 {
+                right
+            } else {
+                left
+            };
+            if self.entries[first_child].1 >= self.entries[index].1 {
+                break;
+            }
+            self.swap_entries(index, first_child);
+            index = first_child;
+        }
+    }
+
+    /// Swap two entries, and update the positions of both keys.
+    ///
+    /// * `i`: index of the first entry.
+    /// * `j`: index of the second entry.
+    fn swap_entries(&mut self, i: usize, j: usize) {
+        self.entries.swap(i, j);
+        for index in [i, j] {
+            if let Some(position) = self.positions.get_mut(&self.entries[index].0) {
+                *position = index;
+            }
+        }
+    }
+}
+
+impl<K, P> Default for IndexedPriorityQueue<K, P> where K: Hash + Eq + Clone, P: Ord {
+    fn default() -> Self {
+        IndexedPriorityQueue::new()
+    }
+}
+
+
+/// Compare two values in reverse of their Ord order, so a heap built with it pops the largest
+/// value first.
+fn reverse_cmp<T: Ord>(a: &T, b: &T) -> Ordering {
+    b.cmp(a)
+}
+
+/// Rearrange the input values into a heap in place, in O(n) (see MinHeap::from_vec).
+///
+/// * `values`: values to be rearranged.
+/// * `compare`: comparator - a value ordered Less than another is closer to the root.
+fn heapify<T, F: FnMut(&T, &T) -> Ordering>(values: &mut [T], compare: &mut F) {
+    for index in (0..values.len() / 2).rev() {
+        sift_down(values, index, compare);
+    }
+}
+
+/// Move the value at the input index up the heap, swapping it with its parent while it is ordered
+/// before the parent.
+///
+/// * `values`: heap, valid except at the input index.
+/// * `index`: index of the value to sift up.
+/// * `compare`: comparator - a value ordered Less than another is closer to the root.
+fn sift_up<T, F: FnMut(&T, &T) -> Ordering>(values: &mut [T], mut index: usize, compare: &mut F) {
+    while index > 0 {
+        let parent = (index - 1) / 2;
+        if compare(&values[index], &values[parent]) != Ordering::Less {
+            break;
+        }
+        values.swap(index, parent);
+        index = parent;
+    }
+}
+
+/// Move the value at the input index down the heap, swapping it with its first-ordered child
+/// while that child is ordered before it.
+///
+/// * `values`: heap, valid except at the input index.
+/// * `index`: index of the value to sift down.
+/// * `compare`: comparator - a value ordered Less than another is closer to the root.
+fn sift_down<T, F: FnMut(&T, &T) -> Ordering>(values: &mut [T], mut index: usize, compare: &mut F) {
+    loop {
+        let left = 2 * index + 1;
+        if left >= values.len() {
+            break;
+        }
+        let right = left + 1;
+        let first_child = if right < values.len() && compare(&values[right], &values[left]) == Ordering::Less {
+            right
+        } else {
+            left
+        };
+        if compare(&values[first_child], &values[index]) != Ordering::Less {
+            break;
+        }
+        values.swap(index, first_child);
+        index = first_child;
+    }
+}
+
+/// Remove the root value from the heap and return it (unless the heap is empty), moving the last
+/// value to the root and sifting it down.
+///
+/// * `values`: heap to remove the root from.
+/// * `compare`: comparator - a value ordered Less than another is closer to the root.
+fn pop_root<T, F: FnMut(&T, &T) -> Ordering>(values: &mut Vec<T>, compare: &mut F) -> Option<T> {
+    if values.is_empty() {
+        return None;
+    }
+    let root = values.swap_remove(0);
+    sift_down(values, 0, compare);
+    Some(root)
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_min_heap() -> MinHeap<u32> {
+    let mut heap: MinHeap<u32> = MinHeap::new();
+    for value in [5, 3, 8, 1, 9, 2] {
+        heap.push(value);
+    }
+    heap
+}
+
+/// Return whether every value in the slice is ordered no later than its children.
+#[cfg(test)]
+fn is_heap<T, F: FnMut(&T, &T) -> Ordering>(values: &[T], mut compare: F) -> bool {
+    (1..values.len()).all(|index| compare(&values[(index - 1) / 2], &values[index]) != Ordering::Greater)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_heap_works() {
+        let mut heap = setup_min_heap();
+        assert_eq!(heap.len(), 6);
+        assert!(is_heap(&heap.values, u32::cmp));
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(3));
+        assert!(is_heap(&heap.values, u32::cmp));
+
+        heap.push(0);
+        assert_eq!(heap.peek(), Some(&0));
+        assert_eq!(heap.into_sorted_vec(), vec![0, 5, 8, 9]);
+        assert_eq!(MinHeap::<u32>::new().pop(), None);
+    }
+
+    #[test]
+    fn min_heap_can_be_built_from_vec() {
+        let values: Vec<u32> = (0..100).map(|value| (value * 37) % 101).collect();
+        let heap = MinHeap::from_vec(values.clone());
+        assert!(is_heap(&heap.values, u32::cmp));
+        assert_eq!(heap.len(), 100);
+
+        let mut sorted = values;
+        sorted.sort();
+        assert_eq!(heap.into_sorted_vec(), sorted);
+        assert!(MinHeap::<u32>::from_vec(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn max_heap_works() {
+        let mut heap: MaxHeap<u32> = MaxHeap::new();
+        for value in [5, 3, 8, 1, 9, 2] {
+            heap.push(value);
+        }
+        assert!(is_heap(&heap.values, reverse_cmp));
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(heap.pop(), Some(9));
+        assert_eq!(heap.pop(), Some(8));
+        assert_eq!(heap.len(), 4);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 5]);
+
+        let heap = MaxHeap::from_vec(vec![4, 10, 3, 5, 1]);
+        assert!(is_heap(&heap.values, reverse_cmp));
+        assert_eq!(heap.into_sorted_vec(), vec![1, 3, 4, 5, 10]);
+        assert_eq!(MaxHeap::<u32>::new().pop(), None);
+    }
+
+    #[test]
+    fn binary_heap_by_works_with_floats_and_fields() {
+        let mut heap = BinaryHeapBy::new(|a: &f64, b: &f64| a.total_cmp(b));
+        for value in [2.5, -1.0, 3.75, 0.0] {
+            heap.push(value);
+        }
+        assert_eq!(heap.peek(), Some(&-1.0));
+        assert_eq!(heap.into_sorted_vec(), vec![-1.0, 0.0, 2.5, 3.75]);
+
+        let edges = vec![("ab", 7), ("bc", 2), ("ac", 5), ("cd", 2)];
+        let by_weight = BinaryHeapBy::from_vec(edges, |a: &(&str, u32), b: &(&str, u32)| a.1.cmp(&b.1));
+        assert!(is_heap(&by_weight.values, &by_weight.compare));
+        assert_eq!(by_weight.len(), 4);
+        let weights: Vec<u32> = by_weight.into_sorted_vec().into_iter().map(|edge| edge.1).collect();
+        assert_eq!(weights, vec![2, 2, 5, 7]);
+    }
+
+    #[test]
+    fn indexed_priority_queue_works() {
+        let mut queue: IndexedPriorityQueue<&str, u32> = IndexedPriorityQueue::new();
+        for (key, priority) in [("a", 7), ("b", 3), ("c", 9), ("d", 1), ("e", 5)] {
+            assert_eq!(queue.push(key, priority), None);
+        }
+        assert_eq!(queue.len(), 5);
+        assert!(queue.contains_key(&"c"));
+        assert_eq!(queue.priority(&"c"), Some(&9));
+        assert_eq!(queue.peek_min(), Some((&"d", &1)));
+
+        // pushing an existing key changes its priority
+        assert_eq!(queue.push("d", 8), Some(1));
+        assert_eq!(queue.len(), 5);
+        assert!(queue.decrease_key(&"c", 2));
+        assert!(!queue.decrease_key(&"c", 4));
+        assert!(!queue.decrease_key(&"z", 0));
+        assert_eq!(queue.change_priority(&"b", 10), Some(3));
+        assert_eq!(queue.change_priority(&"z", 10), None);
+        assert_eq!(queue.remove(&"e"), Some(5));
+        assert!(!queue.contains_key(&"e"));
+
+        let mut popped = Vec::new();
+        while let Some(entry) = queue.pop_min() {
+            for (index, (key, _)) in queue.entries.iter().enumerate() {
+                assert_eq!(queue.positions[key], index);
+            }
+            popped.push(entry);
+        }
+        assert_eq!(popped, vec![("c", 2), ("a", 7), ("d", 8), ("b", 10)]);
+        assert!(queue.positions.is_empty());
+    }
+
+    #[test]
+    fn indexed_priority_queue_runs_dijkstra() {
+        // adjacency list: (to, weight)
+        let graph: Vec<Vec<(usize, u32)>> = vec![
+            vec![(1, 4), (2, 1)],
+            vec![(3, 1)],
+            vec![(1, 2), (3, 5)],
+            vec![]
+        ];
+        let mut distances = vec![u32::MAX; graph.len()];
+        let mut queue = IndexedPriorityQueue::new();
+        queue.push(0, 0);
+        while let Some((node, distance)) = queue.pop_min() {
+            distances[node] = distance;
+            for &(next, weight) in &graph[node] {
+                if distances[next] == u32::MAX && !queue.decrease_key(&next, distance + weight) && !queue.contains_key(&next) {
+                    queue.push(next, distance + weight);
+                }
+            }
+        }
+        assert_eq!(distances, vec![0, 3, 1, 4]);
+    }
+
+    #[test]
+    fn min_heap_keeps_duplicates() {
+        let heap = MinHeap::from_vec(vec![2, 1, 2, 1]);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 1, 2, 2]);
+    }
+}
@@ -0,0 +1,363 @@
+//! K-d tree: a binary tree of points in K dimensions for nearest-neighbour and range searches.
+//!     Each node splits space in two along one axis - the x axis at the root, then y, and so on,
+//!     cycling through the K axes with depth. Points with a smaller coordinate on the node's axis
+//!     go left, the rest go right.
+//!
+//! A nearest-neighbour search descends to the side of each split the query point is on first, then
+//!     visits the other side only if the splitting plane is closer than the farthest neighbour
+//!     found so far. On a balanced tree (as built by KdTree::build) of well-spread points this
+//!     takes about O(log n) time for small K; in high dimensions it degrades towards checking every
+//!     point.
+//!
+//! ```
+//! use rust_datastructures_algorithms::kd_tree::KdTree;
+//!
+//! let mut cities: KdTree<2, &str> = KdTree::new();
+//! cities.insert([0.0, 0.0], "origin");
+//! cities.insert([3.0, 4.0], "north-east");
+//! cities.insert([-2.0, 1.0], "west");
+//!
+//! let nearest = cities.nearest(&[2.0, 2.0]).unwrap();
+//! assert_eq!(*nearest.value, "north-east");
+//! assert_eq!(nearest.distance, 5f64.sqrt());
+//! let closest: Vec<&str> = cities.k_nearest(&[0.0, 1.0], 2).iter().map(|n| *n.value).collect();
+//! assert_eq!(closest, vec!["origin", "west"]);
+//! assert_eq!(cities.within_rectangle(&[-5.0, -1.0], &[1.0, 1.0]).len(), 2);
+//! ```
+
+use crate::heap::BinaryHeapBy;
+
+
+/// A k-d tree struct mapping points with K coordinates to values, allowing repeated points.
+///
+/// * `nodes`: Arena of Nodes, in insertion order - children are linked by index.
+/// * `root`: Index of the root Node, or None if the tree is empty.
+pub struct KdTree<const K: usize, V> {
+    nodes: Vec<Node<K, V>>,
+    root: Option<usize>
+}
+
+
+/// Node of a KdTree struct. Its splitting axis is its depth modulo K.
+///
+/// * `point`: The point's coordinates.
+/// * `value`: The value stored with the point.
+/// * `left`: Index of the subtree of points with a smaller coordinate on this Node's axis.
+/// * `right`: Index of the subtree of points with a coordinate on this Node's axis at least as
+///         large.
+struct Node<const K: usize, V> {
+    point: [f64; K],
+    value: V,
+    left: Option<usize>,
+    right: Option<usize>
+}
+
+
+/// A point found by a nearest-neighbour search of a KdTree struct.
+///
+/// * `point`: The point's coordinates.
+/// * `value`: The value stored with the point.
+/// * `distance`: Euclidean distance from the query point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Neighbor<'a, const K: usize, V> {
+    pub point: &'a [f64; K],
+    pub value: &'a V,
+    pub distance: f64
+}
+
+
+// Method implementations for KdTree struct
+impl<const K: usize, V> KdTree<K, V> {
+
+    /// Return a new, empty KdTree struct. Panics if K is 0.
+    pub fn new() -> KdTree<K, V> {
+        assert!(K > 0, "a k-d tree needs at least one dimension");
+        KdTree { nodes: Vec::new(), root: None }
+    }
+
+    /// Return a new KdTree struct holding the input points, balanced by splitting each subtree at
+    /// the median coordinate on its axis, in O(n log n) time. Panics if K is 0 or a coordinate is
+    /// NaN.
+    ///
+    /// * `points`: points and their values.
+    pub fn build(points: Vec<([f64; K], V)>) -> KdTree<K, V> {
+        let mut tree = KdTree::new();
+        tree.nodes.reserve(points.len());
+        tree.root = tree.build_subtree(points, 0);
+        tree
+    }
+
+    /// Return the number of points.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return whether there are no points.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Return the number of Nodes on the longest path from the root to a leaf (0 for an empty
+    /// tree).
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack: Vec<(usize, usize)> = self.root.map(|root| (root, 1)).into_iter().collect();
+        while let Some((index, depth)) = stack.pop() {
+            height = height.max(depth);
+            let node = &self.nodes[index];
+            stack.extend([node.left, node.right].into_iter().flatten().map(|child| (child, depth + 1)));
+        }
+        height
+    }
+
+    /// Insert a point and its value, as a new leaf. The tree is not rebalanced, so inserting
+    /// points in sorted order makes it deep - use build for a known set of points. Panics if a
+    /// coordinate is NaN.
+    ///
+    /// * `point`: the point's coordinates.
+    /// * `value`: value to store with the point.
+    pub fn insert(&mut self, point: [f64; K], value: V) {
+        assert!(point.iter().all(|coordinate| !coordinate.is_nan()), "coordinates must not be NaN");
+        let index = self.nodes.len();
+        self.nodes.push(Node { point, value, left: None, right: None });
+        let Some(mut current) = self.root else {
+            self.root = Some(index);
+            return;
+        };
+        let mut axis = 0;
+        loop {
+            let node = &mut self.nodes[current];
+            let child = if point[axis] < node.point[axis] { &mut node.left } else { &mut node.right };
+            match child {
+                Some(next) => current = *next,
+                None => {
+                    *child = Some(index);
+                    return;
+                }
+            }
+            axis = (axis + 1) % K;
+        }
+    }
+
+    /// Return the point closest to the input one (by Euclidean distance), or None if the tree is
+    /// empty. Ties are broken arbitrarily.
+    ///
+    /// * `point`: the query point.
+    pub fn nearest(&self, point: &[f64; K]) -> Option<Neighbor<'_, K, V>> {
+        self.k_nearest(point, 1).pop()
+    }
+
+    /// Return the input number of points closest to the input one (or every point, if there are
+    /// fewer), from nearest to farthest. Ties are broken arbitrarily.
+    ///
+    /// The k nearest points found so far are kept in a heap with the farthest on top; a subtree is
+    ///     skipped once k points are found and its splitting plane is farther than that one.
+    ///
+    /// * `point`: the query point.
+    /// * `k`: number of points to find.
+    pub fn k_nearest(&self, point: &[f64; K], k: usize) -> Vec<Neighbor<'_, K, V>> {
+        if k == 0 {
+            return Vec::new();
+        }
+        // (squared distance, node index), farthest first
+        let mut best = BinaryHeapBy::new(|a: &(f64, usize), b: &(f64, usize)| b.0.total_cmp(&a.0));
+        // (node index, depth, squared distance from the query point to the node's region)
+        let mut stack: Vec<(usize, usize, f64)> = self.root.map(|root| (root, 0, 0.0)).into_iter().collect();
+        while let Some((index, depth, region_distance)) = stack.pop() {
+            if best.len() == k && best.peek().is_some_and(|(farthest, _)| region_distance >= *farthest) {
+                continue;
+            }
+            let node = &self.nodes[index];
+            best.push((squared_distance(point, &node.point), index));
+            if best.len() > k {
+                best.pop();
+            }
+            let axis = depth % K;
+            let offset = point[axis] - node.point[axis];
+            let (near, far) = if offset < 0.0 { (node.left, node.right) } else { (node.right, node.left) };
+            // push the far side first, so the near side is searched first
+            if let Some(far) = far {
+                stack.push((far, depth + 1, offset * offset));
+            }
+            if let Some(near) = near {
+                stack.push((near, depth + 1, region_distance));
+            }
+        }
+        let mut nearest: Vec<Neighbor<'_, K, V>> = best.into_sorted_vec().into_iter()
+            .map(|(distance, index)| Neighbor { point: &self.nodes[index].point, value: &self.nodes[index].value, distance: distance.sqrt() })
+            .collect();
+        nearest.reverse();
+        nearest
+    }
+
+    /// Return every point inside the axis-aligned box between the two input corners (boundaries
+    /// included), with its value, in no particular order. Subtrees entirely outside the box on a
+    /// node's axis are skipped.
+    ///
+    /// * `min`: corner with the smallest coordinate on each axis.
+    /// * `max`: corner with the largest coordinate on each axis.
+    pub fn within_rectangle(&self, min: &[f64; K], max: &[f64; K]) -> Vec<(&[f64; K], &V)> {
+        let mut found = Vec::new();
+        let mut stack: Vec<(usize, usize)> = self.root.map(|root| (root, 0)).into_iter().collect();
+        while let Some((index, depth)) = stack.pop() {
+            let node = &self.nodes[index];
+            if (0..K).all(|axis| min[axis] <= node.point[axis] && node.point[axis] <= max[axis]) {
+                found.push((&node.point, &node.value));
+            }
+            let axis = depth % K;
+            // the left subtree's coordinates are at most this node's on its axis, the right's at
+            // least (build puts equal coordinates on both sides)
+            if let Some(left) = node.left.filter(|_| min[axis] <= node.point[axis]) {
+                stack.push((left, depth + 1));
+            }
+            if let Some(right) = node.right.filter(|_| node.point[axis] <= max[axis]) {
+                stack.push((right, depth + 1));
+            }
+        }
+        found
+    }
+
+    /// Build a balanced subtree from the input points, returning the index of its root Node.
+    ///
+    /// * `points`: points (and values) in the subtree.
+    /// * `depth`: depth of the subtree's root, which picks its axis.
+    fn build_subtree(&mut self, mut points: Vec<([f64; K], V)>, depth: usize) -> Option<usize> {
+        if points.is_empty() {
+            return None;
+        }
+        let axis = depth % K;
+        let median = points.len() / 2;
+        assert!(points.iter().all(|(point, _)| !point[axis].is_nan()), "coordinates must not be NaN");
+        points.select_nth_unstable_by(median, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+        let greater = points.split_off(median + 1);
+        let (point, value) = points.pop().expect("the median is the last point left");
+        let index = self.nodes.len();
+        self.nodes.push(Node { point, value, left: None, right: None });
+        self.nodes[index].left = self.build_subtree(points, depth + 1);
+        self.nodes[index].right = self.build_subtree(greater, depth + 1);
+        Some(index)
+    }
+}
+
+/// Return the squared Euclidean distance between two points.
+///
+/// * `a`: one point.
+/// * `b`: the other point.
+fn squared_distance<const K: usize>(a: &[f64; K], b: &[f64; K]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+impl<const K: usize, V> Default for KdTree<K, V> {
+    fn default() -> Self {
+        KdTree::new()
+    }
+}
+
+impl<const K: usize, V> Extend<([f64; K], V)> for KdTree<K, V> {
+    fn extend<I: IntoIterator<Item = ([f64; K], V)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+// Collecting builds a balanced tree.
+impl<const K: usize, V> FromIterator<([f64; K], V)> for KdTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = ([f64; K], V)>>(iter: I) -> Self {
+        KdTree::build(iter.into_iter().collect())
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_kd_tree() -> KdTree<3, usize> {
+    let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut next_coordinate = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 1000) as f64 / 10.0
+    };
+    (0..500).map(|i| ([next_coordinate(), next_coordinate(), next_coordinate()], i)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the distances from the query point to every point of the tree, nearest first.
+    fn sorted_distances<const K: usize, V>(tree: &KdTree<K, V>, point: &[f64; K]) -> Vec<f64> {
+        let mut distances: Vec<f64> = tree.nodes.iter().map(|node| squared_distance(point, &node.point).sqrt()).collect();
+        distances.sort_by(f64::total_cmp);
+        distances
+    }
+
+    #[test]
+    fn kd_tree_works() {
+        let tree = setup_kd_tree();
+        assert_eq!(tree.len(), 500);
+        // balanced: 500 points fit in 9 levels
+        assert_eq!(tree.height(), 9);
+
+        let mut empty: KdTree<2, ()> = KdTree::new();
+        assert!(empty.nearest(&[0.0, 0.0]).is_none());
+        assert!(empty.k_nearest(&[0.0, 0.0], 3).is_empty());
+        empty.insert([1.0, 1.0], ());
+        empty.insert([1.0, 1.0], ());
+        assert_eq!(empty.k_nearest(&[0.0, 0.0], 5).len(), 2);
+        assert_eq!(empty.nearest(&[1.0, 2.0]).map(|n| n.distance), Some(1.0));
+        assert!(tree.k_nearest(&[0.0; 3], 0).is_empty());
+    }
+
+    #[test]
+    fn kd_tree_nearest_matches_brute_force() {
+        let built = setup_kd_tree();
+        // the same points inserted one by one, so the tree isn't balanced
+        let mut inserted = KdTree::new();
+        inserted.extend(built.nodes.iter().map(|node| (node.point, node.value)));
+        for tree in [&built, &inserted] {
+            for query in [[0.0, 0.0, 0.0], [50.0, 50.0, 50.0], [12.3, 98.7, 45.6], [150.0, -20.0, 33.3]] {
+                let expected = sorted_distances(tree, &query);
+                let nearest = tree.nearest(&query).unwrap();
+                assert_eq!(nearest.distance, expected[0]);
+                assert_eq!(squared_distance(&query, nearest.point).sqrt(), nearest.distance);
+
+                let k_nearest = tree.k_nearest(&query, 10);
+                assert_eq!(k_nearest.iter().map(|n| n.distance).collect::<Vec<_>>(), expected[..10]);
+                let mut values: Vec<usize> = k_nearest.iter().map(|n| *n.value).collect();
+                values.sort();
+                values.dedup();
+                assert_eq!(values.len(), 10);
+            }
+        }
+    }
+
+    #[test]
+    fn kd_tree_within_rectangle_matches_brute_force() {
+        let tree = setup_kd_tree();
+        let (min, max) = ([20.0, 10.0, 30.0], [60.0, 45.5, 90.0]);
+        let mut found: Vec<usize> = tree.within_rectangle(&min, &max).into_iter().map(|(_, value)| *value).collect();
+        found.sort();
+        let expected: Vec<usize> = tree.nodes.iter()
+            .filter(|node| (0..3).all(|axis| min[axis] <= node.point[axis] && node.point[axis] <= max[axis]))
+            .map(|node| node.value)
+            .collect::<std::collections::BTreeSet<_>>().into_iter().collect();
+        assert!(!expected.is_empty());
+        assert_eq!(found, expected);
+
+        // points on the boundary count, including repeated coordinates split by build
+        let grid: KdTree<2, (i32, i32)> = (0..5).flat_map(|x| (0..5).map(move |y| ([x as f64, y as f64], (x, y)))).collect();
+        assert_eq!(grid.within_rectangle(&[1.0, 1.0], &[3.0, 2.0]).len(), 6);
+        assert_eq!(grid.within_rectangle(&[2.0, 0.0], &[2.0, 4.0]).len(), 5);
+        assert!(grid.within_rectangle(&[4.5, 0.0], &[9.0, 9.0]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinates must not be NaN")]
+    fn kd_tree_rejects_nan() {
+        let mut tree: KdTree<2, ()> = KdTree::new();
+        tree.insert([0.0, f64::NAN], ());
+    }
+}
@@ -0,0 +1,437 @@
+//! Segment tree with lazy propagation: like the segment_tree module's SegmentTree, but whole
+//!     ranges can be updated at once (e.g. add x to every value in a range) in O(log n). An update
+//!     covering a node's whole span is applied to the node's combined value and left pending
+//!     there, only being pushed down to its children when a later query or update needs to look
+//!     inside the span.
+//!
+//! Updates are a type implementing the RangeUpdate trait for the combining Monoid - it says how
+//!     an update changes the combination of a range, and how two updates compose. Add and Assign
+//!     are provided for the Sum, Min and Max monoids.
+//!
+//! ```
+//! use rust_datastructures_algorithms::lazy_segment_tree::{ Add, LazySegmentTree };
+//! use rust_datastructures_algorithms::segment_tree::Max;
+//!
+//! let mut tree: LazySegmentTree<i64, Max, Add> = LazySegmentTree::build(&[1, 5, 2, 4, 3]);
+//! // add 10 to [2, 4], then query the max of [0, 3)
+//! tree.update(2..=4, 10);
+//! assert_eq!(tree.query(0..3), 12);
+//! assert_eq!(tree.query(..), 14);
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+use crate::segment_tree::{ range_to_indexes, Max, Min, Monoid, Number, Sum };
+
+
+/// Update that can be applied to every value in a range, for a Monoid combining the values.
+///
+/// Implementations must satisfy, for any update u and ranges a and b next to each other (with a
+///     and b standing for their combinations, and n and m for their lengths):
+///     `apply(u, combine(a, b), n + m) == combine(apply(u, a, n), apply(u, b, m))`, and applying
+///     `compose(newer, older)` must equal applying older and then newer.
+pub trait RangeUpdate<T, Op: Monoid<T>> {
+    /// The parameter of an update, e.g. the amount to add.
+    type Update: Clone;
+
+    /// Return the combination of a range after the update is applied to each of its values.
+    ///
+    /// * `update`: the update.
+    /// * `combined`: combination of the range's values before the update.
+    /// * `length`: number of values in the range.
+    fn apply(update: &Self::Update, combined: &T, length: usize) -> T;
+
+    /// Return the single update equal to applying the older update and then the newer one.
+    ///
+    /// * `newer`: update applied second.
+    /// * `older`: update applied first.
+    fn compose(newer: &Self::Update, older: &Self::Update) -> Self::Update;
+}
+
+
+/// Range update adding a number to every value.
+pub struct Add;
+
+/// Range update replacing every value with a number.
+pub struct Assign;
+
+impl<T: Number> RangeUpdate<T, Sum> for Add {
+    type Update = T;
+
+    fn apply(update: &T, combined: &T, length: usize) -> T {
+        *combined + *update * T::from_count(length)
+    }
+
+    fn compose(newer: &T, older: &T) -> T {
+        *newer + *older
+    }
+}
+
+impl<T: Number> RangeUpdate<T, Min> for Add {
+    type Update = T;
+
+    fn apply(update: &T, combined: &T, _length: usize) -> T {
+        *combined + *update
+    }
+
+    fn compose(newer: &T, older: &T) -> T {
+        *newer + *older
+    }
+}
+
+impl<T: Number> RangeUpdate<T, Max> for Add {
+    type Update = T;
+
+    fn apply(update: &T, combined: &T, _length: usize) -> T {
+        *combined + *update
+    }
+
+    fn compose(newer: &T, older: &T) -> T {
+        *newer + *older
+    }
+}
+
+impl<T: Number> RangeUpdate<T, Sum> for Assign {
+    type Update = T;
+
+    fn apply(update: &T, _combined: &T, length: usize) -> T {
+        *update * T::from_count(length)
+    }
+
+    fn compose(newer: &T, _older: &T) -> T {
+        *newer
+    }
+}
+
+impl<T: Number> RangeUpdate<T, Min> for Assign {
+    type Update = T;
+
+    fn apply(update: &T, _combined: &T, _length: usize) -> T {
+        *update
+    }
+
+    fn compose(newer: &T, _older: &T) -> T {
+        *newer
+    }
+}
+
+impl<T: Number> RangeUpdate<T, Max> for Assign {
+    type Update = T;
+
+    fn apply(update: &T, _combined: &T, _length: usize) -> T {
+        *update
+    }
+
+    fn compose(newer: &T, _older: &T) -> T {
+        *newer
+    }
+}
+
+
+/// A lazy segment tree struct over an array of n values, stored top-down in Vecs: the root is at
+/// index 1 and spans the whole array, and the node at index i has children at 2i and 2i + 1
+/// spanning the two halves of its span.
+///
+/// * `length`: Number of values in the array (n).
+/// * `tree`: Combination of the values in each node's span, with every update applied to the node
+///         or its ancestors already counted.
+/// * `pending`: Update applied to each node but not yet to its children (None if there is none).
+/// * `marker`: Marker for the monoid and range update types.
+pub struct LazySegmentTree<T, Op: Monoid<T>, U: RangeUpdate<T, Op>> {
+    length: usize,
+    tree: Vec<T>,
+    pending: Vec<Option<U::Update>>,
+    marker: PhantomData<(Op, U)>
+}
+
+
+// Method implementations for LazySegmentTree struct
+impl<T, Op, U> LazySegmentTree<T, Op, U> where T: Clone, Op: Monoid<T>, U: RangeUpdate<T, Op> {
+
+    /// Return a new LazySegmentTree struct over a copy of the input values, built in O(n).
+    ///
+    /// * `values`: the array to answer queries about.
+    pub fn build(values: &[T]) -> LazySegmentTree<T, Op, U> {
+        let length = values.len();
+        let mut tree = LazySegmentTree {
+            length,
+            tree: vec![Op::identity(); 4 * length.max(1)],
+            pending: vec![None; 4 * length.max(1)],
+            marker: PhantomData
+        };
+        if length > 0 {
+            tree.build_node(1, 0, length, values);
+        }
+        tree
+    }
+
+    /// Return the number of values in the array.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether the array holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the value at the input index of the array, with every update applied. Panics if the
+    /// index is out of bounds.
+    ///
+    /// * `index`: index of the value.
+    pub fn get(&mut self, index: usize) -> T {
+        assert!(index < self.length, "index {} out of bounds for length {}", index, self.length);
+        self.query(index..=index)
+    }
+
+    /// Return the combination of the values in the input range of the array, in order (the
+    /// identity for an empty range). Panics if the range is out of bounds, like slicing.
+    ///
+    /// Takes a mutable reference because pending updates on the way down are pushed to the
+    ///     children. O(log n).
+    ///
+    /// * `range`: range of indexes to combine, such as `start..end` or `..`.
+    pub fn query<R: RangeBounds<usize>>(&mut self, range: R) -> T {
+        let (start, end) = range_to_indexes(range, self.length);
+        if start == end {
+            return Op::identity();
+        }
+        self.query_node(1, 0, self.length, start, end)
+    }
+
+    /// Apply the input update to every value in the input range of the array. Panics if the range
+    /// is out of bounds, like slicing.
+    ///
+    /// The range is split into the O(log n) nodes whose spans it covers exactly; the update is
+    ///     applied to each of those and left pending there, and their ancestors are recombined.
+    ///
+    /// * `range`: range of indexes to update, such as `start..end` or `..`.
+    /// * `update`: the update.
+    pub fn update<R: RangeBounds<usize>>(&mut self, range: R, update: U::Update) {
+        let (start, end) = range_to_indexes(range, self.length);
+        if start < end {
+            self.update_node(1, 0, self.length, start, end, &update);
+        }
+    }
+
+    /// Set the node at the input index and its descendants to the combinations of the input
+    /// values in their spans.
+    ///
+    /// * `node`: index of the node.
+    /// * `node_start`: first index of the node's span.
+    /// * `node_end`: index just past the node's span.
+    /// * `values`: the whole array.
+    fn build_node(&mut self, node: usize, node_start: usize, node_end: usize, values: &[T]) {
+        if node_end - node_start == 1 {
+            self.tree[node] = values[node_start].clone();
+            return;
+        }
+        let middle = node_start + (node_end - node_start) / 2;
+        self.build_node(2 * node, node_start, middle, values);
+        self.build_node(2 * node + 1, middle, node_end, values);
+        self.tree[node] = Op::combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    /// Return the combination of the values in both the node's span and the input range, which
+    /// must overlap.
+    ///
+    /// * `node`: index of the node.
+    /// * `node_start`: first index of the node's span.
+    /// * `node_end`: index just past the node's span.
+    /// * `start`: first index of the range.
+    /// * `end`: index just past the range.
+    fn query_node(&mut self, node: usize, node_start: usize, node_end: usize, start: usize, end: usize) -> T {
+        if start <= node_start && node_end <= end {
+            return self.tree[node].clone();
+        }
+        self.push_down(node, node_start, node_end);
+        let middle = node_start + (node_end - node_start) / 2;
+        if end <= middle {
+            self.query_node(2 * node, node_start, middle, start, end)
+        } else if middle <= start {
+            self.query_node(2 * node + 1, middle, node_end, start, end)
+        } else {
+            let left = self.query_node(2 * node, node_start, middle, start, end);
+            let right = self.query_node(2 * node + 1, middle, node_end, start, end);
+            Op::combine(&left, &right)
+        }
+    }
+
+    /// Apply the update to the values in both the node's span and the input range, which must
+    /// overlap.
+    ///
+    /// * `node`: index of the node.
+    /// * `node_start`: first index of the node's span.
+    /// * `node_end`: index just past the node's span.
+    /// * `start`: first index of the range.
+    /// * `end`: index just past the range.
+    /// * `update`: the update.
+    fn update_node(&mut self, node: usize, node_start: usize, node_end: usize, start: usize, end: usize, update: &U::Update) {
+        if start <= node_start && node_end <= end {
+            self.apply_to_node(node, node_end - node_start, update);
+            return;
+        }
+        self.push_down(node, node_start, node_end);
+        let middle = node_start + (node_end - node_start) / 2;
+        if start < middle {
+            self.update_node(2 * node, node_start, middle, start, end, update);
+        }
+        if middle < end {
+            self.update_node(2 * node + 1, middle, node_end, start, end, update);
+        }
+        self.tree[node] = Op::combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    /// Apply an update to the whole span of the node at the input index, leaving it pending for
+    /// the node's children (composed after any update already pending there).
+    ///
+    /// * `node`: index of the node.
+    /// * `span_length`: number of values in the node's span.
+    /// * `update`: the update.
+    fn apply_to_node(&mut self, node: usize, span_length: usize, update: &U::Update) {
+        self.tree[node] = U::apply(update, &self.tree[node], span_length);
+        if span_length > 1 {
+            self.pending[node] = Some(match &self.pending[node] {
+                Some(older) => U::compose(update, older),
+                None => update.clone()
+            });
+        }
+    }
+
+    /// Apply the update pending at the node at the input index (if any) to its two children.
+    ///
+    /// * `node`: index of the node.
+    /// * `node_start`: first index of the node's span.
+    /// * `node_end`: index just past the node's span.
+    fn push_down(&mut self, node: usize, node_start: usize, node_end: usize) {
+        if let Some(update) = self.pending[node].take() {
+            let middle = node_start + (node_end - node_start) / 2;
+            self.apply_to_node(2 * node, middle - node_start, &update);
+            self.apply_to_node(2 * node + 1, node_end - middle, &update);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_values() -> Vec<i64> {
+    vec![4, -1, 6, 3, 0, 8, -5, 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Range update that can either add to or replace values, so the two mix in one tree.
+    struct AddOrAssign;
+
+    #[derive(Clone, Copy, Debug)]
+    enum Change {
+        Add(i64),
+        Assign(i64)
+    }
+
+    impl RangeUpdate<i64, Sum> for AddOrAssign {
+        type Update = Change;
+
+        fn apply(update: &Change, combined: &i64, length: usize) -> i64 {
+            match update {
+                Change::Add(amount) => combined + amount * length as i64,
+                Change::Assign(value) => value * length as i64
+            }
+        }
+
+        fn compose(newer: &Change, older: &Change) -> Change {
+            match (newer, older) {
+                (Change::Add(amount), Change::Add(earlier)) => Change::Add(amount + earlier),
+                (Change::Add(amount), Change::Assign(value)) => Change::Assign(value + amount),
+                (Change::Assign(value), _) => Change::Assign(*value)
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_segment_tree_works() {
+        let values = setup_values();
+        let mut maximums: LazySegmentTree<i64, Max, Add> = LazySegmentTree::build(&values);
+        assert_eq!(maximums.len(), 8);
+        assert_eq!(maximums.query(..), 8);
+        maximums.update(0..4, 10);
+        assert_eq!(maximums.query(..), 16);
+        assert_eq!(maximums.query(4..), 8);
+        maximums.update(1..=6, -20);
+        assert_eq!(maximums.query(1..7), -4);
+        assert_eq!(maximums.get(0), 14);
+        assert_eq!(maximums.get(3), -7);
+
+        let mut sums: LazySegmentTree<i64, Sum, Assign> = LazySegmentTree::build(&values);
+        assert_eq!(sums.query(..), 17);
+        sums.update(2..6, 1);
+        assert_eq!(sums.query(..), 4 - 1 + 4 - 5 + 2);
+        sums.update(..3, 0);
+        assert_eq!(sums.query(..4), 1);
+        assert_eq!(sums.query(5..5), 0);
+
+        let mut empty: LazySegmentTree<i64, Min, Add> = LazySegmentTree::build(&[]);
+        assert!(empty.is_empty());
+        empty.update(.., 3);
+        assert_eq!(empty.query(..), i64::MAX);
+    }
+
+    #[test]
+    fn lazy_segment_tree_matches_naive() {
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next_random = move |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % bound as u64) as usize
+        };
+        for length in [1, 2, 5, 16, 37] {
+            let mut values: Vec<i64> = (0..length).map(|_| next_random(100) as i64 - 50).collect();
+            let mut sums: LazySegmentTree<i64, Sum, Add> = LazySegmentTree::build(&values);
+            let mut minimums: LazySegmentTree<i64, Min, Assign> = LazySegmentTree::build(&values);
+            let mut assigned = values.clone();
+            for _ in 0..300 {
+                let start = next_random(length + 1);
+                let end = start + next_random(length - start + 1);
+                let amount = next_random(100) as i64 - 50;
+                if next_random(2) == 0 {
+                    sums.update(start..end, amount);
+                    values[start..end].iter_mut().for_each(|value| *value += amount);
+                } else {
+                    minimums.update(start..end, amount);
+                    assigned[start..end].iter_mut().for_each(|value| *value = amount);
+                }
+
+                let start = next_random(length + 1);
+                let end = start + next_random(length - start + 1);
+                assert_eq!(sums.query(start..end), values[start..end].iter().sum::<i64>());
+                assert_eq!(minimums.query(start..end), assigned[start..end].iter().copied().min().unwrap_or(i64::MAX));
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_segment_tree_custom_update() {
+        let mut values = setup_values();
+        let mut tree: LazySegmentTree<i64, Sum, AddOrAssign> = LazySegmentTree::build(&values);
+        let changes = [(0, 8, Change::Add(2)), (2, 5, Change::Assign(7)), (3, 8, Change::Add(-1)), (0, 4, Change::Add(3))];
+        for (start, end, change) in changes {
+            tree.update(start..end, change);
+            for value in &mut values[start..end] {
+                *value = match change {
+                    Change::Add(amount) => *value + amount,
+                    Change::Assign(assigned) => assigned
+                };
+            }
+            for start in 0..=values.len() {
+                for end in start..=values.len() {
+                    assert_eq!(tree.query(start..end), values[start..end].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,514 @@
+//! Least frequently used (LFU) cache: a map holding at most a fixed number of entries, which
+//!     evicts the entry used the fewest times when a new one needs room. Among entries used
+//!     equally often, the one used least recently is evicted.
+//!
+//! Every operation is O(1) (expected, from hashing). Entries are kept in frequency buckets - one
+//!     doubly linked list per use count, newest use first - and the buckets are themselves a
+//!     doubly linked list in order of use count. The cache tracks the smallest use count that has
+//!     a bucket, so the entry to evict is always the oldest in that bucket, and when that bucket
+//!     empties the next smallest is one link away. Using an entry just moves it to the front of
+//!     the next bucket up, which is either the bucket after its own or a new one linked in there.
+//!
+//! ```
+//! use rust_datastructures_algorithms::lfu_cache::LfuCache;
+//!
+//! let mut cache = LfuCache::new(2);
+//! cache.insert("a", 1);
+//! cache.insert("b", 2);
+//! assert_eq!(cache.get("a"), Some(&1));
+//! // "b" has been used least often, so it makes room for "c"
+//! assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+//! assert!(!cache.contains_key("b"));
+//! assert_eq!(cache.frequency("a"), Some(2));
+//! ```
+
+use std::borrow::Borrow;
+use std::hash::Hash;
+use crate::hash_map::OpenHashMap;
+
+
+/// An LFU cache struct, holding its entries in a Vec and linking them into buckets by index.
+///
+/// * `capacity`: Most entries the cache holds.
+/// * `index_of`: Index of the slot holding each key's entry.
+/// * `slots`: Slots holding every entry. Slots of removed entries are left None and reused.
+/// * `free`: Indexes of the empty slots.
+/// * `buckets`: The bucket for each use count that some entry has.
+/// * `min_frequency`: Smallest use count with a bucket - the first in the list of buckets
+///         (meaningless when the cache is empty).
+pub struct LfuCache<K, V> {
+    capacity: usize,
+    index_of: OpenHashMap<K, usize>,
+    slots: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    buckets: OpenHashMap<u64, Bucket>,
+    min_frequency: u64
+}
+
+
+/// Entry in an LfuCache struct.
+///
+/// * `key`: The entry's key.
+/// * `value`: The entry's value.
+/// * `frequency`: Number of times the entry has been used - inserted, updated or read with get.
+/// * `newer`: Index of the entry used next after this one in its bucket (None if it is the
+///         newest).
+/// * `older`: Index of the entry used last before this one in its bucket (None if it is the
+///         oldest).
+struct Node<K, V> {
+    key: K,
+    value: V,
+    frequency: u64,
+    newer: Option<usize>,
+    older: Option<usize>
+}
+
+
+/// Doubly linked list of the entries used the same number of times, in an LfuCache struct. Also
+/// a link in the list of buckets, in order of use count.
+///
+/// * `newest`: Index of the entry used most recently.
+/// * `oldest`: Index of the entry used least recently - the next to be evicted from the bucket.
+/// * `lower`: Use count of the bucket before this one (None if this is the first).
+/// * `higher`: Use count of the bucket after this one (None if this is the last).
+#[derive(Clone, Copy)]
+struct Bucket {
+    newest: usize,
+    oldest: usize,
+    lower: Option<u64>,
+    higher: Option<u64>
+}
+
+
+// Method implementations for LfuCache struct
+impl<K, V> LfuCache<K, V> where K: Hash + Eq + Clone {
+
+    /// Return a new, empty LfuCache struct holding at most the input number of entries. A cache
+    /// with capacity 0 never holds anything.
+    ///
+    /// * `capacity`: most entries the cache holds.
+    pub fn new(capacity: usize) -> LfuCache<K, V> {
+        LfuCache {
+            capacity,
+            index_of: OpenHashMap::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            buckets: OpenHashMap::new(),
+            min_frequency: 0
+        }
+    }
+
+    /// Return the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.index_of.len()
+    }
+
+    /// Return whether the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.index_of.is_empty()
+    }
+
+    /// Return the most entries the cache holds.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Insert a key-value pair into the cache, counting as a use. Return the pair pushed out of
+    /// the cache, if any: for a key already in the cache, the key with its old value; otherwise
+    /// the evicted pair if the cache was full (or the input pair itself if the capacity is 0).
+    ///
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(index) = self.index_of.get(&key).copied() {
+            let old_value = std::mem::replace(&mut self.node_mut(index).value, value);
+            self.touch(index);
+            return Some((key, old_value));
+        }
+        if self.capacity == 0 {
+            return Some((key, value));
+        }
+        let evicted = if self.len() == self.capacity { self.evict() } else { None };
+
+        let node = Node { key: key.clone(), value, frequency: 1, newer: None, older: None };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Some(node);
+                index
+            },
+            None => {
+                self.slots.push(Some(node));
+                self.slots.len() - 1
+            }
+        };
+        self.index_of.insert(key, index);
+        // a new bucket for use count 1 goes first
+        self.push_newest(index, None);
+        evicted
+    }
+
+    /// Return a reference to the value associated with the input key, counting as a use (if the
+    /// key is in the cache).
+    ///
+    /// * `key`: key to look up - any borrowed form of the cache's key type.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let index = *self.index_of.get(key)?;
+        self.touch(index);
+        Some(&self.node(index).value)
+    }
+
+    /// Return a mutable reference to the value associated with the input key, counting as a use
+    /// (if the key is in the cache).
+    ///
+    /// * `key`: key to look up - any borrowed form of the cache's key type.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let index = *self.index_of.get(key)?;
+        self.touch(index);
+        Some(&mut self.node_mut(index).value)
+    }
+
+    /// Return a reference to the value associated with the input key without counting a use (if
+    /// the key is in the cache).
+    ///
+    /// * `key`: key to look up - any borrowed form of the cache's key type.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.index_of.get(key).map(|index| &self.node(*index).value)
+    }
+
+    /// Return whether the input key is in the cache, without counting a use.
+    ///
+    /// * `key`: key to look for - any borrowed form of the cache's key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.index_of.contains_key(key)
+    }
+
+    /// Return the number of times the input key's entry has been used (if the key is in the
+    /// cache).
+    ///
+    /// * `key`: key to look up - any borrowed form of the cache's key type.
+    pub fn frequency<Q>(&self, key: &Q) -> Option<u64> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.index_of.get(key).map(|index| self.node(*index).frequency)
+    }
+
+    /// Remove the input key's entry from the cache and return its value (if the key is in the
+    /// cache).
+    ///
+    /// * `key`: key to be removed - any borrowed form of the cache's key type.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let index = self.index_of.remove(key)?;
+        let frequency = self.node(index).frequency;
+        if self.unlink(index) {
+            self.remove_bucket(frequency);
+        }
+        self.free.push(index);
+        self.slots[index].take().map(|node| node.value)
+    }
+
+    /// Remove every entry from the cache.
+    pub fn clear(&mut self) {
+        self.index_of.clear();
+        self.slots.clear();
+        self.free.clear();
+        self.buckets.clear();
+        self.min_frequency = 0;
+    }
+
+    /// Count a use of the entry at the input index: move it from its bucket to the newest end of
+    /// the next bucket up (linked in right after its old bucket, if it's new), then remove the old
+    /// bucket if it's left empty.
+    ///
+    /// * `index`: index of the entry's slot.
+    fn touch(&mut self, index: usize) {
+        let frequency = self.node(index).frequency;
+        let emptied = self.unlink(index);
+        self.node_mut(index).frequency += 1;
+        self.push_newest(index, Some(frequency));
+        if emptied {
+            self.remove_bucket(frequency);
+        }
+    }
+
+    /// Remove the oldest entry in the bucket with the smallest use count and return its key and
+    /// value (None if the cache is empty).
+    fn evict(&mut self) -> Option<(K, V)> {
+        let index = self.buckets.get(&self.min_frequency)?.oldest;
+        if self.unlink(index) {
+            self.remove_bucket(self.min_frequency);
+        }
+        self.free.push(index);
+        let node = self.slots[index].take().expect("linked slots hold entries");
+        self.index_of.remove(&node.key);
+        Some((node.key, node.value))
+    }
+
+    /// Link the entry at the input index in as the newest in the bucket for its use count,
+    /// creating the bucket if there isn't one.
+    ///
+    /// * `index`: index of the entry's slot.
+    /// * `lower`: use count of the bucket a new bucket is linked in after - the largest use count
+    ///         below the entry's (None to link it in first).
+    fn push_newest(&mut self, index: usize, lower: Option<u64>) {
+        let frequency = self.node(index).frequency;
+        let older = match self.buckets.get_mut(&frequency) {
+            Some(bucket) => {
+                let previous_newest = bucket.newest;
+                bucket.newest = index;
+                self.node_mut(previous_newest).newer = Some(index);
+                Some(previous_newest)
+            },
+            None => {
+                self.insert_bucket(frequency, index, lower);
+                None
+            }
+        };
+        let node = self.node_mut(index);
+        node.newer = None;
+        node.older = older;
+    }
+
+    /// Unlink the entry at the input index from its bucket, and return whether that left the
+    /// bucket empty. An emptied bucket is left for the caller to remove (see remove_bucket), once
+    /// it's no longer needed as a position in the list of buckets.
+    ///
+    /// * `index`: index of the entry's slot.
+    fn unlink(&mut self, index: usize) -> bool {
+        let Node { frequency, newer, older, .. } = *self.node(index);
+        match newer {
+            Some(newer) => self.node_mut(newer).older = older,
+            None => if let Some(older) = older {
+                self.buckets.get_mut(&frequency).expect("linked entries have a bucket").newest = older;
+            }
+        }
+        match older {
+            Some(older) => self.node_mut(older).newer = newer,
+            None => if let Some(newer) = newer {
+                self.buckets.get_mut(&frequency).expect("linked entries have a bucket").oldest = newer;
+            }
+        }
+        newer.is_none() && older.is_none()
+    }
+
+    /// Create the bucket for the input use count, holding just the entry at the input index, and
+    /// link it into the list of buckets after the input one.
+    ///
+    /// * `frequency`: use count of the new bucket.
+    /// * `index`: index of the slot of the bucket's only entry.
+    /// * `lower`: use count of the bucket to link it in after (None to link it in first).
+    fn insert_bucket(&mut self, frequency: u64, index: usize, lower: Option<u64>) {
+        let higher = match lower {
+            Some(lower) => self.buckets.get_mut(&lower).expect("linked buckets exist").higher.replace(frequency),
+            None => {
+                let first = (!self.buckets.is_empty()).then_some(self.min_frequency);
+                self.min_frequency = frequency;
+                first
+            }
+        };
+        if let Some(higher) = higher {
+            self.buckets.get_mut(&higher).expect("linked buckets exist").lower = Some(frequency);
+        }
+        self.buckets.insert(frequency, Bucket { newest: index, oldest: index, lower, higher });
+    }
+
+    /// Remove the (emptied) bucket for the input use count, linking the buckets either side of it
+    /// to each other. If it was the first bucket, the next one's use count becomes the smallest.
+    ///
+    /// * `frequency`: use count of the bucket.
+    fn remove_bucket(&mut self, frequency: u64) {
+        let Bucket { lower, higher, .. } = self.buckets.remove(&frequency).expect("removed buckets exist");
+        match lower {
+            Some(lower) => { self.buckets.get_mut(&lower).expect("linked buckets exist").higher = higher; },
+            None => { self.min_frequency = higher.unwrap_or(0); }
+        }
+        if let Some(higher) = higher {
+            self.buckets.get_mut(&higher).expect("linked buckets exist").lower = lower;
+        }
+    }
+
+    /// Return the entry at the input index, which must be in the cache.
+    ///
+    /// * `index`: index of the entry's slot.
+    fn node(&self, index: usize) -> &Node<K, V> {
+        self.slots[index].as_ref().expect("indexed slots hold entries")
+    }
+
+    /// Return the entry at the input index mutably, which must be in the cache.
+    ///
+    /// * `index`: index of the entry's slot.
+    fn node_mut(&mut self, index: usize) -> &mut Node<K, V> {
+        self.slots[index].as_mut().expect("indexed slots hold entries")
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_lfu_cache() -> LfuCache<u32, &'static str> {
+    let mut cache = LfuCache::new(3);
+    cache.insert(1, "one");
+    cache.insert(2, "two");
+    cache.insert(3, "three");
+    cache
+}
+
+/// Check that every entry is in the bucket for its use count, each bucket is linked both ways,
+/// the buckets are linked both ways in order of use count, and the smallest use count is right.
+#[cfg(test)]
+fn check_buckets<K: Hash + Eq + Clone, V>(cache: &LfuCache<K, V>) {
+    let mut linked = 0;
+    for (frequency, bucket) in cache.buckets.iter() {
+        let mut previous = None;
+        let mut current = Some(bucket.oldest);
+        while let Some(index) = current {
+            let node = cache.node(index);
+            assert_eq!(node.frequency, *frequency);
+            assert_eq!(node.older, previous);
+            assert_eq!(cache.index_of.get(&node.key), Some(&index));
+            linked += 1;
+            previous = current;
+            current = node.newer;
+        }
+        assert_eq!(previous, Some(bucket.newest));
+    }
+    assert_eq!(linked, cache.len());
+    assert!(cache.len() <= cache.capacity());
+    if !cache.is_empty() {
+        assert_eq!(Some(cache.min_frequency), cache.buckets.keys().copied().min());
+    }
+
+    let mut bucket_count = 0;
+    let mut lower = None;
+    let mut current = (!cache.buckets.is_empty()).then_some(cache.min_frequency);
+    while let Some(frequency) = current {
+        let bucket = cache.buckets.get(&frequency).unwrap();
+        assert_eq!(bucket.lower, lower);
+        assert!(lower.is_none_or(|lower| lower < frequency));
+        bucket_count += 1;
+        lower = current;
+        current = bucket.higher;
+    }
+    assert_eq!(bucket_count, cache.buckets.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lfu_cache_works() {
+        let mut cache = setup_lfu_cache();
+        check_buckets(&cache);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get(&1), Some(&"one"));
+        assert_eq!(cache.get(&1), Some(&"one"));
+        assert_eq!(cache.get(&3), Some(&"three"));
+        assert_eq!(cache.frequency(&1), Some(3));
+        assert_eq!(cache.peek(&2), Some(&"two"));
+        assert_eq!(cache.frequency(&2), Some(1));
+
+        // 2 has the fewest uses
+        assert_eq!(cache.insert(4, "four"), Some((2, "two")));
+        check_buckets(&cache);
+        // 4 has been used once, and 1 and 3 more often
+        assert_eq!(cache.insert(5, "five"), Some((4, "four")));
+        assert_eq!(cache.insert(5, "FIVE"), Some((5, "five")));
+        assert_eq!(cache.frequency(&5), Some(2));
+        *cache.get_mut(&5).unwrap() = "5";
+        assert_eq!(cache.peek(&5), Some(&"5"));
+        check_buckets(&cache);
+
+        assert_eq!(cache.remove(&5), Some("5"));
+        assert_eq!(cache.remove(&5), None);
+        assert_eq!(cache.len(), 2);
+        check_buckets(&cache);
+        assert_eq!(cache.insert(6, "six"), None);
+        assert_eq!(cache.slots.len(), 3);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.insert(7, "seven"), None);
+
+        let mut none: LfuCache<u32, u32> = LfuCache::new(0);
+        assert_eq!(none.insert(1, 1), Some((1, 1)));
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn lfu_cache_breaks_ties_by_recency() {
+        let mut cache = LfuCache::new(3);
+        for key in ["a", "b", "c"] {
+            cache.insert(key, ());
+        }
+        // every entry used twice, "b" least recently
+        cache.get("b");
+        cache.get("c");
+        cache.get("a");
+        assert_eq!(cache.insert("d", ()), Some(("b", ())));
+        // a new entry is evicted first, being used only once
+        assert_eq!(cache.insert("e", ()), Some(("d", ())));
+        check_buckets(&cache);
+        // all three are tied once "e" is read again, and "c" was used least recently
+        cache.get("e");
+        assert_eq!(cache.insert("f", ()), Some(("c", ())));
+    }
+
+    #[test]
+    fn lfu_cache_matches_naive() {
+        // (key, value, uses, time of last use) for each entry, evicting the smallest (uses, time)
+        let mut model: Vec<(u32, u64, u64, u64)> = Vec::new();
+        let mut cache = LfuCache::new(8);
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for time in 0..5000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            // skewed keys, so some are used much more than others
+            let key = ((seed % 24) * (seed % 24) / 24) as u32;
+            let position = model.iter().position(|entry| entry.0 == key);
+            match seed % 5 {
+                0 | 1 => {
+                    let expected = position.map(|position| {
+                        model[position].2 += 1;
+                        model[position].3 = time;
+                        model[position].1
+                    });
+                    assert_eq!(cache.get(&key).copied(), expected);
+                },
+                2 if seed.is_multiple_of(3) => {
+                    let expected = position.map(|position| model.remove(position).1);
+                    assert_eq!(cache.remove(&key), expected);
+                },
+                _ => {
+                    let expected = match position {
+                        Some(position) => {
+                            let entry = &mut model[position];
+                            let old = (key, entry.1);
+                            *entry = (key, time, entry.2 + 1, time);
+                            Some(old)
+                        },
+                        None => {
+                            let evicted = if model.len() == 8 {
+                                let victim = (0..model.len()).min_by_key(|index| (model[*index].2, model[*index].3)).unwrap();
+                                let (victim_key, victim_value, _, _) = model.remove(victim);
+                                Some((victim_key, victim_value))
+                            } else {
+                                None
+                            };
+                            model.push((key, time, 1, time));
+                            evicted
+                        }
+                    };
+                    assert_eq!(cache.insert(key, time), expected);
+                }
+            }
+            if time % 25 == 0 {
+                check_buckets(&cache);
+            }
+        }
+        check_buckets(&cache);
+        for (key, value, uses, _) in model {
+            assert_eq!(cache.peek(&key), Some(&value));
+            assert_eq!(cache.frequency(&key), Some(uses));
+        }
+    }
+}
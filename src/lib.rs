@@ -1,11 +1,128 @@
 // use crate::linked_list::{ LinkedList, Node };
 
+// Doc comments in this crate indent continuation lines of `* param:` lists for readability.
+#![allow(clippy::doc_overindented_list_items, clippy::doc_lazy_continuation)]
+
+#[allow(dead_code)]
+pub mod traits;
+
+#[allow(dead_code)]
+mod rng;
+
 #[allow(dead_code)]
 mod linked_list;
 
+#[allow(dead_code)]
+mod doubly_linked_list;
+
+#[allow(dead_code)]
+mod sync_linked_list;
+
+#[allow(dead_code)]
+mod circular_linked_list;
+
+#[allow(dead_code)]
+mod box_linked_list;
+
+#[allow(dead_code)]
+mod ordered_linked_list;
+
+#[allow(dead_code)]
+pub mod stack;
+
+#[allow(dead_code)]
+pub mod queue;
+
+#[allow(dead_code)]
+pub mod deque;
+
+#[allow(dead_code)]
+pub mod heap;
+
+#[allow(dead_code)]
+pub mod pairing_heap;
+
+#[allow(dead_code)]
+pub mod fibonacci_heap;
+
+#[allow(dead_code)]
+pub mod hash_map;
+
+#[allow(dead_code)]
+pub mod hash_set;
+
+#[allow(dead_code)]
+pub mod radix_trie;
+
+#[allow(dead_code)]
+pub mod suffix_array;
+
+#[allow(dead_code)]
+pub mod suffix_automaton;
+
+#[allow(dead_code)]
+pub mod btree;
+
+#[allow(dead_code)]
+pub mod bplus_tree;
+
+#[allow(dead_code)]
+pub mod skip_list;
+
+#[allow(dead_code)]
+pub mod segment_tree;
+
+#[allow(dead_code)]
+pub mod lazy_segment_tree;
+
+#[allow(dead_code)]
+pub mod fenwick_tree;
+
+#[allow(dead_code)]
+pub mod sparse_table;
+
+#[allow(dead_code)]
+pub mod union_find;
+
+#[allow(dead_code)]
+pub mod graph;
+
+#[allow(dead_code)]
+pub mod lfu_cache;
+
+#[allow(dead_code)]
+pub mod bloom_filter;
+
+#[allow(dead_code)]
+pub mod cuckoo_filter;
+
+#[allow(dead_code)]
+pub mod count_min_sketch;
+
+#[allow(dead_code)]
+pub mod ring_buffer;
+
+#[allow(dead_code)]
+pub mod bit_vec;
+
+#[allow(dead_code)]
+pub mod kd_tree;
+
 #[allow(dead_code)]
 mod bst;
 
+#[allow(dead_code)]
+mod bst_by;
+
+#[allow(dead_code)]
+mod bst_map;
+
+#[allow(dead_code)]
+mod splay_tree;
+
+#[allow(dead_code)]
+mod treap;
+
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
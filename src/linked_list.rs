@@ -1,8 +1,13 @@
 //! Simple linked list implementation that works using std::rc::{ Rc, Weak }, and interior
-//!     mutability with RefCell. Non-threadsafe (can be re-implemented with Arc and Mutex)
+//!     mutability with RefCell. Non-threadsafe (see SyncLinkedList in sync_linked_list.rs for an
+//!     Arc and Mutex re-implementation)
 
 use std::rc::{Rc, Weak};
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use crate::traits::{ Queue, Stack };
 
 
 /// A linked list struct containing "pointers" to Node structs.
@@ -15,9 +20,16 @@ use std::cell::RefCell;
 ///     Node to be created. 
 /// * `tail`: Weak reference to a Node, (will not count against Node being dropped, must be
 ///     resolved to an Option<RefCell<Node<T>>> in order to be accecssed with upgrade()
+/// * `length`: Number of Nodes in the list. Maintained by the LinkedList methods that add and
+///     remove Nodes.
+///
+/// The members are private, so no Rc clone of a Node in the list can be held outside it: a Node is
+/// only mutably borrowed by the list's own &mut self methods. That is what lets Iter and IterMut
+/// hand out plain references to values, tied to the borrow of the list.
 pub struct LinkedList<T> {
-    pub head: Option<Rc<RefCell<Node<T>>>>,
-    pub tail: Weak<RefCell<Node<T>>>
+    head: Option<Rc<RefCell<Node<T>>>>,
+    tail: Weak<RefCell<Node<T>>>,
+    length: usize
 }
 
 
@@ -40,13 +52,75 @@ pub struct LinkedList<T> {
 /// There is a danger in not using weak references for this Next member that two Nodes can
 /// point to each other and cause circular reference. For that reason, Node next values should
 /// only be updated using our defined methods with implementation.
-// #[derive(PartialEq, Eq)]
 pub struct Node<T> {
     pub value: T,
     next: Option<Rc<RefCell<Node<T>>>>
 }
 
 
+/// Error returned by LinkedList::insert_at and LinkedList::swap when an index is past the end of
+/// the list.
+///
+/// * `index`: the index that was passed in.
+/// * `len`: the length of the list at the time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexError {
+    pub index: usize,
+    pub len: usize
+}
+
+
+/// Error returned by LinkedList::check_no_leaks for the first Node with unexpected references.
+///
+/// * `index`: position of the Node, counting from 0 at the head.
+/// * `strong`: number of strong (Rc) references to the Node - 1 is expected (its predecessor's next
+///         member, or the head member).
+/// * `weak`: number of Weak references to the Node - 1 is expected for the tail Node (the tail
+///         member), 0 for the rest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RefCountError {
+    pub index: usize,
+    pub strong: usize,
+    pub weak: usize
+}
+
+
+/// Iterator yielding references to the values in a LinkedList struct, from head to tail. Created
+/// by LinkedList::iter.
+///
+/// * `next`: the Node holding the next value to be yielded (None once the tail has been yielded),
+///         borrowed from the LinkedList struct - which keeps every Node alive, and unborrowed
+///         mutably, while the iterator exists.
+pub struct Iter<'a, T> {
+    next: Option<&'a RefCell<Node<T>>>
+}
+
+
+/// Iterator yielding mutable references to the values in a LinkedList struct, from head to tail.
+/// Created by LinkedList::iter_mut.
+///
+/// No RefCell borrow is held between iteration steps (a held RefMut guard couldn't outlive the
+/// step that created it): the mutable LinkedList borrow is what guarantees no other reference to
+/// the values exists, as the list's members are private and nothing else can reach its Nodes.
+///
+/// * `next`: the Node holding the next value to be yielded, borrowed from the LinkedList struct.
+/// * `marker`: ties the yielded references to the mutable borrow of the LinkedList struct (and
+///         keeps T invariant, as for any iterator of mutable references).
+pub struct IterMut<'a, T> {
+    next: Option<&'a RefCell<Node<T>>>,
+    marker: PhantomData<&'a mut LinkedList<T>>
+}
+
+
+/// Iterator yielding the values in a LinkedList struct by value, from head to tail, consuming
+/// the list. Created by LinkedList::into_iter (from the IntoIterator trait).
+///
+/// * `list`: the LinkedList being consumed; each step dequeues its head.
+pub struct IntoIter<T> {
+    list: LinkedList<T>
+}
+
+
 
 // Method implementations for LinkedList struct
 impl<T> LinkedList<T> {
@@ -55,10 +129,46 @@ impl<T> LinkedList<T> {
     pub fn new() -> LinkedList<T> {
         LinkedList {
             head: None,
-            tail: Weak::new()   // calling upgrade on this returns None; empty allocation
+            tail: Weak::new(),  // calling upgrade on this returns None; empty allocation
+            length: 0
         }
     }
 
+    /// Return a new LinkedList holding the values of the input Vec, in the same order (the first
+    /// element becomes the head).
+    ///
+    /// * `values`: Vec of values to be moved into new Nodes.
+    pub fn from_vec(values: Vec<T>) -> LinkedList<T> {
+        let mut list = LinkedList::new();
+        for value in values {
+            list.add_value(value);
+        }
+        list
+    }
+
+    /// Return a Vec holding clones of the values in this LinkedList, from head to tail.
+    pub fn to_vec(&self) -> Vec<T> where T: Clone {
+        self.iter().cloned().collect()
+    }
+
+    /// Consume this LinkedList and return its values in a Vec, from head to tail. Values of Nodes
+    /// that are still shared can't be moved out, and are left out (see IntoIter).
+    pub fn into_vec(self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.length);
+        values.extend(self);
+        values
+    }
+
+    /// Return the number of values in this LinkedList, without traversing it.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this LinkedList holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
     /// Add a Node containing value T to the end of the Linked List (make the new Node the
     /// next member of the current tail Node)
     ///
@@ -79,14 +189,135 @@ impl<T> LinkedList<T> {
                 self.head = Some(new_node); 
             },
         }
+        self.length += 1;
+    }
+
+    /// Add a Node containing value T to the front of the Linked List (make the new Node the head,
+    /// with the current head as its next member), so the list can also be used as a stack.
+    ///
+    /// * `value`: T (matching the LinkedList's generic type parameter) to be stored in a new
+    ///         Node in the LinkedList
+    pub fn push_front(&mut self, value: T) {
+        let new_node: Rc<RefCell<Node<T>>> = Node::new_ref_wrapped(value);
+        match self.head.take() {
+            Some(old_head) => {
+                new_node.borrow_mut().assign_next(Some(old_head));
+            },
+            None => {
+                self.tail = Rc::downgrade(&new_node);
+            }
+        }
+        self.head = Some(new_node);
+        self.length += 1;
     }
     
-    /// Get a reference to the value in the head member (if the head member is not None)
+    /// Get a reference to the value in the head member (if the head member is not None). As for
+    /// Iter, the list's private members mean the Node can't be mutably borrowed while self is.
     pub fn peek_head_value(&self) -> Option<&T> {
-        // Option<T>.clone() -> Option<&T> (an Option<Rc<RefCell<Node<T>>>> we can consume as
-        //      it's a clone of self.head)
-        // Option<T>.map() - returns None or Some(T mapped)
-        self.head.clone().map(|rc| Node::peek_val(Rc::clone(&rc)) )
+        // Option<Rc<RefCell<Node>>>.as_deref() -> Option<&RefCell<Node>>, borrowed from self
+        let node_ref = self.head.as_deref()?;
+        // SAFETY: only the list's &mut self methods mutably borrow its Nodes
+        let node = unsafe { node_ref.try_borrow_unguarded() }.expect("Nodes aren't mutably borrowed while the list is borrowed");
+        Some(&node.value)
+    }
+
+    /// Get a reference to the value in the tail Node (if the list is not empty), through the tail
+    /// member - no traversal needed. See peek_head_value.
+    pub fn peek_tail_value(&self) -> Option<&T> {
+        // upgrade() checks the tail Node exists, but its Rc only lives until the end of this call
+        self.tail.upgrade()?;
+        // SAFETY: the tail Node exists, and the list holds a strong reference to it (as its
+        // predecessor's next member, or the head member) for as long as self is borrowed; only
+        // the list's &mut self methods mutably borrow its Nodes
+        let node = unsafe { (*self.tail.as_ptr()).try_borrow_unguarded() }.expect("Nodes aren't mutably borrowed while the list is borrowed");
+        Some(&node.value)
+    }
+
+    /// Return a RefCell guard of the value in the head Node (if the list is not empty). The Node
+    /// is borrowed until the guard is dropped.
+    pub fn peek_head(&self) -> Option<Ref<'_, T>> {
+        self.head.as_deref().map(Node::peek)
+    }
+
+    /// Return an iterator of references to the values in this LinkedList, from head to tail,
+    /// without removing any Nodes.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+
+    /// Return an iterator of mutable references to the values in this LinkedList, from head to
+    /// tail, so values can be updated in place without removing and re-adding Nodes.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref(),
+            marker: PhantomData
+        }
+    }
+
+    /// Return a reference to the value at the input position, walking index hops from the head
+    /// (O(n), unlike indexing a Vec), or None if the index is not less than len().
+    ///
+    /// * `index`: position of the value, counting from 0 at the head.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    /// Return a mutable reference to the value at the input position (see get). Walks with
+    /// IterMut, so the reference is exclusive for as long as the list is mutably borrowed.
+    ///
+    /// * `index`: position of the value, counting from 0 at the head.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.iter_mut().nth(index)
+    }
+
+    /// Return a reference to the value in the middle Node of this LinkedList (the second of the two
+    /// middle Nodes if the length is even), or None if the list is empty.
+    ///
+    /// Uses the slow/fast pointer technique, without relying on the length member: the fast
+    /// pointer moves two Nodes for every one the slow pointer moves, so the slow pointer is
+    /// halfway when the fast one reaches the end. Both pointers are Iter structs, so the values
+    /// are borrowed from the list like any other iteration.
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.iter();
+        let mut fast = self.iter();
+        // each round the fast pointer passes two Nodes and the slow pointer one
+        while fast.next().is_some() && fast.next().is_some() {
+            slow.next();
+        }
+        slow.next()
+    }
+
+    /// Return a reference to the value n Nodes before the tail (n = 0 is the tail), or None if the
+    /// list is not longer than n.
+    ///
+    /// Uses the two pointer technique in a single pass: a lead pointer starts n Nodes ahead, then
+    /// both move together until the lead pointer reaches the tail. As in middle, both pointers are
+    /// Iter structs.
+    ///
+    /// * `n`: distance from the tail, counting from 0.
+    pub fn nth_from_end(&self, n: usize) -> Option<&T> {
+        let mut lead = self.iter();
+        lead.nth(n)?;
+        let mut trail = self.iter();
+        while lead.next().is_some() {
+            trail.next();
+        }
+        trail.next()
+    }
+
+    /// Return whether any Node in this LinkedList holds a value equal to the input value.
+    ///
+    /// * `value`: value to compare Node values against with ==.
+    pub fn contains(&self, value: &T) -> bool where T: PartialEq {
+        self.iter().any(|node_value| node_value == value)
+    }
+
+    /// Return a reference to the first value in this LinkedList (from the head) that the input
+    /// predicate returns true for, or None if there is none.
+    ///
+    /// * `pred`: closure to test each value with.
+    pub fn find<F: Fn(&T) -> bool>(&self, pred: F) -> Option<&T> {
+        self.iter().find(|node_value| pred(node_value))
     }
 
     /// Removes the current head from the LinkedList and returns it.
@@ -95,13 +326,387 @@ impl<T> LinkedList<T> {
         match self.head.take() {
             Some(node_rc) => {
                 let new_head: Option<Rc<RefCell<Node<T>>>> = node_rc.borrow_mut().get_next();
+                if new_head.is_none() {
+                    // the returned Node may be kept alive - don't let add_value append to it
+                    self.tail = Weak::new();
+                }
                 self.head = new_head;
+                self.length -= 1;
                 Some(node_rc)
             },
             None => None
         }
     }
 
+    /// Remove the head Node from the LinkedList and return its owned value, unwrapping it with
+    /// Rc::try_unwrap and RefCell::into_inner so callers don't handle Rc<RefCell<Node>>s.
+    ///
+    /// Return None if the list is empty. If the head Node is somehow still shared (an Rc clone of
+    ///     it is held outside the list), its value can't be moved out: None is returned and the
+    ///     list is left unchanged, rather than dropping the Node from the list.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if Rc::strong_count(self.head.as_ref()?) > 1 {
+            return None;
+        }
+        let node_rc = self.dequeue()?;
+        Rc::try_unwrap(node_rc).ok().map(|node_refcell| node_refcell.into_inner().consume_get_val())
+    }
+
+    /// Remove the first Node holding a value equal to the input value from the LinkedList, and
+    /// return whether one was found.
+    ///
+    /// Walks the list keeping the Node before the current one, so a matching Node can be unlinked
+    /// by pointing its predecessor's next member past it (or dequeued, if it is the head). If the
+    /// removed Node was the tail, its predecessor becomes the tail.
+    ///
+    /// * `value`: value to compare Node values against with ==.
+    pub fn remove(&mut self, value: &T) -> bool where T: PartialEq {
+        let Some(head) = self.head.clone() else {
+            return false;
+        };
+        if head.borrow().value == *value {
+            self.dequeue();
+            return true;
+        }
+
+        let mut prev = head;
+        loop {
+            let Some(current) = prev.borrow().next.clone() else {
+                return false;
+            };
+            if current.borrow().value == *value {
+                self.unlink_next(&prev);
+                return true;
+            }
+            prev = current;
+        }
+    }
+
+    /// Insert a Node containing value T at the input position, so that it ends up with index
+    /// nodes before it. Index 0 inserts at the head and index len() appends at the tail; anything
+    /// in between walks to the Node before the position and splices the new Node in after it with
+    /// splice_in_next.
+    ///
+    /// Return an IndexError if the index is greater than len().
+    ///
+    /// * `index`: position for the new Node, from 0 to len() inclusive.
+    /// * `value`: T to be stored in the new Node.
+    pub fn insert_at(&mut self, index: usize, value: T) -> Result<(), IndexError> {
+        if index > self.length {
+            return Err(IndexError { index, len: self.length });
+        }
+        if index == 0 {
+            self.push_front(value);
+        } else if index == self.length {
+            self.add_value(value);
+        } else {
+            let prev = self.node_at(index - 1).expect("index checked against length");
+            prev.borrow_mut().splice_in_next(Some(Node::new_ref_wrapped(value)));
+            self.length += 1;
+        }
+        Ok(())
+    }
+
+    /// Insert a Node containing value T after the last Node whose value is less than or equal to it,
+    /// in a single walk from the head - if the list is sorted, it stays sorted, and equal values
+    /// keep their insertion order.
+    ///
+    /// * `value`: T to be stored in the new Node.
+    pub(crate) fn insert_sorted(&mut self, value: T) where T: Ord {
+        let goes_first = match &self.head {
+            Some(head) => value < head.borrow().value,
+            None => true
+        };
+        if goes_first {
+            self.push_front(value);
+            return;
+        }
+
+        let mut prev = self.head.clone().expect("empty list inserts at the head");
+        loop {
+            let next = prev.borrow().next.clone();
+            match next {
+                Some(next_rc) if next_rc.borrow().value <= value => { prev = next_rc; },
+                Some(_) => break,
+                None => {
+                    // prev is the tail
+                    self.add_value(value);
+                    return;
+                }
+            }
+        }
+        prev.borrow_mut().splice_in_next(Some(Node::new_ref_wrapped(value)));
+        self.length += 1;
+    }
+
+    /// Remove the Node at the input position (with index nodes before it) and return its value, or
+    /// None if the index is not less than len().
+    ///
+    /// * `index`: position of the Node to remove, from 0 to len() exclusive.
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.length {
+            return None;
+        }
+        let node_rc = if index == 0 {
+            self.dequeue()?
+        } else {
+            let prev = self.node_at(index - 1)?;
+            self.unlink_next(&prev)?
+        };
+        Rc::try_unwrap(node_rc).ok().map(|node_refcell| node_refcell.into_inner().consume_get_val())
+    }
+
+    /// Swap the values at the two input positions. The values are swapped between the Nodes
+    /// rather than relinking the Nodes, so the head and tail members never need updating, and
+    /// both Nodes are found in a single walk from the head.
+    ///
+    /// Return an IndexError (for the larger index) if either index is not less than len().
+    ///
+    /// * `i`: position of the first value, counting from 0 at the head.
+    /// * `j`: position of the second value.
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<(), IndexError> {
+        let (low, high) = if i <= j { (i, j) } else { (j, i) };
+        if high >= self.length {
+            return Err(IndexError { index: high, len: self.length });
+        }
+        if low == high {
+            return Ok(());
+        }
+        let low_node = self.node_at(low).expect("index checked against length");
+        let mut high_node = Rc::clone(&low_node);
+        for _ in low..high {
+            let next = high_node.borrow().next.clone().expect("index checked against length");
+            high_node = next;
+        }
+        std::mem::swap(&mut low_node.borrow_mut().value, &mut high_node.borrow_mut().value);
+        Ok(())
+    }
+
+    /// Split this LinkedList in two at the input position: this list keeps the first at Nodes and
+    /// the rest are returned as a new LinkedList, with no Nodes copied.
+    ///
+    /// Walks to the Node before the position and cuts its next member; the returned list takes
+    /// this list's tail, and the Node before the cut becomes this list's tail.
+    ///
+    /// Panics if at is greater than len(), like Vec::split_off.
+    ///
+    /// * `at`: number of Nodes for this list to keep, from 0 to len() inclusive.
+    pub fn split_off(&mut self, at: usize) -> LinkedList<T> {
+        if at > self.length {
+            panic!("{}", IndexError { index: at, len: self.length });
+        }
+        if at == 0 {
+            return std::mem::replace(self, LinkedList::new());
+        }
+
+        let mut rest = LinkedList::new();
+        if at < self.length {
+            let last_kept = self.node_at(at - 1).expect("at checked against length");
+            rest.head = last_kept.borrow_mut().get_next();
+            rest.tail = std::mem::replace(&mut self.tail, Rc::downgrade(&last_kept));
+            rest.length = self.length - at;
+            self.length = at;
+        }
+        rest
+    }
+
+    /// Append clones of the values in the input slice to the end of the list, in order (see
+    /// link_chain).
+    ///
+    /// * `values`: slice of values to be cloned into new Nodes.
+    pub fn extend_from_slice(&mut self, values: &[T]) where T: Clone {
+        self.link_chain(values.iter().cloned());
+    }
+
+    /// Append the input values to the end of the list in bulk: build them into a detached chain of
+    /// Nodes first, then link the whole chain to the tail in one splice, so the tail member is
+    /// only updated once rather than once per value.
+    ///
+    /// * `values`: values to be stored in new Nodes, in order.
+    fn link_chain<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        let mut values = values.into_iter();
+        let Some(first_value) = values.next() else {
+            return;
+        };
+        let chain_head = Node::new_ref_wrapped(first_value);
+        let mut chain_tail = Rc::clone(&chain_head);
+        let mut chain_length = 1;
+        for value in values {
+            let new_node = Node::new_ref_wrapped(value);
+            chain_tail.borrow_mut().assign_next(Some(Rc::clone(&new_node)));
+            chain_tail = new_node;
+            chain_length += 1;
+        }
+
+        match self.tail.upgrade() {
+            Some(tail) => tail.borrow_mut().assign_next(Some(chain_head)),
+            None => { self.head = Some(chain_head); }
+        }
+        self.tail = Rc::downgrade(&chain_tail);
+        self.length += chain_length;
+    }
+
+    /// Move every Node of the other LinkedList onto the end of this one in O(1), leaving the
+    /// other list empty: this list's tail Node is linked to the other's head, and this list adopts
+    /// the other's tail member.
+    ///
+    /// * `other`: LinkedList whose Nodes are moved to the end of this one.
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        let Some(other_head) = other.head.take() else {
+            return;
+        };
+        match self.tail.upgrade() {
+            Some(tail) => tail.borrow_mut().assign_next(Some(other_head)),
+            None => { self.head = Some(other_head); }
+        }
+        self.tail = std::mem::replace(&mut other.tail, Weak::new());
+        self.length += other.length;
+        other.length = 0;
+    }
+
+    /// Remove consecutive Nodes holding equal values, keeping the first of each run, so a sorted
+    /// list ends up with no duplicates (see dedup_by).
+    pub fn dedup(&mut self) where T: PartialEq {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Remove consecutive Nodes whose values map to equal keys, keeping the first of each run
+    /// (see dedup_by).
+    ///
+    /// * `key`: closure mapping a value to the key it is compared by.
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Remove each Node whose value the input closure considers the same as the value of the last
+    /// Node kept before it. Removed Nodes are unlinked with unlink_next, so no Nodes are copied,
+    /// and the tail member follows if the last Node is removed.
+    ///
+    /// * `same_bucket`: closure called with (last kept value, following value), returning whether
+    ///         the following Node should be removed.
+    pub fn dedup_by<F: FnMut(&T, &T) -> bool>(&mut self, mut same_bucket: F) {
+        let Some(mut kept) = self.head.clone() else {
+            return;
+        };
+        loop {
+            let Some(next) = kept.borrow().next.clone() else {
+                break;
+            };
+            if same_bucket(&kept.borrow().value, &next.borrow().value) {
+                self.unlink_next(&kept);
+            } else {
+                kept = next;
+            }
+        }
+    }
+
+    /// Merge two sorted LinkedLists into one sorted LinkedList by relinking their Nodes - no Nodes
+    /// are allocated. Where values are equal, Nodes from the first list go first.
+    ///
+    /// The merged tail is whichever input tail ends up with no next Node, so no traversal is
+    /// needed to find it.
+    ///
+    /// * `first`: sorted LinkedList to be merged.
+    /// * `second`: sorted LinkedList to be merged.
+    pub fn merge_sorted(mut first: LinkedList<T>, mut second: LinkedList<T>) -> LinkedList<T> where T: Ord {
+        let mut merged = LinkedList::new();
+        merged.head = Node::merge_sorted_chains(first.head.take(), second.head.take(), &mut T::cmp);
+        merged.tail = match first.tail.upgrade() {
+            Some(first_tail) if first_tail.borrow().next.is_none() => Rc::downgrade(&first_tail),
+            _ => second.tail.clone()
+        };
+        merged.length = first.length + second.length;
+        merged
+    }
+
+    /// Sort the values in this LinkedList in ascending order (see sort_by).
+    pub fn sort(&mut self) where T: Ord {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    /// Sort the values in this LinkedList with a comparator, using merge sort on the Nodes
+    /// themselves: Nodes are relinked, never copied out of the list or reallocated.
+    ///
+    /// The sort is stable (equal values keep their order) and takes O(n log n) time. Recursion
+    /// depth is O(log n), as each level halves the chain being sorted.
+    ///
+    /// * `compare`: comparator closure returning how its first argument is ordered relative to its
+    ///         second.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let sorted_head = Node::merge_sort(self.head.take(), self.length, &mut compare);
+        self.head = sorted_head;
+
+        // the tail Node may have moved - find the new one
+        self.tail = match self.node_at(self.length.saturating_sub(1)) {
+            Some(tail) => Rc::downgrade(&tail),
+            None => Weak::new()
+        };
+    }
+
+    /// Return the number of strong (Rc) references to each Node, from head to tail, not counting
+    /// the Rc clone used to walk the list. Every count should be 1 - anything more is an Rc clone
+    /// held outside the list (which only a bug in the list's own methods could leave), which keeps
+    /// the Node alive after it is removed from the list.
+    pub fn debug_ref_counts(&self) -> Vec<usize> {
+        self.ref_counts().into_iter().map(|(strong, _)| strong).collect()
+    }
+
+    /// Walk the list and check that no Node is referenced from outside it: each Node must have
+    /// exactly one strong reference, and only the tail Node may have a Weak reference (the tail
+    /// member). Also fails if the tail member doesn't point to the last Node.
+    ///
+    /// Return a RefCountError for the first Node that fails the check.
+    pub fn check_no_leaks(&self) -> Result<(), RefCountError> {
+        let counts = self.ref_counts();
+        for (index, &(strong, weak)) in counts.iter().enumerate() {
+            let expected_weak = usize::from(index + 1 == counts.len());
+            if strong != 1 || weak != expected_weak {
+                return Err(RefCountError { index, strong, weak });
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the (strong, weak) reference counts of each Node, from head to tail, not counting
+    /// the Rc clone used to walk the list.
+    fn ref_counts(&self) -> Vec<(usize, usize)> {
+        let mut counts = Vec::with_capacity(self.length);
+        let mut opt_node = self.head.clone();
+        while let Some(node_rc) = opt_node {
+            counts.push((Rc::strong_count(&node_rc) - 1, Rc::weak_count(&node_rc)));
+            opt_node = node_rc.borrow().next.clone();
+        }
+        counts
+    }
+
+    /// Return an Rc clone of the Node at the input position, walking from the head, or None if the
+    /// list is shorter than that.
+    ///
+    /// * `index`: position of the Node, counting from 0 at the head.
+    fn node_at(&self, index: usize) -> Option<Rc<RefCell<Node<T>>>> {
+        let mut node_rc = self.head.clone()?;
+        for _ in 0..index {
+            let next = node_rc.borrow().next.clone()?;
+            node_rc = next;
+        }
+        Some(node_rc)
+    }
+
+    /// Unlink the Node after the input Node from the list, by pointing the input Node's next
+    /// member past it, and return it. If it was the tail, the input Node becomes the tail.
+    ///
+    /// * `prev`: Node in this list whose next Node is to be removed.
+    fn unlink_next(&mut self, prev: &Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
+        let removed = prev.borrow_mut().get_next()?;
+        let after = removed.borrow_mut().get_next();
+        if after.is_none() {
+            self.tail = Rc::downgrade(prev);
+        }
+        prev.borrow_mut().assign_next(after);
+        self.length -= 1;
+        Some(removed)
+    }
+
     /// Remove current head from the LinkedList, consume it, and return its value (unless the
     /// current head is None).
     pub fn dequeue_value(&mut self) -> Option<T> {
@@ -149,10 +754,29 @@ impl<T> Node<T> {
     ///     WARNING: Funny story: you should call Rc::cloneto create node_ref so that it is not 
     ///     the only reference to the Rc value passed in here, or else the returned value may be
     ///     invalidated/dropped when the input node_ref Rc drops at the end of this function
+    #[deprecated(note = "the returned reference can outlive the Node; use Node::peek or Node::with_value")]
     pub fn peek_val<'a>(node_ref: Rc<RefCell<Node<T>>>) -> &'a T {
         unsafe { &(*node_ref.as_ptr()).value }
     }
 
+    /// Borrow the Node in the input RefCell and return a guard of its value. While the guard
+    /// exists, RefCell panics on any attempt to mutably borrow the Node (e.g. to assign its next
+    /// member), instead of leaving a dangling reference.
+    ///
+    /// * `node_ref`: RefCell-wrapped Node to peek into - an &Rc<RefCell<Node>> derefs to this.
+    pub fn peek(node_ref: &RefCell<Node<T>>) -> Ref<'_, T> {
+        Ref::map(node_ref.borrow(), |node| &node.value)
+    }
+
+    /// Call the input closure with a reference to the value in the input Node and return its
+    /// result. The Node is only borrowed for the duration of the call.
+    ///
+    /// * `node_ref`: RefCell-wrapped Node holding the value.
+    /// * `f`: closure to call with the value.
+    pub fn with_value<R, F: FnOnce(&T) -> R>(node_ref: &RefCell<Node<T>>, f: F) -> R {
+        f(&node_ref.borrow().value)
+    }
+
     /// Consume this Node and return its value
     fn consume_get_val(self) -> T {
         self.value
@@ -194,6 +818,82 @@ impl<T> Node<T> {
         }
     }
 
+    /// Recursively merge sort the chain of Nodes starting at the input Node, returning the head of
+    /// the sorted chain: split the chain in two halves, sort each, and merge them.
+    ///
+    /// * `head`: Option-wrapped first Node of the chain.
+    /// * `length`: number of Nodes in the chain.
+    /// * `compare`: mutable reference to the comparator closure.
+    fn merge_sort<F: FnMut(&T, &T) -> Ordering>(
+        head: Option<Rc<RefCell<Node<T>>>>,
+        length: usize,
+        compare: &mut F
+    ) -> Option<Rc<RefCell<Node<T>>>> {
+        if length <= 1 {
+            return head;
+        }
+        let first_length = length / 2;
+
+        // walk to the last Node of the first half and cut the chain after it
+        let mut last_of_first = head.clone()?;
+        for _ in 1..first_length {
+            let next = last_of_first.borrow().next.clone()?;
+            last_of_first = next;
+        }
+        let second_head = last_of_first.borrow_mut().get_next();
+
+        let first = Node::merge_sort(head, first_length, compare);
+        let second = Node::merge_sort(second_head, length - first_length, compare);
+        Node::merge_sorted_chains(first, second, compare)
+    }
+
+    /// Merge two sorted chains of Nodes into one sorted chain by relinking them, and return its
+    /// head. Where values are equal, the Node from the first chain goes first, keeping the merge
+    /// stable.
+    ///
+    /// * `first`: Option-wrapped head of the first sorted chain.
+    /// * `second`: Option-wrapped head of the second sorted chain.
+    /// * `compare`: mutable reference to the comparator closure.
+    fn merge_sorted_chains<F: FnMut(&T, &T) -> Ordering>(
+        mut first: Option<Rc<RefCell<Node<T>>>>,
+        mut second: Option<Rc<RefCell<Node<T>>>>,
+        compare: &mut F
+    ) -> Option<Rc<RefCell<Node<T>>>> {
+        let mut merged_head: Option<Rc<RefCell<Node<T>>>> = None;
+        let mut merged_tail: Option<Rc<RefCell<Node<T>>>> = None;
+
+        loop {
+            let next_node = match (&first, &second) {
+                (Some(first_rc), Some(second_rc)) => {
+                    if compare(&first_rc.borrow().value, &second_rc.borrow().value) == Ordering::Greater {
+                        let node = second.take().unwrap();
+                        second = node.borrow_mut().get_next();
+                        node
+                    } else {
+                        let node = first.take().unwrap();
+                        first = node.borrow_mut().get_next();
+                        node
+                    }
+                },
+                // one chain is used up: the rest of the other is already sorted, so link it whole
+                _ => {
+                    let rest = first.take().or(second.take());
+                    match &merged_tail {
+                        Some(tail) => tail.borrow_mut().assign_next(rest),
+                        None => { merged_head = rest; }
+                    }
+                    return merged_head;
+                }
+            };
+
+            match &merged_tail {
+                Some(tail) => tail.borrow_mut().assign_next(Some(Rc::clone(&next_node))),
+                None => { merged_head = Some(Rc::clone(&next_node)); }
+            }
+            merged_tail = Some(next_node);
+        }
+    }
+
     /// A function that solely invalidates the Node's next member and returns it.
     fn get_next(&mut self) -> Option<Rc<RefCell<Node<T>>>> {
         self.next.take()
@@ -202,6 +902,212 @@ impl<T> Node<T> {
 }
 
 
+// Append values in bulk (see link_chain).
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.link_chain(iter);
+    }
+}
+
+// Deep copy: a derived Clone would copy the head Rc and share every Node with the original list.
+impl<T> Clone for LinkedList<T> where T: Clone {
+
+    /// Return a new LinkedList with a new Node for each value in this one, in the same order.
+    ///     add_value points the copy's tail member at its own last Node.
+    fn clone(&self) -> Self {
+        let mut copy = LinkedList::new();
+        for value in self.iter() {
+            copy.add_value(value.clone());
+        }
+        copy
+    }
+}
+
+// Queue implementation for LinkedList struct: enqueue at the tail, dequeue owned values from the
+// head. Called as Queue::dequeue, as the inherent dequeue method returns the head Node itself.
+impl<T> Queue<T> for LinkedList<T> {
+    fn enqueue(&mut self, value: T) {
+        self.add_value(value);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        self.peek_head_value()
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+// Stack implementation for LinkedList struct: the head is the top of the stack, so push and pop
+// are both O(1).
+impl<T> Stack<T> for LinkedList<T> {
+    fn push(&mut self, value: T) {
+        self.push_front(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_head_value()
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+// Lists are equal when they hold equal values in the same order; comparing the Rc<RefCell>> Nodes
+// themselves isn't possible without borrowing each one, so compare the iterated values.
+impl<T> PartialEq for LinkedList<T> where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Eq for LinkedList<T> where T: Eq {}
+
+// Format as the sequence of values, e.g. [2, 4], so lists can be used in assert_eq!
+impl<T> fmt::Debug for LinkedList<T> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Serialize a LinkedList struct as the sequence of its values, from head to tail.
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for LinkedList<T> where T: serde::Serialize {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+// Deserialize a LinkedList struct from a sequence of values, rebuilding the Node chain in order
+// with add_value so the tail member points to the last Node (see from_vec).
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for LinkedList<T> where T: serde::Deserialize<'de> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values: Vec<T> = Vec::deserialize(deserializer)?;
+        Ok(LinkedList::from_vec(values))
+    }
+}
+
+// Drop Nodes one at a time from the head, so long lists don't drop their next chain recursively.
+impl<T> Drop for LinkedList<T> {
+
+    /// Unlink each Node from the next one before it is dropped. A Node that is still shared (an Rc
+    ///     clone of it is held outside the list) is left intact along with the rest of the chain,
+    ///     which stays reachable through that clone.
+    fn drop(&mut self) {
+        let mut opt_node = self.head.take();
+        while let Some(node_rc) = opt_node {
+            opt_node = match Rc::try_unwrap(node_rc) {
+                Ok(node_refcell) => node_refcell.into_inner().get_next(),
+                Err(_) => None
+            };
+        }
+    }
+}
+
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index {} is out of bounds for a list of length {}", self.index, self.len)
+    }
+}
+
+impl std::error::Error for IndexError {}
+
+impl fmt::Display for RefCountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "node {} has unexpected references: {} strong, {} weak", self.index, self.strong, self.weak)
+    }
+}
+
+impl std::error::Error for RefCountError {}
+
+
+// Iterator implementation for Iter struct
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_ref = self.next.take()?;
+        // SAFETY: the LinkedList borrowed for 'a holds the Node, and only the list's &mut self
+        // methods can mutably borrow it (its members are private), so no RefMut exists for 'a
+        let node = unsafe { node_ref.try_borrow_unguarded() }.expect("Nodes aren't mutably borrowed while the list is borrowed");
+        self.next = node.next.as_deref();
+        Some(&node.value)
+    }
+}
+
+
+// Iterator implementation for IterMut struct
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_ref = self.next.take()?;
+        // SAFETY: the LinkedList is mutably borrowed for 'a and its members are private, so
+        // nothing else can borrow the Node, and each Node is visited once - this is the only
+        // reference to it. The list holds the Node for 'a.
+        let Node { value, next } = unsafe { &mut *node_ref.as_ptr() };
+        self.next = next.as_deref();
+        Some(value)
+    }
+}
+
+
+// Iterator implementation for IntoIter struct
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    /// Dequeue the head Node and unwrap its Rc to take its value. A Node that is still shared (an
+    ///     Rc clone of it is held outside the list) can't be unwrapped, so it is skipped.
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node_rc) = self.list.dequeue() {
+            if let Ok(node_refcell) = Rc::try_unwrap(node_rc) {
+                return Some(node_refcell.into_inner().consume_get_val());
+            }
+        }
+        None
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+
 ////////////////////////////////////////////////////////////////////////////
 //  TESTS
 
@@ -224,6 +1130,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn node_peek_val_works() {
         let mut basic_ll = setup_linked_list();
         let a = basic_ll.dequeue().unwrap();
@@ -242,6 +1149,581 @@ mod tests {
         assert!(result_2.is_some_and(|x| *x == 4));
     }
 
+    #[test]
+    fn iter_works() {
+        let mut basic_ll = setup_linked_list();
+        basic_ll.add_value(6);
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&2, &4, &6]);
+        assert_eq!(basic_ll.iter().map(|x| x * 10).sum::<u32>(), 120);
+
+        let mut count = 0;
+        for value in basic_ll.iter() {
+            assert_eq!(*value, 2 * (count + 1));
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        // iterating doesn't remove anything
+        assert_eq!(basic_ll.peek_head_value(), Some(&2));
+        assert_eq!(LinkedList::<u32>::new().iter().next(), None);
+    }
+
+    #[test]
+    fn iter_mut_works() {
+        let mut basic_ll = setup_linked_list();
+        for value in basic_ll.iter_mut() {
+            *value += 1;
+        }
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&3, &5]);
+
+        if let Some(value) = basic_ll.iter_mut().last() {
+            *value = 10;
+        }
+        assert_eq!(basic_ll.dequeue_value(), Some(3));
+        assert_eq!(basic_ll.dequeue_value(), Some(10));
+        assert_eq!(basic_ll.iter_mut().next(), None);
+    }
+
+    #[test]
+    fn into_iter_works() {
+        let mut basic_ll: LinkedList<String> = LinkedList::new();
+        basic_ll.add_value(String::from("a"));
+        basic_ll.add_value(String::from("b"));
+        for value in &mut basic_ll {
+            value.push('!');
+        }
+        for value in &basic_ll {
+            assert!(value.ends_with('!'));
+        }
+
+        let values: Vec<String> = basic_ll.into_iter().collect();
+        assert_eq!(values, vec![String::from("a!"), String::from("b!")]);
+
+        // a Node shared outside the list is skipped rather than ending iteration
+        let mut basic_ll = setup_linked_list();
+        basic_ll.add_value(6);
+        let shared = basic_ll.head.clone().unwrap();
+        assert_eq!(basic_ll.into_iter().collect::<Vec<_>>(), vec![4, 6]);
+        assert_eq!(shared.borrow().value, 2);
+    }
+
+    #[test]
+    fn len_works() {
+        let mut basic_ll = setup_linked_list();
+        assert_eq!(basic_ll.len(), 2);
+        assert!(!basic_ll.is_empty());
+
+        basic_ll.add_value(6);
+        assert_eq!(basic_ll.len(), 3);
+        basic_ll.dequeue();
+        assert_eq!(basic_ll.dequeue_value(), Some(4));
+        assert_eq!(basic_ll.len(), 1);
+        basic_ll.dequeue_value();
+        assert!(basic_ll.is_empty());
+
+        // dequeuing an empty list leaves the length at 0
+        assert_eq!(basic_ll.dequeue_value(), None);
+        assert_eq!(basic_ll.len(), 0);
+    }
+
+    #[test]
+    fn push_front_works() {
+        let mut basic_ll = setup_linked_list();
+        basic_ll.push_front(0);
+        assert_eq!(basic_ll.peek_head_value(), Some(&0));
+        assert_eq!(basic_ll.len(), 3);
+
+        // pushing to the front of an empty list sets the tail too
+        let mut stack: LinkedList<u32> = LinkedList::new();
+        stack.push_front(1);
+        stack.add_value(2);
+        stack.push_front(3);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &1, &2]);
+        assert_eq!(stack.dequeue_value(), Some(3));
+        assert_eq!(stack.dequeue_value(), Some(1));
+    }
+
+    #[test]
+    fn remove_works() {
+        let mut basic_ll = setup_linked_list();
+        for value in [6, 4, 8] {
+            basic_ll.add_value(value);
+        }
+        assert!(basic_ll.remove(&4));
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&2, &6, &4, &8]);
+        assert!(!basic_ll.remove(&5));
+
+        // removing the tail makes its predecessor the tail
+        assert!(basic_ll.remove(&8));
+        basic_ll.add_value(10);
+        assert!(basic_ll.remove(&2));
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&6, &4, &10]);
+        assert_eq!(basic_ll.len(), 3);
+
+        for value in [4, 10, 6] {
+            assert!(basic_ll.remove(&value));
+        }
+        assert!(basic_ll.is_empty());
+        assert!(!basic_ll.remove(&6));
+        basic_ll.add_value(1);
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&1]);
+    }
+
+    #[test]
+    fn positional_insert_and_remove_work() {
+        let mut basic_ll = setup_linked_list();
+        assert_eq!(basic_ll.insert_at(1, 3), Ok(()));
+        assert_eq!(basic_ll.insert_at(0, 1), Ok(()));
+        assert_eq!(basic_ll.insert_at(4, 5), Ok(()));
+        assert_eq!(basic_ll.insert_at(6, 9), Err(IndexError { index: 6, len: 5 }));
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+        assert_eq!(basic_ll.len(), 5);
+
+        assert_eq!(basic_ll.remove_at(2), Some(3));
+        assert_eq!(basic_ll.remove_at(0), Some(1));
+        assert_eq!(basic_ll.remove_at(2), Some(5));
+        assert_eq!(basic_ll.remove_at(2), None);
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&2, &4]);
+
+        // the tail was fixed up, so appending still works
+        basic_ll.add_value(6);
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&2, &4, &6]);
+        assert_eq!(
+            IndexError { index: 6, len: 5 }.to_string(),
+            "index 6 is out of bounds for a list of length 5"
+        );
+    }
+
+    #[test]
+    fn dequeued_node_is_not_appended_to() {
+        let mut basic_ll = setup_linked_list();
+        basic_ll.dequeue();
+        let last = basic_ll.dequeue();
+        basic_ll.add_value(6);
+        assert!(last.is_some_and(|node| node.borrow_mut().get_next().is_none()));
+        assert_eq!(basic_ll.peek_head_value(), Some(&6));
+    }
+
+    #[test]
+    fn contains_and_find_work() {
+        let mut basic_ll = setup_linked_list();
+        basic_ll.add_value(7);
+        assert!(basic_ll.contains(&4));
+        assert!(!basic_ll.contains(&5));
+        assert_eq!(basic_ll.find(|x| *x > 3), Some(&4));
+        assert_eq!(basic_ll.find(|x| x % 2 == 1), Some(&7));
+        assert_eq!(basic_ll.find(|x| *x > 10), None);
+        assert_eq!(basic_ll.len(), 3);
+    }
+
+    #[test]
+    fn long_linked_list_can_be_dropped() {
+        let mut long_ll: LinkedList<u32> = LinkedList::new();
+        for value in 0..500_000 {
+            long_ll.add_value(value);
+        }
+        drop(long_ll);
+
+        // a shared Node keeps the rest of its chain when the list is dropped
+        let shared_head = {
+            let basic_ll = setup_linked_list();
+            basic_ll.head.clone().unwrap()
+        };
+        let next = shared_head.borrow().next.clone().unwrap();
+        assert_eq!(next.borrow().value, 4);
+    }
+
+    #[test]
+    fn clone_copies_every_node() {
+        let basic_ll = setup_linked_list();
+        let mut copy = basic_ll.clone();
+        assert_eq!(copy.len(), 2);
+        assert!(!Rc::ptr_eq(basic_ll.head.as_ref().unwrap(), copy.head.as_ref().unwrap()));
+
+        // the copy has its own tail, and mutating it leaves the original untouched
+        copy.add_value(6);
+        for value in copy.iter_mut() {
+            *value += 1;
+        }
+        assert_eq!(copy.iter().collect::<Vec<_>>(), vec![&3, &5, &7]);
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&2, &4]);
+        assert!(LinkedList::<u32>::new().clone().is_empty());
+    }
+
+    #[test]
+    fn linked_lists_compare_by_values() {
+        let basic_ll = setup_linked_list();
+        let mut other = LinkedList::new();
+        other.push_front(4);
+        other.push_front(2);
+        assert_eq!(basic_ll, other);
+        assert_eq!(basic_ll, basic_ll.clone());
+
+        other.add_value(6);
+        assert_ne!(basic_ll, other);
+        other.remove(&6);
+        other.remove(&2);
+        other.add_value(2);
+        assert_ne!(basic_ll, other);
+        assert_eq!(format!("{:?}", other), "[4, 2]");
+        assert_eq!(LinkedList::<u32>::new(), LinkedList::new());
+    }
+
+    #[test]
+    fn peek_tail_value_works() {
+        let mut basic_ll = setup_linked_list();
+        assert_eq!(basic_ll.peek_tail_value(), Some(&4));
+        basic_ll.add_value(6);
+        assert_eq!(basic_ll.peek_tail_value(), Some(&6));
+        basic_ll.push_front(0);
+        assert_eq!(basic_ll.peek_tail_value(), Some(&6));
+
+        while basic_ll.dequeue_value().is_some() {}
+        assert_eq!(basic_ll.peek_tail_value(), None);
+        basic_ll.push_front(1);
+        assert_eq!(basic_ll.peek_tail_value(), Some(&1));
+    }
+
+    #[test]
+    fn pop_front_works() {
+        let mut basic_ll: LinkedList<String> = LinkedList::new();
+        basic_ll.add_value(String::from("a"));
+        basic_ll.add_value(String::from("b"));
+        assert_eq!(basic_ll.pop_front(), Some(String::from("a")));
+
+        // a shared head can't be unwrapped, so it stays in the list
+        let shared_head = basic_ll.head.clone();
+        assert_eq!(basic_ll.pop_front(), None);
+        assert_eq!(basic_ll.len(), 1);
+        drop(shared_head);
+
+        assert_eq!(basic_ll.pop_front(), Some(String::from("b")));
+        assert_eq!(basic_ll.pop_front(), None);
+        assert!(basic_ll.is_empty());
+    }
+
+    #[test]
+    fn split_off_works() {
+        let mut basic_ll: LinkedList<u32> = LinkedList::new();
+        for value in 1..=5 {
+            basic_ll.add_value(value);
+        }
+        let mut rest = basic_ll.split_off(2);
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(rest.iter().collect::<Vec<_>>(), vec![&3, &4, &5]);
+        assert_eq!((basic_ll.len(), rest.len()), (2, 3));
+
+        // both tails were updated
+        basic_ll.add_value(10);
+        rest.add_value(20);
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&1, &2, &10]);
+        assert_eq!(rest.iter().collect::<Vec<_>>(), vec![&3, &4, &5, &20]);
+
+        let empty_rest = basic_ll.split_off(3);
+        assert!(empty_rest.is_empty());
+        let whole = basic_ll.split_off(0);
+        assert!(basic_ll.is_empty());
+        assert_eq!(whole.len(), 3);
+        basic_ll.add_value(7);
+        assert_eq!(basic_ll.peek_head_value(), Some(&7));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of bounds")]
+    fn split_off_panics_past_the_end() {
+        let mut basic_ll = setup_linked_list();
+        basic_ll.split_off(3);
+    }
+
+    #[test]
+    fn append_works() {
+        let mut basic_ll = setup_linked_list();
+        let mut other = setup_linked_list();
+        other.add_value(6);
+        basic_ll.append(&mut other);
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&2, &4, &2, &4, &6]);
+        assert_eq!(basic_ll.len(), 5);
+        assert_eq!(basic_ll.peek_tail_value(), Some(&6));
+        assert!(other.is_empty());
+        assert_eq!(other.peek_tail_value(), None);
+
+        // appending to or from an empty list
+        basic_ll.append(&mut other);
+        assert_eq!(basic_ll.len(), 5);
+        other.append(&mut basic_ll);
+        assert_eq!(other.len(), 5);
+        other.add_value(8);
+        assert_eq!(other.peek_tail_value(), Some(&8));
+    }
+
+    #[test]
+    fn node_peek_and_with_value_work() {
+        let mut basic_ll = setup_linked_list();
+        assert_eq!(basic_ll.peek_head().as_deref(), Some(&2));
+        assert!(LinkedList::<u32>::new().peek_head().is_none());
+
+        let a = basic_ll.dequeue().unwrap();
+        assert_eq!(*Node::peek(&a), 2);
+        assert_eq!(Node::with_value(&a, |value| value * 10), 20);
+
+        // the guard keeps the Node borrowed
+        let guard = Node::peek(&a);
+        assert!(a.try_borrow_mut().is_err());
+        drop(guard);
+        assert!(a.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn vec_conversions_work() {
+        let basic_ll = LinkedList::from_vec(vec![3, 1, 2]);
+        assert_eq!(basic_ll.len(), 3);
+        assert_eq!(basic_ll.peek_tail_value(), Some(&2));
+        assert_eq!(basic_ll.to_vec(), vec![3, 1, 2]);
+        assert_eq!(basic_ll.into_vec(), vec![3, 1, 2]);
+
+        let strings = LinkedList::from_vec(vec![String::from("a"), String::from("b")]);
+        assert_eq!(strings.into_vec(), vec![String::from("a"), String::from("b")]);
+        assert!(LinkedList::<u32>::from_vec(Vec::new()).into_vec().is_empty());
+    }
+
+    #[test]
+    fn indexed_access_works() {
+        let mut basic_ll = LinkedList::from_vec(vec![5, 6, 7]);
+        assert_eq!(basic_ll.get(0), Some(&5));
+        assert_eq!(basic_ll.get(2), Some(&7));
+        assert_eq!(basic_ll.get(3), None);
+
+        if let Some(value) = basic_ll.get_mut(1) {
+            *value = 60;
+        }
+        assert_eq!(basic_ll.to_vec(), vec![5, 60, 7]);
+        assert_eq!(basic_ll.get_mut(3), None);
+
+        // values updated through get_mut are seen by every later walk, including after the list
+        // is relinked
+        let mut model = vec![5, 60, 7];
+        for index in 0..3 {
+            *basic_ll.get_mut(index).unwrap() += 100;
+            model[index] += 100;
+            assert_eq!(basic_ll.iter().copied().collect::<Vec<_>>(), model);
+        }
+        basic_ll.sort();
+        model.sort();
+        *basic_ll.get_mut(2).unwrap() = 0;
+        assert_eq!(basic_ll.peek_tail_value(), Some(&0));
+        model[2] = 0;
+        assert_eq!(basic_ll.into_vec(), model);
+    }
+
+    #[test]
+    fn middle_and_nth_from_end_work() {
+        let mut basic_ll = LinkedList::from_vec(vec![1, 2, 3, 4, 5]);
+        assert_eq!(basic_ll.middle(), Some(&3));
+        basic_ll.add_value(6);
+        assert_eq!(basic_ll.middle(), Some(&4));
+        assert_eq!(LinkedList::from_vec(vec![1]).middle(), Some(&1));
+        assert_eq!(LinkedList::<u32>::new().middle(), None);
+
+        assert_eq!(basic_ll.nth_from_end(0), Some(&6));
+        assert_eq!(basic_ll.nth_from_end(2), Some(&4));
+        assert_eq!(basic_ll.nth_from_end(5), Some(&1));
+        assert_eq!(basic_ll.nth_from_end(6), None);
+        assert_eq!(LinkedList::<u32>::new().nth_from_end(0), None);
+
+        for length in 0..8 {
+            let list = LinkedList::from_vec((0..length).collect());
+            assert_eq!(list.middle(), (length > 0).then_some(&(length / 2)));
+            for n in 0..length {
+                assert_eq!(list.nth_from_end(n as usize), Some(&(length - 1 - n)));
+            }
+        }
+    }
+
+    #[test]
+    fn dedup_works() {
+        let mut basic_ll = LinkedList::from_vec(vec![1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        basic_ll.dedup();
+        assert_eq!(basic_ll.to_vec(), vec![1, 2, 3, 1, 4]);
+        assert_eq!(basic_ll.len(), 5);
+        // the last run was removed, so the tail moved back
+        basic_ll.add_value(5);
+        assert_eq!(basic_ll.peek_tail_value(), Some(&5));
+
+        let mut words = LinkedList::from_vec(vec!["apple", "avocado", "banana", "blueberry", "apricot"]);
+        words.dedup_by_key(|word| word.chars().next());
+        assert_eq!(words.to_vec(), vec!["apple", "banana", "apricot"]);
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        empty.dedup();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn merge_sorted_works() {
+        let first = LinkedList::from_vec(vec![1, 4, 4, 9]);
+        let second = LinkedList::from_vec(vec![2, 4, 10, 11]);
+        let mut merged = LinkedList::merge_sorted(first, second);
+        assert_eq!(merged.to_vec(), vec![1, 2, 4, 4, 4, 9, 10, 11]);
+        assert_eq!(merged.len(), 8);
+        assert_eq!(merged.peek_tail_value(), Some(&11));
+
+        merged = LinkedList::merge_sorted(merged, LinkedList::from_vec(vec![0, 5]));
+        assert_eq!(merged.peek_tail_value(), Some(&11));
+        merged.add_value(12);
+        assert_eq!(merged.nth_from_end(1), Some(&11));
+
+        let merged_empty = LinkedList::merge_sorted(LinkedList::new(), LinkedList::from_vec(vec![3]));
+        assert_eq!(merged_empty.peek_tail_value(), Some(&3));
+        assert!(LinkedList::<u32>::merge_sorted(LinkedList::new(), LinkedList::new()).is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn linked_list_can_be_serialized_and_deserialized() {
+        let basic_ll = LinkedList::from_vec(vec![3, 1, 2]);
+        let json = serde_json::to_string(&basic_ll).unwrap();
+        assert_eq!(json, "[3,1,2]");
+
+        let mut loaded: LinkedList<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, basic_ll);
+        assert_eq!(loaded.len(), 3);
+        loaded.add_value(4);
+        assert_eq!(loaded.peek_tail_value(), Some(&4));
+
+        assert!(serde_json::from_str::<LinkedList<u32>>("[]").unwrap().is_empty());
+        assert!(serde_json::from_str::<LinkedList<u32>>("{}").is_err());
+    }
+
+    #[test]
+    fn swap_works() {
+        let mut basic_ll = LinkedList::from_vec(vec![1, 2, 3, 4]);
+        assert_eq!(basic_ll.swap(0, 3), Ok(()));
+        assert_eq!(basic_ll.to_vec(), vec![4, 2, 3, 1]);
+        assert_eq!(basic_ll.swap(2, 1), Ok(()));
+        assert_eq!(basic_ll.swap(1, 1), Ok(()));
+        assert_eq!(basic_ll.to_vec(), vec![4, 3, 2, 1]);
+        assert_eq!(basic_ll.peek_head_value(), Some(&4));
+        assert_eq!(basic_ll.peek_tail_value(), Some(&1));
+
+        assert_eq!(basic_ll.swap(0, 4), Err(IndexError { index: 4, len: 4 }));
+        assert_eq!(basic_ll.to_vec(), vec![4, 3, 2, 1]);
+
+        // selection sort built on swap
+        let mut unsorted = LinkedList::from_vec(vec![5, 1, 4, 2, 3]);
+        for i in 0..unsorted.len() {
+            let (min_index, _) = unsorted.iter().enumerate().skip(i).min_by_key(|(_, value)| **value).unwrap();
+            unsorted.swap(i, min_index).unwrap();
+        }
+        assert_eq!(unsorted.to_vec(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn linked_list_can_be_used_as_queue_and_stack() {
+        fn drain_queue<Q: Queue<u32>>(queue: &mut Q) -> Vec<u32> {
+            let mut values = Vec::new();
+            while let Some(value) = queue.dequeue() {
+                values.push(value);
+            }
+            values
+        }
+        fn drain_stack<S: Stack<u32>>(stack: &mut S) -> Vec<u32> {
+            let mut values = Vec::new();
+            while let Some(value) = stack.pop() {
+                values.push(value);
+            }
+            values
+        }
+
+        let mut queue: LinkedList<u32> = LinkedList::new();
+        let mut stack: LinkedList<u32> = LinkedList::new();
+        for value in 1..=3 {
+            queue.enqueue(value);
+            Stack::push(&mut stack, value);
+        }
+        assert_eq!(queue.peek_front(), Some(&1));
+        assert_eq!(Stack::peek(&stack), Some(&3));
+        assert_eq!(Queue::len(&queue), 3);
+
+        assert_eq!(drain_queue(&mut queue), vec![1, 2, 3]);
+        assert_eq!(drain_stack(&mut stack), vec![3, 2, 1]);
+        assert!(Queue::is_empty(&queue));
+        assert!(Stack::is_empty(&stack));
+    }
+
+    #[test]
+    fn ref_count_diagnostics_work() {
+        let mut basic_ll = LinkedList::from_vec(vec![1, 2, 3]);
+        assert_eq!(basic_ll.debug_ref_counts(), vec![1, 1, 1]);
+        assert_eq!(basic_ll.check_no_leaks(), Ok(()));
+        assert_eq!(LinkedList::<u32>::new().check_no_leaks(), Ok(()));
+
+        let leaked_head = basic_ll.head.clone();
+        assert_eq!(basic_ll.debug_ref_counts(), vec![2, 1, 1]);
+        assert_eq!(basic_ll.check_no_leaks(), Err(RefCountError { index: 0, strong: 2, weak: 0 }));
+        drop(leaked_head);
+
+        let stray_weak = Rc::downgrade(basic_ll.head.as_ref().unwrap());
+        assert_eq!(basic_ll.check_no_leaks(), Err(RefCountError { index: 0, strong: 1, weak: 1 }));
+        drop(stray_weak);
+
+        basic_ll.sort_by(|a, b| b.cmp(a));
+        basic_ll.dedup();
+        basic_ll.remove_at(1);
+        assert_eq!(basic_ll.check_no_leaks(), Ok(()));
+    }
+
+    #[test]
+    fn extend_from_slice_works() {
+        let mut basic_ll = setup_linked_list();
+        basic_ll.extend_from_slice(&[6, 8, 10]);
+        assert_eq!(basic_ll.to_vec(), vec![2, 4, 6, 8, 10]);
+        assert_eq!(basic_ll.len(), 5);
+        assert_eq!(basic_ll.peek_tail_value(), Some(&10));
+        basic_ll.extend_from_slice(&[]);
+        assert_eq!(basic_ll.len(), 5);
+        assert_eq!(basic_ll.check_no_leaks(), Ok(()));
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        empty.extend_from_slice(&[1]);
+        empty.extend(2..4);
+        empty.add_value(4);
+        assert_eq!(empty.to_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(empty.peek_head_value(), Some(&1));
+    }
+
+    #[test]
+    fn sort_works() {
+        let mut basic_ll: LinkedList<u32> = LinkedList::new();
+        for value in [5, 3, 9, 1, 3, 8, 2] {
+            basic_ll.add_value(value);
+        }
+        basic_ll.sort();
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &3, &5, &8, &9]);
+
+        // the tail was updated to the new last Node
+        basic_ll.add_value(0);
+        assert_eq!(basic_ll.iter().last(), Some(&0));
+        assert_eq!(basic_ll.len(), 8);
+
+        basic_ll.sort_by(|a, b| b.cmp(a));
+        assert_eq!(basic_ll.iter().collect::<Vec<_>>(), vec![&9, &8, &5, &3, &3, &2, &1, &0]);
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        empty.sort();
+        empty.add_value(1);
+        empty.sort();
+        assert_eq!(empty.iter().collect::<Vec<_>>(), vec![&1]);
+    }
+
+    #[test]
+    fn sort_by_is_stable() {
+        let mut basic_ll: LinkedList<(u32, char)> = LinkedList::new();
+        for value in [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')] {
+            basic_ll.add_value(value);
+        }
+        basic_ll.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(basic_ll.iter().map(|value| value.1).collect::<String>(), "ebdac");
+    }
+
     #[test]
     fn splice_works() {
         let mut basic_ll = setup_linked_list();
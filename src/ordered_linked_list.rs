@@ -0,0 +1,158 @@
+//! Sorted linked list built on the LinkedList struct from linked_list.rs. Values are inserted at
+//!     their sorted position, so the list is always in ascending order: the smallest value can be
+//!     read and removed in O(1), and searches can stop as soon as they pass the value.
+
+use crate::linked_list::{ Iter, LinkedList };
+
+
+/// A linked list struct that keeps its values in ascending order.
+///
+/// * `list`: LinkedList holding the values in ascending order, with equal values in insertion
+///         order.
+pub struct OrderedLinkedList<T: Ord> {
+    list: LinkedList<T>
+}
+
+
+// Method implementation for OrderedLinkedList struct
+impl<T> OrderedLinkedList<T> where T: Ord {
+
+    /// Return a new, empty OrderedLinkedList struct
+    pub fn new() -> OrderedLinkedList<T> {
+        OrderedLinkedList {
+            list: LinkedList::new()
+        }
+    }
+
+    /// Return the number of values in this OrderedLinkedList.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Return whether this OrderedLinkedList holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Insert a value at its sorted position, after any equal values. Walks from the head to the
+    /// position and splices a new Node in (see LinkedList::insert_sorted), so it takes O(n).
+    ///
+    /// * `value`: value to be inserted.
+    pub fn insert(&mut self, value: T) {
+        self.list.insert_sorted(value);
+    }
+
+    /// Return whether a value equal to the input value is in this OrderedLinkedList. Stops at the
+    /// first greater value rather than walking the rest of the list.
+    ///
+    /// * `value`: value to be searched for.
+    pub fn contains(&self, value: &T) -> bool {
+        self.list.iter().take_while(|node_value| *node_value <= value).any(|node_value| node_value == value)
+    }
+
+    /// Remove the first value equal to the input value, and return whether one was found.
+    ///
+    /// * `value`: value to be removed.
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.contains(value) && self.list.remove(value)
+    }
+
+    /// Get a reference to the smallest value (if the list is not empty).
+    pub fn peek_min(&self) -> Option<&T> {
+        self.list.peek_head_value()
+    }
+
+    /// Remove the smallest value and return it (unless the list is empty).
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// Return an iterator of references to the values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+}
+
+impl<T> Default for OrderedLinkedList<T> where T: Ord {
+    fn default() -> Self {
+        OrderedLinkedList::new()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_ordered_linked_list() -> OrderedLinkedList<u32> {
+    let mut oll: OrderedLinkedList<u32> = OrderedLinkedList::new();
+    for value in [5, 1, 4, 1, 9] {
+        oll.insert(value);
+    }
+    oll
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_linked_list_stays_sorted() {
+        let mut oll = setup_ordered_linked_list();
+        assert_eq!(oll.len(), 5);
+        assert_eq!(oll.iter().collect::<Vec<_>>(), vec![&1, &1, &4, &5, &9]);
+
+        oll.insert(0);
+        oll.insert(10);
+        oll.insert(6);
+        assert_eq!(oll.iter().collect::<Vec<_>>(), vec![&0, &1, &1, &4, &5, &6, &9, &10]);
+    }
+
+    #[test]
+    fn ordered_linked_list_keeps_equal_values_in_insertion_order() {
+        // ordered by priority only
+        struct Job(u32, &'static str);
+        impl PartialEq for Job {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Job {}
+        impl PartialOrd for Job {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Job {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut jobs = OrderedLinkedList::new();
+        jobs.insert(Job(2, "first"));
+        jobs.insert(Job(1, "urgent"));
+        jobs.insert(Job(2, "second"));
+        let names: Vec<_> = std::iter::from_fn(|| jobs.pop_min()).map(|job| job.1).collect();
+        assert_eq!(names, vec!["urgent", "first", "second"]);
+    }
+
+    #[test]
+    fn ordered_linked_list_can_be_searched_and_removed_from() {
+        let mut oll = setup_ordered_linked_list();
+        assert!(oll.contains(&4));
+        assert!(!oll.contains(&3));
+        assert!(!oll.contains(&100));
+
+        assert!(oll.remove(&1));
+        assert!(oll.remove(&9));
+        assert!(!oll.remove(&9));
+        assert_eq!(oll.iter().collect::<Vec<_>>(), vec![&1, &4, &5]);
+
+        // the tail was updated when 9 was removed
+        oll.insert(7);
+        assert_eq!(oll.iter().collect::<Vec<_>>(), vec![&1, &4, &5, &7]);
+        assert_eq!(oll.peek_min(), Some(&1));
+        assert_eq!(oll.pop_min(), Some(1));
+        assert_eq!(oll.peek_min(), Some(&4));
+    }
+}
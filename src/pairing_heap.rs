@@ -0,0 +1,464 @@
+//! Pairing heap implementation that works using std::rc::{ Rc, Weak }, and interior mutability with
+//!     RefCell, like linked_list.rs. A pointer-based alternative to the array heaps in heap.rs:
+//!     two heaps meld in O(1), and a value's key can be decreased in O(1) (amortized O(log n)
+//!     overall) through the handle returned when it was pushed.
+//!
+//! ```
+//! use rust_datastructures_algorithms::pairing_heap::PairingHeap;
+//!
+//! let mut heap = PairingHeap::new();
+//! heap.push(5);
+//! let seven = heap.push(7);
+//! heap.push(3);
+//! assert!(heap.decrease_key(&seven, 1));
+//! assert_eq!(heap.pop(), Some(1));
+//! assert_eq!(heap.pop(), Some(3));
+//! ```
+
+use std::rc::{Rc, Weak};
+use std::cell::{Ref, RefCell};
+
+
+/// A pairing heap struct containing a "pointer" to its root Node - the Node holding the smallest
+/// value.
+///
+/// Each Node's children are a list: the Node points to its first child, and each child points
+/// to its next sibling. Melding two heaps makes the root with the larger value the first child of
+/// the other root, so it is O(1); pop does the deferred work, pairing up the root's children
+/// (see pop).
+///
+/// * `root`: An Option-wrapped reference to the root Node (None if the heap is empty).
+/// * `length`: Number of Nodes in the heap.
+/// * `tag`: Identity of this heap, which NodeHandles are tagged with.
+pub struct PairingHeap<T: Ord> {
+    root: Option<Rc<RefCell<Node<T>>>>,
+    length: usize,
+    tag: Rc<HeapTag>
+}
+
+
+/// Node in a PairingHeap struct.
+///
+/// Forward links (child and sibling members) are strong Rc references that own the Nodes; the
+/// backward link (prev member) is a Weak reference, so no two Nodes own each other.
+///
+/// * `value`: T must be of type that matches the PairingHeap struct this Node is placed in
+/// * `child`: Option holding an Rc to the first child Node - its value is not smaller than this
+///         Node's.
+/// * `sibling`: Option holding an Rc to the next sibling Node (the next child of this Node's
+///         parent).
+/// * `prev`: Weak reference to the parent Node if this Node is a first child, otherwise to the
+///         previous sibling Node (upgrade returns None for the root Node). Lets decrease_key cut a
+///         Node out of its list of siblings in O(1).
+pub struct Node<T> {
+    value: T,
+    child: Option<Rc<RefCell<Node<T>>>>,
+    sibling: Option<Rc<RefCell<Node<T>>>>,
+    prev: Weak<RefCell<Node<T>>>
+}
+
+
+/// Handle to a Node in a PairingHeap struct, returned when a value is pushed. Passing it back to
+/// PairingHeap::decrease_key lowers that Node's value without searching for it.
+///
+/// Holds a Weak reference, so it doesn't keep the Node alive once the value is popped or the heap
+/// is dropped.
+///
+/// * `node`: Weak reference to the Node.
+/// * `tag`: Tag of the heap the value was pushed into.
+pub struct NodeHandle<T> {
+    node: Weak<RefCell<Node<T>>>,
+    tag: Rc<HeapTag>
+}
+
+
+/// Identity of a melding heap (PairingHeap, FibonacciHeap), which its NodeHandles are tagged with
+/// so a handle from another heap can be rejected. Melding a heap into another points the melded
+/// heap's tag at the other's, so the tags form union_find-style trees: a handle belongs to a heap
+/// exactly when following its tag's links ends at that heap's tag.
+///
+/// The links are strong references, kept alive by the handles that need them. A heap's tag is
+/// never shared with another live heap, so it can't be mistaken for a later heap's tag.
+///
+/// * `melded_into`: Tag of the heap this heap was melded into (None for a heap not yet melded).
+pub(crate) struct HeapTag {
+    melded_into: RefCell<Option<Rc<HeapTag>>>
+}
+
+
+// Method implementations for PairingHeap struct
+impl<T> PairingHeap<T> where T: Ord {
+
+    /// Return a new, empty PairingHeap struct
+    pub fn new() -> PairingHeap<T> {
+        PairingHeap {
+            root: None,
+            length: 0,
+            tag: HeapTag::new()
+        }
+    }
+
+    /// Return the number of values in this PairingHeap.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this PairingHeap holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Add a value to the heap in O(1), by melding it as a single-Node heap with this one, and
+    /// return a handle to it.
+    ///
+    /// * `value`: value to be added.
+    pub fn push(&mut self, value: T) -> NodeHandle<T> {
+        let new_node = Rc::new(RefCell::new(Node {
+            value,
+            child: None,
+            sibling: None,
+            prev: Weak::new()
+        }));
+        let handle = NodeHandle { node: Rc::downgrade(&new_node), tag: Rc::clone(&self.tag) };
+        self.root = Some(Node::meld(self.root.take(), new_node));
+        self.length += 1;
+        handle
+    }
+
+    /// Move every value of the other PairingHeap into this one in O(1), by melding the two roots.
+    /// Handles to values in the other heap stay valid, and now refer to this heap.
+    ///
+    /// * `other`: PairingHeap whose values are moved into this one.
+    pub fn meld(&mut self, mut other: PairingHeap<T>) {
+        other.tag.meld_into(&self.tag);
+        if let Some(other_root) = other.root.take() {
+            self.root = Some(Node::meld(self.root.take(), other_root));
+            self.length += other.length;
+            other.length = 0;
+        }
+    }
+
+    /// Return a RefCell guard of the smallest value (if the heap is not empty). The root Node is
+    /// borrowed until the guard is dropped.
+    pub fn peek(&self) -> Option<Ref<'_, T>> {
+        self.root.as_ref().map(|root| Ref::map(root.borrow(), |node| &node.value))
+    }
+
+    /// Remove the smallest value and return it (unless the heap is empty), in amortized
+    /// O(log n).
+    ///
+    /// The root's children become the roots of separate heaps, which are combined in two passes:
+    ///     first melding them in pairs from left to right, then melding the pairs together from
+    ///     right to left. Pairing keeps the new root's list of children short, which is what pays
+    ///     for the O(1) push and meld.
+    pub fn pop(&mut self) -> Option<T> {
+        let old_root = self.root.take()?;
+        let mut children = Vec::new();
+        let mut opt_child = old_root.borrow_mut().child.take();
+        while let Some(child) = opt_child {
+            opt_child = {
+                let mut child_node = child.borrow_mut();
+                child_node.prev = Weak::new();
+                child_node.sibling.take()
+            };
+            children.push(child);
+        }
+
+        let mut pairs = Vec::with_capacity(children.len() / 2 + 1);
+        let mut children = children.into_iter();
+        while let Some(first) = children.next() {
+            pairs.push(match children.next() {
+                Some(second) => Node::meld(Some(first), second),
+                None => first
+            });
+        }
+        self.root = pairs.into_iter().rev().reduce(|melded, pair| Node::meld(Some(pair), melded));
+        self.length -= 1;
+
+        // handles only hold Weak references, so the heap held the only strong reference
+        match Rc::try_unwrap(old_root) {
+            Ok(node_refcell) => Some(node_refcell.into_inner().value),
+            Err(_) => unreachable!("Nodes are only strongly referenced by the heap")
+        }
+    }
+
+    /// Lower the value of the Node the input handle refers to, in O(1): if it is now smaller than
+    /// its parent's, the Node is cut out of its parent's list of children (taking its own
+    /// children with it) and melded with the root.
+    ///
+    /// Return false, leaving the heap unchanged, if the value was already popped, the handle
+    /// came from another heap (that wasn't melded into this one) or the new value is greater than
+    /// the current one.
+    ///
+    /// * `handle`: handle returned by push.
+    /// * `value`: new value, not greater than the current one.
+    pub fn decrease_key(&mut self, handle: &NodeHandle<T>, value: T) -> bool {
+        if !handle.tag.belongs_to(&self.tag) {
+            return false;
+        }
+        let Some(node_rc) = handle.node.upgrade() else {
+            return false;
+        };
+        if value > node_rc.borrow().value {
+            return false;
+        }
+        node_rc.borrow_mut().value = value;
+
+        let Some(prev) = node_rc.borrow().prev.upgrade() else {
+            // the root Node has no parent to be out of order with
+            return true;
+        };
+        // cut the Node out of its list of siblings
+        let next_sibling = node_rc.borrow_mut().sibling.take();
+        if let Some(next_sibling) = &next_sibling {
+            next_sibling.borrow_mut().prev = Rc::downgrade(&prev);
+        }
+        {
+            let mut prev_node = prev.borrow_mut();
+            let is_first_child = prev_node.child.as_ref().is_some_and(|child| Rc::ptr_eq(child, &node_rc));
+            if is_first_child {
+                prev_node.child = next_sibling;
+            } else {
+                prev_node.sibling = next_sibling;
+            }
+        }
+        node_rc.borrow_mut().prev = Weak::new();
+        self.root = Some(Node::meld(self.root.take(), node_rc));
+        true
+    }
+
+    /// Consume this PairingHeap and return its values in ascending order, by popping every value.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.length);
+        while let Some(value) = self.pop() {
+            sorted.push(value);
+        }
+        sorted
+    }
+}
+
+impl<T> Default for PairingHeap<T> where T: Ord {
+    fn default() -> Self {
+        PairingHeap::new()
+    }
+}
+
+// Drop Nodes one at a time with a worklist, so long child and sibling chains aren't dropped
+// recursively.
+impl<T> Drop for PairingHeap<T> where T: Ord {
+    fn drop(&mut self) {
+        let mut worklist: Vec<Rc<RefCell<Node<T>>>> = self.root.take().into_iter().collect();
+        while let Some(node_rc) = worklist.pop() {
+            let mut node = node_rc.borrow_mut();
+            worklist.extend(node.child.take());
+            worklist.extend(node.sibling.take());
+        }
+    }
+}
+
+
+// Method implementations for HeapTag struct
+impl HeapTag {
+
+    /// Return a new tag, for a new heap.
+    pub(crate) fn new() -> Rc<HeapTag> {
+        Rc::new(HeapTag { melded_into: RefCell::new(None) })
+    }
+
+    /// Record that the heap with this tag has been melded into the heap with the input tag.
+    ///
+    /// * `into`: tag of the heap melded into - not yet melded itself.
+    pub(crate) fn meld_into(&self, into: &Rc<HeapTag>) {
+        *self.melded_into.borrow_mut() = Some(Rc::clone(into));
+    }
+
+    /// Return whether a handle with this tag belongs to the heap with the input tag: whether this
+    /// tag's links end at it. Like UnionFind::find, every tag on the way is then linked straight
+    /// to the end, so later checks from any of them take one step.
+    ///
+    /// * `heap_tag`: tag of a heap not yet melded.
+    pub(crate) fn belongs_to(self: &Rc<HeapTag>, heap_tag: &Rc<HeapTag>) -> bool {
+        let mut root = Rc::clone(self);
+        loop {
+            let next = root.melded_into.borrow().clone();
+            match next {
+                Some(next) => root = next,
+                None => break
+            }
+        }
+        let mut current = Rc::clone(self);
+        while !Rc::ptr_eq(&current, &root) {
+            let next = current.melded_into.replace(Some(Rc::clone(&root))).expect("only the root tag has no link");
+            current = next;
+        }
+        Rc::ptr_eq(&root, heap_tag)
+    }
+}
+
+// Drop the tags a tag links to one at a time, so a long chain of melds that no lookup has
+// compressed isn't dropped recursively. A tag still referenced elsewhere ends the chain.
+impl Drop for HeapTag {
+    fn drop(&mut self) {
+        let mut next = self.melded_into.get_mut().take();
+        while let Some(tag) = next {
+            next = Rc::into_inner(tag).and_then(|mut tag| tag.melded_into.get_mut().take());
+        }
+    }
+}
+
+
+// Method implementations for Node struct
+impl<T> Node<T> where T: Ord {
+
+    /// Meld the heap rooted in the input Node into the heap rooted in the Option-wrapped Node, and
+    /// return the root of the result: whichever root holds the larger value becomes the first
+    /// child of the other. Both roots must have no siblings.
+    ///
+    /// * `opt_root`: Option-wrapped root of the first heap.
+    /// * `other_root`: root of the second heap.
+    fn meld(opt_root: Option<Rc<RefCell<Node<T>>>>, other_root: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        let Some(root) = opt_root else {
+            return other_root;
+        };
+        let (parent, child) = if other_root.borrow().value < root.borrow().value {
+            (other_root, root)
+        } else {
+            (root, other_root)
+        };
+
+        let old_first_child = parent.borrow_mut().child.take();
+        if let Some(old_first_child) = &old_first_child {
+            old_first_child.borrow_mut().prev = Rc::downgrade(&child);
+        }
+        {
+            let mut child_node = child.borrow_mut();
+            child_node.sibling = old_first_child;
+            child_node.prev = Rc::downgrade(&parent);
+        }
+        parent.borrow_mut().child = Some(child);
+        parent
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_pairing_heap() -> PairingHeap<u32> {
+    let mut heap: PairingHeap<u32> = PairingHeap::new();
+    for value in [5, 3, 8, 1, 9, 2] {
+        heap.push(value);
+    }
+    heap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairing_heap_works() {
+        let mut heap = setup_pairing_heap();
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.peek().as_deref(), Some(&1));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        heap.push(0);
+        assert_eq!(heap.pop(), Some(0));
+        assert_eq!(heap.into_sorted_vec(), vec![3, 5, 8, 9]);
+        assert_eq!(PairingHeap::<u32>::new().pop(), None);
+    }
+
+    #[test]
+    fn pairing_heaps_can_be_melded() {
+        let mut heap = setup_pairing_heap();
+        let mut other = PairingHeap::new();
+        let handle = other.push(10);
+        other.push(4);
+        heap.meld(other);
+        heap.meld(PairingHeap::new());
+        assert_eq!(heap.len(), 8);
+
+        // handles from the melded heap refer to this one
+        assert!(heap.decrease_key(&handle, 0));
+        assert_eq!(heap.into_sorted_vec(), vec![0, 1, 2, 3, 4, 5, 8, 9]);
+    }
+
+    #[test]
+    fn pairing_heap_rejects_foreign_handles() {
+        let mut heap = setup_pairing_heap();
+        let mut other = PairingHeap::new();
+        let foreign_root = other.push(4);
+        let foreign_child = other.push(7);
+
+        assert!(!heap.decrease_key(&foreign_root, 0));
+        assert!(!heap.decrease_key(&foreign_child, 0));
+        assert_eq!(heap.len(), 6);
+        assert_eq!(other.pop(), Some(4));
+        assert_eq!(other.pop(), Some(7));
+
+        // tags follow a chain of melds: a into b, then b into heap
+        let mut a = PairingHeap::new();
+        let from_a = a.push(20);
+        let mut b = PairingHeap::new();
+        b.push(30);
+        b.meld(a);
+        let mut unrelated = PairingHeap::new();
+        assert!(!unrelated.decrease_key(&from_a, 0));
+        heap.meld(b);
+        assert!(heap.decrease_key(&from_a, 0));
+        assert!(!unrelated.decrease_key(&from_a, 0));
+        assert_eq!(heap.into_sorted_vec(), vec![0, 1, 2, 3, 5, 8, 9, 30]);
+    }
+
+    #[test]
+    fn pairing_heap_can_decrease_keys() {
+        let mut heap: PairingHeap<u32> = PairingHeap::new();
+        let handles: Vec<NodeHandle<u32>> = (0..20).map(|value| heap.push(value * 10)).collect();
+        // pop once so the root's children are paired into a deeper tree
+        assert_eq!(heap.pop(), Some(0));
+
+        assert!(heap.decrease_key(&handles[15], 5));
+        assert!(heap.decrease_key(&handles[7], 6));
+        assert!(heap.decrease_key(&handles[1], 1));
+        assert!(!heap.decrease_key(&handles[2], 25));
+        assert!(!heap.decrease_key(&handles[0], 0));
+
+        assert_eq!(heap.len(), 19);
+        let mut expected: Vec<u32> = (1..20).map(|value| value * 10).filter(|value| ![10, 70, 150].contains(value)).collect();
+        expected.extend([1, 5, 6]);
+        expected.sort();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn pairing_heap_drops_long_chains() {
+        let mut heap = PairingHeap::new();
+        for value in 0..500_000u32 {
+            heap.push(value);
+        }
+        drop(heap);
+
+        let mut heap = PairingHeap::new();
+        for value in (0..500_000u32).rev() {
+            heap.push(value);
+        }
+        assert_eq!(heap.pop(), Some(0));
+        drop(heap);
+    }
+
+    #[test]
+    fn pairing_heap_drops_long_tag_chains() {
+        // each meld links the melded heap's tag to the new heap's, so the handle keeps a chain of
+        // a million tags alive, never compressed by a lookup
+        let mut heap = PairingHeap::new();
+        let handle = heap.push(0u32);
+        for _ in 0..1_000_000 {
+            let mut next = PairingHeap::new();
+            next.meld(heap);
+            heap = next;
+        }
+        drop(heap);
+        drop(handle);
+    }
+}
@@ -0,0 +1,202 @@
+//! First-in, first-out queue backed by a growable circular buffer (ring buffer): values are
+//!     enqueued after the back of the buffer and dequeued from the front, wrapping around the end
+//!     of the allocation, so neither end ever needs values shifted along. The buffer is a
+//!     deque::Deque, used only at its back (for enqueue) and front (for dequeue).
+//!
+//! ```
+//! use rust_datastructures_algorithms::queue::Queue;
+//!
+//! // breadth-first order over a small implicit tree: the children of n are 2n and 2n + 1
+//! let mut queue = Queue::new();
+//! let mut visited = Vec::new();
+//! queue.enqueue(1);
+//! while let Some(n) = queue.dequeue() {
+//!     visited.push(n);
+//!     if n < 4 {
+//!         queue.enqueue(2 * n);
+//!         queue.enqueue(2 * n + 1);
+//!     }
+//! }
+//! assert_eq!(visited, vec![1, 2, 3, 4, 5, 6, 7]);
+//! ```
+
+use crate::deque::{ self, Deque };
+use crate::traits;
+
+
+/// A queue struct holding its values in a Deque struct, which does the circular buffer work:
+/// values are enqueued at the back of the deque and dequeued from its front. When the buffer is
+/// full it doubles in size, so enqueue is amortized O(1).
+///
+/// * `deque`: Deque struct holding the values, front first.
+pub struct Queue<T> {
+    deque: Deque<T>
+}
+
+
+// Method implementations for Queue struct
+impl<T> Queue<T> {
+
+    /// Return a new, empty Queue struct. Nothing is allocated until the first enqueue.
+    pub fn new() -> Queue<T> {
+        Queue { deque: Deque::new() }
+    }
+
+    /// Return a new, empty Queue struct with room for the input number of values before it needs
+    /// to grow.
+    ///
+    /// * `capacity`: number of values to allocate room for.
+    pub fn with_capacity(capacity: usize) -> Queue<T> {
+        Queue { deque: Deque::with_capacity(capacity) }
+    }
+
+    /// Return the number of values in this Queue.
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    /// Return whether this Queue holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// Return the number of values this Queue can hold before it needs to grow.
+    pub fn capacity(&self) -> usize {
+        self.deque.capacity()
+    }
+
+    /// Add a value to the back of the queue, growing the buffer first if it is full.
+    ///
+    /// * `value`: value to be enqueued.
+    pub fn enqueue(&mut self, value: T) {
+        self.deque.push_back(value);
+    }
+
+    /// Remove the value at the front of the queue and return it (unless the queue is empty).
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    /// Get a reference to the value at the front of the queue (if the queue is not empty).
+    pub fn front(&self) -> Option<&T> {
+        self.deque.front()
+    }
+
+    /// Return an iterator of references to the values in this Queue, from front to back.
+    pub fn iter(&self) -> deque::Iter<'_, T> {
+        self.deque.iter()
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.enqueue(value);
+        }
+    }
+}
+
+// Enqueue each value in turn, so the first value collected is at the front.
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Queue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Queue trait implementation, so a Queue struct can be used in code generic over queues.
+impl<T> traits::Queue<T> for Queue<T> {
+    fn enqueue(&mut self, value: T) {
+        Queue::enqueue(self, value);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        Queue::dequeue(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn len(&self) -> usize {
+        Queue::len(self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_queue() -> Queue<u32> {
+    let mut queue: Queue<u32> = Queue::new();
+    queue.enqueue(2);
+    queue.enqueue(4);
+    queue
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_works() {
+        let mut queue = setup_queue();
+        assert_eq!(queue.front(), Some(&2));
+        queue.enqueue(6);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.dequeue(), Some(4));
+        assert_eq!(queue.dequeue(), Some(6));
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(queue.front(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn queue_wraps_around_and_grows() {
+        let mut queue: Queue<u32> = Queue::with_capacity(4);
+        queue.extend([1, 2, 3]);
+        assert_eq!(queue.dequeue(), Some(1));
+        assert_eq!(queue.dequeue(), Some(2));
+
+        // the back wraps around to the start of the buffer
+        queue.extend([4, 5, 6]);
+        assert_eq!(queue.capacity(), 4);
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&3, &4, &5, &6]);
+
+        // growing a wrapped buffer keeps the order
+        queue.enqueue(7);
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.iter().len(), 5);
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&3, &4, &5, &6, &7]);
+
+        let drained: Vec<u32> = std::iter::from_fn(|| queue.dequeue()).collect();
+        assert_eq!(drained, vec![3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn queue_can_be_used_through_queue_trait() {
+        fn drain<Q: traits::Queue<u32>>(queue: &mut Q) -> Vec<u32> {
+            std::iter::from_fn(|| queue.dequeue()).collect()
+        }
+        let mut queue: Queue<u32> = (0..100).collect();
+        assert_eq!(traits::Queue::peek_front(&queue), Some(&0));
+        assert_eq!(drain(&mut queue), (0..100).collect::<Vec<_>>());
+    }
+}
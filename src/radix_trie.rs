@@ -0,0 +1,639 @@
+//! Compressed radix trie (Patricia tree) mapping string keys to values. Where a plain trie has
+//!     one node per character, a radix trie compresses every chain of single-child nodes into a
+//!     single edge labelled with a whole substring, so it has at most two nodes per key however
+//!     long the keys are.
+//!
+//! Inserting a key that shares only part of an edge's label splits the edge in two at the point
+//!     they diverge; removing a key merges edges back together where a node is left with a
+//!     single child and no value.
+//!
+//! ```
+//! use rust_datastructures_algorithms::radix_trie::RadixTrie;
+//!
+//! let mut routes = RadixTrie::new();
+//! routes.insert("/api", "api root");
+//! routes.insert("/api/users", "users");
+//! routes.insert("/api/users/admin", "admin");
+//! routes.insert("/static", "files");
+//!
+//! assert_eq!(routes.longest_prefix("/api/users/42"), Some(("/api/users", &"users")));
+//! let under_users: Vec<String> = routes.iter_prefix("/api/u").map(|(key, _)| key).collect();
+//! assert_eq!(under_users, vec!["/api/users", "/api/users/admin"]);
+//! ```
+
+use std::mem;
+
+
+/// A radix trie struct holding the root Node, which is reached by the empty string.
+///
+/// * `root`: Root Node - its value is the one stored for the empty key.
+/// * `length`: Number of keys in the trie.
+pub struct RadixTrie<V> {
+    root: Node<V>,
+    length: usize
+}
+
+
+/// Node in a RadixTrie struct. The key a Node is reached by is the concatenation of the edge
+/// labels on the path from the root to it.
+///
+/// * `value`: Value stored for this Node's key (None if the key isn't in the trie and the Node
+///         only exists to branch).
+/// * `children`: Edges to this Node's children, sorted by the first character of their labels.
+///         No two labels start with the same character, so at most one edge can match a key.
+struct Node<V> {
+    value: Option<V>,
+    children: Vec<Edge<V>>
+}
+
+
+/// Edge from a Node to one of its children in a RadixTrie struct.
+///
+/// * `label`: Non-empty substring of the keys below this edge, stored as a boxed string slice.
+/// * `node`: Child Node the edge leads to.
+struct Edge<V> {
+    label: Box<str>,
+    node: Node<V>
+}
+
+
+/// Iterator yielding the keys and values of a RadixTrie struct in lexicographic key order.
+/// Created by RadixTrie::iter and RadixTrie::iter_prefix.
+///
+/// Walks the trie depth first with an explicit stack: a Node's key comes before the keys of its
+/// children, and children are visited in order of their labels' first characters, which is
+/// lexicographic order.
+///
+/// * `stack`: Nodes still to be visited, with the keys they are reached by - the next Node is on
+///         top.
+pub struct Iter<'a, V> {
+    stack: Vec<(String, &'a Node<V>)>
+}
+
+
+// Method implementations for RadixTrie struct
+impl<V> RadixTrie<V> {
+
+    /// Return a new, empty RadixTrie struct
+    pub fn new() -> RadixTrie<V> {
+        RadixTrie {
+            root: Node::new(None),
+            length: 0
+        }
+    }
+
+    /// Return the number of keys in this RadixTrie.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this RadixTrie holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the number of Nodes in this RadixTrie, including the root - a measure of its memory
+    /// use. Path compression keeps this under twice the number of keys (plus the root).
+    pub fn node_count(&self) -> usize {
+        let mut count = 0;
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            count += 1;
+            stack.extend(node.children.iter().map(|edge| &edge.node));
+        }
+        count
+    }
+
+    /// Insert a key-value pair into the trie. If the key was already in the trie, replace its
+    /// value and return the old one.
+    ///
+    /// Walks down while whole edge labels match the key. Where the key runs out or diverges part
+    ///     way along an edge, that edge is split into two at the divergence point, with a new Node
+    ///     in between to hold the value or branch to a new leaf.
+    ///
+    /// * `key`: key to be inserted.
+    /// * `value`: value to be associated with the key.
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            let index = match node.child_index(first) {
+                Ok(index) => index,
+                Err(index) => {
+                    node.children.insert(index, Edge { label: rest.into(), node: Node::new(Some(value)) });
+                    self.length += 1;
+                    return None;
+                }
+            };
+            let edge = &mut node.children[index];
+            let common = common_prefix_len(&edge.label, rest);
+            if common < edge.label.len() {
+                edge.split(common);
+            }
+            rest = &rest[common..];
+            node = &mut node.children[index].node;
+        }
+
+        let old_value = node.value.replace(value);
+        if old_value.is_none() {
+            self.length += 1;
+        }
+        old_value
+    }
+
+    /// Get a reference to the value associated with the input key (if the key is in the trie).
+    ///
+    /// * `key`: key to look up.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.find_node(key)?.value.as_ref()
+    }
+
+    /// Get a mutable reference to the value associated with the input key (if the key is in the
+    /// trie).
+    ///
+    /// * `key`: key to look up.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            let index = node.child_index(first).ok()?;
+            let edge = &mut node.children[index];
+            rest = rest.strip_prefix(&*edge.label)?;
+            node = &mut edge.node;
+        }
+        node.value.as_mut()
+    }
+
+    /// Return whether the input key is in the trie.
+    ///
+    /// * `key`: key to look for.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Remove the input key from the trie and return the value that was associated with it (if
+    /// the key was in the trie).
+    ///
+    /// The Node that held the value is removed if it has no children, and merged into its child's
+    ///     edge if it has one, so the trie stays fully compressed. Removing a leaf can leave its
+    ///     parent with a single child too, so the parent is checked the same way.
+    ///
+    /// * `key`: key to be removed.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        let value = self.root.remove(key)?;
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Return the longest key in the trie that is a prefix of the input, and its value - the
+    /// lookup a routing table does to find the most specific route for an address.
+    ///
+    /// * `key`: key to find the longest stored prefix of.
+    pub fn longest_prefix<'k>(&self, key: &'k str) -> Option<(&'k str, &V)> {
+        let mut node = &self.root;
+        let mut matched = 0;
+        let mut longest = node.value.as_ref().map(|value| (matched, value));
+        while let Some(first) = key[matched..].chars().next() {
+            let Ok(index) = node.child_index(first) else {
+                break;
+            };
+            let edge = &node.children[index];
+            if !key[matched..].starts_with(&*edge.label) {
+                break;
+            }
+            matched += edge.label.len();
+            node = &edge.node;
+            if let Some(value) = &node.value {
+                longest = Some((matched, value));
+            }
+        }
+        longest.map(|(length, value)| (&key[..length], value))
+    }
+
+    /// Return an iterator of the keys and references to the values in this RadixTrie, in
+    /// lexicographic key order. Keys are rebuilt from the edge labels as the trie is walked.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter { stack: vec![(String::new(), &self.root)] }
+    }
+
+    /// Return an iterator of the keys starting with the input prefix and references to their
+    /// values, in lexicographic key order. Only the subtree below the prefix is walked.
+    ///
+    /// * `prefix`: prefix of the keys to be yielded - it may end part way along an edge.
+    pub fn iter_prefix(&self, prefix: &str) -> Iter<'_, V> {
+        let mut node = &self.root;
+        let mut key = String::new();
+        let mut rest = prefix;
+        while let Some(first) = rest.chars().next() {
+            let Ok(index) = node.child_index(first) else {
+                return Iter { stack: Vec::new() };
+            };
+            let edge = &node.children[index];
+            if let Some(after_label) = rest.strip_prefix(&*edge.label) {
+                rest = after_label;
+            } else if edge.label.starts_with(rest) {
+                // the prefix ends part way along this edge: every key below it matches
+                rest = "";
+            } else {
+                return Iter { stack: Vec::new() };
+            }
+            key.push_str(&edge.label);
+            node = &edge.node;
+        }
+        Iter { stack: vec![(key, node)] }
+    }
+
+    /// Return an iterator of the keys in this RadixTrie, in lexicographic order.
+    pub fn keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Return the Node reached by the input key (if the trie has one - it may not hold a value).
+    ///
+    /// * `key`: key to follow from the root.
+    fn find_node(&self, key: &str) -> Option<&Node<V>> {
+        let mut node = &self.root;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            let index = node.child_index(first).ok()?;
+            let edge = &node.children[index];
+            rest = rest.strip_prefix(&*edge.label)?;
+            node = &edge.node;
+        }
+        Some(node)
+    }
+}
+
+impl<V> Default for RadixTrie<V> {
+    fn default() -> Self {
+        RadixTrie::new()
+    }
+}
+
+// Insert each key-value pair in turn, so later values replace earlier ones for the same key.
+impl<K, V> Extend<(K, V)> for RadixTrie<V> where K: AsRef<str> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key.as_ref(), value);
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for RadixTrie<V> where K: AsRef<str> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut trie = RadixTrie::new();
+        trie.extend(iter);
+        trie
+    }
+}
+
+impl<'a, V> IntoIterator for &'a RadixTrie<V> {
+    type Item = (String, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Drop edges one at a time with a worklist, so a deep chain of Nodes isn't dropped recursively.
+impl<V> Drop for RadixTrie<V> {
+    fn drop(&mut self) {
+        let mut worklist = mem::take(&mut self.root.children);
+        while let Some(mut edge) = worklist.pop() {
+            worklist.append(&mut edge.node.children);
+        }
+    }
+}
+
+
+// Method implementations for Node struct
+impl<V> Node<V> {
+
+    /// Return a new Node with the input value and no children.
+    ///
+    /// * `value`: value for the Node's key, if any.
+    fn new(value: Option<V>) -> Node<V> {
+        Node {
+            value,
+            children: Vec::new()
+        }
+    }
+
+    /// Binary search the children for the edge whose label starts with the input character.
+    /// Return Ok with its index, or Err with the index a new edge starting with it belongs at.
+    ///
+    /// * `first`: first character of the rest of a key.
+    fn child_index(&self, first: char) -> Result<usize, usize> {
+        self.children.binary_search_by_key(&first, |edge| edge.first_char())
+    }
+
+    /// Remove the input key from below this Node and return its value, then tidy up the edges that
+    /// were followed, from the bottom up: drop an edge's Node if it is now empty, or merge the edge
+    /// with its Node's only child. Walks down with a loop rather than recursing, so keys nested
+    /// arbitrarily deep can be removed.
+    ///
+    /// * `key`: rest of the key to be removed, relative to this Node.
+    fn remove(&mut self, key: &str) -> Option<V> {
+        // find the index of each edge on the way down first, so the walks below can be mutable
+        let mut path = Vec::new();
+        let mut node = &*self;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            let index = node.child_index(first).ok()?;
+            let edge = &node.children[index];
+            rest = rest.strip_prefix(&*edge.label)?;
+            path.push(index);
+            node = &edge.node;
+        }
+        let value = self.descendant_mut(&path).value.take()?;
+
+        // an edge left alone leaves its parent's children unchanged, so the edges above it are too
+        for depth in (0..path.len()).rev() {
+            let parent = self.descendant_mut(&path[..depth]);
+            let edge = &mut parent.children[path[depth]];
+            if edge.node.value.is_some() {
+                break;
+            }
+            match edge.node.children.len() {
+                0 => {
+                    parent.children.remove(path[depth]);
+                },
+                1 => edge.merge_with_child(),
+                _ => break
+            }
+        }
+        Some(value)
+    }
+
+    /// Return a mutable reference to the Node reached from this one by following the input edges.
+    ///
+    /// * `path`: index of the edge to follow out of each Node in turn.
+    fn descendant_mut(&mut self, path: &[usize]) -> &mut Node<V> {
+        path.iter().fold(self, |node, index| &mut node.children[*index].node)
+    }
+}
+
+
+// Method implementations for Edge struct
+impl<V> Edge<V> {
+
+    /// Return the first character of this edge's label.
+    fn first_char(&self) -> char {
+        self.label.chars().next().expect("edge labels are never empty")
+    }
+
+    /// Split this edge in two at the input byte position of its label: this edge keeps the
+    /// first part of the label and leads to a new, valueless Node, whose only edge has the rest
+    /// of the label and leads to the original Node.
+    ///
+    /// * `at`: byte position to split the label at - a character boundary, between 0 and the
+    ///         label's length exclusive.
+    fn split(&mut self, at: usize) {
+        let tail_label: Box<str> = self.label[at..].into();
+        self.label = self.label[..at].into();
+        let old_node = mem::replace(&mut self.node, Node::new(None));
+        self.node.children.push(Edge { label: tail_label, node: old_node });
+    }
+
+    /// Merge this edge with the only edge out of its valueless Node: the labels are joined and this
+    /// edge leads straight to the child's Node.
+    fn merge_with_child(&mut self) {
+        let child = self.node.children.pop().expect("the Node has exactly one child");
+        let mut label = String::with_capacity(self.label.len() + child.label.len());
+        label.push_str(&self.label);
+        label.push_str(&child.label);
+        self.label = label.into_boxed_str();
+        self.node = child.node;
+    }
+}
+
+/// Return the length in bytes of the longest common prefix of the two input strings, always
+/// ending on a character boundary.
+///
+/// * `a`: first string.
+/// * `b`: second string.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, a_char), b_char)| a_char != b_char)
+        .map_or(a.len().min(b.len()), |((index, _), _)| index)
+}
+
+
+// Iterator implementation for Iter struct
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((key, node)) = self.stack.pop() {
+            // push children in reverse so the one with the smallest label is visited first
+            for edge in node.children.iter().rev() {
+                let mut child_key = String::with_capacity(key.len() + edge.label.len());
+                child_key.push_str(&key);
+                child_key.push_str(&edge.label);
+                self.stack.push((child_key, &edge.node));
+            }
+            if let Some(value) = &node.value {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_radix_trie() -> RadixTrie<u32> {
+    let mut trie: RadixTrie<u32> = RadixTrie::new();
+    trie.insert("romane", 1);
+    trie.insert("romanus", 2);
+    trie.insert("romulus", 3);
+    trie.insert("rubens", 4);
+    trie.insert("ruber", 5);
+    trie.insert("rubicon", 6);
+    trie.insert("rubicundus", 7);
+    trie
+}
+
+/// Return the edge labels of a RadixTrie struct in depth-first order, each indented by its depth,
+/// so tests can check the shape of the trie.
+#[cfg(test)]
+fn edge_labels<V>(trie: &RadixTrie<V>) -> Vec<String> {
+    let mut labels = Vec::new();
+    let mut stack: Vec<(usize, &Edge<V>)> = trie.root.children.iter().rev().map(|edge| (0, edge)).collect();
+    while let Some((depth, edge)) = stack.pop() {
+        labels.push(format!("{}{}", " ".repeat(depth), edge.label));
+        stack.extend(edge.node.children.iter().rev().map(|child| (depth + 1, child)));
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radix_trie_works() {
+        let mut trie = setup_radix_trie();
+        assert_eq!(trie.len(), 7);
+        assert_eq!(trie.get("romanus"), Some(&2));
+        assert_eq!(trie.get("roman"), None);
+        assert_eq!(trie.get("rubiconx"), None);
+        assert_eq!(trie.get(""), None);
+        assert!(trie.contains_key("rubicundus"));
+
+        assert_eq!(trie.insert("ruber", 50), Some(5));
+        *trie.get_mut("romane").unwrap() += 10;
+        assert_eq!(trie.get("romane"), Some(&11));
+        assert_eq!(trie.get_mut("rom"), None);
+        assert_eq!(trie.len(), 7);
+
+        assert_eq!(trie.insert("", 0), None);
+        assert_eq!(trie.get(""), Some(&0));
+        assert_eq!(trie.len(), 8);
+    }
+
+    #[test]
+    fn radix_trie_splits_edges_on_insert() {
+        let trie = setup_radix_trie();
+        assert_eq!(edge_labels(&trie), vec![
+            "r",
+            " om",
+            "  an",
+            "   e",
+            "   us",
+            "  ulus",
+            " ub",
+            "  e",
+            "   ns",
+            "   r",
+            "  ic",
+            "   on",
+            "   undus"
+        ]);
+        // 7 leaves, 6 branching Nodes and the root - a plain trie would need 38 Nodes
+        assert_eq!(trie.node_count(), 14);
+
+        // a key ending at a branching Node gives it a value; one ending part way along an edge
+        // splits it, and the new Node holds the value
+        let mut trie = setup_radix_trie();
+        trie.insert("rub", 8);
+        assert_eq!(trie.get("rub"), Some(&8));
+        assert_eq!(trie.node_count(), 14);
+        trie.insert("ro", 9);
+        assert_eq!(edge_labels(&trie)[1..3], [" o".to_string(), "  m".to_string()]);
+        assert_eq!(trie.node_count(), 15);
+        assert_eq!(trie.get("romulus"), Some(&3));
+    }
+
+    #[test]
+    fn radix_trie_merges_edges_on_remove() {
+        let mut trie = setup_radix_trie();
+        assert_eq!(trie.remove("rom"), None);
+        assert_eq!(trie.remove("romanes"), None);
+        assert_eq!(trie.remove("romane"), Some(1));
+        assert_eq!(trie.remove("romane"), None);
+        // "an" was left with the single child "us", so they merge
+        assert_eq!(edge_labels(&trie)[1..4], [" om".to_string(), "  anus".to_string(), "  ulus".to_string()]);
+
+        // removing "romulus" leaves "om" with one child too, which merges all the way up
+        assert_eq!(trie.remove("romulus"), Some(3));
+        assert_eq!(edge_labels(&trie)[1], " omanus");
+        assert_eq!(trie.len(), 5);
+
+        for key in ["rubens", "ruber", "rubicon", "rubicundus"] {
+            assert!(trie.remove(key).is_some());
+        }
+        assert_eq!(edge_labels(&trie), vec!["romanus"]);
+        assert_eq!(trie.remove("romanus"), Some(2));
+        assert!(trie.is_empty());
+        assert_eq!(trie.node_count(), 1);
+
+        // a Node holding a value isn't merged away, even with a single child
+        let mut trie: RadixTrie<u32> = [("a", 1), ("ab", 2), ("abc", 3)].into_iter().collect();
+        assert_eq!(trie.remove("abc"), Some(3));
+        assert_eq!(edge_labels(&trie), vec!["a", " b"]);
+        assert_eq!(trie.remove("a"), Some(1));
+        assert_eq!(edge_labels(&trie), vec!["ab"]);
+    }
+
+    #[test]
+    fn radix_trie_iterates_in_order() {
+        let trie = setup_radix_trie();
+        let keys: Vec<String> = trie.keys().collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        assert_eq!(trie.iter().map(|(_, value)| *value).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7]);
+
+        let prefixed = |prefix| trie.iter_prefix(prefix).map(|(key, _)| key).collect::<Vec<_>>();
+        assert_eq!(prefixed("rub"), vec!["rubens", "ruber", "rubicon", "rubicundus"]);
+        // the prefix can end part way along an edge
+        assert_eq!(prefixed("rubic"), vec!["rubicon", "rubicundus"]);
+        assert_eq!(prefixed("romanus"), vec!["romanus"]);
+        assert_eq!(prefixed("rox"), Vec::<String>::new());
+        assert_eq!(prefixed("rubiconx"), Vec::<String>::new());
+        assert_eq!(prefixed("").len(), 7);
+        assert_eq!(RadixTrie::<u32>::new().iter().count(), 0);
+    }
+
+    #[test]
+    fn radix_trie_longest_prefix_routing() {
+        let mut routes: RadixTrie<&str> = RadixTrie::new();
+        routes.insert("10.", "private");
+        routes.insert("10.1.", "office");
+        routes.insert("10.1.2.", "lab");
+        routes.insert("192.168.", "home");
+
+        assert_eq!(routes.longest_prefix("10.1.2.33"), Some(("10.1.2.", &"lab")));
+        assert_eq!(routes.longest_prefix("10.1.9.1"), Some(("10.1.", &"office")));
+        assert_eq!(routes.longest_prefix("10.10.0.1"), Some(("10.", &"private")));
+        assert_eq!(routes.longest_prefix("172.16.0.1"), None);
+        assert_eq!(routes.longest_prefix("10"), None);
+
+        routes.insert("", "default");
+        assert_eq!(routes.longest_prefix("172.16.0.1"), Some(("", &"default")));
+    }
+
+    #[test]
+    fn radix_trie_splits_on_character_boundaries() {
+        // 'é' and 'è' share their first UTF-8 byte, so labels must diverge at whole characters
+        let mut trie: RadixTrie<u32> = RadixTrie::new();
+        trie.insert("café", 1);
+        trie.insert("cafè", 2);
+        trie.insert("caf", 3);
+        assert_eq!(edge_labels(&trie), vec!["caf", " è", " é"]);
+        assert_eq!(trie.get("café"), Some(&1));
+        assert_eq!(trie.get("cafè"), Some(&2));
+        assert_eq!(trie.iter_prefix("caf").count(), 3);
+        assert_eq!(trie.remove("caf"), Some(3));
+        assert_eq!(trie.keys().collect::<Vec<_>>(), vec!["cafè", "café"]);
+    }
+
+    #[test]
+    fn radix_trie_handles_deeply_nested_keys() {
+        // the keys "a", "aa", "aaa", ... each hold a value, so none of the Nodes can be merged and
+        // the trie is a chain a million Nodes deep - built directly, as inserting the keys would
+        // take quadratic time
+        let n = 1_000_000;
+        let mut node = Node::new(Some(n));
+        for depth in (1..n).rev() {
+            node = Node { value: Some(depth), children: vec![Edge { label: "a".into(), node }] };
+        }
+        let mut trie = RadixTrie { root: Node { value: None, children: vec![Edge { label: "a".into(), node }] }, length: n };
+
+        let deepest = "a".repeat(n);
+        assert_eq!(trie.get(&deepest), Some(&n));
+        assert_eq!(trie.remove(&deepest), Some(n));
+        assert_eq!(trie.remove(&deepest), None);
+        // the middle Node is left with no value and one child, so it is merged away
+        assert_eq!(trie.remove(&deepest[..n / 2]), Some(n / 2));
+        assert_eq!(trie.get(&deepest[..n / 2 + 1]), Some(&(n / 2 + 1)));
+        assert_eq!(trie.len(), n - 2);
+        assert_eq!(trie.node_count(), n - 1);
+        drop(trie);
+    }
+}
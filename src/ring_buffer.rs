@@ -0,0 +1,415 @@
+//! Fixed-capacity ring buffer: a FIFO queue of at most N values stored inline in an array, which
+//!     never allocates. Pushing onto a full buffer either overwrites the oldest value - keeping
+//!     the latest N, as for a moving window - or is rejected, handing the value back - as for a
+//!     bounded producer/consumer queue.
+//!
+//! The values occupy `length` slots from index `front`, wrapping from the end of the array back to
+//!     index 0, so they are always at most two contiguous runs: as_slices returns both, and
+//!     make_contiguous rotates the array so they are one.
+//!
+//! ```
+//! use rust_datastructures_algorithms::ring_buffer::{ Overflow, RingBuffer };
+//!
+//! // moving average of the last 3 readings
+//! let mut window: RingBuffer<f64, 3> = RingBuffer::new();
+//! let mut averages = Vec::new();
+//! for reading in [1.0, 2.0, 6.0, 4.0, 8.0] {
+//!     window.push(reading).unwrap();
+//!     averages.push(window.iter().sum::<f64>() / window.len() as f64);
+//! }
+//! assert_eq!(averages, vec![1.0, 1.5, 3.0, 4.0, 6.0]);
+//!
+//! let mut jobs: RingBuffer<&str, 2> = RingBuffer::with_overflow(Overflow::Reject);
+//! assert_eq!(jobs.push("first"), Ok(None));
+//! assert_eq!(jobs.push("second"), Ok(None));
+//! assert_eq!(jobs.push("third"), Err("third"));
+//! assert_eq!(jobs.pop(), Some("first"));
+//! ```
+
+use std::mem::MaybeUninit;
+use std::ops::Index;
+use crate::deque::wrap_index;
+
+
+/// Enum for what a RingBuffer struct does when a value is pushed while it is full.
+///
+/// * `OverwriteOldest`: Store the value in place of the oldest one, which push returns. The
+///         default.
+/// * `Reject`: Leave the buffer as it is, and have push return the value as an error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+    #[default]
+    OverwriteOldest,
+    Reject
+}
+
+
+/// A ring buffer struct holding at most N values.
+///
+/// * `buffer`: Slots of the ring - exactly the `length` slots from `front` (wrapping around) are
+///         initialized.
+/// * `front`: Index of the slot holding the oldest value.
+/// * `length`: Number of values in the buffer.
+/// * `overflow`: What push does when the buffer is full.
+pub struct RingBuffer<T, const N: usize> {
+    buffer: [MaybeUninit<T>; N],
+    front: usize,
+    length: usize,
+    overflow: Overflow
+}
+
+
+/// Iterator yielding references to the values in a RingBuffer struct, from oldest to newest.
+/// Implements DoubleEndedIterator, so it can be reversed to go from newest to oldest. Created by
+/// RingBuffer::iter.
+///
+/// * `ring`: the RingBuffer struct being iterated over.
+/// * `front_offset`: Position (from the oldest value) of the next value to be yielded from the
+///         front.
+/// * `back_offset`: Position one past the next value to be yielded from the back.
+pub struct Iter<'a, T, const N: usize> {
+    ring: &'a RingBuffer<T, N>,
+    front_offset: usize,
+    back_offset: usize
+}
+
+
+// Method implementations for RingBuffer struct
+impl<T, const N: usize> RingBuffer<T, N> {
+
+    /// Return a new, empty RingBuffer struct that overwrites its oldest value when full. Panics if
+    /// N is 0.
+    pub fn new() -> RingBuffer<T, N> {
+        RingBuffer::with_overflow(Overflow::OverwriteOldest)
+    }
+
+    /// Return a new, empty RingBuffer struct with the input behaviour when full. Panics if N is 0.
+    ///
+    /// * `overflow`: what push does when the buffer is full.
+    pub fn with_overflow(overflow: Overflow) -> RingBuffer<T, N> {
+        assert!(N > 0, "a ring buffer needs a capacity of at least 1");
+        RingBuffer { buffer: [const { MaybeUninit::uninit() }; N], front: 0, length: 0, overflow }
+    }
+
+    /// Return the number of values in this RingBuffer.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this RingBuffer holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return whether this RingBuffer holds N values.
+    pub fn is_full(&self) -> bool {
+        self.length == N
+    }
+
+    /// Return the number of values this RingBuffer can hold (N).
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Return what push does when this RingBuffer is full.
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    /// Add a value as the newest in the buffer. Returns Ok(None) if there was room, Ok with the
+    /// oldest value if the buffer was full and overwrote it, or Err with the input value if the
+    /// buffer was full and rejected it.
+    ///
+    /// * `value`: value to be pushed.
+    pub fn push(&mut self, value: T) -> Result<Option<T>, T> {
+        if self.length < N {
+            let back = self.slot_index(self.length);
+            self.buffer[back].write(value);
+            self.length += 1;
+            return Ok(None);
+        }
+        match self.overflow {
+            Overflow::Reject => Err(value),
+            Overflow::OverwriteOldest => {
+                let oldest = std::mem::replace(&mut self.buffer[self.front], MaybeUninit::new(value));
+                self.front = self.slot_index(1);
+                // SAFETY: the buffer is full, so every slot (the front one included) is initialized
+                Ok(Some(unsafe { oldest.assume_init() }))
+            }
+        }
+    }
+
+    /// Remove the oldest value from the buffer and return it (unless the buffer is empty).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+        // SAFETY: the front slot of a non-empty buffer is initialized, and is treated as
+        // uninitialized from here on, so the value is not read or dropped twice
+        let value = unsafe { self.buffer[self.front].assume_init_read() };
+        self.front = self.slot_index(1);
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Get a reference to the oldest value (if the buffer is not empty).
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Get a reference to the newest value (if the buffer is not empty).
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.length.checked_sub(1)?)
+    }
+
+    /// Return a reference to the value at the input position from the oldest, or None if the
+    /// index is not less than len().
+    ///
+    /// * `index`: position of the value, counting from 0 at the oldest.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.length {
+            return None;
+        }
+        // SAFETY: the first `length` slots from the front are initialized
+        Some(unsafe { self.buffer[self.slot_index(index)].assume_init_ref() })
+    }
+
+    /// Return a mutable reference to the value at the input position from the oldest (see get).
+    ///
+    /// * `index`: position of the value, counting from 0 at the oldest.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.length {
+            return None;
+        }
+        let slot = self.slot_index(index);
+        // SAFETY: the first `length` slots from the front are initialized
+        Some(unsafe { self.buffer[slot].assume_init_mut() })
+    }
+
+    /// Return the values as two slices which, one after the other, run from oldest to newest. The
+    /// second is empty unless the values wrap around the end of the array.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (first, second) = self.initialized_ranges();
+        // SAFETY: the two ranges are exactly the initialized slots
+        unsafe { (assume_init_slice(&self.buffer[first]), assume_init_slice(&self.buffer[second])) }
+    }
+
+    /// Rotate the array so the values are stored in order from index 0, and return them as one
+    /// mutable slice from oldest to newest. Takes O(N) time unless they already are.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.front + self.length > N {
+            // moving slots moves their contents, initialized or not, so only front changes
+            self.buffer.rotate_left(self.front);
+            self.front = 0;
+        }
+        let (values, _) = self.initialized_ranges();
+        // SAFETY: after rotating (if needed) the values don't wrap, so the first range covers them
+        unsafe { &mut *(&mut self.buffer[values] as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+
+    /// Remove every value.
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Return an iterator of references to the values in this RingBuffer, from oldest to newest.
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            ring: self,
+            front_offset: 0,
+            back_offset: self.length
+        }
+    }
+
+    /// Return the index of the slot the input number of places after the front slot, wrapping
+    /// around the end of the array.
+    ///
+    /// * `offset`: number of places after the front slot, less than or equal to N.
+    fn slot_index(&self, offset: usize) -> usize {
+        wrap_index(self.front + offset, N)
+    }
+
+    /// Return the ranges of array indexes holding the values: from the front slot up to the
+    /// newest value or the end of the array, and from index 0 up to the newest value if they wrap.
+    fn initialized_ranges(&self) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let end = self.front + self.length;
+        if end <= N {
+            (self.front..end, 0..0)
+        } else {
+            (self.front..N, 0..end - N)
+        }
+    }
+}
+
+/// Return a slice of initialized slots as a slice of their values.
+///
+/// # Safety
+///
+/// Every slot in the input slice must be initialized.
+///
+/// * `slots`: the slots.
+unsafe fn assume_init_slice<T>(slots: &[MaybeUninit<T>]) -> &[T] {
+    // MaybeUninit<T> has the same layout as T
+    unsafe { &*(slots as *const [MaybeUninit<T>] as *const [T]) }
+}
+
+impl<T, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> Self {
+        RingBuffer::new()
+    }
+}
+
+// Drop the values still in the buffer - the array of MaybeUninit slots won't.
+impl<T, const N: usize> Drop for RingBuffer<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// Indexing from the oldest value; panics if the index is not less than len(), like Vec.
+impl<T, const N: usize> Index<usize> for RingBuffer<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let length = self.length;
+        self.get(index).unwrap_or_else(|| panic!("index {} is out of bounds for a ring buffer of length {}", index, length))
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a RingBuffer<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+// Iterator implementations for Iter struct
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front_offset == self.back_offset {
+            return None;
+        }
+        let value = self.ring.get(self.front_offset);
+        self.front_offset += 1;
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back_offset - self.front_offset;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Iter<'a, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front_offset == self.back_offset {
+            return None;
+        }
+        self.back_offset -= 1;
+        self.ring.get(self.back_offset)
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for Iter<'a, T, N> {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_ring_buffer() -> RingBuffer<u32, 4> {
+    let mut ring = RingBuffer::new();
+    for value in 1..=6 {
+        let _ = ring.push(value);
+    }
+    ring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn ring_buffer_works() {
+        let mut ring = setup_ring_buffer();
+        // 1 and 2 were overwritten
+        assert!(ring.is_full());
+        assert_eq!(ring.iter().collect::<Vec<_>>(), vec![&3, &4, &5, &6]);
+        assert_eq!(ring.iter().rev().collect::<Vec<_>>(), vec![&6, &5, &4, &3]);
+        assert_eq!((ring.front(), ring.back()), (Some(&3), Some(&6)));
+        assert_eq!(ring[1], 4);
+        assert_eq!(ring.get(4), None);
+        assert_eq!(ring.push(7), Ok(Some(3)));
+
+        assert_eq!(ring.pop(), Some(4));
+        assert_eq!(ring.pop(), Some(5));
+        *ring.get_mut(0).unwrap() *= 10;
+        assert_eq!(ring.push(8), Ok(None));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![60, 7, 8]);
+        assert_eq!(ring.iter().len(), 3);
+
+        ring.clear();
+        assert!(ring.is_empty());
+        assert_eq!(ring.pop(), None);
+        assert_eq!(ring.back(), None);
+    }
+
+    #[test]
+    fn ring_buffer_rejects_when_full() {
+        let mut ring: RingBuffer<u32, 3> = RingBuffer::with_overflow(Overflow::Reject);
+        assert_eq!(ring.overflow(), Overflow::Reject);
+        for value in 0..3 {
+            assert_eq!(ring.push(value), Ok(None));
+        }
+        assert_eq!(ring.push(3), Err(3));
+        assert_eq!(ring.iter().collect::<Vec<_>>(), vec![&0, &1, &2]);
+        assert_eq!(ring.pop(), Some(0));
+        assert_eq!(ring.push(3), Ok(None));
+        assert_eq!(ring.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    fn ring_buffer_slices() {
+        let mut ring = setup_ring_buffer();
+        // front is at index 2, so the values wrap around
+        assert_eq!(ring.as_slices(), (&[3, 4][..], &[5, 6][..]));
+        assert_eq!(ring.make_contiguous(), &mut [3, 4, 5, 6]);
+        assert_eq!(ring.as_slices(), (&[3, 4, 5, 6][..], &[][..]));
+
+        ring.pop();
+        ring.make_contiguous().reverse();
+        assert_eq!(ring.iter().collect::<Vec<_>>(), vec![&6, &5, &4]);
+        // the values didn't wrap, so they weren't moved back to index 0
+        assert_eq!(ring.push(7), Ok(None));
+        assert_eq!(ring.as_slices(), (&[6, 5, 4][..], &[7][..]));
+        assert_eq!(ring.push(8), Ok(Some(6)));
+        assert_eq!(ring.as_slices(), (&[5, 4][..], &[7, 8][..]));
+    }
+
+    #[test]
+    fn ring_buffer_drops_each_value_once() {
+        let value = Rc::new(0);
+        {
+            let mut ring: RingBuffer<Rc<u32>, 3> = RingBuffer::new();
+            for _ in 0..5 {
+                let _ = ring.push(Rc::clone(&value));
+            }
+            assert_eq!(Rc::strong_count(&value), 4);
+            ring.pop();
+            assert_eq!(Rc::strong_count(&value), 3);
+            ring.make_contiguous();
+        }
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity of at least 1")]
+    fn ring_buffer_needs_capacity() {
+        RingBuffer::<u32, 0>::new();
+    }
+}
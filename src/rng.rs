@@ -0,0 +1,49 @@
+//! Small pseudo-random number generator for the randomized structures in this crate (treap
+//!     priorities, skip list levels). Not suitable for anything needing unpredictability - only
+//!     for spreading values out so expected-case bounds hold.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{ BuildHasher, Hasher };
+
+
+/// Marsaglia's xorshift64 generator.
+///
+/// * `state`: Last value generated (or the seed). Never 0, since xorshift gets stuck at 0.
+#[derive(Clone, Debug)]
+pub struct XorShift64 {
+    state: u64
+}
+
+
+// Method implementations for XorShift64 struct
+impl XorShift64 {
+
+    /// Return a new XorShift64 struct seeded randomly (from the standard library's per-process
+    ///     random hasher keys).
+    pub fn new() -> XorShift64 {
+        XorShift64::with_seed(RandomState::new().build_hasher().finish())
+    }
+
+    /// Return a new XorShift64 struct generating a reproducible sequence from the input seed.
+    ///
+    /// * `seed`: seed for the generator. A seed of 0 is treated as 1.
+    pub fn with_seed(seed: u64) -> XorShift64 {
+        XorShift64 { state: seed.max(1) }
+    }
+
+    /// Advance the generator and return its next value.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
+impl Default for XorShift64 {
+    fn default() -> Self {
+        XorShift64::new()
+    }
+}
@@ -0,0 +1,368 @@
+//! Segment tree: a complete binary tree over an array where each internal node holds the
+//!     combination of the two below it, so any range of the array is covered by O(log n) nodes.
+//!     Range queries and point updates both take O(log n).
+//!
+//! The combining operation is a type implementing the Monoid trait: an associative operation
+//!     with an identity value. Sum, Min and Max are provided for primitive numbers; the
+//!     operation doesn't need to be commutative, so the order of the array is respected.
+//!
+//! ```
+//! use rust_datastructures_algorithms::segment_tree::{ Min, SegmentTree, Sum };
+//!
+//! let values = [5, 2, 8, 1, 9, 3];
+//! let mut sums: SegmentTree<i64, Sum> = SegmentTree::build(&values);
+//! let minimums: SegmentTree<i64, Min> = SegmentTree::build(&values);
+//! assert_eq!(sums.query(1..4), 11);
+//! assert_eq!(minimums.query(..3), 2);
+//! sums.update(2, 0);
+//! assert_eq!(sums.query(..), 20);
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::{ Add, Bound, Mul, RangeBounds };
+
+
+/// Associative operation with an identity value, used to combine the values in a range.
+///
+/// Implementations must satisfy, for all values a, b and c:
+///     `combine(combine(a, b), c) == combine(a, combine(b, c))` and
+///     `combine(identity(), a) == a == combine(a, identity())`.
+pub trait Monoid<T> {
+
+    /// Return the identity value - the combination of an empty range.
+    fn identity() -> T;
+
+    /// Return the combination of two values, the left one coming first in the array.
+    ///
+    /// * `left`: combination of the earlier values.
+    /// * `right`: combination of the later values.
+    fn combine(left: &T, right: &T) -> T;
+}
+
+
+/// Primitive number types the provided monoids (and range updates) work on.
+pub trait Number: Copy + PartialOrd + Add<Output = Self> + Mul<Output = Self> {
+    /// The additive identity.
+    const ZERO: Self;
+    /// The smallest value - the identity for Max.
+    const MIN: Self;
+    /// The largest value - the identity for Min.
+    const MAX: Self;
+
+    /// Return the input count as this type, e.g. to scale a value by the length of a range.
+    ///
+    /// * `count`: count to convert.
+    fn from_count(count: usize) -> Self;
+}
+
+macro_rules! impl_number {
+    ($($integer:ty),*; $($float:ty),*) => {
+        $(impl Number for $integer {
+            const ZERO: Self = 0;
+            const MIN: Self = <$integer>::MIN;
+            const MAX: Self = <$integer>::MAX;
+
+            fn from_count(count: usize) -> Self {
+                count as $integer
+            }
+        })*
+        $(impl Number for $float {
+            const ZERO: Self = 0.0;
+            const MIN: Self = <$float>::NEG_INFINITY;
+            const MAX: Self = <$float>::INFINITY;
+
+            fn from_count(count: usize) -> Self {
+                count as $float
+            }
+        })*
+    };
+}
+
+impl_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize; f32, f64);
+
+
+/// Monoid adding numbers, with identity 0.
+pub struct Sum;
+
+/// Monoid taking the smaller of two numbers, with identity the largest value of the type.
+pub struct Min;
+
+/// Monoid taking the larger of two numbers, with identity the smallest value of the type.
+pub struct Max;
+
+/// Monoid taking the greatest common divisor of two unsigned integers, with identity 0.
+pub struct Gcd;
+
+impl<T: Number> Monoid<T> for Sum {
+    fn identity() -> T {
+        T::ZERO
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        *left + *right
+    }
+}
+
+impl<T: Number> Monoid<T> for Min {
+    fn identity() -> T {
+        T::MAX
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        if right < left { *right } else { *left }
+    }
+}
+
+impl<T: Number> Monoid<T> for Max {
+    fn identity() -> T {
+        T::MIN
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        if right > left { *right } else { *left }
+    }
+}
+
+macro_rules! impl_gcd {
+    ($($unsigned:ty),*) => {
+        $(impl Monoid<$unsigned> for Gcd {
+            fn identity() -> $unsigned {
+                0
+            }
+
+            fn combine(left: &$unsigned, right: &$unsigned) -> $unsigned {
+                let (mut a, mut b) = (*left, *right);
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                a
+            }
+        })*
+    };
+}
+
+impl_gcd!(u8, u16, u32, u64, u128, usize);
+
+
+/// A segment tree struct over an array of n values, stored bottom-up in a Vec of 2n nodes.
+///
+/// * `length`: Number of values in the array (n).
+/// * `tree`: The nodes - the values themselves at indexes n to 2n - 1, and the combination of the
+///         nodes at 2i and 2i + 1 at each index i from 1 to n - 1. Index 0 is unused.
+/// * `op`: Marker for the monoid combining the values.
+pub struct SegmentTree<T, Op: Monoid<T>> {
+    length: usize,
+    tree: Vec<T>,
+    op: PhantomData<Op>
+}
+
+
+// Method implementations for SegmentTree struct
+impl<T, Op> SegmentTree<T, Op> where T: Clone, Op: Monoid<T> {
+
+    /// Return a new SegmentTree struct over a copy of the input values, built bottom-up in O(n).
+    ///
+    /// * `values`: the array to answer queries about.
+    pub fn build(values: &[T]) -> SegmentTree<T, Op> {
+        let length = values.len();
+        let mut tree = Vec::with_capacity(2 * length);
+        tree.resize(length, Op::identity());
+        tree.extend_from_slice(values);
+        for index in (1..length).rev() {
+            tree[index] = Op::combine(&tree[2 * index], &tree[2 * index + 1]);
+        }
+        SegmentTree { length, tree, op: PhantomData }
+    }
+
+    /// Return the number of values in the array.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether the array holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return a reference to the value at the input index of the array. Panics if the index is
+    /// out of bounds.
+    ///
+    /// * `index`: index of the value.
+    pub fn get(&self, index: usize) -> &T {
+        assert!(index < self.length, "index {} out of bounds for length {}", index, self.length);
+        &self.tree[self.length + index]
+    }
+
+    /// Return the combination of the values in the input range of the array, in order (the
+    /// identity for an empty range). Panics if the range is out of bounds, like slicing.
+    ///
+    /// Walks up from both ends of the range at once: whenever an end is the right child of its
+    ///     parent (for the start) or the left (for the end), that node is outside the parent's
+    ///     span, so it is combined in and the end moves inward. Values from the left and right
+    ///     are kept separately so the operation needn't be commutative. O(log n).
+    ///
+    /// * `range`: range of indexes to combine, such as `start..end` or `..`.
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let (start, end) = range_to_indexes(range, self.length);
+        let (mut left, mut right) = (start + self.length, end + self.length);
+        let mut from_left = Op::identity();
+        let mut from_right = Op::identity();
+        while left < right {
+            if left % 2 == 1 {
+                from_left = Op::combine(&from_left, &self.tree[left]);
+                left += 1;
+            }
+            if right % 2 == 1 {
+                right -= 1;
+                from_right = Op::combine(&self.tree[right], &from_right);
+            }
+            left /= 2;
+            right /= 2;
+        }
+        Op::combine(&from_left, &from_right)
+    }
+
+    /// Replace the value at the input index of the array, recombining its ancestors in O(log n).
+    /// Panics if the index is out of bounds.
+    ///
+    /// * `index`: index of the value.
+    /// * `value`: the new value.
+    pub fn update(&mut self, index: usize, value: T) {
+        assert!(index < self.length, "index {} out of bounds for length {}", index, self.length);
+        let mut node = self.length + index;
+        self.tree[node] = value;
+        while node > 1 {
+            node /= 2;
+            self.tree[node] = Op::combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+        }
+    }
+}
+
+/// Return the start (inclusive) and end (exclusive) indexes of a range over an array of the input
+/// length. Panics if the range is decreasing or goes past the end, like slicing.
+///
+/// * `range`: range of indexes.
+/// * `length`: length of the array.
+pub(crate) fn range_to_indexes<R: RangeBounds<usize>>(range: R, length: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start + 1,
+        Bound::Unbounded => 0
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => end + 1,
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => length
+    };
+    assert!(start <= end, "range starts at {} but ends at {}", start, end);
+    assert!(end <= length, "range end {} out of bounds for length {}", end, length);
+    (start, end)
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_values() -> Vec<i64> {
+    vec![5, -2, 8, 1, 9, -3, 7]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Monoid concatenating strings - associative but not commutative.
+    struct Concat;
+
+    impl Monoid<String> for Concat {
+        fn identity() -> String {
+            String::new()
+        }
+
+        fn combine(left: &String, right: &String) -> String {
+            format!("{}{}", left, right)
+        }
+    }
+
+    #[test]
+    fn segment_tree_works() {
+        let values = setup_values();
+        let mut sums: SegmentTree<i64, Sum> = SegmentTree::build(&values);
+        let minimums: SegmentTree<i64, Min> = SegmentTree::build(&values);
+        let maximums: SegmentTree<i64, Max> = SegmentTree::build(&values);
+        assert_eq!(sums.len(), 7);
+        assert_eq!(sums.query(..), 25);
+        assert_eq!(sums.query(2..5), 18);
+        assert_eq!(sums.query(3..=3), 1);
+        assert_eq!(sums.query(4..4), 0);
+        assert_eq!(minimums.query(..), -3);
+        assert_eq!(minimums.query(..5), -2);
+        assert_eq!(maximums.query(5..), 7);
+        assert_eq!(*sums.get(6), 7);
+
+        sums.update(2, 0);
+        assert_eq!(*sums.get(2), 0);
+        assert_eq!(sums.query(..), 17);
+        assert_eq!(sums.query(2..5), 10);
+
+        let gcds: SegmentTree<u32, Gcd> = SegmentTree::build(&[12, 18, 30, 7, 14]);
+        assert_eq!(gcds.query(..3), 6);
+        assert_eq!(gcds.query(3..), 7);
+        assert_eq!(gcds.query(..), 1);
+
+        let empty: SegmentTree<f64, Max> = SegmentTree::build(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.query(..), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn segment_tree_keeps_order() {
+        let words: Vec<String> = "the quick brown fox jumps".split(' ').map(String::from).collect();
+        let mut tree: SegmentTree<String, Concat> = SegmentTree::build(&words);
+        assert_eq!(tree.query(..), "thequickbrownfoxjumps");
+        assert_eq!(tree.query(1..4), "quickbrownfox");
+        tree.update(0, "a".to_string());
+        for start in 0..=words.len() {
+            for end in start..=words.len() {
+                let expected: String = (start..end).map(|index| tree.get(index).as_str()).collect();
+                assert_eq!(tree.query(start..end), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn segment_tree_matches_naive() {
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next_random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for length in [1, 2, 3, 10, 33, 64] {
+            let mut values: Vec<i64> = (0..length).map(|_| (next_random() % 201) as i64 - 100).collect();
+            let mut sums: SegmentTree<i64, Sum> = SegmentTree::build(&values);
+            let mut maximums: SegmentTree<i64, Max> = SegmentTree::build(&values);
+            for _ in 0..300 {
+                let index = (next_random() % length as u64) as usize;
+                let value = (next_random() % 201) as i64 - 100;
+                values[index] = value;
+                sums.update(index, value);
+                maximums.update(index, value);
+
+                let start = (next_random() % (length as u64 + 1)) as usize;
+                let end = start + (next_random() % (length - start + 1) as u64) as usize;
+                let range = &values[start..end];
+                assert_eq!(sums.query(start..end), range.iter().sum::<i64>());
+                assert_eq!(maximums.query(start..end), range.iter().copied().max().unwrap_or(i64::MIN));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn segment_tree_query_out_of_bounds() {
+        let tree: SegmentTree<i64, Sum> = SegmentTree::build(&setup_values());
+        tree.query(3..8);
+    }
+}
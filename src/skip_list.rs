@@ -0,0 +1,448 @@
+//! Skip list: a sorted linked list with extra "express lane" lists stacked on top of it. Every
+//!     Node is in the bottom list, and each Node is also in the list above with probability 1/2,
+//!     so the list at level k skips about 2^k Nodes at a time. Searches start at the top level
+//!     and drop down a level whenever the next Node would overshoot.
+//!
+//! Insert, search and remove take O(log n) expected time. Unlike the balanced trees in this crate
+//!     (compare the bst and treap modules), nothing is ever rebalanced - a Node's level is drawn
+//!     at random when it is inserted and never changes, so the bounds hold in expectation
+//!     whatever order values arrive in.
+//!
+//! Nodes are kept in a Vec and link to each other by index.
+//!
+//! ```
+//! use rust_datastructures_algorithms::skip_list::SkipList;
+//!
+//! let mut list = SkipList::new();
+//! for value in [30, 10, 50, 20, 40] {
+//!     list.insert(value);
+//! }
+//! assert!(list.contains(&20));
+//! assert_eq!(list.remove(&30), Some(30));
+//! assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 20, 40, 50]);
+//! ```
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use crate::rng::XorShift64;
+
+
+/// Most levels a Node can be in - enough for 2^32 values at the expected spacing.
+const MAX_LEVEL: usize = 32;
+
+
+/// A skip list struct, holding its Nodes in a Vec and linking them by index.
+///
+/// * `nodes`: Slots holding every Node. Slots of removed Nodes are left None and reused.
+/// * `free`: Indexes of the empty slots.
+/// * `head`: Index of the first Node in the list at each level - as many levels as the tallest
+///         Node has.
+/// * `length`: Number of values in the list.
+/// * `rng`: Xorshift generator used to draw the level of each new Node.
+pub struct SkipList<T: Ord> {
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+    head: Vec<Option<usize>>,
+    length: usize,
+    rng: XorShift64
+}
+
+
+/// A Node in a SkipList struct.
+///
+/// * `value`: Value held in this Node - must be comparable with < or > (implement Ord trait)
+/// * `forward`: Index of the next Node in the list at each level this Node is in (None at the end
+///         of a level). Its length is the Node's level, drawn when it was inserted.
+struct Node<T: Ord> {
+    value: T,
+    forward: Vec<Option<usize>>
+}
+
+
+/// Iterator yielding references to the values of a SkipList struct in ascending order, by
+/// following the bottom level.
+///
+/// * `nodes`: The list's Node slots.
+/// * `next`: Index of the Node holding the next value (None once done).
+/// * `remaining`: Number of values not yet yielded.
+pub struct Iter<'a, T: Ord> {
+    nodes: &'a [Option<Node<T>>],
+    next: Option<usize>,
+    remaining: usize
+}
+
+
+// Method implementations for SkipList struct
+impl<T> SkipList<T> where T: Ord {
+
+    /// Return a new, empty SkipList struct, with its level generator seeded randomly.
+    pub fn new() -> SkipList<T> {
+        SkipList::with_rng(XorShift64::new())
+    }
+
+    /// Return a new, empty SkipList struct whose Node levels are drawn from the input seed, so the
+    ///     shape of the list is reproducible.
+    ///
+    /// * `seed`: seed for the level generator.
+    pub fn with_seed(seed: u64) -> SkipList<T> {
+        SkipList::with_rng(XorShift64::with_seed(seed))
+    }
+
+    /// Return a new, empty SkipList struct drawing Node levels from the input generator.
+    ///
+    /// * `rng`: generator for Node levels.
+    fn with_rng(rng: XorShift64) -> SkipList<T> {
+        SkipList { nodes: Vec::new(), free: Vec::new(), head: Vec::new(), length: 0, rng }
+    }
+
+    /// Return the number of values held in this SkipList.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Return whether this SkipList holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Return the number of levels in this SkipList: the level of its tallest Node (0 when empty).
+    pub fn level(&self) -> usize {
+        self.head.len()
+    }
+
+    /// Find whether a value is present in this SkipList.
+    ///
+    /// * `value`: value to look for - any borrowed form of the list's value type.
+    pub fn contains<Q>(&self, value: &Q) -> bool where T: Borrow<Q>, Q: Ord + ?Sized {
+        self.get(value).is_some()
+    }
+
+    /// Return a reference to the value in this SkipList equal to the input (if there is one).
+    ///
+    /// * `value`: value to look for - any borrowed form of the list's value type.
+    pub fn get<Q>(&self, value: &Q) -> Option<&T> where T: Borrow<Q>, Q: Ord + ?Sized {
+        let predecessors = self.find_predecessors(value);
+        let candidate = self.next_at(predecessors[0], 0)?;
+        let found = &self.node(candidate).value;
+        (found.borrow() == value).then_some(found)
+    }
+
+    /// Add the input value to this SkipList, returning whether it was added.
+    ///
+    /// The new Node's level is drawn at random - level k with probability 1/2^k - and it is linked
+    ///     in after the last Node before it at each of its levels, found by one search from the
+    ///     top. If the Node is taller than every other, the list grows new levels for it.
+    ///
+    /// It should be noted - if this value is evaluated as Ordering::Equal (== operator) to a value
+    ///     already in the list, this value will be discarded and false returned.
+    ///
+    /// * `value`: The value to be added into the list.
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut predecessors = self.find_predecessors(&value);
+        if let Some(next) = self.next_at(predecessors[0], 0) {
+            if self.node(next).value == value {
+                return false;
+            }
+        }
+
+        let level = self.random_level();
+        // the head comes before the new Node at any level the list doesn't have yet
+        predecessors.resize(level.max(self.head.len()), None);
+        self.head.resize(level.max(self.head.len()), None);
+        let forward = (0..level).map(|height| self.next_at(predecessors[height], height)).collect();
+        let index = self.allocate(Node { value, forward });
+        for (height, predecessor) in predecessors.iter().enumerate().take(level) {
+            self.set_next(*predecessor, height, Some(index));
+        }
+        self.length += 1;
+        true
+    }
+
+    /// Remove the value in this SkipList equal to the input (if there is one) and return it.
+    ///
+    /// The Node is unlinked at each of its levels from the last Node before it there, and levels
+    ///     left empty are dropped.
+    ///
+    /// * `value`: value to be removed - any borrowed form of the list's value type.
+    pub fn remove<Q>(&mut self, value: &Q) -> Option<T> where T: Borrow<Q>, Q: Ord + ?Sized {
+        let predecessors = self.find_predecessors(value);
+        let index = self.next_at(predecessors[0], 0)?;
+        if self.node(index).value.borrow() != value {
+            return None;
+        }
+
+        let node = self.nodes[index].take().expect("linked slots hold Nodes");
+        self.free.push(index);
+        for (height, next) in node.forward.into_iter().enumerate() {
+            self.set_next(predecessors[height], height, next);
+        }
+        while self.head.last() == Some(&None) {
+            self.head.pop();
+        }
+        self.length -= 1;
+        Some(node.value)
+    }
+
+    /// Return a reference to the smallest value in this SkipList.
+    pub fn first(&self) -> Option<&T> {
+        self.head.first().copied().flatten().map(|index| &self.node(index).value)
+    }
+
+    /// Return an iterator of references to the values in this SkipList, in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { nodes: &self.nodes, next: self.head.first().copied().flatten(), remaining: self.length }
+    }
+
+    /// Return the last Node before the input value at each level (None standing for the head):
+    /// walk right along the top level while the next value is less than the input, then drop a
+    /// level, and so on down to the bottom. Has an entry for every level, and at least one.
+    ///
+    /// * `value`: value to search for - any borrowed form of the list's value type.
+    fn find_predecessors<Q>(&self, value: &Q) -> Vec<Option<usize>> where T: Borrow<Q>, Q: Ord + ?Sized {
+        let mut predecessors = vec![None; self.head.len().max(1)];
+        let mut current = None;
+        for height in (0..self.head.len()).rev() {
+            while let Some(next) = self.next_at(current, height) {
+                if self.node(next).value.borrow().cmp(value) != Ordering::Less {
+                    break;
+                }
+                current = Some(next);
+            }
+            predecessors[height] = current;
+        }
+        predecessors
+    }
+
+    /// Return the index of the Node after the input one at the input level.
+    ///
+    /// * `index`: index of the Node, or None for the head.
+    /// * `height`: level to follow.
+    fn next_at(&self, index: Option<usize>, height: usize) -> Option<usize> {
+        match index {
+            None => self.head.get(height).copied().flatten(),
+            Some(index) => self.node(index).forward[height]
+        }
+    }
+
+    /// Link the input Node to a new next Node at the input level.
+    ///
+    /// * `index`: index of the Node, or None for the head.
+    /// * `height`: level to relink.
+    /// * `next`: index of the new next Node, or None for the end of the level.
+    fn set_next(&mut self, index: Option<usize>, height: usize, next: Option<usize>) {
+        match index {
+            None => self.head[height] = next,
+            Some(index) => self.nodes[index].as_mut().expect("linked slots hold Nodes").forward[height] = next
+        }
+    }
+
+    /// Return the Node at the input index, which must be linked into the list.
+    ///
+    /// * `index`: index of the Node.
+    fn node(&self, index: usize) -> &Node<T> {
+        self.nodes[index].as_ref().expect("linked slots hold Nodes")
+    }
+
+    /// Store a Node in an empty slot (or a new one) and return its index.
+    ///
+    /// * `node`: Node to be stored.
+    fn allocate(&mut self, node: Node<T>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            },
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Draw a level for a new Node: one more than the number of trailing 1 bits of a random
+    /// number, so each further level is half as likely as the one before. Capped at MAX_LEVEL.
+    fn random_level(&mut self) -> usize {
+        (self.rng.next_u64().trailing_ones() as usize + 1).min(MAX_LEVEL)
+    }
+}
+
+impl<T> Default for SkipList<T> where T: Ord {
+    fn default() -> Self {
+        SkipList::new()
+    }
+}
+
+impl<T> fmt::Debug for SkipList<T> where T: Ord + fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Extend<T> for SkipList<T> where T: Ord {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for SkipList<T> where T: Ord {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SkipList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SkipList<T> where T: Ord {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+// Iterator implementation for Iter struct
+impl<'a, T> Iterator for Iter<'a, T> where T: Ord {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes[self.next?].as_ref().expect("linked slots hold Nodes");
+        self.next = node.forward[0];
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> where T: Ord {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_skip_list() -> SkipList<u32> {
+    let mut list = SkipList::with_seed(42);
+    for value in [5, 3, 8, 1, 4, 7, 9, 2, 6] {
+        list.insert(value);
+    }
+    list
+}
+
+/// Check that every level is sorted, every Node is in the levels below its top one and only
+/// those, and the bottom level holds every value.
+#[cfg(test)]
+fn check_invariants<T: Ord>(list: &SkipList<T>) {
+    assert!(list.head.last().is_none_or(|first| first.is_some()), "the top level is empty");
+    for height in 0..list.level() {
+        let mut members = Vec::new();
+        let mut next = list.head[height];
+        while let Some(index) = next {
+            members.push(index);
+            next = list.node(index).forward[height];
+        }
+        assert!(members.windows(2).all(|pair| list.node(pair[0]).value < list.node(pair[1]).value));
+        let expected: Vec<usize> = (0..list.nodes.len())
+            .filter(|index| list.nodes[*index].as_ref().is_some_and(|node| node.forward.len() > height))
+            .collect();
+        let mut sorted_members = members.clone();
+        sorted_members.sort();
+        assert_eq!(sorted_members, expected);
+        if height == 0 {
+            assert_eq!(members.len(), list.len());
+        }
+    }
+    assert_eq!(list.nodes.iter().filter(|slot| slot.is_none()).count(), list.free.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_list_works() {
+        let mut list = setup_skip_list();
+        check_invariants(&list);
+        assert_eq!(list.len(), 9);
+        assert!(list.contains(&4));
+        assert!(!list.contains(&10));
+        assert_eq!(list.get(&7), Some(&7));
+        assert_eq!(list.first(), Some(&1));
+        assert!(!list.insert(4));
+        assert_eq!(list.len(), 9);
+
+        assert_eq!(list.remove(&1), Some(1));
+        assert_eq!(list.remove(&1), None);
+        assert_eq!(list.remove(&10), None);
+        check_invariants(&list);
+        assert_eq!(list.first(), Some(&2));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(list.iter().len(), 8);
+
+        for value in 2..10 {
+            assert_eq!(list.remove(&value), Some(value));
+        }
+        check_invariants(&list);
+        assert!(list.is_empty());
+        assert_eq!(list.level(), 0);
+        assert_eq!(list.first(), None);
+        assert!(list.insert(3));
+        assert_eq!(list.nodes.len(), 9);
+    }
+
+    #[test]
+    fn skip_list_matches_std_btree_set() {
+        let mut list = SkipList::with_seed(7);
+        let mut expected = std::collections::BTreeSet::new();
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for step in 0..5000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let value = (seed % 700) as u32;
+            if seed.is_multiple_of(3) {
+                assert_eq!(list.remove(&value), expected.take(&value));
+            } else {
+                assert_eq!(list.insert(value), expected.insert(value));
+            }
+            assert_eq!(list.contains(&(value + 1)), expected.contains(&(value + 1)));
+            if step % 500 == 0 {
+                check_invariants(&list);
+            }
+        }
+        check_invariants(&list);
+        assert!(list.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn skip_list_levels_stay_logarithmic() {
+        // values in ascending order - the worst case for an unbalanced binary search tree
+        let list: SkipList<u32> = (0..1 << 14).collect();
+        check_invariants(&list);
+        assert!(list.level() <= 2 * 14, "level {}", list.level());
+        // about half the Nodes are in each level above the one below
+        let above_bottom = list.nodes.iter().flatten().filter(|node| node.forward.len() > 1).count();
+        assert!((above_bottom as f64 / list.len() as f64 - 0.5).abs() < 0.05);
+
+        // the same seed gives the same levels
+        let first: SkipList<u32> = { let mut list = SkipList::with_seed(3); list.extend(0..100); list };
+        let second: SkipList<u32> = { let mut list = SkipList::with_seed(3); list.extend(0..100); list };
+        let levels = |list: &SkipList<u32>| list.nodes.iter().flatten().map(|node| node.forward.len()).collect::<Vec<_>>();
+        assert_eq!(levels(&first), levels(&second));
+    }
+
+    #[test]
+    fn skip_list_traits() {
+        let list: SkipList<String> = ["b", "a", "c"].into_iter().map(String::from).collect();
+        assert_eq!(format!("{:?}", list), "[\"a\", \"b\", \"c\"]");
+        assert!(list.contains("b"));
+        assert_eq!((&list).into_iter().count(), 3);
+        assert!(SkipList::<u32>::default().is_empty());
+    }
+}
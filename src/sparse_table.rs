@@ -0,0 +1,277 @@
+//! Sparse table: answers range queries on an array that never changes in O(1), after an
+//!     O(n log n) build. Level k of the table holds the combination of every run of 2^k values,
+//!     and any range is covered by two (possibly overlapping) runs of the largest power of two
+//!     that fits in it.
+//!
+//! Counting the overlap twice is only harmless for idempotent operations - those where combining
+//!     a value with itself changes nothing - so SparseTable takes a Monoid that also implements
+//!     the Idempotent marker trait: Min, Max and Gcd from the segment_tree module. For sums,
+//!     which aren't idempotent, use a segment tree or prefix sums.
+//!
+//! RangeMinimumQuery is the same table over positions, answering which index holds the minimum
+//!     of a range - the building block for lowest common ancestors and for the longest common
+//!     prefix of any two suffixes from an LCP array.
+//!
+//! ```
+//! use rust_datastructures_algorithms::segment_tree::{ Gcd, Max };
+//! use rust_datastructures_algorithms::sparse_table::{ RangeMinimumQuery, SparseTable };
+//!
+//! let values = [12, 18, 6, 30, 15];
+//! let maximums: SparseTable<u32, Max> = SparseTable::build(&values);
+//! let gcds: SparseTable<u32, Gcd> = SparseTable::build(&values);
+//! assert_eq!(maximums.query(1..3), 18);
+//! assert_eq!(gcds.query(3..), 15);
+//!
+//! let rmq = RangeMinimumQuery::new(&values);
+//! assert_eq!(rmq.min_index(..2), Some(0));
+//! assert_eq!(rmq.min(1..), Some(&6));
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+use crate::segment_tree::{ range_to_indexes, Gcd, Max, Min, Monoid, Number };
+
+
+/// Marker for a Monoid that is idempotent and commutative: `combine(a, a) == a` and
+/// `combine(a, b) == combine(b, a)`, so a value counted twice, in either order, doesn't change the
+/// combination.
+pub trait Idempotent<T>: Monoid<T> {}
+
+impl<T: Number> Idempotent<T> for Min {}
+
+impl<T: Number> Idempotent<T> for Max {}
+
+impl<T> Idempotent<T> for Gcd where Gcd: Monoid<T> {}
+
+
+/// A sparse table struct over an array of n values.
+///
+/// * `levels`: Level k holds, at each index i, the combination of the 2^k values starting at i -
+///         so level 0 is a copy of the array, and level k has n - 2^k + 1 entries.
+/// * `op`: Marker for the idempotent monoid combining the values.
+pub struct SparseTable<T, Op: Idempotent<T>> {
+    levels: Vec<Vec<T>>,
+    op: PhantomData<Op>
+}
+
+
+/// A range minimum query struct: a sparse table of the positions of minimums in an array.
+///
+/// * `values`: The array.
+/// * `levels`: Level k holds, at each index i, the index of the smallest of the 2^k values
+///         starting at i (the leftmost one if there are several).
+pub struct RangeMinimumQuery<T: Ord> {
+    values: Vec<T>,
+    levels: Vec<Vec<usize>>
+}
+
+
+// Method implementations for SparseTable struct
+impl<T, Op> SparseTable<T, Op> where T: Clone, Op: Idempotent<T> {
+
+    /// Return a new SparseTable struct over a copy of the input values, built in O(n log n).
+    ///
+    /// * `values`: the array to answer queries about.
+    pub fn build(values: &[T]) -> SparseTable<T, Op> {
+        SparseTable { levels: build_levels(values.to_vec(), Op::combine), op: PhantomData }
+    }
+
+    /// Return the number of values in the array.
+    pub fn len(&self) -> usize {
+        self.levels.first().map_or(0, Vec::len)
+    }
+
+    /// Return whether the array holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the combination of the values in the input range of the array (the identity for an
+    /// empty range), from the two runs of 2^k values starting at its start and ending at its end,
+    /// for the largest 2^k that fits. O(1). Panics if the range is out of bounds, like slicing.
+    ///
+    /// * `range`: range of indexes to combine, such as `start..end` or `..`.
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let (start, end) = range_to_indexes(range, self.len());
+        match covering_runs(start, end) {
+            None => Op::identity(),
+            Some((level, second)) => Op::combine(&self.levels[level][start], &self.levels[level][second])
+        }
+    }
+}
+
+
+// Method implementations for RangeMinimumQuery struct
+impl<T> RangeMinimumQuery<T> where T: Ord {
+
+    /// Return a new RangeMinimumQuery struct over a copy of the input values, built in
+    /// O(n log n).
+    ///
+    /// * `values`: the array to answer queries about.
+    pub fn new(values: &[T]) -> RangeMinimumQuery<T> where T: Clone {
+        RangeMinimumQuery::from_vec(values.to_vec())
+    }
+
+    /// Return a new RangeMinimumQuery struct taking ownership of the input values, built in
+    /// O(n log n).
+    ///
+    /// * `values`: the array to answer queries about.
+    pub fn from_vec(values: Vec<T>) -> RangeMinimumQuery<T> {
+        let levels = build_levels((0..values.len()).collect(), |left: &usize, right: &usize| {
+            if values[*right] < values[*left] { *right } else { *left }
+        });
+        RangeMinimumQuery { values, levels }
+    }
+
+    /// Return the number of values in the array.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return whether the array holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Return the index of the smallest value in the input range of the array - the leftmost if
+    /// there are several, and None for an empty range. O(1). Panics if the range is out of bounds,
+    /// like slicing.
+    ///
+    /// * `range`: range of indexes to search, such as `start..end` or `..`.
+    pub fn min_index<R: RangeBounds<usize>>(&self, range: R) -> Option<usize> {
+        let (start, end) = range_to_indexes(range, self.len());
+        let (level, second) = covering_runs(start, end)?;
+        let (left, right) = (self.levels[level][start], self.levels[level][second]);
+        Some(if self.values[right] < self.values[left] { right } else { left })
+    }
+
+    /// Return a reference to the smallest value in the input range of the array (None for an
+    /// empty range). O(1). Panics if the range is out of bounds, like slicing.
+    ///
+    /// * `range`: range of indexes to search, such as `start..end` or `..`.
+    pub fn min<R: RangeBounds<usize>>(&self, range: R) -> Option<&T> {
+        self.min_index(range).map(|index| &self.values[index])
+    }
+}
+
+/// Return the levels of a sparse table with the input entries as level 0: each entry of level
+/// k + 1 combines the entry at the same index of level k with the one 2^k after it.
+///
+/// * `base`: entries of level 0, one per value of the array.
+/// * `combine`: combines the entries for two runs into the entry for the run covering both.
+fn build_levels<E, F: Fn(&E, &E) -> E>(base: Vec<E>, combine: F) -> Vec<Vec<E>> {
+    let length = base.len();
+    let mut levels = vec![base];
+    let mut run = 1;
+    while 2 * run <= length {
+        let below = levels.last().expect("level 0 is always there");
+        let level = (0..=length - 2 * run).map(|start| combine(&below[start], &below[start + run])).collect();
+        levels.push(level);
+        run *= 2;
+    }
+    levels
+}
+
+/// Return the level of the two runs covering a range, and where the second one starts (None for
+/// an empty range). The first starts at the range's start.
+///
+/// * `start`: first index of the range.
+/// * `end`: index just past the range.
+fn covering_runs(start: usize, end: usize) -> Option<(usize, usize)> {
+    if start == end {
+        return None;
+    }
+    let level = (end - start).ilog2() as usize;
+    Some((level, end - (1 << level)))
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_values() -> Vec<i64> {
+    vec![7, 2, 9, 4, 2, 8, 1, 6, 5, 3]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suffix_array::{ lcp_array, suffix_array };
+
+    #[test]
+    fn sparse_table_works() {
+        let values = setup_values();
+        let minimums: SparseTable<i64, Min> = SparseTable::build(&values);
+        let maximums: SparseTable<i64, Max> = SparseTable::build(&values);
+        assert_eq!(minimums.len(), 10);
+        assert_eq!(minimums.levels.len(), 4);
+        assert_eq!(minimums.query(..), 1);
+        assert_eq!(minimums.query(2..6), 2);
+        assert_eq!(minimums.query(7..=7), 6);
+        assert_eq!(minimums.query(3..3), i64::MAX);
+        assert_eq!(maximums.query(3..), 8);
+
+        let gcds: SparseTable<u64, Gcd> = SparseTable::build(&[36, 24, 60, 42, 7, 49]);
+        assert_eq!(gcds.query(..4), 6);
+        assert_eq!(gcds.query(4..), 7);
+        assert_eq!(gcds.query(..), 1);
+
+        let empty: SparseTable<i64, Max> = SparseTable::build(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.query(..), i64::MIN);
+    }
+
+    #[test]
+    fn sparse_table_matches_naive() {
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for length in [1, 2, 3, 7, 8, 9, 31, 64, 100] {
+            let values: Vec<i64> = (0..length).map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                (seed % 50) as i64
+            }).collect();
+            let maximums: SparseTable<i64, Max> = SparseTable::build(&values);
+            let rmq = RangeMinimumQuery::new(&values);
+            for start in 0..=length {
+                for end in start..=length {
+                    let range = &values[start..end];
+                    assert_eq!(maximums.query(start..end), range.iter().copied().max().unwrap_or(i64::MIN));
+                    let expected = range.iter().copied().min().map(|min| start + range.iter().position(|value| *value == min).unwrap());
+                    assert_eq!(rmq.min_index(start..end), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn range_minimum_query_works() {
+        let rmq = RangeMinimumQuery::from_vec(setup_values());
+        assert_eq!(rmq.len(), 10);
+        // the leftmost of the equal minimums
+        assert_eq!(rmq.min_index(..6), Some(1));
+        assert_eq!(rmq.min_index(2..6), Some(4));
+        assert_eq!(rmq.min(..), Some(&1));
+        assert_eq!(rmq.min(5..5), None);
+
+        let words = RangeMinimumQuery::new(&["pear", "apple", "fig"]);
+        assert_eq!(words.min(..), Some(&"apple"));
+        assert!(RangeMinimumQuery::<u8>::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn range_minimum_query_longest_common_prefix() {
+        // the longest common prefix of any two suffixes is the minimum of the LCP array between
+        // their positions in the suffix array
+        let text = "mississippi";
+        let suffixes = suffix_array(text);
+        let rmq = RangeMinimumQuery::from_vec(lcp_array(text, &suffixes));
+        for first in 0..suffixes.len() {
+            for second in first + 1..suffixes.len() {
+                let (a, b) = (&text[suffixes[first]..], &text[suffixes[second]..]);
+                let expected = a.bytes().zip(b.bytes()).take_while(|(x, y)| x == y).count();
+                assert_eq!(rmq.min(first + 1..=second), Some(&expected));
+            }
+        }
+    }
+}
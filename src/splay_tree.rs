@@ -0,0 +1,374 @@
+//! Splay tree implementation built on the binary search tree Node struct from bst.rs.
+//!     Every access "splays" the accessed Node up to the root with rotations, so recently and
+//!     frequently accessed values stay near the top (amortized O(log n) per operation).
+
+use std::cmp::Ordering;
+use crate::bst::{ Node, TreeTraversalOrders };
+
+
+/// A splay tree struct containing pointers to Node structs (the same Node struct used by
+/// BinarySearchTree).
+///
+/// Unlike BinarySearchTree, searching this tree mutates it: find_value splays the Node it stops at
+/// to the root, so it requires a mutable reference.
+///
+/// * `root`: An Option-wrapped reference to the root Node of the splay tree. This will be None if
+///         there are zero nodes in this tree.
+/// * `size`: The number of Nodes in this tree.
+pub struct SplayTree<T: Ord> {
+    root: Option<Box<Node<T>>>,
+    size: usize
+}
+
+
+// Method implementation for SplayTree struct
+impl<T> SplayTree<T> where T: Ord {
+
+    /// Return a new, empty SplayTree struct
+    fn new() -> SplayTree<T> {
+        SplayTree {
+            root: None,
+            size: 0
+        }
+    }
+
+    /// Return the number of values held in this SplayTree struct.
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Return whether this SplayTree struct holds no values.
+    fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Return the height of this SplayTree struct (see BinarySearchTree::height).
+    fn height(&self) -> usize {
+        Node::height(&self.root)
+    }
+
+    /// Return a reference to the value at the root of this tree - the most recently accessed value.
+    fn peek_root_value(&self) -> Option<&T> {
+        self.root.as_ref().map(|boxed_node| &boxed_node.value)
+    }
+
+    /// Find whether a value is present in this SplayTree struct.
+    ///
+    /// The last Node on the search path (the Node holding the value, if it is present) is splayed
+    ///     to the root.
+    ///
+    /// * `value`: value to be searched for.
+    fn find_value(&mut self, value: &T) -> bool {
+        self.root = SplayTree::splay(self.root.take(), value);
+        self.peek_root_value() == Some(value)
+    }
+
+    /// Add a Node holding the input value to this SplayTree struct, as the new root.
+    ///
+    /// Splay the tree around the value first, so the root holds the value's would-be predecessor
+    ///     or successor. Then split the tree at the root: the new Node takes the old root and its
+    ///     branch on one side, and the old root's other branch on the other side.
+    ///
+    /// If an equal value is already in the tree, it is splayed to the root and the input value is
+    ///     discarded.
+    ///
+    /// * `value`: The value to be added into the splay tree.
+    fn add_value(&mut self, value: T) {
+        let Some(mut old_root) = SplayTree::splay(self.root.take(), &value) else {
+            self.root = Some(Box::new(Node::new(value)));
+            self.size += 1;
+            return;
+        };
+
+        let mut new_root = Node::new(value);
+        match new_root.value.cmp(&old_root.value) {
+            Ordering::Equal => {
+                self.root = Some(old_root);
+                return;
+            },
+            Ordering::Less => {
+                new_root.left_branch = old_root.left_branch.take();
+                old_root.update_subtree_size();
+                new_root.right_branch = Some(old_root);
+            },
+            Ordering::Greater => {
+                new_root.right_branch = old_root.right_branch.take();
+                old_root.update_subtree_size();
+                new_root.left_branch = Some(old_root);
+            }
+        }
+        new_root.update_subtree_size();
+        self.root = Some(Box::new(new_root));
+        self.size += 1;
+    }
+
+    /// Find input value in the SplayTree (using Ordering::Equal (== operator)) and remove it (and
+    ///     its enclosing Node).
+    ///
+    /// Splay the value to the root and detach the root. Then splay the left branch around the same
+    ///     value: everything in it is smaller, so its largest value becomes its root, which has no
+    ///     right child and can adopt the old root's right branch.
+    ///
+    /// Return the value that was stored in the tree, or None if no equal value was found.
+    ///
+    /// * `value`: Value to be removed from the splay tree.
+    fn remove_value(&mut self, value: T) -> Option<T> {
+        let mut old_root = SplayTree::splay(self.root.take(), &value)?;
+        if old_root.value != value {
+            self.root = Some(old_root);
+            return None;
+        }
+
+        self.root = match SplayTree::splay(old_root.left_branch.take(), &value) {
+            Some(mut new_root) => {
+                new_root.right_branch = old_root.right_branch.take();
+                new_root.update_subtree_size();
+                Some(new_root)
+            },
+            None => old_root.right_branch.take()
+        };
+        self.size -= 1;
+        Some(old_root.value)
+    }
+
+    /// Create and return a vector containing references to the values held by Nodes in this
+    /// SplayTree struct, without splaying.
+    ///
+    /// * `order`: A variant of TreeTraversalOrders enum that determines the orders of the value
+    /// references in the returned vector
+    fn collectpeek_traversal_values(&self, order: TreeTraversalOrders) -> Vec<&T> {
+        let mut list = Vec::new();
+        match order {
+            TreeTraversalOrders::Inorder => { Node::collectpeek_inorder(&self.root, &mut list); },
+            TreeTraversalOrders::Preorder => { Node::collectpeek_preorder(&self.root, &mut list); },
+            TreeTraversalOrders::Postorder => { Node::collectpeek_postorder(&self.root, &mut list); },
+            TreeTraversalOrders::Levelorder => { Node::collectpeek_levelorder(&self.root, &mut list); }
+        };
+        list
+    }
+
+    /// Splay the subtree rooted in an Option-wrapped Node around the input value, returning its
+    ///     new root: the Node holding the value if there is one, otherwise the last Node on the
+    ///     value's search path.
+    ///
+    /// Top-down, as in Sleator and Tarjan's paper, so it loops instead of recursing and any depth
+    ///     of Node can be splayed. Walking down the search path, each Node passed is linked into
+    ///     one of two assembly trees: the left tree collects Nodes smaller than the value (each
+    ///     one linked as the right child of the one before), the right tree collects larger Nodes
+    ///     (each linked as the left child of the one before). When the path goes the same way
+    ///     twice in a row ("zig-zig"), the two Nodes are rotated first, which is what halves the
+    ///     depth of the path. At the end, the Node reached takes the two assembly trees as its
+    ///     branches, and its old branches are hung at their inner ends.
+    ///
+    /// The assembly trees are held as lists of Nodes, and joined from the inner end outwards once
+    ///     the walk is over, so every Node's subtree_size can be recomputed from its finished
+    ///     branches.
+    ///
+    /// * `opt_node`: Option-wrapped Node at the top of the subtree to be splayed.
+    /// * `value`: value to splay the subtree around.
+    fn splay(opt_node: Option<Box<Node<T>>>, value: &T) -> Option<Box<Node<T>>> {
+        let mut node = opt_node?;
+        let mut left_tree: Vec<Box<Node<T>>> = Vec::new();
+        let mut right_tree: Vec<Box<Node<T>>> = Vec::new();
+        loop {
+            match value.cmp(&node.value) {
+                Ordering::Equal => break,
+                Ordering::Less => {
+                    let Some(left_child) = node.left_branch.as_ref() else {
+                        break;
+                    };
+                    if *value < left_child.value {      // zig-zig: rotate right first
+                        node = node.rotate_right();
+                        if node.left_branch.is_none() {
+                            break;
+                        }
+                    }
+                    // link the Node into the right tree and continue down its left branch
+                    let next = node.left_branch.take().expect("checked above");
+                    right_tree.push(node);
+                    node = next;
+                },
+                Ordering::Greater => {
+                    let Some(right_child) = node.right_branch.as_ref() else {
+                        break;
+                    };
+                    if *value > right_child.value {     // zig-zig: rotate left first
+                        node = node.rotate_left();
+                        if node.right_branch.is_none() {
+                            break;
+                        }
+                    }
+                    // link the Node into the left tree and continue down its right branch
+                    let next = node.right_branch.take().expect("checked above");
+                    left_tree.push(node);
+                    node = next;
+                }
+            }
+        }
+
+        // assemble: the last Node linked into each tree is its innermost, and adopts the branch
+        // of the new root on that side
+        let mut left_branch = node.left_branch.take();
+        while let Some(mut left_node) = left_tree.pop() {
+            left_node.right_branch = left_branch;
+            left_node.update_subtree_size();
+            left_branch = Some(left_node);
+        }
+        let mut right_branch = node.right_branch.take();
+        while let Some(mut right_node) = right_tree.pop() {
+            right_node.left_branch = right_branch;
+            right_node.update_subtree_size();
+            right_branch = Some(right_node);
+        }
+        node.left_branch = left_branch;
+        node.right_branch = right_branch;
+        node.update_subtree_size();
+        Some(node)
+    }
+}
+
+impl<T> Default for SplayTree<T> where T: Ord {
+    fn default() -> Self {
+        SplayTree::new()
+    }
+}
+
+// Splaying can leave a degenerate chain (sorted insertion always does), which the default
+// recursive drop of Box<Node> can overflow the stack on - so drop the Nodes the same way as
+// BinarySearchTree does.
+impl<T> Drop for SplayTree<T> where T: Ord {
+    fn drop(&mut self) {
+        Node::drop_iteratively(self.root.take());
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_splay_tree() -> SplayTree<u32> {
+    let mut splay_tree: SplayTree<u32> = SplayTree::new();
+    for value in [4, 2, 6, 1, 3, 5] {
+        splay_tree.add_value(value);
+    }
+    splay_tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splay_tree_can_be_created_and_added_to() {
+        let splay_tree = setup_splay_tree();
+        assert_eq!(splay_tree.len(), 6);
+        assert_eq!(splay_tree.peek_root_value(), Some(&5));
+        assert_eq!(
+            splay_tree.collectpeek_traversal_values(TreeTraversalOrders::Inorder),
+            vec![&1, &2, &3, &4, &5, &6]
+        );
+    }
+
+    #[test]
+    fn splay_tree_search_splays_to_root() {
+        let mut splay_tree = setup_splay_tree();
+        assert!(splay_tree.find_value(&1));
+        assert_eq!(splay_tree.peek_root_value(), Some(&1));
+        assert!(splay_tree.find_value(&4));
+        assert_eq!(splay_tree.peek_root_value(), Some(&4));
+
+        assert!(!splay_tree.find_value(&88));
+        assert_eq!(splay_tree.peek_root_value(), Some(&6));
+        assert_eq!(
+            splay_tree.collectpeek_traversal_values(TreeTraversalOrders::Inorder),
+            vec![&1, &2, &3, &4, &5, &6]
+        );
+    }
+
+    #[test]
+    fn splay_tree_ignores_duplicates() {
+        let mut splay_tree = setup_splay_tree();
+        splay_tree.add_value(3);
+        assert_eq!(splay_tree.len(), 6);
+        assert_eq!(splay_tree.peek_root_value(), Some(&3));
+    }
+
+    #[test]
+    fn splay_tree_can_delete_nodes() {
+        let mut splay_tree = setup_splay_tree();
+        assert_eq!(splay_tree.remove_value(4), Some(4));
+        assert_eq!(splay_tree.remove_value(4), None);
+        assert_eq!(splay_tree.remove_value(1), Some(1));
+        assert_eq!(splay_tree.len(), 4);
+        assert_eq!(
+            splay_tree.collectpeek_traversal_values(TreeTraversalOrders::Inorder),
+            vec![&2, &3, &5, &6]
+        );
+        assert_eq!(splay_tree.root.as_ref().unwrap().subtree_size, 4);
+
+        for value in [2, 3, 5, 6] {
+            assert_eq!(splay_tree.remove_value(value), Some(value));
+        }
+        assert!(splay_tree.is_empty());
+        assert!(splay_tree.root.is_none());
+    }
+
+    #[test]
+    fn splay_tree_halves_depth_of_degenerate_chain() {
+        let mut splay_tree: SplayTree<u32> = SplayTree::new();
+        for value in 0..64 {
+            splay_tree.add_value(value);
+        }
+        // sorted insertion leaves a chain of left children
+        assert_eq!(splay_tree.height(), 64);
+
+        // splaying the deepest Node roughly halves the depth of every Node on its path
+        assert!(splay_tree.find_value(&0));
+        assert!(splay_tree.height() <= 34);
+        assert_eq!(splay_tree.root.as_ref().unwrap().subtree_size, 64);
+    }
+
+    #[test]
+    fn splay_tree_deep_chains_can_be_dropped() {
+        let mut splay_tree: SplayTree<u32> = SplayTree::new();
+        for value in 0..1_000_000 {
+            splay_tree.add_value(value);
+        }
+        assert_eq!(splay_tree.len(), 1_000_000);
+        drop(splay_tree);
+    }
+
+    #[test]
+    fn splay_tree_splays_the_deepest_node_of_a_deep_chain() {
+        let n = 1_000_000;
+        let mut splay_tree: SplayTree<u32> = SplayTree::new();
+        for value in 0..n {
+            splay_tree.add_value(value);
+        }
+        // 0 is at the bottom of a chain of n left children
+        assert!(splay_tree.find_value(&0));
+        assert_eq!(splay_tree.peek_root_value(), Some(&0));
+        assert_eq!(splay_tree.root.as_ref().unwrap().subtree_size, n as usize);
+
+        // later splays still start deep in the tree the first one left
+        assert!(splay_tree.find_value(&1));
+        assert_eq!(splay_tree.remove_value(2), Some(2));
+        splay_tree.add_value(2);
+        assert!(splay_tree.find_value(&(n - 1)));
+        assert_eq!(splay_tree.len(), n as usize);
+        assert_eq!(splay_tree.root.as_ref().unwrap().subtree_size, n as usize);
+    }
+
+    #[test]
+    fn splay_tree_can_be_traversed_preorder_and_postorder() {
+        let mut splay_tree: SplayTree<u32> = SplayTree::new();
+        for value in [1, 3, 2] {
+            splay_tree.add_value(value);
+        }
+        //     2
+        //    / \
+        //   1   3
+        assert_eq!(splay_tree.collectpeek_traversal_values(TreeTraversalOrders::Preorder), vec![&2, &1, &3]);
+        assert_eq!(splay_tree.collectpeek_traversal_values(TreeTraversalOrders::Postorder), vec![&1, &3, &2]);
+    }
+}
@@ -0,0 +1,240 @@
+//! Last-in, first-out stack backed by a Vec: the top of the stack is the end of the Vec, so push,
+//!     pop and peek are all O(1) (push amortized, as the Vec may reallocate).
+//!
+//! ```
+//! use rust_datastructures_algorithms::stack::Stack;
+//!
+//! // check brackets are balanced
+//! let mut open = Stack::new();
+//! for c in "{[()()]}".chars() {
+//!     match c {
+//!         '(' | '[' | '{' => open.push(c),
+//!         _ => {
+//!             let expected = match c { ')' => '(', ']' => '[', _ => '{' };
+//!             assert_eq!(open.pop(), Some(expected));
+//!         }
+//!     }
+//! }
+//! assert!(open.is_empty());
+//! ```
+
+use std::iter::Rev;
+use crate::traits;
+
+
+/// A stack struct holding its values in a Vec, bottom to top.
+///
+/// Values left in the stack when it is dropped are dropped from the top down - the order they
+/// would have been popped in.
+///
+/// ```
+/// use rust_datastructures_algorithms::stack::Stack;
+///
+/// let mut stack = Stack::new();
+/// stack.push(1);
+/// stack.push(2);
+/// assert_eq!(stack.peek(), Some(&2));
+/// assert_eq!(stack.pop(), Some(2));
+/// assert_eq!(stack.len(), 1);
+/// ```
+///
+/// * `values`: Vec holding the values, with the top of the stack at the end.
+pub struct Stack<T> {
+    values: Vec<T>
+}
+
+
+// Method implementations for Stack struct
+impl<T> Stack<T> {
+
+    /// Return a new, empty Stack struct
+    pub fn new() -> Stack<T> {
+        Stack {
+            values: Vec::new()
+        }
+    }
+
+    /// Return a new, empty Stack struct with room for the input number of values before it needs
+    /// to reallocate.
+    ///
+    /// * `capacity`: number of values to allocate room for.
+    pub fn with_capacity(capacity: usize) -> Stack<T> {
+        Stack {
+            values: Vec::with_capacity(capacity)
+        }
+    }
+
+    /// Return the number of values in this Stack.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return whether this Stack holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Add a value to the top of the stack.
+    ///
+    /// * `value`: value to be pushed.
+    pub fn push(&mut self, value: T) {
+        self.values.push(value);
+    }
+
+    /// Remove the value at the top of the stack and return it (unless the stack is empty).
+    ///
+    /// ```
+    /// use rust_datastructures_algorithms::stack::Stack;
+    ///
+    /// let mut stack: Stack<u32> = (1..=3).collect();
+    /// assert_eq!(stack.pop(), Some(3));
+    /// assert_eq!(stack.pop(), Some(2));
+    /// assert_eq!(stack.pop(), Some(1));
+    /// assert_eq!(stack.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.values.pop()
+    }
+
+    /// Get a reference to the value at the top of the stack (if the stack is not empty).
+    pub fn peek(&self) -> Option<&T> {
+        self.values.last()
+    }
+
+    /// Get a mutable reference to the value at the top of the stack (if the stack is not empty).
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.values.last_mut()
+    }
+
+    /// Return an iterator of references to the values in this Stack, from the top down - the
+    /// order they would be popped in.
+    ///
+    /// ```
+    /// use rust_datastructures_algorithms::stack::Stack;
+    ///
+    /// let stack: Stack<u32> = (1..=3).collect();
+    /// assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    /// ```
+    pub fn iter(&self) -> Rev<std::slice::Iter<'_, T>> {
+        self.values.iter().rev()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+// Pop every value, so values are dropped from the top down (a Vec drops from the front).
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        while self.values.pop().is_some() {}
+    }
+}
+
+// Push each value in turn, so the last value collected is the top of the stack.
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Stack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.values.extend(iter);
+    }
+}
+
+// Consume the stack, yielding its values in the order they would be popped in.
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = Rev<std::vec::IntoIter<T>>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        std::mem::take(&mut self.values).into_iter().rev()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Rev<std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Stack trait implementation, so a Stack struct can be used in code generic over stacks.
+impl<T> traits::Stack<T> for Stack<T> {
+    fn push(&mut self, value: T) {
+        Stack::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Stack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        Stack::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        Stack::len(self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_stack() -> Stack<u32> {
+    let mut stack: Stack<u32> = Stack::new();
+    stack.push(2);
+    stack.push(4);
+    stack
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn stack_works() {
+        let mut stack = setup_stack();
+        assert_eq!(stack.peek(), Some(&4));
+        if let Some(top) = stack.peek_mut() {
+            *top = 40;
+        }
+        stack.push(6);
+        assert_eq!(stack.len(), 3);
+        assert_eq!((&stack).into_iter().collect::<Vec<_>>(), vec![&6, &40, &2]);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![6, 40, 2]);
+
+        let mut generic: Box<dyn traits::Stack<u32>> = Box::new(setup_stack());
+        generic.push(8);
+        assert_eq!(generic.pop(), Some(8));
+        assert_eq!(generic.len(), 2);
+    }
+
+    #[test]
+    fn stack_drops_values_from_the_top() {
+        struct Logged<'a>(u32, &'a RefCell<Vec<u32>>);
+        impl Drop for Logged<'_> {
+            fn drop(&mut self) {
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+
+        let log = RefCell::new(Vec::new());
+        let mut stack = Stack::new();
+        for value in 1..=3 {
+            stack.push(Logged(value, &log));
+        }
+        drop(stack);
+        assert_eq!(*log.borrow(), vec![3, 2, 1]);
+    }
+}
@@ -0,0 +1,278 @@
+//! Suffix array construction and queries. The suffix array of a text lists the starting
+//!     positions of all its suffixes in lexicographic order, so every occurrence of a pattern
+//!     sits in one contiguous run of it and can be found by binary search.
+//!
+//! suffix_array builds the array by prefix doubling in O(n log n): suffixes are sorted by their
+//!     first 1, 2, 4, ... bytes, each round ranking suffixes by pairs of ranks from the round
+//!     before with a linear-time radix sort. lcp_array then adds the longest common prefix of
+//!     each pair of neighbouring suffixes in O(n) with Kasai's algorithm.
+//!
+//! Positions and lengths are in bytes, so UTF-8 text is indexed byte by byte.
+//!
+//! ```
+//! use rust_datastructures_algorithms::suffix_array::{ contains_substring, lcp_array, suffix_array };
+//!
+//! let text = "banana";
+//! let suffixes = suffix_array(text);
+//! // a, ana, anana, banana, na, nana
+//! assert_eq!(suffixes, vec![5, 3, 1, 0, 4, 2]);
+//! assert_eq!(lcp_array(text, &suffixes), vec![0, 1, 3, 0, 0, 2]);
+//! assert!(contains_substring(text, &suffixes, "nan"));
+//! assert!(!contains_substring(text, &suffixes, "nab"));
+//! ```
+
+use std::cmp::Ordering;
+
+
+/// Return the suffix array of the input text: the starting byte positions of its suffixes,
+/// sorted so the suffixes they start are in lexicographic order.
+///
+/// Prefix doubling: after the round for length k, `rank[i]` orders the suffix at i by its first k
+///     bytes (equal prefixes share a rank). The suffix at i's first 2k bytes are then ordered by
+///     the pair `(rank[i], rank[i + k])`, with suffixes too short to have a second half sorting
+///     first. Sorting the pairs is a two-pass radix sort - by second rank, then stably by first -
+///     which is O(n) per round. Once every rank is distinct the order is final, which takes at
+///     most log2(n) rounds.
+///
+/// * `text`: text to be indexed.
+pub fn suffix_array(text: &str) -> Vec<usize> {
+    let bytes = text.as_bytes();
+    let n = bytes.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut rank: Vec<usize> = bytes.iter().map(|byte| usize::from(*byte)).collect();
+    let mut suffixes = vec![0; n];
+    counting_sort_into(&mut suffixes, &(0..n).collect::<Vec<_>>(), &rank, 256);
+    let mut new_rank = vec![0; n];
+    let mut by_second_rank = Vec::with_capacity(n);
+    let mut k = 1;
+    loop {
+        // suffixes with no second half come first, then the rest in order of their second half -
+        // which is the current order of the suffixes k bytes later
+        by_second_rank.clear();
+        by_second_rank.extend(n.saturating_sub(k)..n);
+        by_second_rank.extend(suffixes.iter().filter(|start| **start >= k).map(|start| start - k));
+        // the radix sort's second pass: stably by first half
+        let max_rank = rank[suffixes[n - 1]];
+        counting_sort_into(&mut suffixes, &by_second_rank, &rank, max_rank + 1);
+
+        let pair = |start: usize| (rank[start], rank.get(start + k).map(|second| second + 1).unwrap_or(0));
+        new_rank[suffixes[0]] = 0;
+        for index in 1..n {
+            let step = usize::from(pair(suffixes[index - 1]) != pair(suffixes[index]));
+            new_rank[suffixes[index]] = new_rank[suffixes[index - 1]] + step;
+        }
+        std::mem::swap(&mut rank, &mut new_rank);
+        if rank[suffixes[n - 1]] == n - 1 {
+            return suffixes;
+        }
+        k *= 2;
+    }
+}
+
+/// Return the LCP array of the input text and its suffix array: entry i is the length of the
+/// longest common prefix of the suffixes at `suffix_array[i - 1]` and `suffix_array[i]`, and entry
+/// 0 is 0.
+///
+/// Kasai's algorithm: going through the suffixes in text order, the suffix at i + 1 shares at
+///     least h - 1 bytes with its predecessor in the suffix array if the suffix at i shared h
+///     bytes with its own, so each comparison can resume one byte before where the last one
+///     stopped. The matched length drops by at most one per suffix, so the total work is O(n).
+///
+/// * `text`: the indexed text.
+/// * `suffix_array`: the text's suffix array, as returned by suffix_array.
+pub fn lcp_array(text: &str, suffix_array: &[usize]) -> Vec<usize> {
+    let bytes = text.as_bytes();
+    let n = suffix_array.len();
+    let mut position_of = vec![0; n];
+    for (index, start) in suffix_array.iter().enumerate() {
+        position_of[*start] = index;
+    }
+
+    let mut lcp = vec![0; n];
+    let mut matched = 0;
+    for start in 0..n {
+        let position = position_of[start];
+        if position == 0 {
+            matched = 0;
+            continue;
+        }
+        let previous = suffix_array[position - 1];
+        while start + matched < n && previous + matched < n && bytes[start + matched] == bytes[previous + matched] {
+            matched += 1;
+        }
+        lcp[position] = matched;
+        matched = matched.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Return the run of the input suffix array whose suffixes start with the pattern - the starting
+/// byte positions of every occurrence of the pattern, in suffix order. Found with two binary
+/// searches, in O(m log n) for a pattern of length m.
+///
+/// * `text`: the indexed text.
+/// * `suffix_array`: the text's suffix array, as returned by suffix_array.
+/// * `pattern`: substring to look for.
+pub fn occurrences<'a>(text: &str, suffix_array: &'a [usize], pattern: &str) -> &'a [usize] {
+    let bytes = text.as_bytes();
+    let pattern = pattern.as_bytes();
+    // compare only the first m bytes of each suffix, so every suffix starting with the pattern
+    // compares Equal and they form one run
+    let compare = |start: &usize| {
+        let suffix = &bytes[*start..];
+        suffix[..suffix.len().min(pattern.len())].cmp(pattern)
+    };
+    let first = suffix_array.partition_point(|start| compare(start) == Ordering::Less);
+    let end = first + suffix_array[first..].partition_point(|start| compare(start) == Ordering::Equal);
+    &suffix_array[first..end]
+}
+
+/// Return whether the pattern occurs in the indexed text, by binary searching its suffix array.
+/// The empty pattern occurs in every text.
+///
+/// * `text`: the indexed text.
+/// * `suffix_array`: the text's suffix array, as returned by suffix_array.
+/// * `pattern`: substring to look for.
+pub fn contains_substring(text: &str, suffix_array: &[usize], pattern: &str) -> bool {
+    pattern.is_empty() || !occurrences(text, suffix_array, pattern).is_empty()
+}
+
+/// Write the positions in source into target, stably sorted by rank (a counting sort).
+///
+/// * `target`: receives the sorted positions - the same length as source.
+/// * `source`: starting positions to be sorted.
+/// * `rank`: rank of the suffix at each position.
+/// * `rank_count`: one more than the largest rank.
+fn counting_sort_into(target: &mut [usize], source: &[usize], rank: &[usize], rank_count: usize) {
+    let mut next_slot = vec![0; rank_count + 1];
+    for start in source {
+        next_slot[rank[*start] + 1] += 1;
+    }
+    for index in 1..next_slot.len() {
+        next_slot[index] += next_slot[index - 1];
+    }
+    for start in source {
+        let slot = &mut next_slot[rank[*start]];
+        target[*slot] = *start;
+        *slot += 1;
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_text() -> &'static str {
+    "mississippi"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the suffix array by sorting the suffixes directly - O(n^2 log n), for checking.
+    fn naive_suffix_array(text: &str) -> Vec<usize> {
+        let mut suffixes: Vec<usize> = (0..text.len()).collect();
+        suffixes.sort_by_key(|start| &text.as_bytes()[*start..]);
+        suffixes
+    }
+
+    /// Return pseudo-random texts over small alphabets, which have lots of repeats.
+    fn random_texts() -> Vec<String> {
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut texts = Vec::new();
+        for length in 0..60 {
+            for alphabet in [b"ab".as_slice(), b"acgt", b"xyz123"] {
+                let text: String = (0..length).map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    char::from(alphabet[(seed % alphabet.len() as u64) as usize])
+                }).collect();
+                texts.push(text);
+            }
+        }
+        texts
+    }
+
+    #[test]
+    fn suffix_array_works() {
+        let text = setup_text();
+        let suffixes = suffix_array(text);
+        assert_eq!(suffixes, vec![10, 7, 4, 1, 0, 9, 8, 6, 3, 5, 2]);
+        assert_eq!(lcp_array(text, &suffixes), vec![0, 1, 1, 4, 0, 0, 1, 0, 2, 1, 3]);
+
+        assert_eq!(suffix_array(""), Vec::<usize>::new());
+        assert_eq!(suffix_array("a"), vec![0]);
+        assert_eq!(suffix_array("aaaa"), vec![3, 2, 1, 0]);
+        assert_eq!(lcp_array("aaaa", &[3, 2, 1, 0]), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn suffix_array_matches_naive_sort() {
+        for text in random_texts() {
+            let suffixes = suffix_array(&text);
+            assert_eq!(suffixes, naive_suffix_array(&text), "text {:?}", text);
+
+            let lcp = lcp_array(&text, &suffixes);
+            for index in 1..suffixes.len() {
+                let (a, b) = (&text[suffixes[index - 1]..], &text[suffixes[index]..]);
+                let expected = a.bytes().zip(b.bytes()).take_while(|(x, y)| x == y).count();
+                assert_eq!(lcp[index], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn suffix_array_substring_queries() {
+        let text = setup_text();
+        let suffixes = suffix_array(text);
+        for pattern in ["ssi", "issi", "mississippi", "p", "i", ""] {
+            assert!(contains_substring(text, &suffixes, pattern), "pattern {:?}", pattern);
+        }
+        for pattern in ["spi", "ippis", "mississippis", "x", "pim"] {
+            assert!(!contains_substring(text, &suffixes, pattern), "pattern {:?}", pattern);
+        }
+
+        let mut found = occurrences(text, &suffixes, "ssi").to_vec();
+        found.sort();
+        assert_eq!(found, vec![2, 5]);
+        assert_eq!(occurrences(text, &suffixes, "i").len(), 4);
+        assert!(!contains_substring("", &[], "a"));
+
+        // every substring of a random text is found, at every position it occurs
+        for text in random_texts().into_iter().filter(|text| text.len() > 20) {
+            let suffixes = suffix_array(&text);
+            for (start, end) in [(0, 3), (5, 9), (10, 20)] {
+                let pattern = &text[start..end];
+                let mut found = occurrences(&text, &suffixes, pattern).to_vec();
+                found.sort();
+                let expected: Vec<usize> = (0..=text.len() - pattern.len())
+                    .filter(|position| text[*position..].starts_with(pattern))
+                    .collect();
+                assert_eq!(found, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn suffix_array_counts_distinct_substrings() {
+        // each suffix adds its length minus the prefix it shares with the suffix before it
+        for text in random_texts().into_iter().take(60) {
+            let suffixes = suffix_array(&text);
+            let shared: usize = lcp_array(&text, &suffixes).iter().sum();
+            let distinct = text.len() * (text.len() + 1) / 2 - shared;
+
+            let mut naive = std::collections::HashSet::new();
+            for start in 0..text.len() {
+                for end in start + 1..=text.len() {
+                    naive.insert(&text[start..end]);
+                }
+            }
+            assert_eq!(distinct, naive.len());
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Suffix automaton: the smallest deterministic automaton accepting exactly the suffixes of a
+//!     text. Every substring of the text is the label of exactly one path from the initial
+//!     state, so substring checks take time proportional to the pattern alone.
+//!
+//! The automaton is built online, one character at a time, in O(n log k) for an alphabet of k
+//!     characters, and has at most 2n - 1 states (for n of at least 2). Each state stands for a
+//!     class of substrings that end at the same set of positions in the text, which is what
+//!     makes counting distinct substrings and finding common substrings cheap.
+//!
+//! The automaton works on chars rather than bytes (unlike the suffix_array module), so the
+//!     substrings it counts and returns are always valid strings.
+//!
+//! ```
+//! use rust_datastructures_algorithms::suffix_automaton::SuffixAutomaton;
+//!
+//! let automaton = SuffixAutomaton::new("abcbc");
+//! assert!(automaton.contains("cbc"));
+//! assert!(!automaton.contains("cc"));
+//! // a, b, c, ab, bc, cb, abc, bcb, cbc, abcb, bcbc, abcbc
+//! assert_eq!(automaton.count_distinct_substrings(), 12);
+//! assert_eq!(automaton.longest_common_substring("xxbcbcx"), "bcbc");
+//! ```
+
+use std::collections::BTreeMap;
+
+
+/// Index of the initial state, which stands for the empty string.
+const INITIAL: usize = 0;
+
+
+/// A suffix automaton struct, holding its states in a Vec and referring to them by index.
+///
+/// * `states`: Every state of the automaton - the initial state is at index 0.
+/// * `last`: Index of the state for the whole text (the longest suffix), which the next
+///         character extends.
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    last: usize
+}
+
+
+/// State in a SuffixAutomaton struct. The substrings a state stands for are suffixes of one
+/// another, with lengths from one more than its suffix link's length up to its own length.
+///
+/// * `length`: Length of the longest substring this state stands for.
+/// * `link`: Suffix link - index of the state for the longest suffix of this state's substrings
+///         that ends at more positions in the text (None only for the initial state).
+/// * `transitions`: Map from each character to the state reached by appending it.
+struct State {
+    length: usize,
+    link: Option<usize>,
+    transitions: BTreeMap<char, usize>
+}
+
+
+// Method implementations for SuffixAutomaton struct
+impl SuffixAutomaton {
+
+    /// Return the SuffixAutomaton struct of the input text.
+    ///
+    /// * `text`: text to be indexed.
+    pub fn new(text: &str) -> SuffixAutomaton {
+        let mut automaton = SuffixAutomaton {
+            states: vec![State { length: 0, link: None, transitions: BTreeMap::new() }],
+            last: INITIAL
+        };
+        for character in text.chars() {
+            automaton.extend(character);
+        }
+        automaton
+    }
+
+    /// Return the number of states in this SuffixAutomaton - at most 2n - 1 for a text of n >= 2
+    /// characters (n + 1 for shorter texts).
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Return whether the pattern is a substring of the indexed text, by following its
+    /// characters from the initial state, in O(m log k) for a pattern of m characters.
+    ///
+    /// * `pattern`: substring to look for.
+    pub fn contains(&self, pattern: &str) -> bool {
+        let mut state = INITIAL;
+        for character in pattern.chars() {
+            match self.states[state].transitions.get(&character) {
+                Some(next) => state = *next,
+                None => return false
+            }
+        }
+        true
+    }
+
+    /// Return the number of distinct non-empty substrings of the indexed text. Each state stands
+    /// for one substring of every length between its suffix link's length (exclusive) and its own
+    /// length, and no substring is stood for by two states.
+    pub fn count_distinct_substrings(&self) -> usize {
+        self.states.iter()
+            .filter_map(|state| state.link.map(|link| state.length - self.states[link].length))
+            .sum()
+    }
+
+    /// Return the longest substring of the input that is also a substring of the indexed text
+    /// (the first one in the input if there are several, and "" if they share no characters).
+    ///
+    /// Walks the input through the automaton, tracking the longest substring of the text that
+    ///     ends at each position. When the next character has no transition, the match is
+    ///     shortened by following suffix links until it does, so the walk is O(m log k) overall.
+    ///
+    /// * `other`: text to find the longest common substring with.
+    pub fn longest_common_substring<'a>(&self, other: &'a str) -> &'a str {
+        let char_starts: Vec<usize> = other.char_indices().map(|(start, _)| start).collect();
+        let mut state = INITIAL;
+        let mut matched = 0;
+        // (number of characters, index of the last character) of the longest match so far
+        let mut best = (0, 0);
+        for (index, character) in other.chars().enumerate() {
+            while state != INITIAL && !self.states[state].transitions.contains_key(&character) {
+                state = self.states[state].link.expect("only the initial state has no suffix link");
+                matched = self.states[state].length;
+            }
+            match self.states[state].transitions.get(&character) {
+                Some(next) => {
+                    state = *next;
+                    matched += 1;
+                },
+                None => {
+                    matched = 0;
+                }
+            }
+            if matched > best.0 {
+                best = (matched, index);
+            }
+        }
+
+        let (length, last_index) = best;
+        if length == 0 {
+            return "";
+        }
+        let start = char_starts[last_index + 1 - length];
+        let end = char_starts.get(last_index + 1).copied().unwrap_or(other.len());
+        &other[start..end]
+    }
+
+    /// Append a character to the indexed text, updating the automaton online.
+    ///
+    /// A new state is made for the whole new text, and every suffix of the old text without a
+    ///     transition on the character gets one to it. Where an existing transition is found, the
+    ///     new state's suffix link goes to its target - unless that target also stands for longer
+    ///     substrings, in which case it is split by cloning it with only the shorter ones.
+    ///
+    /// * `character`: character to be appended.
+    fn extend(&mut self, character: char) {
+        let current = self.states.len();
+        self.states.push(State {
+            length: self.states[self.last].length + 1,
+            link: None,
+            transitions: BTreeMap::new()
+        });
+
+        let mut previous = Some(self.last);
+        while let Some(state) = previous {
+            if self.states[state].transitions.contains_key(&character) {
+                break;
+            }
+            self.states[state].transitions.insert(character, current);
+            previous = self.states[state].link;
+        }
+
+        let link = match previous {
+            None => INITIAL,
+            Some(state) => {
+                let next = self.states[state].transitions[&character];
+                if self.states[state].length + 1 == self.states[next].length {
+                    next
+                } else {
+                    self.split(state, next, character)
+                }
+            }
+        };
+        self.states[current].link = Some(link);
+        self.last = current;
+    }
+
+    /// Clone the input state's target into a new state standing for only its substrings of up to
+    /// the state's length plus one, and redirect transitions on the character from the state and
+    /// its suffix link chain to the clone. Return the clone's index.
+    ///
+    /// * `state`: state whose transition on the character leads to next.
+    /// * `next`: state to be split.
+    /// * `character`: character of the transition.
+    fn split(&mut self, state: usize, next: usize, character: char) -> usize {
+        let clone = self.states.len();
+        self.states.push(State {
+            length: self.states[state].length + 1,
+            link: self.states[next].link,
+            transitions: self.states[next].transitions.clone()
+        });
+        self.states[next].link = Some(clone);
+
+        let mut previous = Some(state);
+        while let Some(redirected) = previous {
+            match self.states[redirected].transitions.get_mut(&character) {
+                Some(target) if *target == next => *target = clone,
+                _ => break
+            }
+            previous = self.states[redirected].link;
+        }
+        clone
+    }
+}
+
+/// Return the longest substring of the second text that is also a substring of the first, by
+/// building a SuffixAutomaton struct of the first - O(n + m) states and steps.
+///
+/// * `first`: text to be indexed.
+/// * `second`: text to find the longest common substring in.
+pub fn longest_common_substring<'a>(first: &str, second: &'a str) -> &'a str {
+    SuffixAutomaton::new(first).longest_common_substring(second)
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_suffix_automaton() -> SuffixAutomaton {
+    SuffixAutomaton::new("mississippi")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suffix_array::{ lcp_array, suffix_array };
+
+    /// Return pseudo-random texts over small alphabets, which have lots of repeats.
+    fn random_texts() -> Vec<String> {
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut texts = Vec::new();
+        for length in 0..40 {
+            for alphabet in [b"ab".as_slice(), b"acgt"] {
+                let text: String = (0..length).map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    char::from(alphabet[(seed % alphabet.len() as u64) as usize])
+                }).collect();
+                texts.push(text);
+            }
+        }
+        texts
+    }
+
+    #[test]
+    fn suffix_automaton_works() {
+        let automaton = setup_suffix_automaton();
+        for pattern in ["", "m", "ssi", "issip", "mississippi", "ppi"] {
+            assert!(automaton.contains(pattern), "pattern {:?}", pattern);
+        }
+        for pattern in ["spi", "mm", "mississippii", "x"] {
+            assert!(!automaton.contains(pattern), "pattern {:?}", pattern);
+        }
+        assert_eq!(automaton.count_distinct_substrings(), 53);
+        assert!(automaton.state_count() < 2 * 11);
+
+        let empty = SuffixAutomaton::new("");
+        assert_eq!(empty.state_count(), 1);
+        assert_eq!(empty.count_distinct_substrings(), 0);
+        assert!(empty.contains(""));
+        assert!(!empty.contains("a"));
+    }
+
+    #[test]
+    fn suffix_automaton_matches_naive_substrings() {
+        for text in random_texts() {
+            let automaton = SuffixAutomaton::new(&text);
+            assert!(automaton.state_count() <= (2 * text.len()).saturating_sub(1).max(text.len() + 1));
+
+            let mut substrings = std::collections::HashSet::new();
+            for start in 0..text.len() {
+                for end in start + 1..=text.len() {
+                    substrings.insert(&text[start..end]);
+                    assert!(automaton.contains(&text[start..end]));
+                }
+            }
+            assert_eq!(automaton.count_distinct_substrings(), substrings.len());
+            assert!(!automaton.contains(&format!("{}x", text)));
+
+            // the suffix array counts the same substrings from the other direction
+            let suffixes = suffix_array(&text);
+            let shared: usize = lcp_array(&text, &suffixes).iter().sum();
+            assert_eq!(automaton.count_distinct_substrings(), text.len() * (text.len() + 1) / 2 - shared);
+        }
+    }
+
+    #[test]
+    fn suffix_automaton_longest_common_substring() {
+        assert_eq!(longest_common_substring("mississippi", "dissipate"), "issip");
+        assert_eq!(longest_common_substring("dissipate", "mississippi"), "issip");
+        assert_eq!(longest_common_substring("abc", "xyz"), "");
+        assert_eq!(longest_common_substring("", "abc"), "");
+        assert_eq!(longest_common_substring("abc", "abc"), "abc");
+        // the first of equally long matches in the second text
+        assert_eq!(longest_common_substring("abxcd", "cdyab"), "cd");
+
+        // multi-byte characters are matched and sliced whole
+        assert_eq!(longest_common_substring("naïve café", "a café au lait"), " café");
+
+        let texts = random_texts();
+        for pair in texts.windows(2) {
+            let (first, second) = (&pair[0], &pair[1]);
+            let found = longest_common_substring(first, second);
+            assert!(first.contains(found) && second.contains(found));
+            let longest = (0..second.len())
+                .flat_map(|start| (start..=second.len()).map(move |end| &second[start..end]))
+                .filter(|candidate| first.contains(candidate))
+                .map(str::len)
+                .max()
+                .unwrap_or(0);
+            assert_eq!(found.len(), longest);
+        }
+    }
+}
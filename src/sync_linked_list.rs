@@ -0,0 +1,212 @@
+//! Thread-safe linked list implementation: the same singly linked structure as linked_list.rs, but
+//!     using std::sync::{ Arc, Weak } and Mutex in place of Rc, Weak and RefCell, so the list is
+//!     Send + Sync and can be shared between threads (e.g. as a work queue inside an Arc).
+
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+
+/// A thread-safe linked list struct containing "pointers" to Node structs.
+///
+/// Every method takes &self and locks the list's state for the duration of the call, so one
+/// SyncLinkedList can be shared between threads (wrapped in an Arc, or borrowed by scoped threads)
+/// and added to and dequeued from concurrently.
+///
+/// * `state`: Mutex guarding the head, tail and length of the list (see ListState).
+pub struct SyncLinkedList<T> {
+    state: Mutex<ListState<T>>
+}
+
+
+/// The members of a SyncLinkedList struct, locked together so head, tail and length are always
+/// updated consistently.
+///
+/// * `head`: An Option-wrapped reference to the first Node.
+/// * `tail`: Weak reference to the last Node (upgrade returns None if the list is empty).
+/// * `length`: Number of Nodes in the list.
+struct ListState<T> {
+    head: Option<Arc<Mutex<Node<T>>>>,
+    tail: Weak<Mutex<Node<T>>>,
+    length: usize
+}
+
+
+/// Node in a SyncLinkedList struct.
+///
+/// * `value`: T must be of type that matches the SyncLinkedList struct this Node is placed in
+/// * `next`: Option holding an Arc to the next Node - the only strong reference to it, apart from
+///         the list's head member for the first Node.
+pub struct Node<T> {
+    value: T,
+    next: Option<Arc<Mutex<Node<T>>>>
+}
+
+
+// Method implementations for SyncLinkedList struct
+impl<T> SyncLinkedList<T> {
+
+    /// Return a new, empty SyncLinkedList struct
+    pub fn new() -> SyncLinkedList<T> {
+        SyncLinkedList {
+            state: Mutex::new(ListState {
+                head: None,
+                tail: Weak::new(),
+                length: 0
+            })
+        }
+    }
+
+    /// Return the number of values in this SyncLinkedList at the time of the call.
+    pub fn len(&self) -> usize {
+        self.lock().length
+    }
+
+    /// Return whether this SyncLinkedList held no values at the time of the call.
+    pub fn is_empty(&self) -> bool {
+        self.lock().length == 0
+    }
+
+    /// Add a Node containing value T to the end of the list.
+    ///
+    /// * `value`: T to be stored in a new Node at the tail of the list.
+    pub fn add_value(&self, value: T) {
+        let new_node = Arc::new(Mutex::new(Node { value, next: None }));
+        let mut state = self.lock();
+        match state.tail.upgrade() {
+            Some(old_tail) => {
+                lock_node(&old_tail).next = Some(Arc::clone(&new_node));
+            },
+            None => {
+                state.head = Some(Arc::clone(&new_node));
+            }
+        }
+        state.tail = Arc::downgrade(&new_node);
+        state.length += 1;
+    }
+
+    /// Return a clone of the value in the head Node (if the list is not empty). The value can't be
+    ///     borrowed, as another thread may dequeue it as soon as the list is unlocked.
+    pub fn peek_head_value(&self) -> Option<T> where T: Clone {
+        let state = self.lock();
+        state.head.as_ref().map(|head| lock_node(head).value.clone())
+    }
+
+    /// Remove the head Node from the list and return its value (unless the list is empty).
+    pub fn dequeue_value(&self) -> Option<T> {
+        let mut state = self.lock();
+        let old_head = state.head.take()?;
+        state.head = lock_node(&old_head).next.take();
+        if state.head.is_none() {
+            // otherwise another thread could upgrade the tail and append to the removed Node
+            state.tail = Weak::new();
+        }
+        state.length -= 1;
+        drop(state);
+
+        // the list held the only strong reference to the head Node
+        match Arc::try_unwrap(old_head) {
+            Ok(node_mutex) => Some(node_mutex.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()).value),
+            Err(_) => unreachable!("Nodes are only strongly referenced by the list")
+        }
+    }
+
+    /// Lock the state of this list. A panic in another thread while it held the lock can't leave
+    ///     the state half-updated (no method panics mid-update), so poisoning is ignored.
+    fn lock(&self) -> MutexGuard<'_, ListState<T>> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T> Default for SyncLinkedList<T> {
+    fn default() -> Self {
+        SyncLinkedList::new()
+    }
+}
+
+// Drop Nodes one at a time from the head, so long lists don't drop their next chain recursively.
+impl<T> Drop for SyncLinkedList<T> {
+    fn drop(&mut self) {
+        let mut opt_node = self.lock().head.take();
+        while let Some(node_arc) = opt_node {
+            opt_node = lock_node(&node_arc).next.take();
+        }
+    }
+}
+
+/// Lock a Node in a SyncLinkedList struct, ignoring poisoning (see SyncLinkedList::lock).
+///
+/// * `node`: reference to the Arc-wrapped Node to lock.
+fn lock_node<T>(node: &Mutex<Node<T>>) -> MutexGuard<'_, Node<T>> {
+    node.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_sync_linked_list() -> SyncLinkedList<u32> {
+    let sll: SyncLinkedList<u32> = SyncLinkedList::new();
+    sll.add_value(2);
+    sll.add_value(4);
+    sll
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn sync_linked_list_works() {
+        let sll = setup_sync_linked_list();
+        assert_eq!(sll.len(), 2);
+        assert_eq!(sll.peek_head_value(), Some(2));
+        assert_eq!(sll.dequeue_value(), Some(2));
+        assert_eq!(sll.dequeue_value(), Some(4));
+        assert_eq!(sll.dequeue_value(), None);
+        assert!(sll.is_empty());
+
+        sll.add_value(6);
+        assert_eq!(sll.peek_head_value(), Some(6));
+    }
+
+    #[test]
+    fn sync_linked_list_is_send_and_sync() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<SyncLinkedList<u32>>();
+        assert_send_sync::<Arc<SyncLinkedList<String>>>();
+    }
+
+    #[test]
+    fn sync_linked_list_can_be_shared_between_threads() {
+        let queue = Arc::new(SyncLinkedList::new());
+        let producers: Vec<_> = (0..4u64)
+            .map(|thread_index| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for value in 0..1000 {
+                        queue.add_value(thread_index * 1000 + value);
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        assert_eq!(queue.len(), 4000);
+
+        let sums: Vec<u64> = thread::scope(|scope| {
+            let consumers: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| {
+                    let mut sum = 0;
+                    while let Some(value) = queue.dequeue_value() {
+                        sum += value;
+                    }
+                    sum
+                }))
+                .collect();
+            consumers.into_iter().map(|consumer| consumer.join().unwrap()).collect()
+        });
+        assert_eq!(sums.iter().sum::<u64>(), (0..4000).sum::<u64>());
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+//! Traits for the abstract data types the structures in this crate can back, so generic code
+//!     (e.g. a breadth-first search that needs a queue) can be written once and run on any of
+//!     them. queue::Queue implements Queue, and stack::Stack implements Stack.
+//!
+//! The singly linked list implements both traits too, but its module is private to the crate, so
+//!     those impls are crate-internal: they back generic code inside the crate and its tests, and
+//!     can't be reached from outside it.
+//!
+//! ```
+//! use rust_datastructures_algorithms::queue;
+//! use rust_datastructures_algorithms::traits::Queue;
+//!
+//! // breadth-first order over a small implicit tree: the children of n are 2n and 2n + 1
+//! fn breadth_first(mut queue: impl Queue<u32>) -> Vec<u32> {
+//!     let mut visited = Vec::new();
+//!     queue.enqueue(1);
+//!     while let Some(n) = queue.dequeue() {
+//!         visited.push(n);
+//!         if n < 4 {
+//!             queue.enqueue(2 * n);
+//!             queue.enqueue(2 * n + 1);
+//!         }
+//!     }
+//!     visited
+//! }
+//!
+//! assert_eq!(breadth_first(queue::Queue::new()), vec![1, 2, 3, 4, 5, 6, 7]);
+//! ```
+
+
+/// First-in, first-out collection: values are dequeued in the order they were enqueued.
+pub trait Queue<T> {
+
+    /// Add a value to the back of the queue.
+    ///
+    /// * `value`: value to be enqueued.
+    fn enqueue(&mut self, value: T);
+
+    /// Remove the value at the front of the queue (the one enqueued earliest) and return it, or
+    /// None if the queue is empty.
+    fn dequeue(&mut self) -> Option<T>;
+
+    /// Return a reference to the value at the front of the queue, without removing it.
+    fn peek_front(&self) -> Option<&T>;
+
+    /// Return the number of values in the queue.
+    fn len(&self) -> usize;
+
+    /// Return whether the queue holds no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+
+/// Last-in, first-out collection: values are popped in the reverse of the order they were pushed.
+pub trait Stack<T> {
+
+    /// Add a value to the top of the stack.
+    ///
+    /// * `value`: value to be pushed.
+    fn push(&mut self, value: T);
+
+    /// Remove the value at the top of the stack (the one pushed latest) and return it, or None if
+    /// the stack is empty.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Return a reference to the value at the top of the stack, without removing it.
+    fn peek(&self) -> Option<&T>;
+
+    /// Return the number of values in the stack.
+    fn len(&self) -> usize;
+
+    /// Return whether the stack holds no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
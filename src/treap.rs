@@ -0,0 +1,509 @@
+//! Treap (randomized binary search tree) implementation using Box references to Nodes.
+//!     Each Node is given a random priority, and the tree is kept in binary search tree order by
+//!     value and in max-heap order by priority at the same time. Random priorities make the shape
+//!     of the tree that of a randomly-built BST, so its expected height is O(log n) no matter
+//!     what order values are added in.
+
+use std::cmp::Ordering;
+use crate::rng::XorShift64;
+
+
+/// A treap struct containing pointers to Node structs.
+///
+/// * `root`: An Option-wrapped reference to the root Node of the treap. This will be None if there
+///         are zero nodes in this treap.
+/// * `rng`: Xorshift generator used to give new Nodes their priorities.
+pub struct Treap<T: Ord> {
+    root: Option<Box<Node<T>>>,
+    rng: XorShift64
+}
+
+
+/// A Node in a Treap struct.
+///
+/// * `value`: Value held in this Node - must be comparable with < or > (implement Ord trait)
+/// * `priority`: Random priority of this Node. No Node has a higher priority than its parent.
+/// * `left_branch`: Option-wrapped reference to another Node, which should contain a Node with
+///         lesser value.
+/// * `right_branch`: Option-wrapped reference to another Node, which should contain a Node with
+///         greater value.
+/// * `subtree_size`: Number of Nodes in the subtree rooted at this Node (including itself), so
+///         the sizes of the treaps produced by split are known without counting.
+pub struct Node<T: Ord> {
+    value: T,
+    priority: u64,
+    left_branch: Option<Box<Node<T>>>,
+    right_branch: Option<Box<Node<T>>>,
+    subtree_size: usize
+}
+
+
+/// Option-wrapped Node at the top of a (possibly empty) subtree, as held by branch members.
+type Subtree<T> = Option<Box<Node<T>>>;
+
+
+// Method implementation for Treap struct
+impl<T> Treap<T> where T: Ord {
+
+    /// Return a new, empty Treap struct, with its priority generator seeded randomly.
+    fn new() -> Treap<T> {
+        Treap { root: None, rng: XorShift64::new() }
+    }
+
+    /// Return a new, empty Treap struct whose Node priorities are generated from the input seed,
+    ///     so the shape of the treap is reproducible.
+    ///
+    /// * `seed`: seed for the priority generator.
+    fn with_seed(seed: u64) -> Treap<T> {
+        Treap {
+            root: None,
+            rng: XorShift64::with_seed(seed)
+        }
+    }
+
+    /// Return the number of values held in this Treap struct.
+    fn len(&self) -> usize {
+        Node::subtree_size(&self.root)
+    }
+
+    /// Return whether this Treap struct holds no values.
+    fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Return the height of this Treap struct: the number of Nodes on the longest path from the
+    ///     root Node down to a leaf Node.
+    fn height(&self) -> usize {
+        Node::height(&self.root)
+    }
+
+    /// Find whether a value is present in a Treap struct.
+    ///
+    /// * `value`: value to be searched for.
+    fn find_value(&self, value: &T) -> bool {
+        let mut opt_node = self.root.as_deref();
+        while let Some(node) = opt_node {
+            match value.cmp(&node.value) {
+                Ordering::Less => { opt_node = node.left_branch.as_deref(); },
+                Ordering::Greater => { opt_node = node.right_branch.as_deref(); },
+                Ordering::Equal => { return true; }
+            }
+        }
+        false
+    }
+
+    /// Add a Node holding the input value, with a random priority, to this Treap struct.
+    ///
+    /// The Node is first added as a leaf like in a plain binary search tree, then rotated up while
+    ///     its priority is higher than its parent's.
+    ///
+    /// It should be noted - if this value is evaluated as Ordering::Equal (== operator) to another
+    ///     Node's value in this treap, this value will be discarded without a Node being added.
+    ///
+    /// * `value`: The value to be added into the treap.
+    fn add_value(&mut self, value: T) {
+        let priority = self.rng.next_u64();
+        self.root = Node::insert(self.root.take(), value, priority);
+    }
+
+    /// Find input value in the Treap (using Ordering::Equal (== operator)) and remove it (and its
+    ///     enclosing Node).
+    ///
+    /// The Node holding the value is rotated down, always lifting its higher-priority child above
+    ///     it, until it has at most one child and can be replaced by that child.
+    ///
+    /// Return the value that was stored in the treap, or None if no equal value was found.
+    ///
+    /// * `value`: Value to be removed from the treap.
+    fn remove_value(&mut self, value: T) -> Option<T> {
+        let mut removed_value = None;
+        self.root = Node::remove(self.root.take(), &value, &mut removed_value);
+        removed_value
+    }
+
+    /// Consume this Treap struct and split it into two: the first holding all values less than the
+    ///     input value, and the second holding all values greater than or equal to it.
+    ///
+    /// Only the Nodes on the search path for the input value are relinked, so this costs
+    ///     O(height).
+    ///
+    /// * `value`: value to split the treap at. Doesn't need to be in the treap.
+    fn split(mut self, value: &T) -> (Treap<T>, Treap<T>) {
+        let (lesser_root, greater_root) = Node::split(self.root.take(), value);
+        let greater_rng = XorShift64::with_seed(self.rng.next_u64());
+        (
+            Treap { root: lesser_root, rng: self.rng },
+            Treap { root: greater_root, rng: greater_rng }
+        )
+    }
+
+    /// Join two Treap structs into one, where every value in the first is less than every value in
+    ///     the second (e.g. the two halves returned by split). Costs O(height).
+    ///
+    /// Panics if the largest value in `lesser` is not less than the smallest value in `greater`.
+    ///
+    /// * `lesser`: treap holding the smaller values.
+    /// * `greater`: treap holding the larger values.
+    fn merge(mut lesser: Treap<T>, mut greater: Treap<T>) -> Treap<T> {
+        if let (Some(lesser_max), Some(greater_min)) = (lesser.max(), greater.min()) {
+            assert!(lesser_max < greater_min, "can't merge treaps with overlapping values");
+        }
+        Treap {
+            root: Node::merge(lesser.root.take(), greater.root.take()),
+            rng: lesser.rng
+        }
+    }
+
+    /// Return a reference to the smallest value in this treap.
+    fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left_child) = node.left_branch.as_deref() {
+            node = left_child;
+        }
+        Some(&node.value)
+    }
+
+    /// Return a reference to the largest value in this treap.
+    fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right_child) = node.right_branch.as_deref() {
+            node = right_child;
+        }
+        Some(&node.value)
+    }
+
+    /// Create and return a vector containing references to the values held by Nodes in this
+    /// Treap struct, in inorder (ascending) order.
+    fn collectpeek_inorder_values(&self) -> Vec<&T> {
+        let mut list = Vec::new();
+        Node::collectpeek_inorder(&self.root, &mut list);
+        list
+    }
+}
+
+impl<T> Default for Treap<T> where T: Ord {
+    fn default() -> Self {
+        Treap::new()
+    }
+}
+
+
+// Method implementations for Node struct in a Treap struct.
+impl<T> Node<T> where T: Ord {
+
+    /// Return a new, boxed Node struct with the value T and the input priority.
+    ///
+    /// * `value`: Value to be stored in the Node.
+    /// * `priority`: Heap priority of the Node.
+    fn new_boxed(value: T, priority: u64) -> Box<Node<T>> {
+        Box::new(Node {
+            value,
+            priority,
+            left_branch: None,
+            right_branch: None,
+            subtree_size: 1
+        })
+    }
+
+    /// Return the number of Nodes in the subtree rooted in an Option-wrapped Node reference (0 if
+    ///     there is no Node).
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    fn subtree_size(opt_node: &Option<Box<Node<T>>>) -> usize {
+        opt_node.as_ref().map_or(0, |boxed_node| boxed_node.subtree_size)
+    }
+
+    /// Recompute this Node's subtree_size from the subtree sizes of its branches.
+    fn update_subtree_size(&mut self) {
+        self.subtree_size = 1 + Node::subtree_size(&self.left_branch) + Node::subtree_size(&self.right_branch);
+    }
+
+    /// Recursively compute the height of the subtree rooted in an Option-wrapped Node reference.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    fn height(opt_node: &Option<Box<Node<T>>>) -> usize {
+        match opt_node {
+            Some(boxed_node) => {
+                1 + Node::height(&boxed_node.left_branch).max(Node::height(&boxed_node.right_branch))
+            },
+            None => 0
+        }
+    }
+
+    /// Rotate the subtree rooted at this Node to the right, returning its new root (this Node's
+    ///     left child). See bst::Node::rotate_right.
+    fn rotate_right(mut self: Box<Self>) -> Box<Node<T>> {
+        let mut new_root = self.left_branch.take().expect("can't rotate right without a left child");
+        self.left_branch = new_root.right_branch.take();
+        self.update_subtree_size();
+        new_root.right_branch = Some(self);
+        new_root.update_subtree_size();
+        new_root
+    }
+
+    /// Rotate the subtree rooted at this Node to the left, returning its new root (this Node's
+    ///     right child). See bst::Node::rotate_left.
+    fn rotate_left(mut self: Box<Self>) -> Box<Node<T>> {
+        let mut new_root = self.right_branch.take().expect("can't rotate left without a right child");
+        self.right_branch = new_root.left_branch.take();
+        self.update_subtree_size();
+        new_root.left_branch = Some(self);
+        new_root.update_subtree_size();
+        new_root
+    }
+
+    /// Recursively add a Node holding the input value to the subtree rooted in an Option-wrapped
+    ///     Node, returning the new root of the subtree.
+    ///
+    /// After the value has been added to a branch, the root of that branch may have a higher
+    ///     priority than this Node; if so, rotate it above this Node.
+    ///
+    /// * `opt_node`: Option-wrapped Node at the top of the subtree.
+    /// * `value`: value to be added.
+    /// * `priority`: priority of the Node to be added.
+    fn insert(opt_node: Option<Box<Node<T>>>, value: T, priority: u64) -> Option<Box<Node<T>>> {
+        let Some(mut node) = opt_node else {
+            return Some(Node::new_boxed(value, priority));
+        };
+        match value.cmp(&node.value) {
+            Ordering::Less => {
+                node.left_branch = Node::insert(node.left_branch.take(), value, priority);
+                node.update_subtree_size();
+                if node.left_branch.as_ref().unwrap().priority > node.priority {
+                    node = node.rotate_right();
+                }
+            },
+            Ordering::Greater => {
+                node.right_branch = Node::insert(node.right_branch.take(), value, priority);
+                node.update_subtree_size();
+                if node.right_branch.as_ref().unwrap().priority > node.priority {
+                    node = node.rotate_left();
+                }
+            },
+            Ordering::Equal => ()
+        }
+        Some(node)
+    }
+
+    /// Recursively remove the Node holding the input value from the subtree rooted in an
+    ///     Option-wrapped Node, returning the new root of the subtree.
+    ///
+    /// Once found, a Node with two children is rotated down below its higher-priority child
+    ///     (which keeps heap order), and removal continues in the branch it was rotated into.
+    ///
+    /// * `opt_node`: Option-wrapped Node at the top of the subtree.
+    /// * `value`: value to be removed.
+    /// * `removed_value`: set to the value held by the removed Node, if one was found and removed.
+    fn remove(opt_node: Option<Box<Node<T>>>, value: &T, removed_value: &mut Option<T>) -> Option<Box<Node<T>>> {
+        let mut node = opt_node?;
+        match value.cmp(&node.value) {
+            Ordering::Less => {
+                node.left_branch = Node::remove(node.left_branch.take(), value, removed_value);
+            },
+            Ordering::Greater => {
+                node.right_branch = Node::remove(node.right_branch.take(), value, removed_value);
+            },
+            Ordering::Equal => {
+                match (&node.left_branch, &node.right_branch) {
+                    (None, _) => {
+                        *removed_value = Some(node.value);
+                        return node.right_branch;
+                    },
+                    (_, None) => {
+                        *removed_value = Some(node.value);
+                        return node.left_branch;
+                    },
+                    (Some(left_child), Some(right_child)) => {
+                        if left_child.priority > right_child.priority {
+                            node = node.rotate_right();
+                            node.right_branch = Node::remove(node.right_branch.take(), value, removed_value);
+                        } else {
+                            node = node.rotate_left();
+                            node.left_branch = Node::remove(node.left_branch.take(), value, removed_value);
+                        }
+                    }
+                }
+            }
+        }
+        node.update_subtree_size();
+        Some(node)
+    }
+
+    /// Recursively split the subtree rooted in an Option-wrapped Node into the subtree of values
+    ///     less than the input value and the subtree of values greater than or equal to it.
+    ///
+    /// If this Node's value belongs in the lesser subtree, so does its whole left branch: split
+    ///     its right branch, keep the lesser part as its new right branch and hand back the rest.
+    ///     Otherwise, the mirror image. Heap order holds in both parts since no Node gains a parent
+    ///     with lower priority.
+    ///
+    /// * `opt_node`: Option-wrapped Node at the top of the subtree.
+    /// * `value`: value to split the subtree at.
+    fn split(opt_node: Option<Box<Node<T>>>, value: &T) -> (Subtree<T>, Subtree<T>) {
+        let Some(mut node) = opt_node else {
+            return (None, None);
+        };
+        if &node.value < value {
+            let (lesser, greater) = Node::split(node.right_branch.take(), value);
+            node.right_branch = lesser;
+            node.update_subtree_size();
+            (Some(node), greater)
+        } else {
+            let (lesser, greater) = Node::split(node.left_branch.take(), value);
+            node.left_branch = greater;
+            node.update_subtree_size();
+            (lesser, Some(node))
+        }
+    }
+
+    /// Recursively merge two subtrees where every value in the first is less than every value in
+    ///     the second, returning the root of the merged subtree.
+    ///
+    /// The root with the higher priority becomes the merged root: if it is the lesser root, its
+    ///     right branch is merged with the greater subtree, otherwise the greater root's left branch
+    ///     is merged with the lesser subtree.
+    ///
+    /// * `lesser`: Option-wrapped Node at the top of the subtree holding smaller values.
+    /// * `greater`: Option-wrapped Node at the top of the subtree holding larger values.
+    fn merge(lesser: Option<Box<Node<T>>>, greater: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+        match (lesser, greater) {
+            (None, greater) => greater,
+            (lesser, None) => lesser,
+            (Some(mut lesser_node), Some(mut greater_node)) => {
+                if lesser_node.priority > greater_node.priority {
+                    lesser_node.right_branch = Node::merge(lesser_node.right_branch.take(), Some(greater_node));
+                    lesser_node.update_subtree_size();
+                    Some(lesser_node)
+                } else {
+                    greater_node.left_branch = Node::merge(Some(lesser_node), greater_node.left_branch.take());
+                    greater_node.update_subtree_size();
+                    Some(greater_node)
+                }
+            }
+        }
+    }
+
+    /// Assign to a Vec (using a mutable reference to it) node value references of this Node and
+    /// its branch-children Nodes, using inorder traversal, recursively calling this method.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    /// * `list`: mutable references to the Vec where Node value references should be added.
+    fn collectpeek_inorder<'a>(
+        opt_node: &'a Option<Box<Node<T>>>,
+        list: &mut Vec<&'a T>
+    ) {
+        if let Some(boxed_node) = opt_node {
+            Node::collectpeek_inorder(&boxed_node.left_branch, list);
+            list.push(&boxed_node.value);
+            Node::collectpeek_inorder(&boxed_node.right_branch, list);
+        }
+    }
+
+    /// Recursively check that no Node in the subtree has a higher priority than its parent and that
+    ///     every subtree_size is correct. Used by tests.
+    ///
+    /// * `opt_node`: Option-wrapped Node reference - can be called directly on references to a
+    ///         Node's branch members
+    fn is_heap_ordered(opt_node: &Option<Box<Node<T>>>) -> bool {
+        match opt_node {
+            Some(boxed_node) => {
+                let children_ok = [&boxed_node.left_branch, &boxed_node.right_branch].into_iter()
+                    .all(|branch| branch.as_ref().is_none_or(|child| child.priority <= boxed_node.priority));
+                let size_ok = boxed_node.subtree_size
+                    == 1 + Node::subtree_size(&boxed_node.left_branch) + Node::subtree_size(&boxed_node.right_branch);
+                children_ok && size_ok
+                    && Node::is_heap_ordered(&boxed_node.left_branch)
+                    && Node::is_heap_ordered(&boxed_node.right_branch)
+            },
+            None => true
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_treap() -> Treap<u32> {
+    let mut treap: Treap<u32> = Treap::with_seed(42);
+    for value in [4, 2, 6, 1, 3, 5] {
+        treap.add_value(value);
+    }
+    treap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn treap_can_be_created_and_added_to() {
+        let mut treap = setup_treap();
+        treap.add_value(3);
+        assert_eq!(treap.len(), 6);
+        assert_eq!(treap.collectpeek_inorder_values(), vec![&1, &2, &3, &4, &5, &6]);
+        assert!(Node::is_heap_ordered(&treap.root));
+    }
+
+    #[test]
+    fn treap_can_be_searched() {
+        let treap = setup_treap();
+        assert!(treap.find_value(&4));
+        assert!(treap.find_value(&1));
+        assert!(!treap.find_value(&88));
+        assert_eq!(treap.min(), Some(&1));
+        assert_eq!(treap.max(), Some(&6));
+    }
+
+    #[test]
+    fn treap_can_delete_nodes() {
+        let mut treap = setup_treap();
+        assert_eq!(treap.remove_value(4), Some(4));
+        assert_eq!(treap.remove_value(4), None);
+        assert_eq!(treap.remove_value(1), Some(1));
+        assert_eq!(treap.collectpeek_inorder_values(), vec![&2, &3, &5, &6]);
+        assert_eq!(treap.len(), 4);
+        assert!(Node::is_heap_ordered(&treap.root));
+    }
+
+    #[test]
+    fn treap_stays_shallow_under_sorted_input() {
+        let mut treap: Treap<u32> = Treap::with_seed(7);
+        for value in 0..1000 {
+            treap.add_value(value);
+        }
+        assert_eq!(treap.len(), 1000);
+        assert!(treap.height() < 50);
+        assert!(Node::is_heap_ordered(&treap.root));
+    }
+
+    #[test]
+    fn treap_can_be_split_and_merged() {
+        let treap = setup_treap();
+        let (lesser, greater) = treap.split(&4);
+        assert_eq!(lesser.collectpeek_inorder_values(), vec![&1, &2, &3]);
+        assert_eq!(greater.collectpeek_inorder_values(), vec![&4, &5, &6]);
+        assert_eq!(lesser.len(), 3);
+        assert!(Node::is_heap_ordered(&lesser.root));
+        assert!(Node::is_heap_ordered(&greater.root));
+
+        let merged = Treap::merge(lesser, greater);
+        assert_eq!(merged.collectpeek_inorder_values(), vec![&1, &2, &3, &4, &5, &6]);
+        assert_eq!(merged.len(), 6);
+        assert!(Node::is_heap_ordered(&merged.root));
+
+        let (empty, all) = merged.split(&0);
+        assert!(empty.is_empty());
+        assert_eq!(all.len(), 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn treap_merge_rejects_overlapping_values() {
+        let (lesser, greater) = setup_treap().split(&4);
+        Treap::merge(greater, lesser);
+    }
+}
@@ -0,0 +1,280 @@
+//! Union-find (disjoint set union): keeps track of how the elements 0 to n - 1 are split into
+//!     disjoint sets as sets are merged, answering whether two elements are in the same set.
+//!
+//! Each set is a tree of parent pointers whose root stands for the set. Union by size hangs the
+//!     smaller tree under the larger one's root, which keeps trees O(log n) deep, and path
+//!     compression points every element on a find's path straight at the root. Together they make
+//!     any sequence of m operations take O(m α(n)) time, where α is the inverse Ackermann
+//!     function - at most 4 for any n that fits in memory.
+//!
+//! ```
+//! use rust_datastructures_algorithms::union_find::UnionFind;
+//!
+//! let mut sets = UnionFind::new(6);
+//! sets.union(0, 1);
+//! sets.union(1, 2);
+//! sets.union(4, 5);
+//! assert!(sets.connected(0, 2));
+//! assert!(!sets.connected(2, 3));
+//! assert_eq!(sets.component_size(1), 3);
+//! assert_eq!(sets.components().collect::<Vec<_>>(), vec![vec![0, 1, 2], vec![3], vec![4, 5]]);
+//! ```
+
+use std::vec;
+
+
+/// A union-find struct over the elements 0 to n - 1.
+///
+/// * `parent`: Parent of each element in its set's tree - roots are their own parent.
+/// * `size`: Number of elements in the tree under each root (only kept up to date for roots).
+/// * `component_count`: Number of disjoint sets.
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    component_count: usize
+}
+
+
+/// Iterator yielding the sets of a UnionFind struct, each as a Vec of its elements in ascending
+/// order, with the sets in order of their smallest elements. Created by UnionFind::components.
+///
+/// * `groups`: The sets still to be yielded.
+pub struct Components {
+    groups: vec::IntoIter<Vec<usize>>
+}
+
+
+// Method implementations for UnionFind struct
+impl UnionFind {
+
+    /// Return a new UnionFind struct with the elements 0 to n - 1, each in a set of its own.
+    ///
+    /// * `n`: number of elements.
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind { parent: (0..n).collect(), size: vec![1; n], component_count: n }
+    }
+
+    /// Return the number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Return whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Return the number of disjoint sets.
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// Add a new element, in a set of its own, and return it (the old number of elements).
+    pub fn make_set(&mut self) -> usize {
+        let element = self.parent.len();
+        self.parent.push(element);
+        self.size.push(1);
+        self.component_count += 1;
+        element
+    }
+
+    /// Return the root of the input element's set, which stands for the set: two elements are in
+    /// the same set exactly when their roots are the same. Panics if the element is out of
+    /// bounds.
+    ///
+    /// Path compression: after finding the root, every element on the way to it is made a child
+    ///     of the root, so later finds from any of them take one step.
+    ///
+    /// * `element`: element to find the set of.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = element;
+        while current != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merge the sets of the two input elements, returning whether they were in different sets.
+    /// Panics if either element is out of bounds.
+    ///
+    /// Union by size: the root of the smaller set becomes a child of the root of the larger, so
+    ///     an element only moves deeper when its set at least doubles in size.
+    ///
+    /// * `first`: element in one set.
+    /// * `second`: element in the other set.
+    pub fn union(&mut self, first: usize, second: usize) -> bool {
+        let (mut larger, mut smaller) = (self.find(first), self.find(second));
+        if larger == smaller {
+            return false;
+        }
+        if self.size[larger] < self.size[smaller] {
+            std::mem::swap(&mut larger, &mut smaller);
+        }
+        self.parent[smaller] = larger;
+        self.size[larger] += self.size[smaller];
+        self.component_count -= 1;
+        true
+    }
+
+    /// Return whether the two input elements are in the same set. Panics if either element is out
+    /// of bounds.
+    ///
+    /// * `first`: one element.
+    /// * `second`: the other element.
+    pub fn connected(&mut self, first: usize, second: usize) -> bool {
+        self.find(first) == self.find(second)
+    }
+
+    /// Return the number of elements in the input element's set (including itself). Panics if the
+    /// element is out of bounds.
+    ///
+    /// * `element`: element in the set.
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+
+    /// Return an iterator of the sets, each as a Vec of its elements in ascending order, with the
+    /// sets in order of their smallest elements. Groups every element by its root in O(n α(n)).
+    pub fn components(&mut self) -> Components {
+        let mut group_of_root = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.component_count);
+        for element in 0..self.len() {
+            let root = self.find(element);
+            if group_of_root[root] == usize::MAX {
+                group_of_root[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[group_of_root[root]].push(element);
+        }
+        Components { groups: groups.into_iter() }
+    }
+}
+
+
+// Iterator implementation for Components struct
+impl Iterator for Components {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.groups.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.groups.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Components {}
+
+
+////////////////////////////////////////////////////////////////////////////
+//  TESTS
+
+fn setup_union_find() -> UnionFind {
+    let mut sets = UnionFind::new(10);
+    for (first, second) in [(0, 3), (3, 7), (1, 2), (5, 9), (9, 8), (7, 0)] {
+        sets.union(first, second);
+    }
+    sets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the number of parent steps from the element to its root, without compressing.
+    fn depth(sets: &UnionFind, mut element: usize) -> usize {
+        let mut steps = 0;
+        while sets.parent[element] != element {
+            element = sets.parent[element];
+            steps += 1;
+        }
+        steps
+    }
+
+    #[test]
+    fn union_find_works() {
+        let mut sets = setup_union_find();
+        assert_eq!(sets.len(), 10);
+        assert_eq!(sets.component_count(), 5);
+        assert!(sets.connected(0, 7));
+        assert!(sets.connected(5, 8));
+        assert!(!sets.connected(2, 3));
+        assert_eq!(sets.component_size(3), 3);
+        assert_eq!(sets.component_size(4), 1);
+
+        assert!(!sets.union(7, 3));
+        assert!(sets.union(2, 8));
+        assert_eq!(sets.component_count(), 4);
+        assert_eq!(sets.component_size(1), 5);
+
+        let components = sets.components();
+        assert_eq!(components.len(), 4);
+        assert_eq!(components.collect::<Vec<_>>(), vec![vec![0, 3, 7], vec![1, 2, 5, 8, 9], vec![4], vec![6]]);
+
+        assert_eq!(sets.make_set(), 10);
+        assert_eq!(sets.component_count(), 5);
+        assert!(sets.union(10, 4));
+        assert_eq!(sets.component_size(4), 2);
+
+        let mut empty = UnionFind::new(0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.components().count(), 0);
+    }
+
+    #[test]
+    fn union_find_matches_naive_labels() {
+        let n = 200;
+        let mut sets = UnionFind::new(n);
+        // label of each element's set, relabelled wholesale on every merge
+        let mut labels: Vec<usize> = (0..n).collect();
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        for _ in 0..300 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let (first, second) = ((seed % n as u64) as usize, ((seed >> 32) % n as u64) as usize);
+            let (old, new) = (labels[second], labels[first]);
+            assert_eq!(sets.union(first, second), old != new);
+            labels.iter_mut().filter(|label| **label == old).for_each(|label| *label = new);
+
+            let (a, b) = (((seed >> 16) % n as u64) as usize, ((seed >> 48) % n as u64) as usize);
+            assert_eq!(sets.connected(a, b), labels[a] == labels[b]);
+            assert_eq!(sets.component_size(a), labels.iter().filter(|label| **label == labels[a]).count());
+        }
+        let mut distinct = labels.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(sets.component_count(), distinct.len());
+        assert_eq!(sets.components().map(|group| group.len()).sum::<usize>(), n);
+    }
+
+    #[test]
+    fn union_find_trees_stay_shallow() {
+        // union by size alone keeps every tree at most log2(n) deep
+        let n = 1 << 10;
+        let mut sets = UnionFind::new(n);
+        let mut width = 1;
+        while width < n {
+            for start in (0..n).step_by(2 * width) {
+                sets.union(start + width, start);
+            }
+            width *= 2;
+        }
+        assert_eq!(sets.component_count(), 1);
+        assert!((0..n).all(|element| depth(&sets, element) <= 10));
+
+        // and path compression flattens the path a find follows
+        let deepest = (0..n).max_by_key(|element| depth(&sets, *element)).unwrap();
+        assert!(depth(&sets, deepest) > 1);
+        sets.find(deepest);
+        assert_eq!(depth(&sets, deepest), 1);
+    }
+}